@@ -62,6 +62,18 @@ export function registerStepFunction(stepId: string, stepFn: StepFunction) {
   stepFn.stepId = stepId;
 }
 
+/**
+ * Register several step functions at once, as emitted by the SWC plugin with
+ * `batchStepRegistrations` enabled.
+ */
+export function registerStepFunctions(
+  steps: ReadonlyArray<readonly [stepId: string, stepFn: StepFunction]>
+) {
+  for (const [stepId, stepFn] of steps) {
+    registerStepFunction(stepId, stepFn);
+  }
+}
+
 /**
  * Find a registered step function by name
  */
@@ -3,10 +3,11 @@
 use serde::Deserialize;
 use std::path::Path;
 use swc_core::{
+    common::sync::Lrc,
     ecma::{ast::*, visit::*},
     plugin::{plugin_transform, proxies::TransformPluginProgramMetadata},
 };
-use swc_workflow::{StepTransform, TransformMode};
+use swc_workflow::{StepTransform, TransformMode, TransformOptions};
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
@@ -24,6 +25,10 @@ struct WasmConfig {
     /// where the same package specifier may resolve to different files depending on
     /// the condition (e.g., "workflow" vs "default").
     module_specifier: Option<String>,
+    /// Optional settings that tweak the generated output (see `TransformOptions`).
+    /// These are flattened into the top-level config, e.g. `{ "mode": "step", "registrationFormat": "dual" }`.
+    #[serde(flatten)]
+    options: TransformOptions,
 }
 
 #[plugin_transform]
@@ -85,7 +90,12 @@ pub fn process_transform(
         plugin_config.mode,
         normalized_filename,
         plugin_config.module_specifier,
-    );
+    )
+    .with_options(plugin_config.options)
+    .with_source_map(Lrc::new(metadata.source_map.clone()));
+    if let Some(comments) = metadata.comments {
+        visitor = visitor.with_comments(comments);
+    }
     program.visit_mut_with(&mut visitor);
     program
 }
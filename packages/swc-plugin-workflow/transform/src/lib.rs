@@ -1,20 +1,29 @@
 mod naming;
 
+use regex::Regex;
 use serde::Deserialize;
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use swc_core::{
-    common::{errors::HANDLER, SyntaxContext, DUMMY_SP},
+    common::{
+        BytePos, DUMMY_SP, Span, Spanned, SyntaxContext,
+        comments::Comments,
+        errors::{Applicability, DiagnosticBuilder, Emitter, HANDLER, Handler, SourceMapper},
+        sync::Lrc,
+    },
     ecma::{
         ast::*,
-        visit::{noop_visit_mut_type, VisitMut, VisitMutWith},
+        visit::{Visit, VisitMut, VisitMutWith, VisitWith, noop_visit_mut_type, noop_visit_type},
     },
 };
 
 #[derive(Debug, Clone)]
-enum WorkflowErrorKind {
+enum WorkflowErrorKind<'a> {
     NonAsyncFunction {
         span: swc_core::common::Span,
-        directive: &'static str,
+        directive: &'a str,
+        // Where to suggest inserting `async `, with `suggestAsyncFix`
+        async_at: Option<BytePos>,
     },
     MisplacedDirective {
         span: swc_core::common::Span,
@@ -24,16 +33,91 @@ enum WorkflowErrorKind {
     MisspelledDirective {
         span: swc_core::common::Span,
         directive: String,
-        expected: &'static str,
+        expected: &'a str,
     },
     ForbiddenExpression {
         span: swc_core::common::Span,
-        expr: &'static str,
-        directive: &'static str,
+        expr: &'a str,
+        directive: &'a str,
     },
     InvalidExport {
         span: swc_core::common::Span,
-        directive: &'static str,
+        directive: &'a str,
+    },
+    ShadowedFunction {
+        span: swc_core::common::Span,
+        name: String,
+        directive: &'a str,
+    },
+    MisorderedImport {
+        span: swc_core::common::Span,
+        directive: &'a str,
+    },
+    TemplateLiteralDirective {
+        span: swc_core::common::Span,
+        directive: &'a str,
+    },
+    GeneratorStep {
+        span: swc_core::common::Span,
+        directive: &'a str,
+    },
+    ScriptNotModule {
+        span: swc_core::common::Span,
+        directive: &'a str,
+    },
+    ConflictingDirectives {
+        span: swc_core::common::Span,
+        directive: &'a str,
+        other: &'a str,
+    },
+    PrivateMethod {
+        span: swc_core::common::Span,
+        directive: &'a str,
+    },
+    Accessor {
+        span: swc_core::common::Span,
+        directive: &'a str,
+    },
+    FunctionInAccessor {
+        span: swc_core::common::Span,
+        directive: &'a str,
+    },
+    InvalidId {
+        span: swc_core::common::Span,
+        id: &'a str,
+        pattern: &'a str,
+    },
+    ForbiddenImport {
+        span: swc_core::common::Span,
+        directive: &'a str,
+        specifier: &'a str,
+    },
+    ObjectNestingTooDeep {
+        span: swc_core::common::Span,
+        directive: &'a str,
+        max_depth: usize,
+    },
+    ImmediatelyInvoked {
+        span: swc_core::common::Span,
+        directive: &'a str,
+    },
+    UnexportedWorkflow {
+        span: swc_core::common::Span,
+        name: &'a str,
+        directive: &'a str,
+    },
+    NonDeterministicCall {
+        span: swc_core::common::Span,
+        call: &'a str,
+        directive: &'a str,
+    },
+    MissingModuleSpecifier {
+        span: swc_core::common::Span,
+        filename: &'a str,
+    },
+    DuplicateStepId {
+        span: swc_core::common::Span,
+        id: &'a str,
     },
 }
 
@@ -43,9 +127,33 @@ enum DirectiveLocation {
     FunctionBody,
 }
 
-fn emit_error(error: WorkflowErrorKind) {
+fn emit_error(error: WorkflowErrorKind<'_>) {
+    emit_diagnostic(error, DiagnosticLevel::Error);
+}
+
+// Report `error` at `level` if it's one that lenient mode may downgrade (forbidden expressions,
+// misspelled directives and non-deterministic calls), or as an error otherwise
+fn emit_diagnostic(error: WorkflowErrorKind<'_>, level: DiagnosticLevel) {
+    let as_warning = level == DiagnosticLevel::Warn
+        && matches!(
+            error,
+            WorkflowErrorKind::ForbiddenExpression { .. }
+                | WorkflowErrorKind::MisspelledDirective { .. }
+                | WorkflowErrorKind::NonDeterministicCall { .. }
+        );
+
+    let suggestion = match error {
+        WorkflowErrorKind::NonAsyncFunction {
+            async_at: Some(pos),
+            ..
+        } => Some(Span::new(pos, pos)),
+        _ => None,
+    };
+
     let (span, msg) = match error {
-        WorkflowErrorKind::NonAsyncFunction { span, directive } => (
+        WorkflowErrorKind::NonAsyncFunction {
+            span, directive, ..
+        } => (
             span,
             format!(
                 "Functions marked with \"{}\" must be async functions",
@@ -59,11 +167,16 @@ fn emit_error(error: WorkflowErrorKind) {
         } => (
             span,
             format!(
-                "The \"{}\" directive must be at the top of the {}",
+                "The \"{}\" directive must be at the top of the {}; only other directives such as \"use strict\" may come before it, not {}",
                 directive,
                 match location {
                     DirectiveLocation::Module => "file",
                     DirectiveLocation::FunctionBody => "function body",
+                },
+                match location {
+                    DirectiveLocation::Module => "imports or other statements",
+                    DirectiveLocation::FunctionBody =>
+                        "declarations (including functions) or other statements",
                 }
             ),
         ),
@@ -96,46 +209,312 @@ fn emit_error(error: WorkflowErrorKind) {
                 directive
             ),
         ),
+        WorkflowErrorKind::ShadowedFunction {
+            span,
+            name,
+            directive,
+        } => (
+            span,
+            format!(
+                "\"{}\" is already declared as a \"{}\" function and cannot be redeclared in the same scope",
+                name, directive
+            ),
+        ),
+        WorkflowErrorKind::MisorderedImport { span, directive } => (
+            span,
+            format!(
+                "Imports in a \"{}\" file must come directly after the directive, before any other statement",
+                directive
+            ),
+        ),
+        WorkflowErrorKind::GeneratorStep { span, directive } => (
+            span,
+            format!(
+                "Async generator functions cannot be marked with \"{}\" yet, since a step produces a single result; return the collected values instead",
+                directive
+            ),
+        ),
+        WorkflowErrorKind::TemplateLiteralDirective { span, directive } => (
+            span,
+            format!(
+                "Directives must be plain string literals, use \"{}\" instead of a template literal",
+                directive
+            ),
+        ),
+        WorkflowErrorKind::ScriptNotModule { span, directive } => (
+            span,
+            format!(
+                "Files using \"{}\" must be ES modules, but this file was parsed as a script",
+                directive
+            ),
+        ),
+        WorkflowErrorKind::ConflictingDirectives {
+            span,
+            directive,
+            other,
+        } => (
+            span,
+            format!(
+                "The \"{}\" directive cannot be combined with \"{}\" in the same function body",
+                directive, other
+            ),
+        ),
+        WorkflowErrorKind::PrivateMethod { span, directive } => (
+            span,
+            format!(
+                "Private methods cannot be marked with \"{}\", since the runtime cannot reference them from outside the class body",
+                directive
+            ),
+        ),
+        WorkflowErrorKind::Accessor { span, directive } => (
+            span,
+            format!(
+                "Getters and setters cannot be marked with \"{}\", since turning them into functions would lose their accessor semantics",
+                directive
+            ),
+        ),
+        WorkflowErrorKind::FunctionInAccessor { span, directive } => (
+            span,
+            format!(
+                "Functions defined inside a getter or setter cannot be marked with \"{}\", since a new function is created on every access and can't be registered ahead of time; define it as a method or property of the object instead",
+                directive
+            ),
+        ),
+        WorkflowErrorKind::InvalidId { span, id, pattern } => (
+            span,
+            format!(
+                "The generated ID \"{}\" doesn't match the required pattern \"{}\"",
+                id, pattern
+            ),
+        ),
+        WorkflowErrorKind::ForbiddenImport {
+            span,
+            directive,
+            specifier,
+        } => (
+            span,
+            format!(
+                "Files with \"{}\" functions cannot import \"{}\", since workflows must run the same way on every replay; move the code that needs it into a step",
+                directive, specifier
+            ),
+        ),
+        WorkflowErrorKind::ObjectNestingTooDeep {
+            span,
+            directive,
+            max_depth,
+        } => (
+            span,
+            format!(
+                "This object literal is nested deeper than `maxObjectNestingDepth` ({}), so it isn't searched for \"{}\" functions; flatten the object or raise the limit",
+                max_depth, directive
+            ),
+        ),
+        WorkflowErrorKind::ImmediatelyInvoked { span, directive } => (
+            span,
+            format!(
+                "Immediately invoked functions cannot be marked with \"{}\", since they run when the module is loaded and are never registered; declare the function and call it instead",
+                directive
+            ),
+        ),
+        WorkflowErrorKind::UnexportedWorkflow {
+            span,
+            name,
+            directive,
+        } => (
+            span,
+            format!(
+                "\"{}\" is marked with \"{}\" but isn't exported; export it so the workflow can be found and started",
+                name, directive
+            ),
+        ),
+        WorkflowErrorKind::NonDeterministicCall {
+            span,
+            call,
+            directive,
+        } => (
+            span,
+            format!(
+                "`{}` gives a different result on every replay of a \"{}\" function; move it into a step so its result is recorded",
+                call, directive
+            ),
+        ),
+        WorkflowErrorKind::MissingModuleSpecifier { span, filename } => (
+            span,
+            format!(
+                "No module specifier was given for \"{}\", so its IDs would be derived from the file name; pass the module specifier the file is imported by",
+                filename
+            ),
+        ),
+        WorkflowErrorKind::DuplicateStepId { span, id } => (
+            span,
+            format!(
+                "Another step function already has the ID \"{}\", so only one of them could be registered; give the steps nested in the same function distinct names",
+                id
+            ),
+        ),
+    };
+
+    HANDLER.with(|handler| {
+        let mut diagnostic = if as_warning {
+            handler.struct_span_warn(span, &msg)
+        } else {
+            handler.struct_span_err(span, &msg)
+        };
+        if let Some(suggestion) = suggestion {
+            diagnostic.span_suggestion_with_applicability(
+                suggestion,
+                "make it async",
+                "async ".to_string(),
+                Applicability::MachineApplicable,
+            );
+        }
+        diagnostic.emit()
+    });
+}
+
+// Opt-in diagnostics that point out likely mistakes without failing the build
+#[derive(Debug, Clone)]
+enum WorkflowWarningKind<'a> {
+    WorkflowWithoutSteps {
+        span: swc_core::common::Span,
+        directive: &'a str,
+    },
+    DirectiveFileImport {
+        span: swc_core::common::Span,
+        directive: &'a str,
+        specifier: &'a str,
+    },
+    ComputedStepKey {
+        span: swc_core::common::Span,
+        name: &'a str,
+        member: bool,
+    },
+    UnhandledStepCall {
+        span: swc_core::common::Span,
+    },
+    AwaitedNonStep {
+        span: swc_core::common::Span,
+        name: &'a str,
+    },
+    ReassignedStepObject {
+        span: swc_core::common::Span,
+        name: &'a str,
+    },
+    InlinedNestedStep {
+        span: swc_core::common::Span,
+        directive: &'a str,
+    },
+}
+
+fn emit_warning(warning: WorkflowWarningKind<'_>) {
+    let (span, msg) = match warning {
+        WorkflowWarningKind::WorkflowWithoutSteps { span, directive } => (
+            span,
+            format!(
+                "This \"{}\" function doesn't call any step function; move its work into steps so it runs durably and deterministically",
+                directive
+            ),
+        ),
+        WorkflowWarningKind::DirectiveFileImport {
+            span,
+            directive,
+            specifier,
+        } => (
+            span,
+            format!(
+                "This \"{}\" file imports from \"{}\", which is also a directive file; its functions are replaced by the transform, so only call them as steps or workflows",
+                directive, specifier
+            ),
+        ),
+        WorkflowWarningKind::ComputedStepKey { span, name, member } => (
+            span,
+            if member {
+                format!(
+                    "Computed keys that read a member (e.g. an enum value) aren't resolved for steps, so this step is named `{}` after its position in the object; use a string literal key to give it a stable ID",
+                    name
+                )
+            } else {
+                format!(
+                    "This computed key can't be resolved at compile time, so the step is named `{}` after its position in the object; use a string literal key to give it a stable ID",
+                    name
+                )
+            },
+        ),
+        WorkflowWarningKind::UnhandledStepCall { span } => (
+            span,
+            "This step call is awaited outside of a try/catch, so once its retries are used up the error ends the workflow; catch it to handle the failure explicitly".to_string(),
+        ),
+        WorkflowWarningKind::AwaitedNonStep { span, name } => (
+            span,
+            format!(
+                "`{}` isn't a step function of this file, so awaiting it in a workflow repeats its work on every replay; call it from a step instead",
+                name
+            ),
+        ),
+        WorkflowWarningKind::ReassignedStepObject { span, name } => (
+            span,
+            format!(
+                "`{}` holds object property steps, which stay registered for the object it was declared with; reassigning it leaves the new object out of sync with the registered steps",
+                name
+            ),
+        ),
+        WorkflowWarningKind::InlinedNestedStep { span, directive } => (
+            span,
+            format!(
+                "This \"{}\" function is nested in another step and captures its variables, so it runs as part of the enclosing step rather than as a step of its own; move it out of the enclosing step to make it a separate step",
+                directive
+            ),
+        ),
     };
 
-    HANDLER.with(|handler| handler.struct_span_err(span, &msg).emit());
+    HANDLER.with(|handler| handler.struct_span_warn(span, &msg).emit());
 }
 
-// Helper function to detect similar strings (typos)
-fn detect_similar_strings(a: &str, b: &str) -> bool {
-    let a_chars: Vec<char> = a.chars().collect();
-    let b_chars: Vec<char> = b.chars().collect();
+// The shortest literal that counts as a typo
+const MIN_MISSPELLING_LEN: usize = 4;
 
-    if (a_chars.len() as i32 - b_chars.len() as i32).abs() > 1 {
+// Helper function to detect similar strings (typos) of the directive `b`
+fn detect_similar_strings(a: &str, b: &str) -> bool {
+    if a.chars().count() < MIN_MISSPELLING_LEN {
         return false;
     }
+    // Only literals that start with the directive's leading word (e.g. `use `) can be typos.
+    // This keeps short literals and other frameworks' directives (`"use client"`) out.
+    if let Some((word, _)) = b.split_once(' ') {
+        if !a
+            .strip_prefix(word)
+            .is_some_and(|rest| rest.starts_with(' '))
+        {
+            return false;
+        }
+    }
 
-    let mut differences = 0;
-    let mut i = 0;
-    let mut j = 0;
-
-    while i < a_chars.len() && j < b_chars.len() {
-        if a_chars[i] != b_chars[j] {
-            differences += 1;
-            if differences > 1 {
-                return false;
-            }
+    let a_chars: Vec<char> = a.chars().collect();
+    let b_chars: Vec<char> = b.chars().collect();
 
-            if a_chars.len() > b_chars.len() {
-                i += 1;
-            } else if b_chars.len() > a_chars.len() {
-                j += 1;
-            } else {
-                i += 1;
-                j += 1;
-            }
-        } else {
-            i += 1;
-            j += 1;
-        }
+    if a_chars.len().abs_diff(b_chars.len()) > 1 {
+        return false;
     }
 
-    differences + (a_chars.len() - i) + (b_chars.len() - j) == 1
+    // Strip the common prefix and suffix. The strings are at distance 1 exactly when what's left
+    // is a single substituted, inserted or deleted character.
+    let prefix = a_chars
+        .iter()
+        .zip(&b_chars)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let max_suffix = a_chars.len().min(b_chars.len()) - prefix;
+    let suffix = a_chars[prefix..]
+        .iter()
+        .rev()
+        .zip(b_chars[prefix..].iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let a_rest = a_chars.len() - prefix - suffix;
+    let b_rest = b_chars.len() - prefix - suffix;
+    matches!((a_rest, b_rest), (1, 1) | (1, 0) | (0, 1))
 }
 
 /// Check if an object literal has the expected keys for the `using` transformation env object.
@@ -169,12 +548,13 @@ fn is_using_env_object(obj: &ObjectLit) -> bool {
 }
 
 /// Check if a list of statements represents the TypeScript `using` transformation pattern.
-/// When TypeScript transforms `using` declarations, it creates:
+/// When TypeScript transforms `using` (or `await using`) declarations, it creates:
 /// ```js
 /// const env = { stack: [], error: void 0, hasError: false };
 /// try { ... } catch (e) { ... } finally { ... }
 /// ```
-/// This function returns the try block's body if the pattern matches.
+/// For `await using` it only differs in awaiting the result of `__disposeResources(env)` in the
+/// `finally` block. This function returns the try block's body if the pattern matches.
 ///
 /// The pattern matching is strict to avoid false positives:
 /// - First statement must be a const declaration with an object containing stack/error/hasError keys
@@ -230,81 +610,778 @@ fn is_using_pattern(stmts: &[Stmt]) -> bool {
 
 /// Helper to get a directive from the first statement of a block.
 fn get_directive_from_block(block: &BlockStmt, directive: &str) -> bool {
-    if let Some(first_stmt) = block.stmts.first() {
-        if let Stmt::Expr(ExprStmt { expr, .. }) = first_stmt {
-            if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
-                return value == directive;
-            }
+    if let Some(Stmt::Expr(ExprStmt { expr, .. })) = block.stmts.first() {
+        if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
+            return value == directive;
         }
     }
     false
 }
 
-/// Helper to get the first string literal from a block (for misspelling detection).
-fn get_first_string_literal_from_block(
-    block: &BlockStmt,
-) -> Option<(&Str, swc_core::common::Span)> {
-    if let Some(first_stmt) = block.stmts.first() {
-        if let Stmt::Expr(ExprStmt { expr, span, .. }) = first_stmt {
-            if let Expr::Lit(Lit::Str(s)) = &**expr {
-                return Some((s, *span));
-            }
-        }
+/// Whether a statement can be part of a directive prologue (a string literal expression
+/// statement such as `"use strict"`).
+fn is_prologue_stmt(stmt: &Stmt) -> bool {
+    if let Stmt::Expr(ExprStmt { expr, .. }) = stmt {
+        return matches!(&**expr, Expr::Lit(Lit::Str(_)));
     }
-    None
+    false
 }
 
-/// Helper to remove a directive from the first statement of a try block in a `using` pattern.
-/// Only removes if the pattern is verified first.
-fn remove_directive_from_using_pattern(stmts: &mut [Stmt], directive: &str) {
-    // First verify this is actually the using pattern
-    if !is_using_pattern(stmts) {
+/// Comments at the top of the file (such as a `/*! @license */` banner) are emitted with the
+/// item at the start of the module. When the transform puts an import or the manifest comment
+/// in front of that item, move the start onto the added item so the banner stays first.
+fn keep_leading_comments_first(items: &mut [ModuleItem], module_span: swc_core::common::Span) {
+    let lo = module_span.lo;
+    if lo.is_dummy() || !items.iter().any(|item| item.span_lo() == lo) {
         return;
     }
-
-    if stmts.len() >= 2 {
-        if let Stmt::Try(try_stmt) = &mut stmts[1] {
-            let block = &mut try_stmt.block;
-            if !block.stmts.is_empty() {
-                if let Stmt::Expr(ExprStmt { expr, .. }) = &block.stmts[0] {
-                    if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
-                        if value == directive {
-                            block.stmts.remove(0);
-                        }
-                    }
-                }
-            }
-        }
+    let span = match items.first_mut() {
+        Some(ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl))) => &mut import_decl.span,
+        Some(ModuleItem::Stmt(Stmt::Expr(expr_stmt))) => &mut expr_stmt.span,
+        _ => return,
+    };
+    if span.is_dummy() {
+        *span = swc_core::common::Span::new(lo, lo);
     }
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq)]
-#[serde(rename_all = "camelCase", deny_unknown_fields)]
-pub enum TransformMode {
-    Step,
-    Workflow,
-    Client,
+/// A value of the JSON manifest, with the keys of its objects in the order they are emitted.
+enum ManifestValue {
+    Str(String),
+    Num(usize),
+    Object(Vec<(String, ManifestValue)>),
 }
 
-#[derive(Debug)]
-pub struct StepTransform {
-    mode: TransformMode,
-    filename: String,
-    // The module specifier used for ID generation (e.g., "point@0.0.1" or "./src/models/Point")
-    // If None, falls back to using "./{filename}" format
-    module_specifier: Option<String>,
-    // Track if the file has a top-level "use step" directive
-    has_file_step_directive: bool,
-    // Track if the file has a top-level "use workflow" directive
-    has_file_workflow_directive: bool,
+impl ManifestValue {
+    fn entry(key: &str, value: ManifestValue) -> (String, ManifestValue) {
+        (key.to_string(), value)
+    }
+
+    /// Serialize as JSON. Strings are escaped as by `JSON.stringify`, and the `/` of a `*/` is
+    /// escaped too, so a name can't end the comment the manifest is emitted in.
+    fn to_json(&self) -> String {
+        match self {
+            ManifestValue::Str(value) => json_string(value),
+            ManifestValue::Num(value) => value.to_string(),
+            ManifestValue::Object(entries) => {
+                let entries: Vec<String> = entries
+                    .iter()
+                    .map(|(key, value)| format!("{}:{}", json_string(key), value.to_json()))
+                    .collect();
+                format!("{{{}}}", entries.join(","))
+            }
+        }
+    }
+
+    /// Build the JavaScript literal for the value, e.g. for the exported manifest.
+    fn to_expr(&self) -> Expr {
+        match self {
+            ManifestValue::Str(value) => Expr::Lit(Lit::Str(Str {
+                span: DUMMY_SP,
+                value: value.as_str().into(),
+                raw: None,
+            })),
+            ManifestValue::Num(value) => Expr::Lit(Lit::Num(Number {
+                span: DUMMY_SP,
+                value: *value as f64,
+                raw: None,
+            })),
+            ManifestValue::Object(entries) => Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props: entries
+                    .iter()
+                    .map(|(key, value)| {
+                        PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                            key: PropName::Str(Str {
+                                span: DUMMY_SP,
+                                value: key.as_str().into(),
+                                raw: None,
+                            }),
+                            value: Box::new(value.to_expr()),
+                        })))
+                    })
+                    .collect(),
+            }),
+        }
+    }
+}
+
+fn json_string(value: &str) -> String {
+    serde_json::Value::from(value)
+        .to_string()
+        .replace("*/", "*\\/")
+}
+
+/// Helper to find `directive` within the leading directive prologue of a block, so that other
+/// prologue entries such as `"use strict"` may come before it.
+fn find_prologue_directive(stmts: &[Stmt], directive: &str) -> Option<usize> {
+    stmts
+        .iter()
+        .take_while(|stmt| is_prologue_stmt(stmt))
+        .position(|stmt| {
+            if let Stmt::Expr(ExprStmt { expr, .. }) = stmt {
+                if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
+                    return value == directive;
+                }
+            }
+            false
+        })
+}
+
+/// Helper to check whether a function body starts with `directive`, either in its prologue or
+/// inside the TypeScript `using` transformation pattern.
+fn body_has_directive(stmts: &[Stmt], directive: &str) -> bool {
+    find_prologue_directive(stmts, directive).is_some()
+        || get_try_block_from_using_pattern(stmts)
+            .is_some_and(|block| get_directive_from_block(block, directive))
+}
+
+/// Helper to get the name an object property step is registered under. A computed key that
+/// isn't a string or number literal can't be resolved statically, so it's named by its position
+/// in the object literal instead, in brackets so it can't clash with a real `computed0` key.
+fn object_property_key(key: &PropName, index: usize) -> String {
+    match key {
+        PropName::Ident(ident) => ident.sym.to_string(),
+        PropName::Str(s) => s.value.to_string_lossy().to_string(),
+        PropName::Num(n) => n.value.to_string(),
+        PropName::BigInt(n) => n.value.to_string(),
+        PropName::Computed(computed) => match &*computed.expr {
+            Expr::Lit(Lit::Str(s)) => s.value.to_string_lossy().to_string(),
+            Expr::Lit(Lit::Num(n)) => n.value.to_string(),
+            _ => format!("[computed{}]", index),
+        },
+    }
+}
+
+/// Helper to append an object property key to the path of the object it's in. Objects that are
+/// only destructured have no name, so their properties start the path.
+fn object_property_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}/{}", parent, key)
+    }
+}
+
+/// Helper to get the name of the variable an object property step is hoisted into, e.g.
+/// `tools$search` for `tools.search`, prefixed with the workflow it's defined in. Its parts are
+/// joined with `separator` (the `hoistedNameSeparator` option), which also replaces the `/` of a
+/// nested path. The brackets of a `[computed0]` key are left out, and other characters that
+/// can't be part of an identifier, such as the `-` in a `"not-found"` key, become `_`. The
+/// result may clash with another name and is made unique by
+/// `StepTransform::object_property_hoisted_var`.
+fn object_property_hoist_name(
+    workflow_name: Option<&str>,
+    parent: &str,
+    prop_key: &str,
+    separator: &str,
+) -> String {
+    let mut name = String::new();
+    let parts = workflow_name.into_iter().chain([parent, prop_key]);
+    for (index, part) in parts.enumerate() {
+        if index > 0 {
+            name.push_str(separator);
+        }
+        for c in part.chars() {
+            match c {
+                '/' => name.push_str(separator),
+                '[' | ']' => {}
+                c if c.is_alphanumeric() || c == '_' || c == '$' => name.push(c),
+                _ => name.push('_'),
+            }
+        }
+    }
+    // A numeric key of an object that is only destructured, e.g. for `{ 2: async () => {} }`
+    if !name.starts_with(Ident::is_valid_start) {
+        name.insert(0, '_');
+    }
+    name
+}
+
+/// Helper to get the value of a no-substitution template literal (e.g. `` `use step` ``),
+/// which is not a directive but is sometimes emitted by bundlers in place of one.
+fn get_template_literal_value(expr: &Expr) -> Option<String> {
+    if let Expr::Tpl(Tpl { exprs, quasis, .. }) = expr {
+        if exprs.is_empty() && quasis.len() == 1 {
+            return quasis[0]
+                .cooked
+                .as_ref()
+                .map(|cooked| cooked.to_string_lossy().to_string());
+        }
+    }
+    None
+}
+
+/// Helper to get the first string literal from a block (for misspelling detection).
+fn get_first_string_literal_from_block(
+    block: &BlockStmt,
+) -> Option<(&Str, swc_core::common::Span)> {
+    if let Some(Stmt::Expr(ExprStmt { expr, span, .. })) = block.stmts.first() {
+        if let Expr::Lit(Lit::Str(s)) = &**expr {
+            return Some((s, *span));
+        }
+    }
+    None
+}
+
+/// Helper to remove a directive from the first statement of a try block in a `using` pattern.
+/// Only removes if the pattern is verified first.
+fn remove_directive_from_using_pattern(stmts: &mut [Stmt], directive: &str) {
+    // First verify this is actually the using pattern
+    if !is_using_pattern(stmts) {
+        return;
+    }
+
+    if stmts.len() >= 2 {
+        if let Stmt::Try(try_stmt) = &mut stmts[1] {
+            let block = &mut try_stmt.block;
+            if !block.stmts.is_empty() {
+                if let Stmt::Expr(ExprStmt { expr, .. }) = &block.stmts[0] {
+                    if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
+                        if value == directive {
+                            block.stmts.remove(0);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Check if a callee is one of the well-known async downleveling helpers.
+fn is_async_helper_callee(callee: &Callee) -> bool {
+    if let Callee::Expr(expr) = callee {
+        if let Expr::Ident(ident) = &**expr {
+            return matches!(
+                ident.sym.as_ref(),
+                "_asyncToGenerator" | "_async_to_generator" | "asyncToGenerator"
+            );
+        }
+    }
+    false
+}
+
+/// Check if a list of statements represents an async function body that was
+/// downleveled by an async-to-generator transform (e.g. SWC or Babel). These
+/// transforms rewrite `async function f() { ... }` into:
+/// ```js
+/// function f() {
+///   return _asyncToGenerator(function* () { ... })();
+/// }
+/// ```
+/// This function returns the generator's body if the pattern matches.
+///
+/// The pattern matching is strict to avoid false positives:
+/// - The body must consist of a single `return` statement
+/// - The returned value must invoke the result of a known helper call (`_asyncToGenerator`,
+///   `_async_to_generator`, `asyncToGenerator`), either directly or via `.apply()`/`.call()`
+/// - The helper's only argument must be a generator function expression
+fn get_generator_body_from_async_helper_pattern(stmts: &[Stmt]) -> Option<&BlockStmt> {
+    let [Stmt::Return(ReturnStmt { arg: Some(arg), .. })] = stmts else {
+        return None;
+    };
+    let Expr::Call(call) = &**arg else {
+        return None;
+    };
+
+    // Unwrap the invocation of the wrapped function: `helper(...)()` or
+    // `helper(...).apply(this, arguments)`
+    let helper_call = match &call.callee {
+        Callee::Expr(callee) => match &**callee {
+            Expr::Call(inner) => inner,
+            Expr::Member(member) => match (&*member.obj, &member.prop) {
+                (Expr::Call(inner), MemberProp::Ident(prop))
+                    if prop.sym == "apply" || prop.sym == "call" =>
+                {
+                    inner
+                }
+                _ => return None,
+            },
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    if !is_async_helper_callee(&helper_call.callee) || helper_call.args.len() != 1 {
+        return None;
+    }
+    match &*helper_call.args[0].expr {
+        Expr::Fn(fn_expr) if fn_expr.function.is_generator && !fn_expr.function.is_async => {
+            fn_expr.function.body.as_ref()
+        }
+        _ => None,
+    }
+}
+
+/// Helper to remove a directive from the generator body of a downleveled async function.
+fn remove_directive_from_async_helper_pattern(stmts: &mut [Stmt], directive: &str) {
+    // First verify this is actually the async helper pattern
+    if get_generator_body_from_async_helper_pattern(stmts).is_none() {
+        return;
+    }
+
+    if let [Stmt::Return(ReturnStmt { arg: Some(arg), .. })] = stmts {
+        if let Expr::Call(call) = &mut **arg {
+            let helper_call = match &mut call.callee {
+                Callee::Expr(callee) => match &mut **callee {
+                    Expr::Call(inner) => inner,
+                    Expr::Member(member) => match &mut *member.obj {
+                        Expr::Call(inner) => inner,
+                        _ => return,
+                    },
+                    _ => return,
+                },
+                _ => return,
+            };
+            if let Expr::Fn(fn_expr) = &mut *helper_call.args[0].expr {
+                if let Some(body) = &mut fn_expr.function.body {
+                    if get_directive_from_block(body, directive) {
+                        body.stmts.remove(0);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Check if a function is async, either natively or because its body matches
+/// the downleveled async-to-generator pattern.
+fn is_async_function(function: &Function) -> bool {
+    function.is_async
+        || function
+            .body
+            .as_ref()
+            .is_some_and(|body| get_generator_body_from_async_helper_pattern(&body.stmts).is_some())
+}
+
+/// Helper to insert new module items at the given indices of the original list.
+/// Expects `inserts` sorted by index; items sharing an index keep their relative order.
+fn splice_items_in_order(items: &mut Vec<ModuleItem>, inserts: Vec<(usize, ModuleItem)>) {
+    if inserts.is_empty() {
+        return;
+    }
+
+    let mut inserts = inserts.into_iter().peekable();
+    let mut result = Vec::with_capacity(items.len() + inserts.len());
+    for (i, item) in std::mem::take(items).into_iter().enumerate() {
+        while let Some((_, inserted)) = inserts.next_if(|(index, _)| *index <= i) {
+            result.push(inserted);
+        }
+        result.push(item);
+    }
+    result.extend(inserts.map(|(_, inserted)| inserted));
+    *items = result;
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub enum TransformMode {
+    Step,
+    Workflow,
+    Client,
+}
+
+/// How the runtime helpers referenced by the generated code (e.g. `registerStepFunction`)
+/// are brought into scope.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum RegistrationFormat {
+    /// Import the helpers with ESM `import` declarations.
+    #[default]
+    Esm,
+    /// Resolve the helpers at runtime so the output works when loaded as either ESM or CJS:
+    /// `globalThis[Symbol.for("<source>")]` is used when the runtime exposes it, and
+    /// `require("<source>")` otherwise.
+    Dual,
+}
+
+/// How the JSON manifest of discovered workflows, steps, classes and caches is emitted.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ManifestFormat {
+    /// A `/**__internal_workflows{...}*/` comment keyed by kind, then by filename.
+    #[default]
+    Comment,
+    /// A `/**__internal_workflows_ndjson ... */` comment holding a single-line JSON record keyed
+    /// by filename, so records from a concatenated bundle can be collected and merged line by line.
+    Ndjson,
+}
+
+/// Where the manifest of a file ends up in the output.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ManifestOutput {
+    /// A comment in the format chosen by `manifest_format`.
+    #[default]
+    Comment,
+    /// Nowhere; the build collects the manifest by other means.
+    None,
+    /// An `export const __workflow_manifest = { ... }` object literal at the end of the module,
+    /// which survives minifiers that strip comments. It has the JSON shape of
+    /// [`ManifestFormat::Comment`].
+    ExportConst,
+}
+
+/// Which TypeScript type annotation, if any, is attached to the bindings generated for step
+/// proxies in workflow mode (e.g. `var add = globalThis[Symbol.for("WORKFLOW_USE_STEP")](...)`).
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ProxyTypeAnnotation {
+    /// Leave the generated bindings unannotated.
+    #[default]
+    None,
+    /// Annotate the generated bindings as `any`.
+    Any,
+    /// Annotate the generated bindings with a function type built from the original step
+    /// function's parameters and return type (`Promise<any>` when no return type is declared).
+    Signature,
+}
+
+/// What happens when a file parsed as a script (rather than an ES module) contains step or
+/// workflow functions.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ScriptHandling {
+    /// Convert the script to a module, so the generated imports can be added.
+    #[default]
+    Convert,
+    /// Emit an error and leave the script unchanged.
+    Error,
+}
+
+/// What the workflow registrations emitted in workflow mode do when the module is evaluated
+/// again (e.g. during hot module reloading) and an ID is already registered.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum DuplicateWorkflowRegistration {
+    /// Replace the previous registration.
+    #[default]
+    Overwrite,
+    /// Keep the previous registration and skip the new one.
+    Keep,
+    /// Replace the previous registration and log a `console.warn` about it.
+    Warn,
+}
+
+/// The level that mistakes which don't break the generated code, such as a misspelled
+/// directive or a forbidden `this`, are reported at.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum DiagnosticLevel {
+    /// Report them as errors, which fail the build.
+    #[default]
+    Error,
+    /// Report them as warnings, e.g. for editor or lint integrations. Structural problems such
+    /// as a non-async step function are still errors.
+    Warn,
+}
+
+/// Optional settings for [`StepTransform`]. Every option defaults to the plugin's
+/// standard behavior, so `TransformOptions::default()` produces the same output as
+/// [`StepTransform::new`] on its own.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct TransformOptions {
+    /// How runtime helpers such as `registerStepFunction` are brought into scope.
+    pub registration_format: RegistrationFormat,
+    /// Module the runtime helpers of step mode (`registerStepFunction`,
+    /// `__private_getClosureVars`) are brought in from.
+    pub private_import_source: String,
+    /// Module `registerSerializationClass` is brought in from.
+    pub class_serialization_import_source: String,
+    /// Type annotation attached to generated step proxy bindings in workflow mode.
+    pub proxy_type_annotation: ProxyTypeAnnotation,
+    /// Require the imports of a file with a module-level directive to come directly after the
+    /// directive, before any other statement.
+    pub strict_import_order: bool,
+    /// Require module-level workflow functions to be exported, instead of registering
+    /// non-exported ones as well.
+    pub require_exported_workflows: bool,
+    /// Report an error instead of deriving IDs from the file name when the file defines
+    /// workflows, steps, classes or caches but no module specifier was given.
+    pub require_module_specifier: bool,
+    /// Allow a file with a module-level directive to export literal constants and classes next
+    /// to its async functions. They're left untouched; exported functions still need to be
+    /// async.
+    pub allow_non_function_exports: bool,
+    /// Additional runtime-provided globals (e.g. `Deno`, `Bun`, `structuredClone`) that
+    /// nested steps should never capture as closure variables.
+    pub extra_globals: Vec<String>,
+    /// Wrap step proxies generated in workflow mode in a function expression named after the
+    /// step, so stack traces show the step name.
+    pub named_step_wrappers: bool,
+    /// Give the function wrapping a step proxy in workflow mode the same number of leading params
+    /// as the step, so code relying on `fn.length` keeps working.
+    pub preserve_step_arity: bool,
+    /// Attach a suggestion that inserts `async` to the error about a non-async step, workflow or
+    /// cache function, so editors can offer it as a quick fix.
+    pub suggest_async_fix: bool,
+    /// Separator between the enclosing function and a nested step in the step ID, e.g. the `/`
+    /// of `step//./input//calculate/scale`.
+    pub nested_step_id_separator: String,
+    /// Separator between the enclosing function and a nested step in the name of the variable the
+    /// step is hoisted into, e.g. the `$` of `calculate$scale`. Must only contain characters that
+    /// can be part of an identifier.
+    pub hoisted_name_separator: String,
+    /// Format of the JSON manifest comment emitted at the top of each file.
+    pub manifest_format: ManifestFormat,
+    /// Where the manifest is emitted: as a comment, as an exported object, or not at all.
+    pub manifest_output: ManifestOutput,
+    /// Include the transform mode (`"mode":"step"`, `"workflow"` or `"client"`) in the manifest.
+    pub manifest_mode: bool,
+    /// Include the version of this transform (`"transformVersion":"x.y.z"`) in the manifest.
+    pub manifest_transform_version: bool,
+    /// Include the 1-based `"line"` and `"col"` of each step and workflow in the manifest.
+    /// Needs a source map (see [`StepTransform::with_source_map`]).
+    pub include_positions: bool,
+    /// Directive marking step functions, `"use step"` when unset.
+    pub step_directive: Option<String>,
+    /// Directive marking workflow functions, `"use workflow"` when unset.
+    pub workflow_directive: Option<String>,
+    /// Names of decorators (e.g. `step` for `@step` or `@step()`) that mark a class method as
+    /// a step, like the step directive at the start of its body. On a class, they mark all of
+    /// its async methods.
+    pub step_decorators: Vec<String>,
+    /// Names of decorators that mark a static class method as a workflow. On a class, they
+    /// mark all of its static async methods.
+    pub workflow_decorators: Vec<String>,
+    /// Whether scripts containing step or workflow functions are converted to modules.
+    pub script_handling: ScriptHandling,
+    /// Warn about workflow functions that never call a step function declared in the same file.
+    pub warn_workflows_without_steps: bool,
+    /// Warn when a file with a module-level directive imports from another directive file.
+    pub warn_directive_file_imports: bool,
+    /// Warn about object property steps under a computed key that isn't a literal, since their
+    /// IDs depend on their position in the object. On by default.
+    pub warn_computed_step_keys: bool,
+    /// Warn about step calls a workflow function awaits outside of a try/catch.
+    pub warn_unhandled_step_calls: bool,
+    /// Warn about calls a workflow function awaits that don't go to a step function of the same
+    /// file or an import.
+    pub warn_awaited_non_steps: bool,
+    /// Warn about assignments to a variable whose object literal holds step functions, since
+    /// the registered steps keep pointing at the functions of the original object.
+    pub warn_reassigned_step_objects: bool,
+    /// Module specifiers of directive files for `warn_directive_file_imports`, in addition to
+    /// files named with a `.step` or `.workflow` segment.
+    pub directive_files: Vec<String>,
+    /// Modules (e.g. `fs`, `child_process`) that files with workflow functions must not import,
+    /// checked in workflow mode. `node:` imports and subpaths such as `fs/promises` match too.
+    pub forbidden_workflow_imports: Vec<String>,
+    /// Deprecated: `false` leaves the manifest out of the output, the same as
+    /// `manifest_output: ManifestOutput::None`, which takes its place.
+    pub emit_manifest_comment: bool,
+    /// Regular expression every generated step and workflow ID must match (e.g.
+    /// `^(step|workflow)//\./[a-z0-9/_-]+//.+$`). Unanchored patterns may match any part of it.
+    pub id_pattern: Option<String>,
+    /// Register all steps of a file in step mode with a single
+    /// `registerStepFunctions([[id, fn], ...])` call instead of one `registerStepFunction` call
+    /// per step.
+    pub batch_step_registrations: bool,
+    /// How workflow registrations handle an ID that is already registered when the module is
+    /// evaluated more than once.
+    pub duplicate_workflow_registration: DuplicateWorkflowRegistration,
+    /// Level of diagnostics for forbidden expressions and misspelled directives.
+    pub diagnostics: DiagnosticLevel,
+    /// Annotate the step proxies generated in workflow mode with `/*#__PURE__*/`, so bundlers
+    /// can remove the ones whose binding is unused. Needs the file's comments (see
+    /// [`StepTransform::with_comments`]).
+    pub pure_step_proxies: bool,
+    /// Report calls to `Math.random()`, `Date.now()` and `new Date()` in workflow functions at
+    /// this level. They aren't checked if unset.
+    pub non_deterministic_calls: Option<DiagnosticLevel>,
+    /// How many levels of nested object literals (and object arguments of calls) are searched
+    /// for object property steps before the transform stops with an error.
+    pub max_object_nesting_depth: usize,
+    /// Give the declarations of nested and object property steps hoisted in step and client
+    /// mode the span of the original function, so source maps point them back to it.
+    pub preserve_hoisted_spans: bool,
+}
+
+impl Default for TransformOptions {
+    fn default() -> Self {
+        Self {
+            registration_format: RegistrationFormat::default(),
+            private_import_source: "workflow/internal/private".to_string(),
+            class_serialization_import_source: "workflow/internal/class-serialization".to_string(),
+            proxy_type_annotation: ProxyTypeAnnotation::default(),
+            strict_import_order: false,
+            require_exported_workflows: false,
+            require_module_specifier: false,
+            allow_non_function_exports: false,
+            extra_globals: Vec::new(),
+            named_step_wrappers: false,
+            preserve_step_arity: false,
+            suggest_async_fix: false,
+            nested_step_id_separator: "/".to_string(),
+            hoisted_name_separator: "$".to_string(),
+            manifest_format: ManifestFormat::default(),
+            manifest_output: ManifestOutput::default(),
+            manifest_mode: false,
+            manifest_transform_version: false,
+            include_positions: false,
+            step_directive: None,
+            workflow_directive: None,
+            step_decorators: Vec::new(),
+            workflow_decorators: Vec::new(),
+            script_handling: ScriptHandling::default(),
+            warn_workflows_without_steps: false,
+            warn_directive_file_imports: false,
+            warn_computed_step_keys: true,
+            warn_unhandled_step_calls: false,
+            warn_awaited_non_steps: false,
+            warn_reassigned_step_objects: false,
+            directive_files: Vec::new(),
+            forbidden_workflow_imports: Vec::new(),
+            emit_manifest_comment: true,
+            id_pattern: None,
+            batch_step_registrations: false,
+            duplicate_workflow_registration: DuplicateWorkflowRegistration::default(),
+            diagnostics: DiagnosticLevel::default(),
+            pure_step_proxies: false,
+            non_deterministic_calls: None,
+            max_object_nesting_depth: 32,
+            preserve_hoisted_spans: false,
+        }
+    }
+}
+
+/// Run the transform over `program` in place, as the SWC plugin does.
+///
+/// `filename` is the path used in the manifest and, when `module_specifier` is `None`, for ID
+/// generation (see [`StepTransform::new`]). Use [`StepTransform::with_options`] directly to pass
+/// [`TransformOptions`].
+///
+/// Must be called within `swc_core::common::GLOBALS`. Invalid usage is reported through
+/// `swc_core::common::errors::HANDLER`, which has to be set for inputs that may contain errors.
+///
+/// ```
+/// use swc_core::common::{FileName, Globals, SourceMap, GLOBALS};
+/// use swc_core::ecma::ast::{ModuleDecl, ModuleItem, Program};
+/// use swc_core::ecma::parser::{Parser, StringInput, Syntax};
+/// use swc_workflow::{transform_program, TransformMode};
+///
+/// let cm = SourceMap::default();
+/// let fm = cm.new_source_file(
+///     FileName::Anon.into(),
+///     "export async function add(a, b) { \"use step\"; return a + b; }".to_string(),
+/// );
+/// let module = Parser::new(Syntax::default(), StringInput::from(&*fm), None)
+///     .parse_module()
+///     .unwrap();
+/// let mut program = Program::Module(module);
+///
+/// GLOBALS.set(&Globals::new(), || {
+///     transform_program(&mut program, TransformMode::Step, "src/math.js".into(), None);
+/// });
+///
+/// // Step mode imports `registerStepFunction` to register the step
+/// let Program::Module(module) = program else { unreachable!() };
+/// assert!(matches!(
+///     &module.body[0],
+///     ModuleItem::ModuleDecl(ModuleDecl::Import(_))
+/// ));
+/// ```
+pub fn transform_program(
+    program: &mut Program,
+    mode: TransformMode,
+    filename: String,
+    module_specifier: Option<String>,
+) {
+    program.visit_mut_with(&mut StepTransform::new(mode, filename, module_specifier));
+}
+
+/// What a file defines, as listed in its JSON manifest comment: `(name, ID)` pairs sorted by
+/// name, e.g. `("add", "step//./src/math//add")`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorkflowManifest {
+    pub workflows: Vec<(String, String)>,
+    pub steps: Vec<(String, String)>,
+    /// Classes with custom serialization.
+    pub classes: Vec<(String, String)>,
+    /// `"use cache"` functions.
+    pub caches: Vec<(String, String)>,
+}
+
+/// Collect the workflows, steps, classes and caches `program` defines without modifying it.
+///
+/// The result matches the manifest comment [`transform_program`] would emit for the same
+/// arguments, so tooling doesn't have to parse it back out of the output. Use
+/// [`StepTransform::analyze`] to apply [`TransformOptions`].
+pub fn analyze(
+    program: &Program,
+    mode: TransformMode,
+    filename: String,
+    module_specifier: Option<String>,
+) -> WorkflowManifest {
+    StepTransform::new(mode, filename, module_specifier).analyze(program)
+}
+
+// Drops the diagnostics of `StepTransform::analyze`
+struct SilentEmitter;
+
+impl Emitter for SilentEmitter {
+    fn emit(&mut self, _: &mut DiagnosticBuilder<'_>) {}
+}
+
+// The source map given to `StepTransform::with_source_map`, which has no `Debug` of its own
+struct ManifestSourceMap(Lrc<dyn SourceMapper>);
+
+impl std::fmt::Debug for ManifestSourceMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ManifestSourceMap")
+    }
+}
+
+// The comments given to `StepTransform::with_comments`, likewise without a `Debug`
+struct ProxyComments(Box<dyn Comments>);
+
+impl std::fmt::Debug for ProxyComments {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ProxyComments")
+    }
+}
+
+#[derive(Debug)]
+pub struct StepTransform {
+    mode: TransformMode,
+    filename: String,
+    // Optional settings that tweak the generated output
+    options: TransformOptions,
+    // Directive strings marking step and workflow functions ("use step" and "use workflow"
+    // unless configured otherwise)
+    step_directive: String,
+    workflow_directive: String,
+    // Compiled `id_pattern` option, and the generated IDs that don't match it with the first
+    // real span they were created for (reported once the whole file has been transformed)
+    id_pattern: Option<Regex>,
+    invalid_ids: RefCell<HashMap<String, swc_core::common::Span>>,
+    // The module specifier used for ID generation (e.g., "point@0.0.1" or "./src/models/Point")
+    // If None, falls back to using "./{filename}" format
+    module_specifier: Option<String>,
+    // Track if the file has a top-level "use step" directive
+    has_file_step_directive: bool,
+    // Track if the file has a top-level "use workflow" directive
+    has_file_workflow_directive: bool,
     // Set of function names that are step functions
     step_function_names: HashSet<String>,
     // Set of function names that are workflow functions
     workflow_function_names: HashSet<String>,
+    // Span of the first function recorded under each step/workflow name, for `include_positions`
+    step_function_spans: HashMap<String, swc_core::common::Span>,
+    workflow_function_spans: HashMap<String, swc_core::common::Span>,
+    // Resolves those spans to lines and columns (see `with_source_map`)
+    source_map: Option<ManifestSourceMap>,
+    // Receives the `/*#__PURE__*/` annotations of step proxies (see `with_comments`)
+    comments: Option<ProxyComments>,
+    // Set of "use cache" function names (nested ones are scoped as "parent/name")
+    cache_function_names: HashSet<String>,
     // Map from export name to actual const name for default exports (e.g., "default" -> "__default")
     workflow_export_to_const_name: std::collections::HashMap<String, String>,
-    // Set of function names that have been registered (to avoid duplicates)
+    // Set of step IDs that have been registered (to avoid duplicates)
     registered_functions: HashSet<String>,
+    // The functions hoisted steps were registered for, by step ID
+    hoisted_step_spans: HashMap<String, swc_core::common::Span>,
     // Collect registration calls for step mode
     registration_calls: Vec<Stmt>,
     // Track closure variables
@@ -315,6 +1392,17 @@ pub struct StepTransform {
     // Track context for validation
     in_step_function: bool,
     in_workflow_function: bool,
+    // Inside an instance method step, where `this` is the serialized instance
+    in_method_step: bool,
+    // Inside a function without a directive nested in a step or workflow, which binds its own
+    // `this`, `arguments` and `super`
+    in_plain_function: bool,
+    // Spans of the functions and arrows that are steps or workflows, including those whose
+    // directive has already been removed
+    directive_function_spans: HashSet<swc_core::common::Span>,
+    // The workflow bodies as they were before the transform, for the step call warnings once
+    // the steps have been collected (see `check_step_calls`)
+    workflow_step_calls: Vec<WorkflowStepCalls>,
     // Track the current workflow function name (for nested step naming)
     current_workflow_function_name: Option<String>,
     // Track the current parent function name (for all functions, not just workflows)
@@ -337,6 +1425,11 @@ pub struct StepTransform {
     // (parent_var_name, prop_name, fn_expr, span, parent_workflow_name, was_arrow)
     object_property_step_functions:
         Vec<(String, String, FnExpr, swc_core::common::Span, String, bool)>,
+    // Variables the object property steps are hoisted into, by (parent_workflow_name,
+    // parent_var_name, prop_name)
+    object_property_hoisted_names: HashMap<(String, String, String), String>,
+    // How many object literals deep process_object_properties_for_step_functions currently is
+    object_nesting_depth: usize,
     // Track nested step functions inside workflow functions for hoisting in step mode
     // (fn_name, fn_expr, span, closure_vars, was_arrow, parent_workflow_name)
     nested_step_functions: Vec<(
@@ -351,12 +1444,12 @@ pub struct StepTransform {
     #[allow(dead_code)]
     anonymous_fn_counter: usize,
     // Track object properties that need to be converted to initializer calls in workflow mode
-    // (parent_var_name, prop_name, step_id)
-    object_property_workflow_conversions: Vec<(String, String, String)>,
+    // (parent_var_name, prop_name, step_id, span)
+    object_property_workflow_conversions: Vec<(String, String, String, swc_core::common::Span)>,
     // Current context: variable name being processed when visiting object properties
     #[allow(dead_code)]
     current_var_context: Option<String>,
-    // Track module-level imports to exclude from closure variables
+    // Track module-level imports (and configured extra globals) to exclude from closure variables
     module_imports: HashSet<String>,
     // Track the current class name for static method transformations
     current_class_name: Option<String>,
@@ -379,6 +1472,10 @@ pub struct StepTransform {
     // Track instance step methods to strip from class and assign as properties (workflow mode)
     // (class_name, method_name, step_id)
     instance_step_methods_to_strip: Vec<(String, String, String)>,
+    // Track instance field steps (`run = async () => { "use step"; }`), which only exist on
+    // instances, so they are registered through a function that calls the field (step mode)
+    // (field_name, step_id)
+    instance_field_step_registrations: Vec<(String, String)>,
     // Track classes that need serialization registration (for `this` serialization in static methods)
     // Set of class names that have static step/workflow methods
     classes_needing_serialization: HashSet<String>,
@@ -455,84 +1552,238 @@ struct ClosureVariableCollector {
     closure_vars: HashSet<String>,
     local_vars: HashSet<String>,
     params: HashSet<String>,
+    // Free variables of nested functions, resolved against this scope once it's fully collected
+    nested_free_vars: HashSet<String>,
+    // The configured step directive, for error messages
+    step_directive: String,
+    // The level forbidden expressions are reported at
+    diagnostics: DiagnosticLevel,
+    // Whether `this` here belongs to a function or class nested in the step rather than to the
+    // step itself
+    own_this: bool,
 }
 
 impl ClosureVariableCollector {
-    fn new() -> Self {
+    fn new(step_directive: &str, diagnostics: DiagnosticLevel) -> Self {
         Self {
             closure_vars: HashSet::new(),
             local_vars: HashSet::new(),
             params: HashSet::new(),
+            nested_free_vars: HashSet::new(),
+            step_directive: step_directive.to_string(),
+            diagnostics,
+            own_this: false,
         }
     }
 
-    fn collect_from_function(function: &Function, module_imports: &HashSet<String>) -> Vec<String> {
-        let mut collector = Self::new();
+    // A collector for a nested scope, which binds its own `this` if it's a (non-arrow) function
+    // or class
+    fn nested(&self, own_this: bool) -> Self {
+        let mut nested = Self::new(&self.step_directive, self.diagnostics);
+        nested.own_this = self.own_this || own_this;
+        nested
+    }
 
-        // Add module-level imports to local_vars so they're not considered closure vars
-        collector.local_vars.extend(module_imports.iter().cloned());
+    // Direct references plus the free variables of nested functions that aren't bound in this
+    // scope. Nested functions are resolved last so that bindings declared after them (e.g.
+    // hoisted function declarations) still count as local.
+    fn into_free_vars(self) -> HashSet<String> {
+        let mut vars = self.closure_vars;
+        vars.extend(
+            self.nested_free_vars
+                .into_iter()
+                .filter(|name| !self.params.contains(name) && !self.local_vars.contains(name)),
+        );
+        vars
+    }
 
-        // Collect parameters
+    // Nested functions get their own scope: their params and locals shadow outer bindings
+    fn collect_from_nested_function(&mut self, function: &Function, ident: Option<&Ident>) {
+        let mut nested = self.nested(true);
+        // A named function expression can refer to itself, and `arguments` is its own
+        if let Some(ident) = ident {
+            nested.local_vars.insert(ident.sym.to_string());
+        }
+        nested.params.insert("arguments".to_string());
         for param in &function.params {
-            collector.collect_param_names(&param.pat);
+            nested.collect_param_names(&param.pat);
         }
-
-        // Visit function body to collect references and declarations
         if let Some(body) = &function.body {
-            collector.collect_from_block_stmt(body);
+            nested.collect_from_block_stmt(body);
         }
-
-        // Return closure vars sorted for deterministic output
-        let mut vars: Vec<String> = collector.closure_vars.into_iter().collect();
-        vars.sort();
-        vars
+        self.nested_free_vars.extend(nested.into_free_vars());
     }
 
-    fn collect_from_arrow_expr(arrow: &ArrowExpr, module_imports: &HashSet<String>) -> Vec<String> {
-        let mut collector = Self::new();
-
-        // Add module-level imports to local_vars so they're not considered closure vars
-        collector.local_vars.extend(module_imports.iter().cloned());
-
-        // Collect parameters
+    fn collect_from_nested_arrow(&mut self, arrow: &ArrowExpr) {
+        let mut nested = self.nested(false);
         for param in &arrow.params {
-            collector.collect_param_names(param);
+            nested.collect_param_names(param);
         }
-
-        // Visit arrow body
         match &*arrow.body {
-            BlockStmtOrExpr::BlockStmt(block) => {
-                collector.collect_from_block_stmt(block);
-            }
-            BlockStmtOrExpr::Expr(expr) => {
-                collector.collect_from_expr(expr);
-            }
+            BlockStmtOrExpr::BlockStmt(block) => nested.collect_from_block_stmt(block),
+            BlockStmtOrExpr::Expr(expr) => nested.collect_from_expr(expr),
         }
-
-        // Return closure vars sorted for deterministic output
-        let mut vars: Vec<String> = collector.closure_vars.into_iter().collect();
-        vars.sort();
-        vars
+        self.nested_free_vars.extend(nested.into_free_vars());
     }
 
-    fn collect_param_names(&mut self, pat: &Pat) {
-        match pat {
-            Pat::Ident(ident) => {
-                self.params.insert(ident.id.sym.to_string());
-            }
-            Pat::Array(array) => {
-                for elem in array.elems.iter().flatten() {
-                    self.collect_param_names(elem);
-                }
-            }
-            Pat::Object(obj) => {
-                for prop in &obj.props {
+    // Computed keys and `extends` are evaluated where the class is defined, so they reference this
+    // scope directly. Methods, constructors, field initializers and static blocks run later in
+    // scopes of their own, like nested functions.
+    fn collect_from_class(&mut self, class: &Class, ident: Option<&Ident>) {
+        if let Some(super_class) = &class.super_class {
+            self.collect_from_expr(super_class);
+        }
+
+        // The class body can refer to the class by its own name
+        let mut body = self.nested(true);
+        if let Some(ident) = ident {
+            body.local_vars.insert(ident.sym.to_string());
+        }
+        for member in &class.body {
+            match member {
+                ClassMember::Method(method) => {
+                    if let PropName::Computed(computed) = &method.key {
+                        self.collect_from_expr(&computed.expr);
+                    }
+                    body.collect_from_nested_function(&method.function, None);
+                }
+                ClassMember::PrivateMethod(method) => {
+                    body.collect_from_nested_function(&method.function, None);
+                }
+                ClassMember::ClassProp(prop) => {
+                    if let PropName::Computed(computed) = &prop.key {
+                        self.collect_from_expr(&computed.expr);
+                    }
+                    if let Some(value) = &prop.value {
+                        body.collect_from_nested_expr(value);
+                    }
+                }
+                ClassMember::PrivateProp(prop) => {
+                    if let Some(value) = &prop.value {
+                        body.collect_from_nested_expr(value);
+                    }
+                }
+                ClassMember::Constructor(constructor) => {
+                    let mut nested = self.nested(true);
+                    nested.params.insert("arguments".to_string());
+                    for param in &constructor.params {
+                        match param {
+                            ParamOrTsParamProp::Param(param) => {
+                                nested.collect_param_names(&param.pat)
+                            }
+                            ParamOrTsParamProp::TsParamProp(prop) => match &prop.param {
+                                TsParamPropParam::Ident(binding) => {
+                                    nested.params.insert(binding.id.sym.to_string());
+                                }
+                                TsParamPropParam::Assign(assign) => {
+                                    nested.collect_param_names(&assign.left)
+                                }
+                            },
+                        }
+                    }
+                    if let Some(block) = &constructor.body {
+                        nested.collect_from_block_stmt(block);
+                    }
+                    body.nested_free_vars.extend(nested.into_free_vars());
+                }
+                ClassMember::StaticBlock(block) => {
+                    let mut nested = self.nested(true);
+                    nested.collect_from_block_stmt(&block.body);
+                    body.nested_free_vars.extend(nested.into_free_vars());
+                }
+                _ => {}
+            }
+        }
+        self.nested_free_vars.extend(body.into_free_vars());
+    }
+
+    fn collect_from_nested_expr(&mut self, expr: &Expr) {
+        let mut nested = self.nested(false);
+        nested.collect_from_expr(expr);
+        self.nested_free_vars.extend(nested.into_free_vars());
+    }
+
+    fn collect_from_function(
+        function: &Function,
+        module_imports: &HashSet<String>,
+        step_directive: &str,
+        diagnostics: DiagnosticLevel,
+    ) -> Vec<String> {
+        let mut collector = Self::new(step_directive, diagnostics);
+
+        // Add module-level imports to local_vars so they're not considered closure vars
+        collector.local_vars.extend(module_imports.iter().cloned());
+
+        // Collect parameters
+        for param in &function.params {
+            collector.collect_param_names(&param.pat);
+        }
+
+        // Visit function body to collect references and declarations
+        if let Some(body) = &function.body {
+            collector.collect_from_block_stmt(body);
+        }
+
+        // Return closure vars sorted for deterministic output
+        let mut vars: Vec<String> = collector.into_free_vars().into_iter().collect();
+        vars.sort();
+        vars
+    }
+
+    fn collect_from_arrow_expr(
+        arrow: &ArrowExpr,
+        module_imports: &HashSet<String>,
+        step_directive: &str,
+        diagnostics: DiagnosticLevel,
+    ) -> Vec<String> {
+        let mut collector = Self::new(step_directive, diagnostics);
+
+        // Add module-level imports to local_vars so they're not considered closure vars
+        collector.local_vars.extend(module_imports.iter().cloned());
+
+        // Collect parameters
+        for param in &arrow.params {
+            collector.collect_param_names(param);
+        }
+
+        // Visit arrow body
+        match &*arrow.body {
+            BlockStmtOrExpr::BlockStmt(block) => {
+                collector.collect_from_block_stmt(block);
+            }
+            BlockStmtOrExpr::Expr(expr) => {
+                collector.collect_from_expr(expr);
+            }
+        }
+
+        // Return closure vars sorted for deterministic output
+        let mut vars: Vec<String> = collector.into_free_vars().into_iter().collect();
+        vars.sort();
+        vars
+    }
+
+    fn collect_param_names(&mut self, pat: &Pat) {
+        match pat {
+            Pat::Ident(ident) => {
+                self.params.insert(ident.id.sym.to_string());
+            }
+            Pat::Array(array) => {
+                for elem in array.elems.iter().flatten() {
+                    self.collect_param_names(elem);
+                }
+            }
+            Pat::Object(obj) => {
+                for prop in &obj.props {
                     match prop {
                         ObjectPatProp::KeyValue(kv) => {
                             self.collect_param_names(&kv.value);
                         }
                         ObjectPatProp::Assign(assign) => {
                             self.params.insert(assign.key.id.sym.to_string());
+                            // Default values may reference closure vars, e.g. `{ message = fallback }`
+                            if let Some(value) = &assign.value {
+                                self.collect_from_expr(value);
+                            }
                         }
                         ObjectPatProp::Rest(rest) => {
                             self.collect_param_names(&rest.arg);
@@ -545,6 +1796,7 @@ impl ClosureVariableCollector {
             }
             Pat::Assign(assign) => {
                 self.collect_param_names(&assign.left);
+                self.collect_from_expr(&assign.right);
             }
             _ => {}
         }
@@ -572,7 +1824,11 @@ impl ClosureVariableCollector {
                     }
                     Decl::Fn(fn_decl) => {
                         self.local_vars.insert(fn_decl.ident.sym.to_string());
-                        // Don't visit nested function bodies for closure detection
+                        self.collect_from_nested_function(&fn_decl.function, None);
+                    }
+                    Decl::Class(class_decl) => {
+                        self.local_vars.insert(class_decl.ident.sym.to_string());
+                        self.collect_from_class(&class_decl.class, None);
                     }
                     _ => {}
                 }
@@ -623,10 +1879,70 @@ impl ClosureVariableCollector {
                 self.collect_from_expr(&while_stmt.test);
                 self.collect_from_stmt(&while_stmt.body);
             }
+            Stmt::ForOf(for_of_stmt) => {
+                // `for await (... of ...)` has the same shape, only `is_await` differs
+                self.collect_from_for_head(&for_of_stmt.left);
+                self.collect_from_expr(&for_of_stmt.right);
+                self.collect_from_stmt(&for_of_stmt.body);
+            }
+            Stmt::ForIn(for_in_stmt) => {
+                self.collect_from_for_head(&for_in_stmt.left);
+                self.collect_from_expr(&for_in_stmt.right);
+                self.collect_from_stmt(&for_in_stmt.body);
+            }
+            Stmt::Try(try_stmt) => {
+                self.collect_from_block_stmt(&try_stmt.block);
+                if let Some(handler) = &try_stmt.handler {
+                    // The catch binding is a parameter of the handler, not a closure var
+                    if let Some(param) = &handler.param {
+                        self.collect_param_names(param);
+                    }
+                    self.collect_from_block_stmt(&handler.body);
+                }
+                if let Some(finalizer) = &try_stmt.finalizer {
+                    self.collect_from_block_stmt(finalizer);
+                }
+            }
+            Stmt::Switch(switch_stmt) => {
+                self.collect_from_expr(&switch_stmt.discriminant);
+                for case in &switch_stmt.cases {
+                    if let Some(test) = &case.test {
+                        self.collect_from_expr(test);
+                    }
+                    for stmt in &case.cons {
+                        self.collect_from_stmt(stmt);
+                    }
+                }
+            }
             _ => {}
         }
     }
 
+    // Collect the loop binding of a `for...of` / `for...in` statement
+    fn collect_from_for_head(&mut self, head: &ForHead) {
+        match head {
+            ForHead::VarDecl(var_decl) => {
+                for declarator in &var_decl.decls {
+                    self.collect_declared_names(&declarator.name);
+                }
+            }
+            ForHead::Pat(pat) => {
+                // Assigning to an existing binding (e.g. `for (item of items)`), which may be a
+                // closure var itself
+                if let Pat::Ident(ident) = &**pat {
+                    self.collect_from_ident_binding(&ident.id);
+                } else if let Pat::Expr(expr) = &**pat {
+                    self.collect_from_expr(expr);
+                }
+            }
+            ForHead::UsingDecl(using_decl) => {
+                for declarator in &using_decl.decls {
+                    self.collect_declared_names(&declarator.name);
+                }
+            }
+        }
+    }
+
     fn collect_declared_names(&mut self, pat: &Pat) {
         match pat {
             Pat::Ident(ident) => {
@@ -682,9 +1998,26 @@ impl ClosureVariableCollector {
                     self.collect_from_expr(&arg.expr);
                 }
             }
+            Expr::New(new_expr) => {
+                self.collect_from_expr(&new_expr.callee);
+                for arg in new_expr.args.iter().flatten() {
+                    self.collect_from_expr(&arg.expr);
+                }
+            }
             Expr::Member(member) => {
-                self.collect_from_expr(&member.obj);
+                self.collect_from_member_expr(member);
             }
+            Expr::OptChain(opt_chain) => match &*opt_chain.base {
+                OptChainBase::Member(member) => {
+                    self.collect_from_member_expr(member);
+                }
+                OptChainBase::Call(call) => {
+                    self.collect_from_expr(&call.callee);
+                    for arg in &call.args {
+                        self.collect_from_expr(&arg.expr);
+                    }
+                }
+            },
             Expr::Bin(bin) => {
                 self.collect_from_expr(&bin.left);
                 self.collect_from_expr(&bin.right);
@@ -705,17 +2038,18 @@ impl ClosureVariableCollector {
             Expr::Object(obj) => {
                 for prop in &obj.props {
                     match prop {
-                        PropOrSpread::Prop(prop) => {
-                            match &**prop {
-                                Prop::KeyValue(kv) => {
-                                    self.collect_from_expr(&kv.value);
-                                }
-                                Prop::Method(_method) => {
-                                    // Don't visit nested method bodies
-                                }
-                                _ => {}
+                        PropOrSpread::Prop(prop) => match &**prop {
+                            Prop::KeyValue(kv) => {
+                                self.collect_from_expr(&kv.value);
                             }
-                        }
+                            Prop::Shorthand(ident) => {
+                                self.collect_from_expr(&Expr::Ident(ident.clone()));
+                            }
+                            Prop::Method(method) => {
+                                self.collect_from_nested_function(&method.function, None);
+                            }
+                            _ => {}
+                        },
                         PropOrSpread::Spread(spread) => {
                             self.collect_from_expr(&spread.expr);
                         }
@@ -736,29 +2070,29 @@ impl ClosureVariableCollector {
                     self.collect_from_expr(expr);
                 }
             }
-            Expr::Arrow(_arrow) => {
-                // Don't visit nested arrow function bodies for closure detection
+            Expr::Arrow(arrow) => {
+                self.collect_from_nested_arrow(arrow);
+            }
+            Expr::Fn(fn_expr) => {
+                self.collect_from_nested_function(&fn_expr.function, fn_expr.ident.as_ref());
             }
-            Expr::Fn(_) => {
-                // Don't visit nested function bodies for closure detection
+            Expr::Class(class_expr) => {
+                self.collect_from_class(&class_expr.class, class_expr.ident.as_ref());
             }
             Expr::Assign(assign) => {
                 self.collect_from_expr(&assign.right);
                 // Also check the left side for references (e.g., obj.prop = value)
-                match &assign.left {
-                    AssignTarget::Simple(simple) => {
-                        match simple {
-                            SimpleAssignTarget::Ident(ident) => {
-                                // This is an assignment to a variable, check if it's a closure var
-                                self.collect_from_ident_binding(&ident.id);
-                            }
-                            SimpleAssignTarget::Member(member) => {
-                                self.collect_from_expr(&member.obj);
-                            }
-                            _ => {}
+                if let AssignTarget::Simple(simple) = &assign.left {
+                    match simple {
+                        SimpleAssignTarget::Ident(ident) => {
+                            // This is an assignment to a variable, check if it's a closure var
+                            self.collect_from_ident_binding(&ident.id);
                         }
+                        SimpleAssignTarget::Member(member) => {
+                            self.collect_from_member_expr(member);
+                        }
+                        _ => {}
                     }
-                    _ => {}
                 }
             }
             Expr::Update(update) => {
@@ -767,17 +2101,185 @@ impl ClosureVariableCollector {
             Expr::Await(await_expr) => {
                 self.collect_from_expr(&await_expr.arg);
             }
+            Expr::Seq(seq) => {
+                for expr in &seq.exprs {
+                    self.collect_from_expr(expr);
+                }
+            }
+            Expr::Yield(yield_expr) => {
+                if let Some(arg) = &yield_expr.arg {
+                    self.collect_from_expr(arg);
+                }
+            }
             _ => {}
         }
     }
 
+    fn collect_from_member_expr(&mut self, member: &MemberExpr) {
+        self.collect_from_expr(&member.obj);
+        match &member.prop {
+            // Computed keys (e.g. `config[key]`) are expressions that may reference closure vars
+            MemberProp::Computed(computed) => {
+                self.collect_from_expr(&computed.expr);
+            }
+            // `#x` is not a variable, but `this.#x` ties the step to its class instance, which
+            // can't be passed as a closure var and breaks once the step is hoisted out of the
+            // class. A function or class nested in the step has a `this` of its own.
+            MemberProp::PrivateName(_) if !self.own_this => {
+                if let Expr::This(this) = &*member.obj {
+                    emit_diagnostic(
+                        WorkflowErrorKind::ForbiddenExpression {
+                            span: this.span,
+                            expr: "this",
+                            directive: self.step_directive.as_str(),
+                        },
+                        self.diagnostics,
+                    );
+                }
+            }
+            MemberProp::PrivateName(_) | MemberProp::Ident(_) => {}
+        }
+    }
+
     fn collect_from_ident_binding(&mut self, ident: &Ident) {
         let name = ident.sym.to_string();
-        if !self.params.contains(&name) && !self.local_vars.contains(&name) {
-            if !is_global_identifier(&name) {
-                self.closure_vars.insert(name);
+        if !self.params.contains(&name)
+            && !self.local_vars.contains(&name)
+            && !is_global_identifier(&name)
+        {
+            self.closure_vars.insert(name);
+        }
+    }
+}
+
+// `() => ({ var1, var2 })`, which hands the closure variables of a step or cache function to
+// the runtime
+fn create_closure_vars_thunk(closure_vars: &[String]) -> Expr {
+    let closure_obj = Expr::Object(ObjectLit {
+        span: DUMMY_SP,
+        props: closure_vars
+            .iter()
+            .map(|var_name| {
+                PropOrSpread::Prop(Box::new(Prop::Shorthand(Ident::new(
+                    var_name.clone().into(),
+                    DUMMY_SP,
+                    SyntaxContext::empty(),
+                ))))
+            })
+            .collect(),
+    });
+
+    Expr::Arrow(ArrowExpr {
+        span: DUMMY_SP,
+        ctxt: SyntaxContext::empty(),
+        params: vec![],
+        body: Box::new(BlockStmtOrExpr::Expr(Box::new(closure_obj))),
+        is_async: false,
+        is_generator: false,
+        type_params: None,
+        return_type: None,
+    })
+}
+
+// Collects the identifiers bound by a declaration pattern
+fn collect_binding_idents<'a>(pat: &'a Pat, idents: &mut Vec<&'a Ident>) {
+    match pat {
+        Pat::Ident(binding) => idents.push(&binding.id),
+        Pat::Array(array_pat) => {
+            for elem in array_pat.elems.iter().flatten() {
+                collect_binding_idents(elem, idents);
+            }
+        }
+        Pat::Object(obj_pat) => {
+            for prop in &obj_pat.props {
+                match prop {
+                    ObjectPatProp::KeyValue(kv) => collect_binding_idents(&kv.value, idents),
+                    ObjectPatProp::Assign(assign) => idents.push(&assign.key.id),
+                    ObjectPatProp::Rest(rest) => collect_binding_idents(&rest.arg, idents),
+                }
             }
         }
+        Pat::Rest(rest_pat) => collect_binding_idents(&rest_pat.arg, idents),
+        Pat::Assign(assign_pat) => collect_binding_idents(&assign_pat.left, idents),
+        _ => {}
+    }
+}
+
+fn any_type() -> Box<TsType> {
+    Box::new(TsType::TsKeywordType(TsKeywordType {
+        span: DUMMY_SP,
+        kind: TsKeywordTypeKind::TsAnyKeyword,
+    }))
+}
+
+// Converts a step function parameter into a parameter of a function type. Patterns and default
+// values aren't allowed there, so destructured params become `argN` and defaults make the
+// param optional; any declared type is kept.
+fn proxy_fn_param(pat: &Pat, index: usize) -> TsFnParam {
+    let placeholder = |optional: bool, type_ann: Option<Box<TsTypeAnn>>| {
+        let mut id = Ident::new(
+            format!("arg{}", index).into(),
+            DUMMY_SP,
+            SyntaxContext::empty(),
+        );
+        id.optional = optional;
+        TsFnParam::Ident(BindingIdent { id, type_ann })
+    };
+    match pat {
+        Pat::Ident(binding) => TsFnParam::Ident(binding.clone()),
+        Pat::Rest(rest) => TsFnParam::Rest(RestPat {
+            span: DUMMY_SP,
+            dot3_token: DUMMY_SP,
+            arg: match &*rest.arg {
+                Pat::Ident(binding) => Box::new(Pat::Ident(binding.id.clone().into())),
+                _ => Box::new(Pat::Ident(
+                    Ident::new(
+                        format!("arg{}", index).into(),
+                        DUMMY_SP,
+                        SyntaxContext::empty(),
+                    )
+                    .into(),
+                )),
+            },
+            type_ann: rest.type_ann.clone(),
+        }),
+        Pat::Assign(assign) => match &*assign.left {
+            Pat::Ident(binding) => {
+                let mut binding = binding.clone();
+                binding.id.optional = true;
+                TsFnParam::Ident(binding)
+            }
+            Pat::Array(array) => placeholder(true, array.type_ann.clone()),
+            Pat::Object(object) => placeholder(true, object.type_ann.clone()),
+            _ => placeholder(true, None),
+        },
+        Pat::Array(array) => placeholder(false, array.type_ann.clone()),
+        Pat::Object(object) => placeholder(false, object.type_ann.clone()),
+        _ => placeholder(false, None),
+    }
+}
+
+// Number of params counted by a function's `length`: those before the first one with a default
+// value or a rest param. A TypeScript `this` param isn't counted.
+fn fn_length<'a>(params: impl IntoIterator<Item = &'a Pat>) -> usize {
+    params
+        .into_iter()
+        .filter(|pat| !matches!(pat, Pat::Ident(binding) if binding.id.sym == *"this"))
+        .take_while(|pat| !matches!(pat, Pat::Assign(_) | Pat::Rest(_)))
+        .count()
+}
+
+fn fn_length_of_function(function: &Function) -> usize {
+    fn_length(function.params.iter().map(|param| &param.pat))
+}
+
+// `fn_length` of a step defined as an arrow function or function expression initializer
+fn fn_length_of_expr(expr: &Expr) -> usize {
+    match expr {
+        Expr::Arrow(arrow) => fn_length(&arrow.params),
+        Expr::Fn(fn_expr) => fn_length_of_function(&fn_expr.function),
+        Expr::Paren(paren) => fn_length_of_expr(&paren.expr),
+        _ => 0,
     }
 }
 
@@ -860,6 +2362,19 @@ fn is_global_identifier(name: &str) -> bool {
             | "exports"
             | "__dirname"
             | "__filename"
+            | "crypto"
+            | "performance"
+            | "structuredClone"
+            | "queueMicrotask"
+            | "btoa"
+            | "atob"
+            | "AbortController"
+            | "AbortSignal"
+            | "Blob"
+            | "File"
+            | "FormData"
+            | "WebSocket"
+            | "EventTarget"
     )
 }
 
@@ -868,24 +2383,32 @@ fn is_global_identifier(name: &str) -> bool {
 // closure destructuring pattern (which use SyntaxContext::empty()).
 struct ClosureVariableNormalizer {
     closure_vars: HashSet<String>,
+    // Bindings declared in the body itself (e.g. a `const x` in an inner block), which must
+    // keep their own SyntaxContext even when a captured variable has the same name
+    locals: HashSet<Id>,
 }
 
 impl ClosureVariableNormalizer {
-    fn new(closure_vars: &[String]) -> Self {
+    fn new(closure_vars: &[String], body: &BlockStmt) -> Self {
+        let mut locals = LocalBindingCollector::default();
+        body.visit_with(&mut locals);
         Self {
             closure_vars: closure_vars.iter().cloned().collect(),
+            locals: locals.bindings,
         }
     }
 
     fn normalize_function_body(closure_vars: &[String], body: &mut BlockStmt) {
-        let mut normalizer = Self::new(closure_vars);
+        let mut normalizer = Self::new(closure_vars, body);
         body.visit_mut_with(&mut normalizer);
     }
 }
 
 impl VisitMut for ClosureVariableNormalizer {
     fn visit_mut_ident(&mut self, ident: &mut Ident) {
-        if self.closure_vars.contains(&ident.sym.to_string()) {
+        if self.closure_vars.contains(&ident.sym.to_string())
+            && !self.locals.contains(&ident.to_id())
+        {
             // Replace with a new identifier that has SyntaxContext::empty()
             // This ensures it matches the destructuring pattern we create
             *ident = Ident::new(ident.sym.clone(), ident.span, SyntaxContext::empty());
@@ -899,6 +2422,30 @@ impl VisitMut for ClosureVariableNormalizer {
     noop_visit_mut_type!();
 }
 
+// Collects the bindings declared directly in a function body, for ClosureVariableNormalizer
+#[derive(Default)]
+struct LocalBindingCollector {
+    bindings: HashSet<Id>,
+}
+
+impl Visit for LocalBindingCollector {
+    fn visit_binding_ident(&mut self, ident: &BindingIdent) {
+        self.bindings.insert(ident.id.to_id());
+    }
+
+    fn visit_fn_decl(&mut self, fn_decl: &FnDecl) {
+        self.bindings.insert(fn_decl.ident.to_id());
+    }
+
+    fn visit_class_decl(&mut self, class_decl: &ClassDecl) {
+        self.bindings.insert(class_decl.ident.to_id());
+    }
+
+    // Like ClosureVariableNormalizer, don't descend into nested functions
+    fn visit_function(&mut self, _: &Function) {}
+    fn visit_arrow_expr(&mut self, _: &ArrowExpr) {}
+}
+
 impl StepTransform {
     fn process_stmt(&mut self, stmt: &mut Stmt) {
         match stmt {
@@ -915,8 +2462,12 @@ impl StepTransform {
                 );
 
                 if self.should_transform_function(&fn_decl.function, false) {
-                    if self.validate_async_function(&fn_decl.function, fn_decl.function.span) {
-                        self.step_function_names.insert(fn_name.clone());
+                    if self.validate_async_function(
+                        &fn_decl.function,
+                        fn_decl.function.span,
+                        self.step_directive.as_str(),
+                    ) {
+                        self.record_step_function(fn_name.clone(), fn_decl.function.span);
 
                         if !self.in_module_level {
                             match self.mode {
@@ -930,6 +2481,8 @@ impl StepTransform {
                                         ClosureVariableCollector::collect_from_function(
                                             &cloned_function,
                                             &self.module_imports,
+                                            &self.step_directive,
+                                            self.options.diagnostics,
                                         );
 
                                     let fn_expr = FnExpr {
@@ -951,7 +2504,7 @@ impl StepTransform {
                                     let hoisted_name =
                                         if let Some(parent) = &self.current_parent_function_name {
                                             if !parent.is_empty() {
-                                                format!("{}${}", parent, fn_name)
+                                                self.hoisted_step_name(parent, &fn_name)
                                             } else {
                                                 fn_name.clone()
                                             }
@@ -983,14 +2536,13 @@ impl StepTransform {
                                         declare: false,
                                     }));
                                     *stmt = Stmt::Decl(var_decl);
-                                    return;
                                 }
                                 TransformMode::Workflow => {
                                     // Include parent workflow name in step ID
                                     let step_fn_name = if let Some(parent) =
                                         &self.current_workflow_function_name
                                     {
-                                        format!("{}/{}", parent, fn_name)
+                                        self.nested_step_id_name(parent, &fn_name)
                                     } else {
                                         fn_name.clone()
                                     };
@@ -1005,12 +2557,26 @@ impl StepTransform {
                                         ClosureVariableCollector::collect_from_function(
                                             &fn_decl.function,
                                             &self.module_imports,
+                                            &self.step_directive,
+                                            self.options.diagnostics,
                                         );
-                                    let proxy_ref =
-                                        self.create_step_proxy_reference(&step_id, &closure_vars);
+                                    let proxy_ref = self.wrap_step_proxy(
+                                        &fn_name,
+                                        self.create_step_proxy_reference(
+                                            &step_id,
+                                            &closure_vars,
+                                            fn_decl.function.span,
+                                        ),
+                                        &closure_vars,
+                                        fn_length_of_function(&fn_decl.function),
+                                    );
+                                    let type_ann =
+                                        self.create_proxy_type_ann_for_function(&fn_decl.function);
 
+                                    // The declaration takes over the function's span, so comments
+                                    // attached to it stay in front of the declaration
                                     let var_decl = Decl::Var(Box::new(VarDecl {
-                                        span: DUMMY_SP,
+                                        span: fn_decl.function.span,
                                         ctxt: SyntaxContext::empty(),
                                         kind: VarDeclKind::Var,
                                         decls: vec![VarDeclarator {
@@ -1021,7 +2587,7 @@ impl StepTransform {
                                                     DUMMY_SP,
                                                     SyntaxContext::empty(),
                                                 ),
-                                                type_ann: None,
+                                                type_ann,
                                             }),
                                             init: Some(Box::new(proxy_ref)),
                                             definite: false,
@@ -1030,14 +2596,12 @@ impl StepTransform {
                                     }));
 
                                     *stmt = Stmt::Decl(var_decl);
-                                    return;
                                 }
                                 TransformMode::Client => {
                                     // In client mode for nested step functions, just remove directive
                                     // WITHOUT registering - the function will be undefined since the
                                     // workflow body is replaced with throw Error
                                     self.remove_use_step_directive(&mut fn_decl.function.body);
-                                    return;
                                 }
                             }
                         } else {
@@ -1062,7 +2626,8 @@ impl StepTransform {
                                             fn_decl.function.span,
                                             false,
                                         );
-                                        let mut proxy_call = self.create_step_proxy(&step_id);
+                                        let mut proxy_call =
+                                            self.create_step_proxy(&step_id, fn_decl.function.span);
                                         if let Expr::Call(call) = &mut proxy_call {
                                             call.args = fn_decl
                                                 .function
@@ -1088,8 +2653,12 @@ impl StepTransform {
                         }
                     }
                 } else if self.should_transform_workflow_function(&fn_decl.function, false) {
-                    if self.validate_async_function(&fn_decl.function, fn_decl.function.span) {
-                        self.workflow_function_names.insert(fn_name.clone());
+                    if self.validate_async_function(
+                        &fn_decl.function,
+                        fn_decl.function.span,
+                        self.workflow_directive.as_str(),
+                    ) {
+                        self.record_workflow_function(fn_name.clone(), fn_decl.function.span);
                         let fn_span = fn_decl.function.span;
 
                         match self.mode {
@@ -1187,28 +2756,25 @@ impl StepTransform {
 
                             // Check if the initializer is an arrow function with object literal body
                             if let Expr::Arrow(arrow_expr) = &mut **init {
-                                match &mut *arrow_expr.body {
-                                    BlockStmtOrExpr::Expr(expr) => {
-                                        // Handle both direct object literals and parenthesized ones
-                                        let obj_lit_mut = match &mut **expr {
-                                            Expr::Object(obj) => Some(obj),
-                                            Expr::Paren(paren) => {
-                                                if let Expr::Object(obj) = &mut *paren.expr {
-                                                    Some(obj)
-                                                } else {
-                                                    None
-                                                }
+                                if let BlockStmtOrExpr::Expr(expr) = &mut *arrow_expr.body {
+                                    // Handle both direct object literals and parenthesized ones
+                                    let obj_lit_mut = match &mut **expr {
+                                        Expr::Object(obj) => Some(obj),
+                                        Expr::Paren(paren) => {
+                                            if let Expr::Object(obj) = &mut *paren.expr {
+                                                Some(obj)
+                                            } else {
+                                                None
                                             }
-                                            _ => None,
-                                        };
-
-                                        if let Some(obj_lit) = obj_lit_mut {
-                                            self.process_object_properties_for_step_functions(
-                                                obj_lit, &name,
-                                            );
                                         }
+                                        _ => None,
+                                    };
+
+                                    if let Some(obj_lit) = obj_lit_mut {
+                                        self.process_object_properties_for_step_functions(
+                                            obj_lit, &name,
+                                        );
                                     }
-                                    _ => {}
                                 }
                             }
                         }
@@ -1225,13 +2791,24 @@ impl StepTransform {
         Self {
             mode,
             filename,
+            options: TransformOptions::default(),
+            step_directive: "use step".to_string(),
+            workflow_directive: "use workflow".to_string(),
+            id_pattern: None,
+            invalid_ids: RefCell::new(HashMap::new()),
             module_specifier,
             has_file_step_directive: false,
             has_file_workflow_directive: false,
             step_function_names: HashSet::new(),
             workflow_function_names: HashSet::new(),
+            step_function_spans: HashMap::new(),
+            workflow_function_spans: HashMap::new(),
+            source_map: None,
+            comments: None,
+            cache_function_names: HashSet::new(),
             workflow_export_to_const_name: HashMap::new(),
             registered_functions: HashSet::new(),
+            hoisted_step_spans: HashMap::new(),
             registration_calls: Vec::new(),
             names: Vec::new(),
             should_track_names: false,
@@ -1239,6 +2816,10 @@ impl StepTransform {
             in_callee: false,
             in_step_function: false,
             in_workflow_function: false,
+            in_method_step: false,
+            in_plain_function: false,
+            directive_function_spans: HashSet::new(),
+            workflow_step_calls: Vec::new(),
             current_workflow_function_name: None,
             current_parent_function_name: None,
             workflow_exports_to_expand: Vec::new(),
@@ -1249,6 +2830,8 @@ impl StepTransform {
             default_workflow_exports: Vec::new(),
             declared_identifiers: HashSet::new(),
             object_property_step_functions: Vec::new(),
+            object_property_hoisted_names: HashMap::new(),
+            object_nesting_depth: 0,
             nested_step_functions: Vec::new(),
             anonymous_fn_counter: 0,
             object_property_workflow_conversions: Vec::new(),
@@ -1261,12 +2844,119 @@ impl StepTransform {
             static_step_methods_to_strip: Vec::new(),
             instance_method_step_registrations: Vec::new(),
             instance_step_methods_to_strip: Vec::new(),
+            instance_field_step_registrations: Vec::new(),
             classes_needing_serialization: HashSet::new(),
             serialization_symbol_identifiers: HashMap::new(),
             classes_for_manifest: HashSet::new(),
         }
     }
 
+    /// Apply optional [`TransformOptions`] to the transform.
+    pub fn with_options(mut self, mut options: TransformOptions) -> Self {
+        // Extra globals are excluded from closure vars the same way module imports are
+        self.module_imports
+            .extend(options.extra_globals.iter().cloned());
+        if let Some(directive) = &options.step_directive {
+            self.step_directive = directive.clone();
+        }
+        if let Some(directive) = &options.workflow_directive {
+            self.workflow_directive = directive.clone();
+        }
+        if self.step_directive.is_empty() || self.workflow_directive.is_empty() {
+            panic!("Invalid stepDirective/workflowDirective: they must not be empty");
+        }
+        if self.step_directive == self.workflow_directive {
+            panic!(
+                "Invalid stepDirective/workflowDirective: both are \"{}\", but they must differ",
+                self.step_directive
+            );
+        }
+        if options.private_import_source.is_empty() {
+            panic!("Invalid privateImportSource: it must not be empty");
+        }
+        if options.class_serialization_import_source.is_empty() {
+            panic!("Invalid classSerializationImportSource: it must not be empty");
+        }
+        if options.nested_step_id_separator.is_empty() {
+            panic!("Invalid nestedStepIdSeparator: it must not be empty");
+        }
+        if options.hoisted_name_separator.is_empty()
+            || !options
+                .hoisted_name_separator
+                .chars()
+                .all(Ident::is_valid_continue)
+        {
+            panic!(
+                "Invalid hoistedNameSeparator \"{}\": it must be non-empty and only contain characters that can be part of an identifier",
+                options.hoisted_name_separator
+            );
+        }
+        self.id_pattern = options.id_pattern.as_deref().map(|pattern| {
+            Regex::new(pattern)
+                .unwrap_or_else(|err| panic!("Invalid idPattern \"{}\": {}", pattern, err))
+        });
+        if !options.emit_manifest_comment {
+            options.manifest_output = ManifestOutput::None;
+        }
+        self.options = options;
+        self
+    }
+
+    /// Source map of the file, used to add the line and column of each step and workflow to the
+    /// manifest when [`TransformOptions::include_positions`] is set.
+    pub fn with_source_map(mut self, source_map: Lrc<dyn SourceMapper>) -> Self {
+        self.source_map = Some(ManifestSourceMap(source_map));
+        self
+    }
+
+    /// Comments of the file, used to annotate the step proxies of workflow mode as
+    /// `/*#__PURE__*/` when [`TransformOptions::pure_step_proxies`] is set.
+    pub fn with_comments(mut self, comments: impl Comments + 'static) -> Self {
+        self.comments = Some(ProxyComments(Box::new(comments)));
+        self
+    }
+
+    /// Collect the workflows, steps, classes and caches `program` defines without modifying it,
+    /// like [`analyze`] but with the options and source map given to the transform.
+    ///
+    /// Diagnostics are not reported, since transforming the file reports them already, so only
+    /// `GLOBALS` has to be set.
+    pub fn analyze(mut self, program: &Program) -> WorkflowManifest {
+        // The output is dropped, so it doesn't need any annotations
+        self.comments = None;
+        let handler = Handler::with_emitter(false, false, Box::new(SilentEmitter));
+        HANDLER.set(&handler, || program.clone().visit_mut_with(&mut self));
+        self.manifest()
+    }
+
+    // With `pure_step_proxies`, mark a step proxy as free of side effects, so bundlers can drop
+    // it when its binding is unused. The comment goes on the callee, which is printed right where
+    // the call starts: the call keeps the span of the original function, and a comment at that
+    // position would be printed before the whole declaration (which has the same span).
+    fn annotate_pure(&self, mut call: CallExpr) -> Expr {
+        if self.options.pure_step_proxies {
+            if let (Some(comments), Callee::Expr(callee)) = (&self.comments, &mut call.callee) {
+                if let Expr::Member(member) = &mut **callee {
+                    member.span = swc_core::common::Span::dummy_with_cmt();
+                    comments.0.add_pure_comment(member.span.lo);
+                }
+            }
+        }
+        Expr::Call(call)
+    }
+
+    fn record_step_function(&mut self, name: String, span: swc_core::common::Span) {
+        self.step_function_spans.entry(name.clone()).or_insert(span);
+        self.step_function_names.insert(name);
+    }
+
+    fn record_workflow_function(&mut self, name: String, span: swc_core::common::Span) {
+        self.workflow_function_spans
+            .entry(name.clone())
+            .or_insert(span);
+        self.workflow_function_names.insert(name);
+    }
+
     // Get the module path to use for ID generation.
     // Uses the module_specifier if provided, otherwise falls back to "./{filename}" format.
     fn get_module_path(&self) -> String {
@@ -1291,15 +2981,77 @@ impl StepTransform {
             }
             Some(name) => {
                 let prefix = if is_workflow { "workflow" } else { "step" };
-                naming::format_name(prefix, &self.get_module_path(), name)
+                let id = naming::format_name(prefix, &self.get_module_path(), name);
+                self.check_id(&id, span);
+                id
             }
             None => {
                 let prefix = if is_workflow { "workflow" } else { "step" };
-                naming::format_name(prefix, &self.get_module_path(), span.lo.0)
+                let id = naming::format_name(prefix, &self.get_module_path(), span.lo.0);
+                self.check_id(&id, span);
+                id
             }
         }
     }
 
+    // Remember generated IDs that don't match the `id_pattern` option. The same ID is created
+    // several times (often without a span), so they are reported together at the end.
+    fn check_id(&self, id: &str, span: swc_core::common::Span) {
+        let Some(pattern) = &self.id_pattern else {
+            return;
+        };
+        if pattern.is_match(id) {
+            return;
+        }
+        let mut invalid_ids = self.invalid_ids.borrow_mut();
+        let recorded_span = invalid_ids.entry(id.to_string()).or_insert(span);
+        if recorded_span.is_dummy() {
+            *recorded_span = span;
+        }
+    }
+
+    // With `require_module_specifier`, report a file whose manifest would use IDs derived from
+    // its file name, once, at its first step or workflow function
+    fn report_missing_module_specifier(&self) {
+        if !self.options.require_module_specifier || self.module_specifier.is_some() {
+            return;
+        }
+        if self.manifest() == WorkflowManifest::default() {
+            return;
+        }
+        let span = self
+            .step_function_spans
+            .values()
+            .chain(self.workflow_function_spans.values())
+            .filter(|span| !span.is_dummy())
+            .min_by_key(|span| span.lo)
+            .copied()
+            .unwrap_or(DUMMY_SP);
+        emit_error(WorkflowErrorKind::MissingModuleSpecifier {
+            span,
+            filename: &self.filename,
+        });
+    }
+
+    fn report_invalid_ids(&self) {
+        let Some(pattern) = &self.id_pattern else {
+            return;
+        };
+        let mut invalid_ids: Vec<_> = self.invalid_ids.borrow_mut().drain().collect();
+        invalid_ids.sort();
+        for (id, span) in invalid_ids {
+            emit_error(WorkflowErrorKind::InvalidId {
+                span,
+                id: &id,
+                pattern: pattern.as_str(),
+            });
+        }
+    }
+
+    fn create_cache_id(&self, name: &str) -> String {
+        naming::format_name("cache", &self.get_module_path(), name)
+    }
+
     // Generate a unique identifier that doesn't conflict with existing declarations
     fn generate_unique_name(&self, base_name: &str) -> String {
         let mut name = base_name.to_string();
@@ -1313,11 +3065,35 @@ impl StepTransform {
         name
     }
 
-    // Collect all declared identifiers in the module to avoid naming collisions
-    fn collect_declared_identifiers(&mut self, items: &[ModuleItem]) {
-        for item in items {
-            match item {
-                ModuleItem::Stmt(Stmt::Decl(decl)) => match decl {
+    // Name of the variable the object property step `prop_key` of `parent` in the current
+    // workflow is hoisted into, unique among the module's declarations and the other hoisted
+    // steps (`"a-b"` and `a_b` keys would both become `o$a_b`)
+    fn object_property_hoisted_var(&mut self, parent: &str, prop_key: &str) -> String {
+        let workflow_name = self.current_workflow_function_name.clone();
+        let key = (
+            workflow_name.clone().unwrap_or_default(),
+            parent.to_string(),
+            prop_key.to_string(),
+        );
+        if let Some(name) = self.object_property_hoisted_names.get(&key) {
+            return name.clone();
+        }
+        let name = self.generate_unique_name(&object_property_hoist_name(
+            workflow_name.as_deref(),
+            parent,
+            prop_key,
+            &self.options.hoisted_name_separator,
+        ));
+        self.declared_identifiers.insert(name.clone());
+        self.object_property_hoisted_names.insert(key, name.clone());
+        name
+    }
+
+    // Collect all declared identifiers in the module to avoid naming collisions
+    fn collect_declared_identifiers(&mut self, items: &[ModuleItem]) {
+        for item in items {
+            match item {
+                ModuleItem::Stmt(Stmt::Decl(decl)) => match decl {
                     Decl::Fn(fn_decl) => {
                         self.declared_identifiers
                             .insert(fn_decl.ident.sym.to_string());
@@ -1391,6 +3167,11 @@ impl StepTransform {
                         }
                         _ => {}
                     },
+                    // TypeScript's `import x = require("y")` (or `= Namespace.member`)
+                    ModuleDecl::TsImportEquals(import_equals) => {
+                        self.declared_identifiers
+                            .insert(import_equals.id.sym.to_string());
+                    }
                     ModuleDecl::Import(import_decl) => {
                         for specifier in &import_decl.specifiers {
                             match specifier {
@@ -1444,10 +3225,8 @@ impl StepTransform {
                 self.declared_identifiers.insert(ident.id.sym.to_string());
             }
             Pat::Array(array_pat) => {
-                for elem in &array_pat.elems {
-                    if let Some(elem) = elem {
-                        self.collect_idents_from_pat(elem);
-                    }
+                for elem in array_pat.elems.iter().flatten() {
+                    self.collect_idents_from_pat(elem);
                 }
             }
             Pat::Object(obj_pat) => {
@@ -1475,6 +3254,546 @@ impl StepTransform {
         }
     }
 
+    // Imports must directly follow the module-level directive (`strictImportOrder`)
+    fn check_import_order(&self, items: &[ModuleItem]) {
+        let directive = if self.has_file_step_directive {
+            self.step_directive.as_str()
+        } else if self.has_file_workflow_directive {
+            self.workflow_directive.as_str()
+        } else {
+            return;
+        };
+
+        // Skip the directive prologue; anything else before an import is misplaced
+        let mut after_prologue = false;
+        let mut found_statement = false;
+        for item in items {
+            match item {
+                ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. }))
+                    if !after_prologue && matches!(&**expr, Expr::Lit(Lit::Str(_))) => {}
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => {
+                    after_prologue = true;
+                    if found_statement {
+                        emit_error(WorkflowErrorKind::MisorderedImport {
+                            span: import_decl.span,
+                            directive,
+                        });
+                    }
+                }
+                _ => {
+                    after_prologue = true;
+                    found_statement = true;
+                }
+            }
+        }
+    }
+
+    // Module-level workflow functions must be exported, inline or through `export { ... }` or
+    // `export default` (`requireExportedWorkflows`)
+    fn check_unexported_workflows(&self, items: &[ModuleItem]) {
+        let mut exported = HashSet::new();
+        for item in items {
+            match item {
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(named)) if named.src.is_none() => {
+                    for specifier in &named.specifiers {
+                        if let ExportSpecifier::Named(ExportNamedSpecifier {
+                            orig: ModuleExportName::Ident(orig),
+                            ..
+                        }) = specifier
+                        {
+                            exported.insert(orig.sym.as_str());
+                        }
+                    }
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export_default)) => {
+                    if let Expr::Ident(ident) = &*export_default.expr {
+                        exported.insert(ident.sym.as_str());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let directive = self.workflow_directive.as_str();
+        let is_workflow = |expr: &Expr| match expr {
+            Expr::Fn(fn_expr) => fn_expr
+                .function
+                .body
+                .as_ref()
+                .is_some_and(|body| body_has_directive(&body.stmts, directive)),
+            Expr::Arrow(arrow) => match &*arrow.body {
+                BlockStmtOrExpr::BlockStmt(body) => body_has_directive(&body.stmts, directive),
+                BlockStmtOrExpr::Expr(_) => false,
+            },
+            _ => false,
+        };
+        for item in items {
+            match item {
+                ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl))) => {
+                    let name = fn_decl.ident.sym.as_str();
+                    let has_directive = fn_decl
+                        .function
+                        .body
+                        .as_ref()
+                        .is_some_and(|body| body_has_directive(&body.stmts, directive));
+                    if has_directive && !exported.contains(name) {
+                        emit_error(WorkflowErrorKind::UnexportedWorkflow {
+                            span: fn_decl.ident.span,
+                            name,
+                            directive,
+                        });
+                    }
+                }
+                ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) => {
+                    for declarator in &var_decl.decls {
+                        let (Pat::Ident(binding), Some(init)) =
+                            (&declarator.name, &declarator.init)
+                        else {
+                            continue;
+                        };
+                        let name = binding.id.sym.as_str();
+                        if is_workflow(init) && !exported.contains(name) {
+                            emit_error(WorkflowErrorKind::UnexportedWorkflow {
+                                span: binding.id.span,
+                                name,
+                                directive,
+                            });
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Warn about imports of other directive files, known either from the `directive_files`
+    // option or by a `.step`/`.workflow` segment in the file name (e.g. `./send-email.step.ts`)
+    fn check_directive_file_imports(&self, items: &[ModuleItem]) {
+        let directive = if self.has_file_step_directive {
+            self.step_directive.as_str()
+        } else if self.has_file_workflow_directive {
+            self.workflow_directive.as_str()
+        } else {
+            return;
+        };
+
+        for item in items {
+            let ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) = item else {
+                continue;
+            };
+            // Type-only imports don't load the other file at runtime
+            if import_decl.type_only {
+                continue;
+            }
+
+            let specifier = import_decl.src.value.to_string_lossy();
+            let file_name = specifier.rsplit('/').next().unwrap_or_default();
+            let is_directive_file = self.options.directive_files.iter().any(|f| *f == specifier)
+                || file_name
+                    .split('.')
+                    .skip(1)
+                    .any(|segment| segment == "step" || segment == "workflow");
+            if is_directive_file {
+                emit_warning(WorkflowWarningKind::DirectiveFileImport {
+                    span: import_decl.span,
+                    directive,
+                    specifier: &specifier,
+                });
+            }
+        }
+    }
+
+    // Warn about assignments to a variable holding object property steps. The steps are hoisted
+    // and registered for the object literal the variable was declared with, so a new object
+    // assigned to it no longer matches them.
+    fn check_reassigned_step_objects(&self, program: &Program) {
+        let names: HashSet<&str> = self
+            .object_property_step_functions
+            .iter()
+            .filter_map(|(parent_var_name, ..)| parent_var_name.split('/').next())
+            .filter(|name| !name.is_empty())
+            .collect();
+        if !names.is_empty() {
+            program.visit_with(&mut StepObjectReassignmentFinder {
+                names: &names,
+                shadowed: Vec::new(),
+            });
+        }
+    }
+
+    // Warn about workflows that never reference a step and about the calls they await, with the
+    // workflow bodies collected before the transform removed the directives (see
+    // `StepCallCollector`). The step names come from the steps collected by the first pass:
+    // function and variable names, and the method/property keys for `obj.step()` calls.
+    fn check_step_calls(&self) {
+        let mut step_names: HashSet<String> = self
+            .step_function_names
+            .iter()
+            .map(|name| name.rsplit(['.', '#']).next().unwrap_or(name).to_string())
+            .collect();
+        step_names.extend(
+            self.nested_step_functions
+                .iter()
+                .map(|(name, ..)| name.clone()),
+        );
+        step_names.extend(
+            self.object_property_step_functions
+                .iter()
+                .map(|(_, prop_name, ..)| prop_name.clone()),
+        );
+
+        for workflow in &self.workflow_step_calls {
+            if self.options.warn_workflows_without_steps
+                && !workflow
+                    .references
+                    .iter()
+                    .any(|name| step_names.contains(name))
+            {
+                emit_warning(WorkflowWarningKind::WorkflowWithoutSteps {
+                    span: workflow.span,
+                    directive: &self.workflow_directive,
+                });
+            }
+            for call in &workflow.awaited_calls {
+                if step_names.contains(&call.name) {
+                    if self.options.warn_unhandled_step_calls && !call.in_try {
+                        emit_warning(WorkflowWarningKind::UnhandledStepCall { span: call.span });
+                    }
+                } else if self.options.warn_awaited_non_steps {
+                    // Imports may be steps of other files, and `Promise.all()` and friends wait
+                    // for the calls passed to them, which are checked themselves
+                    let is_known_elsewhere = call.root.as_deref().is_some_and(|root| {
+                        root == "Promise" || self.module_imports.contains(root)
+                    });
+                    if !is_known_elsewhere {
+                        emit_warning(WorkflowWarningKind::AwaitedNonStep {
+                            span: call.span,
+                            name: &call.name,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    // Report imports of modules listed in `forbidden_workflow_imports` from a file that defines
+    // workflows. Workflows are replayed, so their I/O belongs in steps.
+    fn check_forbidden_workflow_imports(&self, items: &[ModuleItem]) {
+        let forbidden_imports = &self.options.forbidden_workflow_imports;
+        if forbidden_imports.is_empty() || self.workflow_function_names.is_empty() {
+            return;
+        }
+
+        for item in items {
+            let ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) = item else {
+                continue;
+            };
+            if import_decl.type_only {
+                continue;
+            }
+
+            let specifier = import_decl.src.value.to_string_lossy();
+            let module = specifier.strip_prefix("node:").unwrap_or(&specifier);
+            let is_forbidden = forbidden_imports.iter().any(|forbidden| {
+                let forbidden = forbidden.strip_prefix("node:").unwrap_or(forbidden);
+                module
+                    .strip_prefix(forbidden)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            });
+            if is_forbidden {
+                emit_error(WorkflowErrorKind::ForbiddenImport {
+                    span: import_decl.span,
+                    directive: self.workflow_directive.as_str(),
+                    specifier: &specifier,
+                });
+            }
+        }
+    }
+
+    // A step or workflow function that is redeclared later in the module would have its
+    // registration reference the shadowing binding instead of the function itself
+    fn check_shadowed_directive_functions(&self, items: &[ModuleItem]) {
+        let mut directive_functions: HashMap<String, &str> = HashMap::new();
+
+        for item in items {
+            let (decl, is_exported) = match item {
+                ModuleItem::Stmt(Stmt::Decl(decl)) => (decl, false),
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => {
+                    (&export_decl.decl, true)
+                }
+                _ => continue,
+            };
+
+            let mut declared: Vec<(&Ident, Option<&str>)> = Vec::new();
+            match decl {
+                Decl::Fn(fn_decl) => {
+                    let directive = if self.has_step_directive(&fn_decl.function, is_exported) {
+                        Some(self.step_directive.as_str())
+                    } else if self.has_workflow_directive(&fn_decl.function, is_exported) {
+                        Some(self.workflow_directive.as_str())
+                    } else {
+                        None
+                    };
+                    declared.push((&fn_decl.ident, directive));
+                }
+                Decl::Var(var_decl) => {
+                    for declarator in &var_decl.decls {
+                        let directive = match declarator.init.as_deref() {
+                            Some(Expr::Arrow(arrow)) => {
+                                if self.has_step_directive_arrow(arrow, is_exported) {
+                                    Some(self.step_directive.as_str())
+                                } else if self.has_workflow_directive_arrow(arrow, is_exported) {
+                                    Some(self.workflow_directive.as_str())
+                                } else {
+                                    None
+                                }
+                            }
+                            Some(Expr::Fn(fn_expr)) => {
+                                if self.has_step_directive(&fn_expr.function, is_exported) {
+                                    Some(self.step_directive.as_str())
+                                } else if self
+                                    .has_workflow_directive(&fn_expr.function, is_exported)
+                                {
+                                    Some(self.workflow_directive.as_str())
+                                } else {
+                                    None
+                                }
+                            }
+                            _ => None,
+                        };
+                        let mut idents = Vec::new();
+                        collect_binding_idents(&declarator.name, &mut idents);
+                        declared.extend(idents.into_iter().map(|ident| (ident, directive)));
+                    }
+                }
+                Decl::Class(class_decl) => declared.push((&class_decl.ident, None)),
+                _ => {}
+            }
+
+            for (ident, directive) in declared {
+                let name = ident.sym.to_string();
+                if let Some(existing) = directive_functions.get(&name) {
+                    emit_error(WorkflowErrorKind::ShadowedFunction {
+                        span: ident.span,
+                        name,
+                        directive: existing,
+                    });
+                } else if let Some(directive) = directive {
+                    directive_functions.insert(name, directive);
+                }
+            }
+        }
+    }
+
+    // Report `(async () => { "use step"; ... })()` at module level. It runs once while the module
+    // loads, so there's nothing to register or call as a step or workflow.
+    fn check_immediately_invoked_directives(&self, items: &[ModuleItem]) {
+        let directives = [
+            self.step_directive.as_str(),
+            self.workflow_directive.as_str(),
+        ];
+        let mut invoked: Vec<&Expr> = Vec::new();
+        for item in items {
+            match item {
+                ModuleItem::Stmt(Stmt::Expr(expr_stmt)) => invoked.push(&expr_stmt.expr),
+                ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl)))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    decl: Decl::Var(var_decl),
+                    ..
+                })) => {
+                    invoked.extend(
+                        var_decl
+                            .decls
+                            .iter()
+                            .filter_map(|decl| decl.init.as_deref()),
+                    );
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export_default)) => {
+                    invoked.push(&export_default.expr);
+                }
+                _ => {}
+            }
+        }
+
+        for mut expr in invoked {
+            // `await (async () => { ... })()` and `((async () => { ... }))()` are invoked too
+            while let Expr::Paren(ParenExpr { expr: inner, .. })
+            | Expr::Await(AwaitExpr { arg: inner, .. }) = expr
+            {
+                expr = inner;
+            }
+            let Expr::Call(CallExpr {
+                callee: Callee::Expr(callee),
+                ..
+            }) = expr
+            else {
+                continue;
+            };
+            let mut callee = &**callee;
+            while let Expr::Paren(paren) = callee {
+                callee = &paren.expr;
+            }
+
+            let (span, body) = match callee {
+                Expr::Arrow(arrow_expr) => match &*arrow_expr.body {
+                    BlockStmtOrExpr::BlockStmt(block) => (arrow_expr.span, block),
+                    BlockStmtOrExpr::Expr(_) => continue,
+                },
+                Expr::Fn(fn_expr) => match &fn_expr.function.body {
+                    Some(block) => (fn_expr.function.span, block),
+                    None => continue,
+                },
+                _ => continue,
+            };
+            for directive in directives {
+                if find_prologue_directive(&body.stmts, directive).is_some() {
+                    emit_error(WorkflowErrorKind::ImmediatelyInvoked { span, directive });
+                }
+            }
+        }
+    }
+
+    // In a file with a module-level directive, `async function foo() {}` exported later via
+    // `export { foo }` is as much a step/workflow as `export async function foo() {}`. Give it
+    // the directive in its own body so the rest of the transform treats it like any other
+    // directive function. Literal constants and classes are only allowed with
+    // `allow_non_function_exports`, like when they're exported inline.
+    fn mark_specifier_exports(&self, items: &mut [ModuleItem]) {
+        let directive = if self.has_file_step_directive {
+            self.step_directive.as_str()
+        } else if self.has_file_workflow_directive {
+            self.workflow_directive.as_str()
+        } else {
+            return;
+        };
+
+        let mut exported: HashMap<String, swc_core::common::Span> = HashMap::new();
+        for item in items.iter() {
+            let ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(named)) = item else {
+                continue;
+            };
+            if named.src.is_some() || named.type_only {
+                continue;
+            }
+            // Each invalid export is reported at its own specifier
+            for specifier in &named.specifiers {
+                if let ExportSpecifier::Named(ExportNamedSpecifier {
+                    span,
+                    orig: ModuleExportName::Ident(orig),
+                    is_type_only: false,
+                    ..
+                }) = specifier
+                {
+                    exported.entry(orig.sym.to_string()).or_insert(*span);
+                }
+            }
+        }
+        if exported.is_empty() {
+            return;
+        }
+
+        let directive_stmt = || {
+            Stmt::Expr(ExprStmt {
+                span: DUMMY_SP,
+                expr: Box::new(Expr::Lit(Lit::Str(Str {
+                    span: DUMMY_SP,
+                    value: directive.into(),
+                    raw: None,
+                }))),
+            })
+        };
+        let add_directive = |stmts: &mut Vec<Stmt>| {
+            if find_prologue_directive(stmts, directive).is_none() {
+                stmts.insert(0, directive_stmt());
+            }
+        };
+        for item in items.iter_mut() {
+            let ModuleItem::Stmt(Stmt::Decl(decl)) = item else {
+                continue;
+            };
+            match decl {
+                Decl::Fn(fn_decl) => {
+                    let Some(&span) = exported.get(fn_decl.ident.sym.as_str()) else {
+                        continue;
+                    };
+                    if !fn_decl.function.is_async {
+                        emit_error(WorkflowErrorKind::InvalidExport { span, directive });
+                    } else if let Some(body) = &mut fn_decl.function.body {
+                        add_directive(&mut body.stmts);
+                    }
+                }
+                Decl::Var(var_decl) => {
+                    for decl in var_decl.decls.iter_mut() {
+                        let Pat::Ident(binding) = &decl.name else {
+                            continue;
+                        };
+                        let Some(&span) = exported.get(binding.id.sym.as_str()) else {
+                            continue;
+                        };
+                        match decl.init.as_deref_mut() {
+                            Some(Expr::Fn(fn_expr)) if fn_expr.function.is_async => {
+                                if let Some(body) = &mut fn_expr.function.body {
+                                    add_directive(&mut body.stmts);
+                                }
+                            }
+                            Some(Expr::Arrow(arrow_expr)) if arrow_expr.is_async => {
+                                if let BlockStmtOrExpr::Expr(expr) = &mut *arrow_expr.body {
+                                    let arg = std::mem::replace(expr, Expr::undefined(DUMMY_SP));
+                                    *arrow_expr.body = BlockStmtOrExpr::BlockStmt(BlockStmt {
+                                        stmts: vec![Stmt::Return(ReturnStmt {
+                                            span: DUMMY_SP,
+                                            arg: Some(arg),
+                                        })],
+                                        ..Default::default()
+                                    });
+                                }
+                                if let BlockStmtOrExpr::BlockStmt(body) = &mut *arrow_expr.body {
+                                    add_directive(&mut body.stmts);
+                                }
+                            }
+                            Some(Expr::Lit(_)) if self.options.allow_non_function_exports => {}
+                            Some(Expr::Fn(_) | Expr::Arrow(_) | Expr::Lit(_)) => {
+                                emit_error(WorkflowErrorKind::InvalidExport { span, directive });
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                Decl::Class(_) if self.options.allow_non_function_exports => {}
+                Decl::Class(class_decl) => {
+                    if let Some(&span) = exported.get(class_decl.ident.sym.as_str()) {
+                        emit_error(WorkflowErrorKind::InvalidExport { span, directive });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Name of a step nested in the function `parent` as used in its ID, e.g. `calculate/scale`
+    // (see `nestedStepIdSeparator`)
+    fn nested_step_id_name(&self, parent: &str, name: &str) -> String {
+        let separator = &self.options.nested_step_id_separator;
+        format!("{}{}{}", parent, separator, name)
+    }
+
+    // Name of the variable a step nested in the function `parent` is hoisted into, e.g.
+    // `calculate$scale` (see `hoistedNameSeparator`)
+    fn hoisted_step_name(&self, parent: &str, name: &str) -> String {
+        let separator = &self.options.hoisted_name_separator;
+        format!("{}{}{}", parent, separator, name)
+    }
+
+    // Path of an object property step as used in its ID and manifest entry, e.g. `tools/round`.
+    // Its parts are joined with `nestedStepIdSeparator` like a step nested in a function.
+    fn object_property_id_path(&self, parent: &str, key: &str) -> String {
+        let path = object_property_path(parent, key);
+        match self.options.nested_step_id_separator.as_str() {
+            "/" => path,
+            separator => path.replace('/', separator),
+        }
+    }
+
     // Create an identifier for an object property step function
     // Used for functions defined as object properties, e.g., tool({ execute: async () => {...} })
     fn create_object_property_id(
@@ -1483,14 +3802,61 @@ impl StepTransform {
         prop_name: &str,
         is_workflow: bool,
         workflow_name: Option<&str>,
+        span: swc_core::common::Span,
     ) -> String {
+        let path = self.object_property_id_path(parent_var_name, prop_name);
         let fn_name = if let Some(wf_name) = workflow_name {
-            format!("{}/{}/{}", wf_name, parent_var_name, prop_name)
+            self.nested_step_id_name(wf_name, &path)
         } else {
-            format!("{}/{}", parent_var_name, prop_name)
+            path
         };
         let prefix = if is_workflow { "workflow" } else { "step" };
-        naming::format_name(prefix, &self.get_module_path(), &fn_name)
+        let id = naming::format_name(prefix, &self.get_module_path(), &fn_name);
+        self.check_id(&id, span);
+        id
+    }
+
+    // Process the properties of an object nested inside another one, unless that goes deeper
+    // than max_object_nesting_depth. That is only an error if the object holds a step, plain
+    // data may be nested as deep as it likes.
+    fn process_nested_object_properties(&mut self, obj_lit: &mut ObjectLit, parent_path: &str) {
+        if self.object_nesting_depth >= self.options.max_object_nesting_depth {
+            let mut finder = DirectiveFunctionFinder {
+                directives: [&self.step_directive, &self.workflow_directive],
+                functions: Vec::new(),
+            };
+            obj_lit.visit_with(&mut finder);
+            if !finder.functions.is_empty() {
+                emit_error(WorkflowErrorKind::ObjectNestingTooDeep {
+                    span: obj_lit.span,
+                    directive: self.step_directive.as_str(),
+                    max_depth: self.options.max_object_nesting_depth,
+                });
+            }
+            return;
+        }
+        self.object_nesting_depth += 1;
+        self.process_object_properties_for_step_functions(obj_lit, parent_path);
+        self.object_nesting_depth -= 1;
+    }
+
+    // Warn about a step under a computed key other than a literal, which is named after its
+    // position in the object
+    fn check_computed_step_key(&self, key: &PropName, prop_key: &str) {
+        if !self.options.warn_computed_step_keys {
+            return;
+        }
+        let PropName::Computed(computed) = key else {
+            return;
+        };
+        if matches!(&*computed.expr, Expr::Lit(Lit::Str(_) | Lit::Num(_))) {
+            return;
+        }
+        emit_warning(WorkflowWarningKind::ComputedStepKey {
+            span: computed.span,
+            name: prop_key,
+            member: matches!(&*computed.expr, Expr::Member(_)),
+        });
     }
 
     // Process object properties for step functions
@@ -1499,16 +3865,12 @@ impl StepTransform {
         obj_lit: &mut ObjectLit,
         parent_var_name: &str,
     ) {
-        for prop in &mut obj_lit.props {
+        for (index, prop) in obj_lit.props.iter_mut().enumerate() {
             if let PropOrSpread::Prop(boxed_prop) = prop {
                 match &mut **boxed_prop {
                     Prop::KeyValue(kv_prop) => {
                         // Get the property key first
-                        let prop_key = match &kv_prop.key {
-                            PropName::Ident(ident) => ident.sym.to_string(),
-                            PropName::Str(s) => s.value.to_string_lossy().to_string(),
-                            _ => continue, // Skip complex keys
-                        };
+                        let prop_key = object_property_key(&kv_prop.key, index);
 
                         // Check if we should transform this property
                         let should_transform = match &*kv_prop.value {
@@ -1522,13 +3884,16 @@ impl StepTransform {
                         };
 
                         if should_transform {
+                            self.check_computed_step_key(&kv_prop.key, &prop_key);
+
                             // Process the transformation
                             match &mut *kv_prop.value {
                                 Expr::Arrow(arrow_expr) => {
                                     if !arrow_expr.is_async {
                                         emit_error(WorkflowErrorKind::NonAsyncFunction {
                                             span: arrow_expr.span,
-                                            directive: "use step",
+                                            directive: self.step_directive.as_str(),
+                                            async_at: self.async_fix_at(arrow_expr.span),
                                         });
                                     } else {
                                         // Remove the directive first
@@ -1599,7 +3964,8 @@ impl StepTransform {
                                     if !fn_expr.function.is_async {
                                         emit_error(WorkflowErrorKind::NonAsyncFunction {
                                             span: fn_expr.function.span,
-                                            directive: "use step",
+                                            directive: self.step_directive.as_str(),
+                                            async_at: self.async_fix_at(fn_expr.function.span),
                                         });
                                     } else {
                                         // Remove the directive first
@@ -1637,8 +4003,9 @@ impl StepTransform {
                             match &mut *kv_prop.value {
                                 Expr::Object(nested_obj) => {
                                     // Recursively process nested objects with compound path
-                                    let compound_path = format!("{}/{}", parent_var_name, prop_key);
-                                    self.process_object_properties_for_step_functions(
+                                    let compound_path =
+                                        object_property_path(parent_var_name, &prop_key);
+                                    self.process_nested_object_properties(
                                         nested_obj,
                                         &compound_path,
                                     );
@@ -1648,8 +4015,8 @@ impl StepTransform {
                                     for arg in &mut call_expr.args {
                                         if let Expr::Object(nested_obj) = &mut *arg.expr {
                                             let compound_path =
-                                                format!("{}/{}", parent_var_name, prop_key);
-                                            self.process_object_properties_for_step_functions(
+                                                object_property_path(parent_var_name, &prop_key);
+                                            self.process_nested_object_properties(
                                                 nested_obj,
                                                 &compound_path,
                                             );
@@ -1662,17 +4029,18 @@ impl StepTransform {
                     }
                     Prop::Method(method_prop) => {
                         // Handle object methods like: execute() { "use step"; ... }
-                        let prop_key = match &method_prop.key {
-                            PropName::Ident(ident) => ident.sym.to_string(),
-                            PropName::Str(s) => s.value.to_string_lossy().to_string(),
-                            _ => continue, // Skip complex keys
-                        };
+                        let prop_key = object_property_key(&method_prop.key, index);
 
                         if self.has_use_step_directive(&method_prop.function.body) {
+                            self.check_computed_step_key(&method_prop.key, &prop_key);
                             if !method_prop.function.is_async {
                                 emit_error(WorkflowErrorKind::NonAsyncFunction {
                                     span: method_prop.function.span,
-                                    directive: "use step",
+                                    directive: self.step_directive.as_str(),
+                                    async_at: self.method_async_fix_at(
+                                        &method_prop.key,
+                                        &method_prop.function,
+                                    ),
                                 });
                             } else {
                                 // Remove the directive first
@@ -1703,37 +4071,31 @@ impl StepTransform {
                                 match self.mode {
                                     TransformMode::Step => {
                                         // In step mode, replace method with key-value property referencing the hoisted variable
-                                        // Replace slashes with $ in parent_var_name to create valid JS identifier
-                                        let safe_parent_name = parent_var_name.replace('/', "$");
-                                        let hoist_var_name = if let Some(ref workflow_name) =
-                                            self.current_workflow_function_name
-                                        {
-                                            format!(
-                                                "{}${}${}",
-                                                workflow_name, safe_parent_name, prop_key
-                                            )
-                                        } else {
-                                            format!("{}${}", safe_parent_name, prop_key)
-                                        };
+                                        let hoist_var_name = self.object_property_hoisted_var(
+                                            parent_var_name,
+                                            &prop_key,
+                                        );
                                         let step_id = self.create_object_property_id(
                                             parent_var_name,
                                             &prop_key,
                                             false,
                                             self.current_workflow_function_name.as_deref(),
+                                            span,
                                         );
                                         // Replace the method with a key-value property referencing the hoisted function
-                                        *boxed_prop = Box::new(Prop::KeyValue(KeyValueProp {
+                                        **boxed_prop = Prop::KeyValue(KeyValueProp {
                                             key: method_prop.key.clone(),
                                             value: Box::new(Expr::Ident(Ident::new(
                                                 hoist_var_name.into(),
                                                 DUMMY_SP,
                                                 SyntaxContext::empty(),
                                             ))),
-                                        }));
+                                        });
                                         self.object_property_workflow_conversions.push((
                                             parent_var_name.to_string(),
                                             prop_key,
                                             step_id,
+                                            span,
                                         ));
                                     }
                                     TransformMode::Workflow => {
@@ -1743,50 +4105,47 @@ impl StepTransform {
                                             &prop_key,
                                             false,
                                             self.current_workflow_function_name.as_deref(),
+                                            span,
                                         );
-                                        *boxed_prop = Box::new(Prop::KeyValue(KeyValueProp {
+                                        **boxed_prop = Prop::KeyValue(KeyValueProp {
                                             key: method_prop.key.clone(),
                                             value: Box::new(self.create_step_initializer(&step_id)),
-                                        }));
+                                        });
                                         self.object_property_workflow_conversions.push((
                                             parent_var_name.to_string(),
                                             prop_key,
                                             step_id,
+                                            span,
                                         ));
                                     }
                                     TransformMode::Client => {
                                         // In client mode, replace method with key-value property referencing the hoisted variable
                                         // (same as step mode) so the stepId property is accessible
-                                        let safe_parent_name = parent_var_name.replace('/', "$");
-                                        let hoist_var_name = if let Some(ref workflow_name) =
-                                            self.current_workflow_function_name
-                                        {
-                                            format!(
-                                                "{}${}${}",
-                                                workflow_name, safe_parent_name, prop_key
-                                            )
-                                        } else {
-                                            format!("{}${}", safe_parent_name, prop_key)
-                                        };
+                                        let hoist_var_name = self.object_property_hoisted_var(
+                                            parent_var_name,
+                                            &prop_key,
+                                        );
                                         let step_id = self.create_object_property_id(
                                             parent_var_name,
                                             &prop_key,
                                             false,
                                             self.current_workflow_function_name.as_deref(),
+                                            span,
                                         );
                                         // Replace the method with a key-value property referencing the hoisted function
-                                        *boxed_prop = Box::new(Prop::KeyValue(KeyValueProp {
+                                        **boxed_prop = Prop::KeyValue(KeyValueProp {
                                             key: method_prop.key.clone(),
                                             value: Box::new(Expr::Ident(Ident::new(
                                                 hoist_var_name.into(),
                                                 DUMMY_SP,
                                                 SyntaxContext::empty(),
                                             ))),
-                                        }));
+                                        });
                                         self.object_property_workflow_conversions.push((
                                             parent_var_name.to_string(),
                                             prop_key,
                                             step_id,
+                                            span,
                                         ));
                                     }
                                 }
@@ -1805,26 +4164,20 @@ impl StepTransform {
         kv_prop: &mut KeyValueProp,
         parent_var_name: &str,
         prop_key: &str,
-        _span: swc_core::common::Span,
+        span: swc_core::common::Span,
     ) {
         let step_id = self.create_object_property_id(
             parent_var_name,
             prop_key,
             false,
             self.current_workflow_function_name.as_deref(),
+            span,
         );
 
         match self.mode {
             TransformMode::Step => {
                 // In step mode, replace with reference to hoisted variable
-                // Replace slashes with $ in parent_var_name to create valid JS identifier
-                let safe_parent_name = parent_var_name.replace('/', "$");
-                let hoist_var_name =
-                    if let Some(ref workflow_name) = self.current_workflow_function_name {
-                        format!("{}${}${}", workflow_name, safe_parent_name, prop_key)
-                    } else {
-                        format!("{}${}", safe_parent_name, prop_key)
-                    };
+                let hoist_var_name = self.object_property_hoisted_var(parent_var_name, prop_key);
                 *kv_prop.value = Expr::Ident(Ident::new(
                     hoist_var_name.into(),
                     DUMMY_SP,
@@ -1835,6 +4188,7 @@ impl StepTransform {
                     parent_var_name.to_string(),
                     prop_key.to_string(),
                     step_id,
+                    span,
                 ));
             }
             TransformMode::Workflow => {
@@ -1844,18 +4198,13 @@ impl StepTransform {
                     parent_var_name.to_string(),
                     prop_key.to_string(),
                     step_id,
+                    span,
                 ));
             }
             TransformMode::Client => {
                 // In client mode, replace with reference to hoisted variable
                 // (same as step mode) so the stepId property is accessible
-                let safe_parent_name = parent_var_name.replace('/', "$");
-                let hoist_var_name =
-                    if let Some(ref workflow_name) = self.current_workflow_function_name {
-                        format!("{}${}${}", workflow_name, safe_parent_name, prop_key)
-                    } else {
-                        format!("{}${}", safe_parent_name, prop_key)
-                    };
+                let hoist_var_name = self.object_property_hoisted_var(parent_var_name, prop_key);
                 *kv_prop.value = Expr::Ident(Ident::new(
                     hoist_var_name.into(),
                     DUMMY_SP,
@@ -1866,6 +4215,7 @@ impl StepTransform {
                     parent_var_name.to_string(),
                     prop_key.to_string(),
                     step_id,
+                    span,
                 ));
             }
         }
@@ -1976,6 +4326,25 @@ impl StepTransform {
         }
     }
 
+    // Report a step or workflow directive in the body of a getter or setter, or in a function
+    // defined inside it (e.g. a getter returning a step)
+    fn check_accessor_directives(&self, span: swc_core::common::Span, stmts: &[Stmt]) {
+        for directive in [&self.step_directive, &self.workflow_directive] {
+            if body_has_directive(stmts, directive) {
+                emit_error(WorkflowErrorKind::Accessor { span, directive });
+            }
+        }
+
+        let mut finder = DirectiveFunctionFinder {
+            directives: [&self.step_directive, &self.workflow_directive],
+            functions: Vec::new(),
+        };
+        stmts.visit_with(&mut finder);
+        for (span, directive) in finder.functions {
+            emit_error(WorkflowErrorKind::FunctionInAccessor { span, directive });
+        }
+    }
+
     // Check if a function has the "use step" directive
     fn has_use_step_directive(&self, body: &Option<BlockStmt>) -> bool {
         if let Some(body) = body {
@@ -1989,7 +4358,7 @@ impl StepTransform {
                 }) = stmt
                 {
                     if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
-                        if value == "use step" {
+                        if value == self.step_directive.as_str() {
                             if !is_first_meaningful {
                                 emit_error(WorkflowErrorKind::MisplacedDirective {
                                     span: *stmt_span,
@@ -1999,39 +4368,63 @@ impl StepTransform {
                             }
                             return true;
                         } else if detect_similar_strings(
-                            &value.to_string_lossy().to_string(),
-                            "use step",
+                            value.to_string_lossy().as_ref(),
+                            self.step_directive.as_str(),
                         ) {
-                            emit_error(WorkflowErrorKind::MisspelledDirective {
-                                span: *stmt_span,
-                                directive: value.to_string_lossy().to_string(),
-                                expected: "use step",
-                            });
+                            emit_diagnostic(
+                                WorkflowErrorKind::MisspelledDirective {
+                                    span: *stmt_span,
+                                    directive: value.to_string_lossy().to_string(),
+                                    expected: self.step_directive.as_str(),
+                                },
+                                self.options.diagnostics,
+                            );
                         }
                     }
+                    if get_template_literal_value(expr).as_deref()
+                        == Some(self.step_directive.as_str())
+                    {
+                        emit_error(WorkflowErrorKind::TemplateLiteralDirective {
+                            span: *stmt_span,
+                            directive: self.step_directive.as_str(),
+                        });
+                    }
+                }
+                // Any statement other than a string literal ends the directive prologue
+                if !is_prologue_stmt(stmt) {
+                    is_first_meaningful = false;
                 }
-                // Any non-directive statement means directives can't come after
-                is_first_meaningful = false;
             }
 
             // Check for directive inside TypeScript `using` transformation pattern
             if let Some(try_block) = get_try_block_from_using_pattern(&body.stmts) {
-                if get_directive_from_block(try_block, "use step") {
+                if get_directive_from_block(try_block, self.step_directive.as_str()) {
                     return true;
                 }
                 // Also check for misspellings inside the using pattern's try block
                 if let Some((str_lit, span)) = get_first_string_literal_from_block(try_block) {
                     let value = str_lit.value.to_string_lossy().to_string();
-                    if detect_similar_strings(&value, "use step") {
-                        emit_error(WorkflowErrorKind::MisspelledDirective {
-                            span,
-                            directive: value,
-                            expected: "use step",
-                        });
+                    if detect_similar_strings(&value, self.step_directive.as_str()) {
+                        emit_diagnostic(
+                            WorkflowErrorKind::MisspelledDirective {
+                                span,
+                                directive: value,
+                                expected: self.step_directive.as_str(),
+                            },
+                            self.options.diagnostics,
+                        );
                     }
                 }
             }
 
+            // Check for directive inside a downleveled async function's generator body
+            if let Some(generator_body) = get_generator_body_from_async_helper_pattern(&body.stmts)
+            {
+                if get_directive_from_block(generator_body, self.step_directive.as_str()) {
+                    return true;
+                }
+            }
+
             false
         } else {
             false
@@ -2053,7 +4446,7 @@ impl StepTransform {
                     if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
                         #[cfg(debug_assertions)]
                         eprintln!("directive candidate: {:?}", value);
-                        if value == "use workflow" {
+                        if value == self.workflow_directive.as_str() {
                             if !is_first_meaningful {
                                 emit_error(WorkflowErrorKind::MisplacedDirective {
                                     span: *stmt_span,
@@ -2063,35 +4456,51 @@ impl StepTransform {
                             }
                             return true;
                         } else if detect_similar_strings(
-                            &value.to_string_lossy().to_string(),
-                            "use workflow",
+                            value.to_string_lossy().as_ref(),
+                            self.workflow_directive.as_str(),
                         ) {
-                            emit_error(WorkflowErrorKind::MisspelledDirective {
-                                span: *stmt_span,
-                                directive: value.to_string_lossy().to_string(),
-                                expected: "use workflow",
-                            });
+                            emit_diagnostic(
+                                WorkflowErrorKind::MisspelledDirective {
+                                    span: *stmt_span,
+                                    directive: value.to_string_lossy().to_string(),
+                                    expected: self.workflow_directive.as_str(),
+                                },
+                                self.options.diagnostics,
+                            );
                         }
                     }
+                    if get_template_literal_value(expr).as_deref()
+                        == Some(self.workflow_directive.as_str())
+                    {
+                        emit_error(WorkflowErrorKind::TemplateLiteralDirective {
+                            span: *stmt_span,
+                            directive: self.workflow_directive.as_str(),
+                        });
+                    }
+                }
+                // Any statement other than a string literal ends the directive prologue
+                if !is_prologue_stmt(stmt) {
+                    is_first_meaningful = false;
                 }
-                // Any non-directive statement means directives can't come after
-                is_first_meaningful = false;
             }
 
             // Check for directive inside TypeScript `using` transformation pattern
             if let Some(try_block) = get_try_block_from_using_pattern(&body.stmts) {
-                if get_directive_from_block(try_block, "use workflow") {
+                if get_directive_from_block(try_block, self.workflow_directive.as_str()) {
                     return true;
                 }
                 // Also check for misspellings inside the using pattern's try block
                 if let Some((str_lit, span)) = get_first_string_literal_from_block(try_block) {
                     let value = str_lit.value.to_string_lossy().to_string();
-                    if detect_similar_strings(&value, "use workflow") {
-                        emit_error(WorkflowErrorKind::MisspelledDirective {
-                            span,
-                            directive: value,
-                            expected: "use workflow",
-                        });
+                    if detect_similar_strings(&value, self.workflow_directive.as_str()) {
+                        emit_diagnostic(
+                            WorkflowErrorKind::MisspelledDirective {
+                                span,
+                                directive: value,
+                                expected: self.workflow_directive.as_str(),
+                            },
+                            self.options.diagnostics,
+                        );
                     }
                 }
             }
@@ -2111,7 +4520,7 @@ impl StepTransform {
             match item {
                 ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, span, .. })) => {
                     if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
-                        if value == "use step" {
+                        if value == self.step_directive.as_str() {
                             if !is_first_meaningful {
                                 emit_error(WorkflowErrorKind::MisplacedDirective {
                                     span: *span,
@@ -2122,7 +4531,7 @@ impl StepTransform {
                                 found_directive = true;
                                 // Don't break - continue checking for other directives
                             }
-                        } else if value == "use workflow" {
+                        } else if value == self.workflow_directive.as_str() {
                             // Can't have both directives
                             if found_directive {
                                 emit_error(WorkflowErrorKind::MisplacedDirective {
@@ -2132,18 +4541,34 @@ impl StepTransform {
                                 });
                             }
                         } else if detect_similar_strings(
-                            &value.to_string_lossy().to_string(),
-                            "use step",
+                            value.to_string_lossy().as_ref(),
+                            self.step_directive.as_str(),
                         ) {
-                            emit_error(WorkflowErrorKind::MisspelledDirective {
-                                span: *span,
-                                directive: value.to_string_lossy().to_string(),
-                                expected: "use step",
-                            });
+                            emit_diagnostic(
+                                WorkflowErrorKind::MisspelledDirective {
+                                    span: *span,
+                                    directive: value.to_string_lossy().to_string(),
+                                    expected: self.step_directive.as_str(),
+                                },
+                                self.options.diagnostics,
+                            );
                         }
                     }
-                    // Any non-directive expression statement means directives can't come after
-                    if !found_directive {
+                    if get_template_literal_value(expr).as_deref()
+                        == Some(self.step_directive.as_str())
+                    {
+                        emit_error(WorkflowErrorKind::TemplateLiteralDirective {
+                            span: *span,
+                            directive: self.step_directive.as_str(),
+                        });
+                    }
+                    // Any expression statement other than a prologue entry like "use strict"
+                    // ends the directive prologue ("use workflow" conflicts, so it ends it too)
+                    let is_prologue_entry = matches!(
+                        &**expr,
+                        Expr::Lit(Lit::Str(Str { value, .. })) if value != self.workflow_directive.as_str()
+                    );
+                    if !found_directive && !is_prologue_entry {
                         is_first_meaningful = false;
                     }
                 }
@@ -2175,7 +4600,7 @@ impl StepTransform {
             match item {
                 ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, span, .. })) => {
                     if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
-                        if value == "use workflow" {
+                        if value == self.workflow_directive.as_str() {
                             if !is_first_meaningful {
                                 emit_error(WorkflowErrorKind::MisplacedDirective {
                                     span: *span,
@@ -2186,7 +4611,7 @@ impl StepTransform {
                                 found_directive = true;
                                 // Don't break - continue checking for other directives
                             }
-                        } else if value == "use step" {
+                        } else if value == self.step_directive.as_str() {
                             // Can't have both directives
                             if found_directive {
                                 emit_error(WorkflowErrorKind::MisplacedDirective {
@@ -2196,18 +4621,34 @@ impl StepTransform {
                                 });
                             }
                         } else if detect_similar_strings(
-                            &value.to_string_lossy().to_string(),
-                            "use workflow",
+                            value.to_string_lossy().as_ref(),
+                            self.workflow_directive.as_str(),
                         ) {
-                            emit_error(WorkflowErrorKind::MisspelledDirective {
-                                span: *span,
-                                directive: value.to_string_lossy().to_string(),
-                                expected: "use workflow",
-                            });
+                            emit_diagnostic(
+                                WorkflowErrorKind::MisspelledDirective {
+                                    span: *span,
+                                    directive: value.to_string_lossy().to_string(),
+                                    expected: self.workflow_directive.as_str(),
+                                },
+                                self.options.diagnostics,
+                            );
                         }
                     }
-                    // Any non-directive expression statement means directives can't come after
-                    if !found_directive {
+                    if get_template_literal_value(expr).as_deref()
+                        == Some(self.workflow_directive.as_str())
+                    {
+                        emit_error(WorkflowErrorKind::TemplateLiteralDirective {
+                            span: *span,
+                            directive: self.workflow_directive.as_str(),
+                        });
+                    }
+                    // Any expression statement other than a prologue entry like "use strict"
+                    // ends the directive prologue ("use step" conflicts, so it ends it too)
+                    let is_prologue_entry = matches!(
+                        &**expr,
+                        Expr::Lit(Lit::Str(Str { value, .. })) if value != self.step_directive.as_str()
+                    );
+                    if !found_directive && !is_prologue_entry {
                         is_first_meaningful = false;
                     }
                 }
@@ -2235,16 +4676,19 @@ impl StepTransform {
         if let Some(body) = body {
             if !body.stmts.is_empty() {
                 // First try to remove from the top level
-                if let Stmt::Expr(ExprStmt { expr, .. }) = &body.stmts[0] {
-                    if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
-                        if value == "use step" {
-                            body.stmts.remove(0);
-                            return;
-                        }
-                    }
+                if let Some(index) =
+                    find_prologue_directive(&body.stmts, self.step_directive.as_str())
+                {
+                    body.stmts.remove(index);
+                    return;
                 }
                 // Also try to remove from inside the `using` pattern's try block
-                remove_directive_from_using_pattern(&mut body.stmts, "use step");
+                remove_directive_from_using_pattern(&mut body.stmts, self.step_directive.as_str());
+                // Or from inside a downleveled async function's generator body
+                remove_directive_from_async_helper_pattern(
+                    &mut body.stmts,
+                    self.step_directive.as_str(),
+                );
             }
         }
     }
@@ -2254,16 +4698,17 @@ impl StepTransform {
         if let Some(body) = body {
             if !body.stmts.is_empty() {
                 // First try to remove from the top level
-                if let Stmt::Expr(ExprStmt { expr, .. }) = &body.stmts[0] {
-                    if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
-                        if value == "use workflow" {
-                            body.stmts.remove(0);
-                            return;
-                        }
-                    }
+                if let Some(index) =
+                    find_prologue_directive(&body.stmts, self.workflow_directive.as_str())
+                {
+                    body.stmts.remove(index);
+                    return;
                 }
                 // Also try to remove from inside the `using` pattern's try block
-                remove_directive_from_using_pattern(&mut body.stmts, "use workflow");
+                remove_directive_from_using_pattern(
+                    &mut body.stmts,
+                    self.workflow_directive.as_str(),
+                );
             }
         }
     }
@@ -2271,28 +4716,39 @@ impl StepTransform {
     // Check if an arrow function has the "use step" directive
     fn has_use_step_directive_arrow(&self, body: &BlockStmtOrExpr) -> bool {
         if let BlockStmtOrExpr::BlockStmt(body) = body {
-            // Check for direct directive
-            if let Some(first_stmt) = body.stmts.first() {
-                if let Stmt::Expr(ExprStmt { expr, .. }) = first_stmt {
-                    if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
-                        return value == "use step";
-                    }
+            // Check for direct directive, possibly after other prologue entries like "use strict"
+            if find_prologue_directive(&body.stmts, self.step_directive.as_str()).is_some() {
+                return true;
+            }
+            if let Some(Stmt::Expr(ExprStmt { expr, span, .. })) = body.stmts.first() {
+                if let Expr::Lit(Lit::Str(_)) = &**expr {
+                    return false;
+                }
+                if get_template_literal_value(expr).as_deref() == Some(self.step_directive.as_str())
+                {
+                    emit_error(WorkflowErrorKind::TemplateLiteralDirective {
+                        span: *span,
+                        directive: self.step_directive.as_str(),
+                    });
                 }
             }
             // Check for directive inside TypeScript `using` transformation pattern
             if let Some(try_block) = get_try_block_from_using_pattern(&body.stmts) {
-                if get_directive_from_block(try_block, "use step") {
+                if get_directive_from_block(try_block, self.step_directive.as_str()) {
                     return true;
                 }
                 // Also check for misspellings inside the using pattern's try block
                 if let Some((str_lit, span)) = get_first_string_literal_from_block(try_block) {
                     let value = str_lit.value.to_string_lossy().to_string();
-                    if detect_similar_strings(&value, "use step") {
-                        emit_error(WorkflowErrorKind::MisspelledDirective {
-                            span,
-                            directive: value,
-                            expected: "use step",
-                        });
+                    if detect_similar_strings(&value, self.step_directive.as_str()) {
+                        emit_diagnostic(
+                            WorkflowErrorKind::MisspelledDirective {
+                                span,
+                                directive: value,
+                                expected: self.step_directive.as_str(),
+                            },
+                            self.options.diagnostics,
+                        );
                     }
                 }
             }
@@ -2303,35 +4759,185 @@ impl StepTransform {
     // Check if an arrow function has the "use workflow" directive
     fn has_use_workflow_directive_arrow(&self, body: &BlockStmtOrExpr) -> bool {
         if let BlockStmtOrExpr::BlockStmt(body) = body {
-            // Check for direct directive
-            if let Some(first_stmt) = body.stmts.first() {
-                if let Stmt::Expr(ExprStmt { expr, .. }) = first_stmt {
-                    if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
-                        return value == "use workflow";
-                    }
+            // Check for direct directive, possibly after other prologue entries like "use strict"
+            if find_prologue_directive(&body.stmts, self.workflow_directive.as_str()).is_some() {
+                return true;
+            }
+            if let Some(Stmt::Expr(ExprStmt { expr, span, .. })) = body.stmts.first() {
+                if let Expr::Lit(Lit::Str(_)) = &**expr {
+                    return false;
+                }
+                if get_template_literal_value(expr).as_deref()
+                    == Some(self.workflow_directive.as_str())
+                {
+                    emit_error(WorkflowErrorKind::TemplateLiteralDirective {
+                        span: *span,
+                        directive: self.workflow_directive.as_str(),
+                    });
                 }
             }
             // Check for directive inside TypeScript `using` transformation pattern
             if let Some(try_block) = get_try_block_from_using_pattern(&body.stmts) {
-                if get_directive_from_block(try_block, "use workflow") {
+                if get_directive_from_block(try_block, self.workflow_directive.as_str()) {
                     return true;
                 }
                 // Also check for misspellings inside the using pattern's try block
                 if let Some((str_lit, span)) = get_first_string_literal_from_block(try_block) {
                     let value = str_lit.value.to_string_lossy().to_string();
-                    if detect_similar_strings(&value, "use workflow") {
-                        emit_error(WorkflowErrorKind::MisspelledDirective {
-                            span,
-                            directive: value,
-                            expected: "use workflow",
+                    if detect_similar_strings(&value, self.workflow_directive.as_str()) {
+                        emit_diagnostic(
+                            WorkflowErrorKind::MisspelledDirective {
+                                span,
+                                directive: value,
+                                expected: self.workflow_directive.as_str(),
+                            },
+                            self.options.diagnostics,
+                        );
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    // Check if a function has the "use cache" directive
+    fn has_use_cache_directive(&self, body: &Option<BlockStmt>) -> bool {
+        if let Some(body) = body {
+            let mut is_first_meaningful = true;
+
+            for stmt in body.stmts.iter() {
+                if let Stmt::Expr(ExprStmt {
+                    expr,
+                    span: stmt_span,
+                    ..
+                }) = stmt
+                {
+                    if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
+                        if value == "use cache" {
+                            if !is_first_meaningful {
+                                emit_error(WorkflowErrorKind::MisplacedDirective {
+                                    span: *stmt_span,
+                                    directive: value.to_string_lossy().to_string(),
+                                    location: DirectiveLocation::FunctionBody,
+                                });
+                            }
+                            return true;
+                        } else if detect_similar_strings(
+                            value.to_string_lossy().as_ref(),
+                            "use cache",
+                        ) {
+                            emit_diagnostic(
+                                WorkflowErrorKind::MisspelledDirective {
+                                    span: *stmt_span,
+                                    directive: value.to_string_lossy().to_string(),
+                                    expected: "use cache",
+                                },
+                                self.options.diagnostics,
+                            );
+                        }
+                    }
+                    if get_template_literal_value(expr).as_deref() == Some("use cache") {
+                        emit_error(WorkflowErrorKind::TemplateLiteralDirective {
+                            span: *stmt_span,
+                            directive: "use cache",
                         });
                     }
                 }
+                // Any statement other than a string literal ends the directive prologue
+                if !is_prologue_stmt(stmt) {
+                    is_first_meaningful = false;
+                }
+            }
+
+            // Check for directive inside TypeScript `using` transformation pattern
+            if let Some(try_block) = get_try_block_from_using_pattern(&body.stmts) {
+                if get_directive_from_block(try_block, "use cache") {
+                    return true;
+                }
+            }
+
+            // Check for directive inside a downleveled async function's generator body
+            if let Some(generator_body) = get_generator_body_from_async_helper_pattern(&body.stmts)
+            {
+                if get_directive_from_block(generator_body, "use cache") {
+                    return true;
+                }
+            }
+
+            false
+        } else {
+            false
+        }
+    }
+
+    // Check if an arrow function has the "use cache" directive
+    fn has_use_cache_directive_arrow(&self, body: &BlockStmtOrExpr) -> bool {
+        if let BlockStmtOrExpr::BlockStmt(body) = body {
+            // Check for direct directive, possibly after other prologue entries like "use strict"
+            if find_prologue_directive(&body.stmts, "use cache").is_some() {
+                return true;
+            }
+            if let Some(Stmt::Expr(ExprStmt { expr, span, .. })) = body.stmts.first() {
+                if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
+                    if detect_similar_strings(value.to_string_lossy().as_ref(), "use cache") {
+                        emit_diagnostic(
+                            WorkflowErrorKind::MisspelledDirective {
+                                span: *span,
+                                directive: value.to_string_lossy().to_string(),
+                                expected: "use cache",
+                            },
+                            self.options.diagnostics,
+                        );
+                    }
+                    return false;
+                }
+                if get_template_literal_value(expr).as_deref() == Some("use cache") {
+                    emit_error(WorkflowErrorKind::TemplateLiteralDirective {
+                        span: *span,
+                        directive: "use cache",
+                    });
+                }
+            }
+            // Check for directive inside TypeScript `using` transformation pattern
+            if let Some(try_block) = get_try_block_from_using_pattern(&body.stmts) {
+                return get_directive_from_block(try_block, "use cache");
             }
         }
         false
     }
 
+    // Remove "use cache" directive from function body
+    fn remove_use_cache_directive(&self, body: &mut Option<BlockStmt>) {
+        if let Some(body) = body {
+            if !body.stmts.is_empty() {
+                // First try to remove from the top level
+                if let Some(index) = find_prologue_directive(&body.stmts, "use cache") {
+                    body.stmts.remove(index);
+                    return;
+                }
+                // Also try to remove from inside the `using` pattern's try block
+                remove_directive_from_using_pattern(&mut body.stmts, "use cache");
+                // Or from inside a downleveled async function's generator body
+                remove_directive_from_async_helper_pattern(&mut body.stmts, "use cache");
+            }
+        }
+    }
+
+    // Remove "use cache" directive from arrow function body
+    fn remove_use_cache_directive_arrow(&self, body: &mut BlockStmtOrExpr) {
+        if let BlockStmtOrExpr::BlockStmt(body) = body {
+            if !body.stmts.is_empty() {
+                // First try to remove from the top level
+                if let Some(index) = find_prologue_directive(&body.stmts, "use cache") {
+                    body.stmts.remove(index);
+                    return;
+                }
+                // Also try to remove from inside the `using` pattern's try block
+                remove_directive_from_using_pattern(&mut body.stmts, "use cache");
+            }
+        }
+    }
+
     /// Extract the symbol name from a `Symbol.for('...')` expression
     /// Returns Some("workflow-serialize") or Some("workflow-deserialize") if it matches, None otherwise
     fn extract_symbol_for_name(&self, expr: &Expr) -> Option<String> {
@@ -2418,16 +5024,14 @@ impl StepTransform {
         if let BlockStmtOrExpr::BlockStmt(body) = body {
             if !body.stmts.is_empty() {
                 // First try to remove from the top level
-                if let Stmt::Expr(ExprStmt { expr, .. }) = &body.stmts[0] {
-                    if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
-                        if value == "use step" {
-                            body.stmts.remove(0);
-                            return;
-                        }
-                    }
+                if let Some(index) =
+                    find_prologue_directive(&body.stmts, self.step_directive.as_str())
+                {
+                    body.stmts.remove(index);
+                    return;
                 }
                 // Also try to remove from inside the `using` pattern's try block
-                remove_directive_from_using_pattern(&mut body.stmts, "use step");
+                remove_directive_from_using_pattern(&mut body.stmts, self.step_directive.as_str());
             }
         }
     }
@@ -2437,16 +5041,17 @@ impl StepTransform {
         if let BlockStmtOrExpr::BlockStmt(body) = body {
             if !body.stmts.is_empty() {
                 // First try to remove from the top level
-                if let Stmt::Expr(ExprStmt { expr, .. }) = &body.stmts[0] {
-                    if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
-                        if value == "use workflow" {
-                            body.stmts.remove(0);
-                            return;
-                        }
-                    }
+                if let Some(index) =
+                    find_prologue_directive(&body.stmts, self.workflow_directive.as_str())
+                {
+                    body.stmts.remove(index);
+                    return;
                 }
                 // Also try to remove from inside the `using` pattern's try block
-                remove_directive_from_using_pattern(&mut body.stmts, "use workflow");
+                remove_directive_from_using_pattern(
+                    &mut body.stmts,
+                    self.workflow_directive.as_str(),
+                );
             }
         }
     }
@@ -2501,99 +5106,184 @@ impl StepTransform {
         include_register: bool,
         include_closure_vars: bool,
     ) -> ModuleItem {
-        let mut specifiers = vec![];
+        let mut names = vec![];
 
         if include_closure_vars {
-            specifiers.push(ImportSpecifier::Named(ImportNamedSpecifier {
-                span: DUMMY_SP,
-                local: Ident::new(
-                    "__private_getClosureVars".into(),
-                    DUMMY_SP,
-                    SyntaxContext::empty(),
-                ),
-                imported: None,
-                is_type_only: false,
-            }));
+            names.push("__private_getClosureVars");
         }
 
         if include_register {
-            specifiers.push(ImportSpecifier::Named(ImportNamedSpecifier {
-                span: DUMMY_SP,
-                local: Ident::new(
-                    "registerStepFunction".into(),
-                    DUMMY_SP,
-                    SyntaxContext::empty(),
-                ),
-                imported: None,
-                is_type_only: false,
-            }));
+            names.push(if self.options.batch_step_registrations {
+                "registerStepFunctions"
+            } else {
+                "registerStepFunction"
+            });
         }
 
-        ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-            span: DUMMY_SP,
-            specifiers,
-            src: Box::new(Str {
-                span: DUMMY_SP,
-                value: "workflow/internal/private".into(),
-                raw: None,
-            }),
-            type_only: false,
-            with: None,
-            phase: ImportPhase::Evaluation,
-        }))
+        self.create_runtime_import(&names, &self.options.private_import_source)
     }
 
     // Generate the import for registerSerializationClass from a Node.js-free module (workflow mode)
     // This is separate from create_private_imports to avoid pulling in Node.js dependencies
     // (like async_hooks) in workflow bundles.
     fn create_class_serialization_import(&self) -> ModuleItem {
-        ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-            span: DUMMY_SP,
-            specifiers: vec![ImportSpecifier::Named(ImportNamedSpecifier {
-                span: DUMMY_SP,
-                local: Ident::new(
-                    "registerSerializationClass".into(),
-                    DUMMY_SP,
-                    SyntaxContext::empty(),
-                ),
-                imported: None,
-                is_type_only: false,
-            })],
-            src: Box::new(Str {
+        self.create_runtime_import(
+            &["registerSerializationClass"],
+            &self.options.class_serialization_import_source,
+        )
+    }
+
+    // Bring named runtime helpers into scope according to the configured registration format:
+    // - Esm: import { a, b } from "source";
+    // - Dual: const { a, b } = globalThis[Symbol.for("source")] ?? require("source");
+    fn create_runtime_import(&self, names: &[&str], source: &str) -> ModuleItem {
+        let str_lit = |value: &str| {
+            Box::new(Expr::Lit(Lit::Str(Str {
                 span: DUMMY_SP,
-                value: "workflow/internal/class-serialization".into(),
+                value: value.into(),
                 raw: None,
-            }),
-            type_only: false,
-            with: None,
-            phase: ImportPhase::Evaluation,
-        }))
-    }
+            })))
+        };
+        let ident = |name: &str| Ident::new(name.into(), DUMMY_SP, SyntaxContext::empty());
 
-    // Create a registration call statement: registerSerializationClass("class//...", ClassName)
-    // Used in workflow mode and client mode to register classes for serialization
-    fn create_class_serialization_registration(&self, class_name: &str) -> Stmt {
-        let class_id = naming::format_name("class", &self.get_module_path(), class_name);
-        Stmt::Expr(ExprStmt {
-            span: DUMMY_SP,
-            expr: Box::new(Expr::Call(CallExpr {
+        match self.options.registration_format {
+            RegistrationFormat::Esm => ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
                 span: DUMMY_SP,
-                ctxt: SyntaxContext::empty(),
-                callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
-                    "registerSerializationClass".into(),
-                    DUMMY_SP,
-                    SyntaxContext::empty(),
-                )))),
-                args: vec![
-                    // First argument: class ID
-                    ExprOrSpread {
-                        spread: None,
-                        expr: Box::new(Expr::Lit(Lit::Str(Str {
+                specifiers: names
+                    .iter()
+                    .map(|name| {
+                        ImportSpecifier::Named(ImportNamedSpecifier {
                             span: DUMMY_SP,
-                            value: class_id.into(),
-                            raw: None,
-                        }))),
-                    },
+                            local: ident(name),
+                            imported: None,
+                            is_type_only: false,
+                        })
+                    })
+                    .collect(),
+                src: Box::new(Str {
+                    span: DUMMY_SP,
+                    value: source.into(),
+                    raw: None,
+                }),
+                type_only: false,
+                with: None,
+                phase: ImportPhase::Evaluation,
+            })),
+            RegistrationFormat::Dual => {
+                // globalThis[Symbol.for("source")]
+                let global_lookup = Expr::Member(MemberExpr {
+                    span: DUMMY_SP,
+                    obj: Box::new(Expr::Ident(ident("globalThis"))),
+                    prop: MemberProp::Computed(ComputedPropName {
+                        span: DUMMY_SP,
+                        expr: Box::new(Expr::Call(CallExpr {
+                            span: DUMMY_SP,
+                            ctxt: SyntaxContext::empty(),
+                            callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+                                span: DUMMY_SP,
+                                obj: Box::new(Expr::Ident(ident("Symbol"))),
+                                prop: MemberProp::Ident(IdentName::new("for".into(), DUMMY_SP)),
+                            }))),
+                            args: vec![ExprOrSpread {
+                                spread: None,
+                                expr: str_lit(source),
+                            }],
+                            type_args: None,
+                        })),
+                    }),
+                });
+                // typeof require === "function" ? require("source") : undefined, since
+                // `require` doesn't exist in ESM
+                let require_call = Expr::Paren(ParenExpr {
+                    span: DUMMY_SP,
+                    expr: Box::new(Expr::Cond(CondExpr {
+                        span: DUMMY_SP,
+                        test: Box::new(Expr::Bin(BinExpr {
+                            span: DUMMY_SP,
+                            op: BinaryOp::EqEqEq,
+                            left: Box::new(Expr::Unary(UnaryExpr {
+                                span: DUMMY_SP,
+                                op: UnaryOp::TypeOf,
+                                arg: Box::new(Expr::Ident(ident("require"))),
+                            })),
+                            right: str_lit("function"),
+                        })),
+                        cons: Box::new(Expr::Call(CallExpr {
+                            span: DUMMY_SP,
+                            ctxt: SyntaxContext::empty(),
+                            callee: Callee::Expr(Box::new(Expr::Ident(ident("require")))),
+                            args: vec![ExprOrSpread {
+                                spread: None,
+                                expr: str_lit(source),
+                            }],
+                            type_args: None,
+                        })),
+                        alt: Box::new(Expr::Ident(ident("undefined"))),
+                    })),
+                });
+
+                ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+                    span: DUMMY_SP,
+                    ctxt: SyntaxContext::empty(),
+                    kind: VarDeclKind::Const,
+                    declare: false,
+                    decls: vec![VarDeclarator {
+                        span: DUMMY_SP,
+                        name: Pat::Object(ObjectPat {
+                            span: DUMMY_SP,
+                            props: names
+                                .iter()
+                                .map(|name| {
+                                    ObjectPatProp::Assign(AssignPatProp {
+                                        span: DUMMY_SP,
+                                        key: BindingIdent {
+                                            id: ident(name),
+                                            type_ann: None,
+                                        },
+                                        value: None,
+                                    })
+                                })
+                                .collect(),
+                            optional: false,
+                            type_ann: None,
+                        }),
+                        init: Some(Box::new(Expr::Bin(BinExpr {
+                            span: DUMMY_SP,
+                            op: BinaryOp::NullishCoalescing,
+                            left: Box::new(global_lookup),
+                            right: Box::new(require_call),
+                        }))),
+                        definite: false,
+                    }],
+                }))))
+            }
+        }
+    }
+
+    // Create a registration call statement: registerSerializationClass("class//...", ClassName)
+    // Used in workflow mode and client mode to register classes for serialization
+    fn create_class_serialization_registration(&self, class_name: &str) -> Stmt {
+        let class_id = naming::format_name("class", &self.get_module_path(), class_name);
+        Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr: Box::new(Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                ctxt: SyntaxContext::empty(),
+                callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
+                    "registerSerializationClass".into(),
+                    DUMMY_SP,
+                    SyntaxContext::empty(),
+                )))),
+                args: vec![
+                    // First argument: class ID
+                    ExprOrSpread {
+                        spread: None,
+                        expr: Box::new(Expr::Lit(Lit::Str(Str {
+                            span: DUMMY_SP,
+                            value: class_id.into(),
+                            raw: None,
+                        }))),
+                    },
                     // Second argument: ClassName
                     ExprOrSpread {
                         spread: None,
@@ -2610,7 +5300,84 @@ impl StepTransform {
     }
 
     // Create a proxy reference: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step_id", closure_fn) (workflow mode)
-    fn create_step_proxy_reference(&self, step_id: &str, closure_vars: &[String]) -> Expr {
+    // Create a cache initializer: globalThis[Symbol.for("WORKFLOW_USE_CACHE")]("cache_id", fn)
+    // The function itself is passed along so the runtime can memoize its result per ID and arguments
+    // The closure variables of a nested cache function are passed like those of a step proxy,
+    // so the runtime can tell calls apart that capture different values
+    fn create_cache_initializer(
+        &self,
+        cache_id: &str,
+        cache_fn: Expr,
+        closure_vars: &[String],
+    ) -> Expr {
+        let mut args = vec![
+            ExprOrSpread {
+                spread: None,
+                expr: Box::new(Expr::Lit(Lit::Str(Str {
+                    span: DUMMY_SP,
+                    value: cache_id.into(),
+                    raw: None,
+                }))),
+            },
+            ExprOrSpread {
+                spread: None,
+                expr: Box::new(cache_fn),
+            },
+        ];
+        if !closure_vars.is_empty() {
+            args.push(ExprOrSpread {
+                spread: None,
+                expr: Box::new(create_closure_vars_thunk(closure_vars)),
+            });
+        }
+
+        Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            ctxt: SyntaxContext::empty(),
+            callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+                span: DUMMY_SP,
+                obj: Box::new(Expr::Ident(Ident::new(
+                    "globalThis".into(),
+                    DUMMY_SP,
+                    SyntaxContext::empty(),
+                ))),
+                prop: MemberProp::Computed(ComputedPropName {
+                    span: DUMMY_SP,
+                    expr: Box::new(Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        ctxt: SyntaxContext::empty(),
+                        callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+                            span: DUMMY_SP,
+                            obj: Box::new(Expr::Ident(Ident::new(
+                                "Symbol".into(),
+                                DUMMY_SP,
+                                SyntaxContext::empty(),
+                            ))),
+                            prop: MemberProp::Ident(IdentName::new("for".into(), DUMMY_SP)),
+                        }))),
+                        args: vec![ExprOrSpread {
+                            spread: None,
+                            expr: Box::new(Expr::Lit(Lit::Str(Str {
+                                span: DUMMY_SP,
+                                value: "WORKFLOW_USE_CACHE".into(),
+                                raw: None,
+                            }))),
+                        }],
+                        type_args: None,
+                    })),
+                }),
+            }))),
+            args,
+            type_args: None,
+        })
+    }
+
+    fn create_step_proxy_reference(
+        &self,
+        step_id: &str,
+        closure_vars: &[String],
+        span: swc_core::common::Span,
+    ) -> Expr {
         let mut args = vec![ExprOrSpread {
             spread: None,
             expr: Box::new(Expr::Lit(Lit::Str(Str {
@@ -2622,40 +5389,14 @@ impl StepTransform {
 
         // If there are closure variables, add them as a second argument
         if !closure_vars.is_empty() {
-            // Create arrow function: () => ({ var1, var2 })
-            let closure_obj = Expr::Object(ObjectLit {
-                span: DUMMY_SP,
-                props: closure_vars
-                    .iter()
-                    .map(|var_name| {
-                        PropOrSpread::Prop(Box::new(Prop::Shorthand(Ident::new(
-                            var_name.clone().into(),
-                            DUMMY_SP,
-                            SyntaxContext::empty(),
-                        ))))
-                    })
-                    .collect(),
-            });
-
-            let closure_fn = Expr::Arrow(ArrowExpr {
-                span: DUMMY_SP,
-                ctxt: SyntaxContext::empty(),
-                params: vec![],
-                body: Box::new(BlockStmtOrExpr::Expr(Box::new(closure_obj))),
-                is_async: false,
-                is_generator: false,
-                type_params: None,
-                return_type: None,
-            });
-
             args.push(ExprOrSpread {
                 spread: None,
-                expr: Box::new(closure_fn),
+                expr: Box::new(create_closure_vars_thunk(closure_vars)),
             });
         }
 
-        Expr::Call(CallExpr {
-            span: DUMMY_SP,
+        self.annotate_pure(CallExpr {
+            span,
             ctxt: SyntaxContext::empty(),
             callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
                 span: DUMMY_SP,
@@ -2695,9 +5436,9 @@ impl StepTransform {
         })
     }
 
-    fn create_step_proxy(&self, step_id: &str) -> Expr {
+    fn create_step_proxy(&self, step_id: &str, span: swc_core::common::Span) -> Expr {
         Expr::Call(CallExpr {
-            span: DUMMY_SP,
+            span,
             ctxt: SyntaxContext::empty(),
             callee: Callee::Expr(Box::new(Expr::Call(CallExpr {
                 span: DUMMY_SP,
@@ -2753,7 +5494,7 @@ impl StepTransform {
     // Create an initializer for a step function in workflow mode
     // Produces: globalThis[Symbol.for("WORKFLOW_USE_STEP")](step_id)
     fn create_step_initializer(&self, step_id: &str) -> Expr {
-        Expr::Call(CallExpr {
+        self.annotate_pure(CallExpr {
             span: DUMMY_SP,
             ctxt: SyntaxContext::empty(),
             callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
@@ -2801,6 +5542,181 @@ impl StepTransform {
         })
     }
 
+    // With `namedStepWrappers`, wrap a step proxy in a function expression named after the step
+    // so stack traces show its name, and with `preserveStepArity` give the wrapper `arity`
+    // leading params so its `length` matches the step (workflow mode):
+    // function name(arg0, arg1, ...args) { return <proxy>(arg0, arg1, ...args); }
+    fn wrap_step_proxy(
+        &self,
+        name: &str,
+        proxy: Expr,
+        closure_vars: &[String],
+        arity: usize,
+    ) -> Expr {
+        let arity = if self.options.preserve_step_arity {
+            arity
+        } else {
+            0
+        };
+        if !self.options.named_step_wrappers && arity == 0 {
+            return proxy;
+        }
+
+        // The wrapper params must not shadow a closure var referenced by the proxy
+        let unique_ident = |base: String| {
+            let mut param_name = base.clone();
+            let mut counter = 0;
+            while closure_vars.contains(&param_name) {
+                counter += 1;
+                param_name = format!("{}${}", base, counter);
+            }
+            Ident::new(param_name.into(), DUMMY_SP, SyntaxContext::empty())
+        };
+        let leading: Vec<Ident> = (0..arity)
+            .map(|index| unique_ident(format!("arg{}", index)))
+            .collect();
+        let args = unique_ident("args".to_string());
+
+        let mut params: Vec<Param> = leading
+            .iter()
+            .map(|ident| Param {
+                span: DUMMY_SP,
+                decorators: vec![],
+                pat: Pat::Ident(ident.clone().into()),
+            })
+            .collect();
+        params.push(Param {
+            span: DUMMY_SP,
+            decorators: vec![],
+            pat: Pat::Rest(RestPat {
+                span: DUMMY_SP,
+                dot3_token: DUMMY_SP,
+                arg: Box::new(Pat::Ident(args.clone().into())),
+                type_ann: None,
+            }),
+        });
+        let mut call_args: Vec<ExprOrSpread> = leading
+            .into_iter()
+            .map(|ident| ExprOrSpread {
+                spread: None,
+                expr: Box::new(Expr::Ident(ident)),
+            })
+            .collect();
+        call_args.push(ExprOrSpread {
+            spread: Some(DUMMY_SP),
+            expr: Box::new(Expr::Ident(args)),
+        });
+
+        Expr::Fn(FnExpr {
+            ident: self
+                .options
+                .named_step_wrappers
+                .then(|| Ident::new(name.into(), DUMMY_SP, SyntaxContext::empty())),
+            function: Box::new(Function {
+                params,
+                decorators: vec![],
+                span: DUMMY_SP,
+                ctxt: SyntaxContext::empty(),
+                body: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    ctxt: SyntaxContext::empty(),
+                    stmts: vec![Stmt::Return(ReturnStmt {
+                        span: DUMMY_SP,
+                        arg: Some(Box::new(Expr::Call(CallExpr {
+                            span: DUMMY_SP,
+                            ctxt: SyntaxContext::empty(),
+                            callee: Callee::Expr(Box::new(proxy)),
+                            args: call_args,
+                            type_args: None,
+                        }))),
+                    })],
+                }),
+                is_generator: false,
+                is_async: false,
+                type_params: None,
+                return_type: None,
+            }),
+        })
+    }
+
+    // Type annotation for a generated step proxy binding (workflow mode), derived from the
+    // original step function according to the `proxyTypeAnnotation` option
+    fn create_proxy_type_ann(
+        &self,
+        params: &[&Pat],
+        return_type: Option<&TsTypeAnn>,
+        type_params: Option<&TsTypeParamDecl>,
+    ) -> Option<Box<TsTypeAnn>> {
+        let type_ann = match self.options.proxy_type_annotation {
+            ProxyTypeAnnotation::None => return None,
+            ProxyTypeAnnotation::Any => any_type(),
+            ProxyTypeAnnotation::Signature => {
+                let params = params
+                    .iter()
+                    .enumerate()
+                    .map(|(index, pat)| proxy_fn_param(pat, index))
+                    .collect();
+                // Steps are always async, so fall back to `Promise<any>` rather than `any`
+                let return_type = return_type.cloned().map(Box::new).unwrap_or_else(|| {
+                    Box::new(TsTypeAnn {
+                        span: DUMMY_SP,
+                        type_ann: Box::new(TsType::TsTypeRef(TsTypeRef {
+                            span: DUMMY_SP,
+                            type_name: TsEntityName::Ident(Ident::new(
+                                "Promise".into(),
+                                DUMMY_SP,
+                                SyntaxContext::empty(),
+                            )),
+                            type_params: Some(Box::new(TsTypeParamInstantiation {
+                                span: DUMMY_SP,
+                                params: vec![any_type()],
+                            })),
+                        })),
+                    })
+                });
+                Box::new(TsType::TsFnOrConstructorType(
+                    TsFnOrConstructorType::TsFnType(TsFnType {
+                        span: DUMMY_SP,
+                        params,
+                        type_params: type_params.cloned().map(Box::new),
+                        type_ann: return_type,
+                    }),
+                ))
+            }
+        };
+        Some(Box::new(TsTypeAnn {
+            span: DUMMY_SP,
+            type_ann,
+        }))
+    }
+
+    fn create_proxy_type_ann_for_function(&self, function: &Function) -> Option<Box<TsTypeAnn>> {
+        let params: Vec<&Pat> = function.params.iter().map(|param| &param.pat).collect();
+        self.create_proxy_type_ann(
+            &params,
+            function.return_type.as_deref(),
+            function.type_params.as_deref(),
+        )
+    }
+
+    // Same as `create_proxy_type_ann_for_function`, for a step defined as an arrow function or
+    // function expression initializer
+    fn create_proxy_type_ann_for_expr(&self, expr: &Expr) -> Option<Box<TsTypeAnn>> {
+        match expr {
+            Expr::Arrow(arrow) => {
+                let params: Vec<&Pat> = arrow.params.iter().collect();
+                self.create_proxy_type_ann(
+                    &params,
+                    arrow.return_type.as_deref(),
+                    arrow.type_params.as_deref(),
+                )
+            }
+            Expr::Fn(fn_expr) => self.create_proxy_type_ann_for_function(&fn_expr.function),
+            Expr::Paren(paren) => self.create_proxy_type_ann_for_expr(&paren.expr),
+            _ => None,
+        }
+    }
+
     // Create a statement that adds workflowId property to a function (client mode)
     fn create_workflow_id_assignment(&self, fn_name: &str, span: swc_core::common::Span) -> Stmt {
         // For workflow ID generation, normalize auto-generated __default variants to "default"
@@ -2810,7 +5726,7 @@ impl StepTransform {
             && self
                 .workflow_export_to_const_name
                 .get("default")
-                .map_or(false, |const_name| const_name == fn_name)
+                .is_some_and(|const_name| const_name == fn_name)
         {
             "default"
         } else {
@@ -2877,13 +5793,17 @@ impl StepTransform {
 
     // Create a workflow registration call for workflow mode:
     // globalThis.__private_workflows.set("workflowId", functionName);
-    fn create_workflow_registration(&self, fn_name: &str, span: swc_core::common::Span) -> Stmt {
+    fn create_workflow_registration(
+        &self,
+        fn_name: &str,
+        span: swc_core::common::Span,
+    ) -> Vec<Stmt> {
         // Generate the workflow ID (same logic as create_workflow_id_assignment)
         let id_name = if (fn_name == "__default" || fn_name.starts_with("__default$"))
             && self
                 .workflow_export_to_const_name
                 .get("default")
-                .map_or(false, |const_name| const_name == fn_name)
+                .is_some_and(|const_name| const_name == fn_name)
         {
             "default"
         } else {
@@ -2891,10 +5811,29 @@ impl StepTransform {
         };
         let workflow_id = self.create_id(Some(id_name), span, true);
 
-        // Create: globalThis.__private_workflows.set("workflowId", functionName)
-        Stmt::Expr(ExprStmt {
-            span: DUMMY_SP,
-            expr: Box::new(Expr::Call(CallExpr {
+        self.create_workflows_set(
+            &workflow_id,
+            Expr::Ident(Ident::new(fn_name.into(), DUMMY_SP, SyntaxContext::empty())),
+        )
+    }
+
+    // Register `workflow` under `workflow_id`, guarded according to the
+    // `duplicate_workflow_registration` option:
+    // - Overwrite: globalThis.__private_workflows.set("workflowId", workflow);
+    // - Keep: if (!globalThis.__private_workflows.has("workflowId")) globalThis.__private_workflows.set(...);
+    // - Warn: if (globalThis.__private_workflows.has("workflowId")) console.warn("...");
+    //         globalThis.__private_workflows.set(...);
+    fn create_workflows_set(&self, workflow_id: &str, workflow: Expr) -> Vec<Stmt> {
+        let str_lit = |value: &str| {
+            Box::new(Expr::Lit(Lit::Str(Str {
+                span: DUMMY_SP,
+                value: value.into(),
+                raw: None,
+            })))
+        };
+        // globalThis.__private_workflows.<method>(args)
+        let workflows_call = |method: &str, args: Vec<Box<Expr>>| {
+            Box::new(Expr::Call(CallExpr {
                 span: DUMMY_SP,
                 ctxt: SyntaxContext::empty(),
                 callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
@@ -2911,52 +5850,140 @@ impl StepTransform {
                             DUMMY_SP,
                         )),
                     })),
-                    prop: MemberProp::Ident(IdentName::new("set".into(), DUMMY_SP)),
+                    prop: MemberProp::Ident(IdentName::new(method.into(), DUMMY_SP)),
                 }))),
-                args: vec![
-                    // First argument: workflow ID
-                    ExprOrSpread {
-                        spread: None,
-                        expr: Box::new(Expr::Lit(Lit::Str(Str {
-                            span: DUMMY_SP,
-                            value: workflow_id.into(),
-                            raw: None,
-                        }))),
-                    },
-                    // Second argument: function reference
-                    ExprOrSpread {
-                        spread: None,
-                        expr: Box::new(Expr::Ident(Ident::new(
-                            fn_name.into(),
-                            DUMMY_SP,
-                            SyntaxContext::empty(),
-                        ))),
-                    },
-                ],
+                args: args
+                    .into_iter()
+                    .map(|expr| ExprOrSpread { spread: None, expr })
+                    .collect(),
                 type_args: None,
-            })),
-        })
-    }
-
-    // Create a registration call for step mode
-    fn create_registration_call(&mut self, name: &str, span: swc_core::common::Span) {
-        // Only register each function once
-        if !self.registered_functions.contains(name) {
-            self.registered_functions.insert(name.to_string());
+            }))
+        };
 
-            // Create the step ID
-            let step_id = self.create_id(Some(name), span, false);
+        let set = Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr: workflows_call("set", vec![str_lit(workflow_id), Box::new(workflow)]),
+        });
+        let has = workflows_call("has", vec![str_lit(workflow_id)]);
 
-            self.registration_calls.push(Stmt::Expr(ExprStmt {
+        match self.options.duplicate_workflow_registration {
+            DuplicateWorkflowRegistration::Overwrite => vec![set],
+            DuplicateWorkflowRegistration::Keep => vec![Stmt::If(IfStmt {
                 span: DUMMY_SP,
-                expr: Box::new(Expr::Call(CallExpr {
+                test: Box::new(Expr::Unary(UnaryExpr {
                     span: DUMMY_SP,
-                    ctxt: SyntaxContext::empty(),
-                    callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
-                        "registerStepFunction".into(),
-                        DUMMY_SP,
-                        SyntaxContext::empty(),
-                    )))),
+                    op: UnaryOp::Bang,
+                    arg: has,
+                })),
+                cons: Box::new(set),
+                alt: None,
+            })],
+            DuplicateWorkflowRegistration::Warn => {
+                let message = format!(
+                    "Workflow \"{}\" was registered more than once; replacing the previous registration",
+                    workflow_id
+                );
+                let warn = Stmt::Expr(ExprStmt {
+                    span: DUMMY_SP,
+                    expr: Box::new(Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        ctxt: SyntaxContext::empty(),
+                        callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+                            span: DUMMY_SP,
+                            obj: Box::new(Expr::Ident(Ident::new(
+                                "console".into(),
+                                DUMMY_SP,
+                                SyntaxContext::empty(),
+                            ))),
+                            prop: MemberProp::Ident(IdentName::new("warn".into(), DUMMY_SP)),
+                        }))),
+                        args: vec![ExprOrSpread {
+                            spread: None,
+                            expr: str_lit(&message),
+                        }],
+                        type_args: None,
+                    })),
+                });
+                vec![
+                    Stmt::If(IfStmt {
+                        span: DUMMY_SP,
+                        test: has,
+                        cons: Box::new(warn),
+                        alt: None,
+                    }),
+                    set,
+                ]
+            }
+        }
+    }
+
+    // Replace the `registerStepFunction(id, fn)` statements at the end of `items`, starting at
+    // `start`, with a single `registerStepFunctions([[id, fn], ...])` call
+    fn batch_registration_calls(&self, items: &mut Vec<ModuleItem>, start: usize) {
+        if !self.options.batch_step_registrations || start >= items.len() {
+            return;
+        }
+
+        let entries = items
+            .drain(start..)
+            .filter_map(|item| match item {
+                ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. })) => match *expr {
+                    Expr::Call(call) => Some(call.args),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .map(|args| {
+                Some(ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(Expr::Array(ArrayLit {
+                        span: DUMMY_SP,
+                        elems: args.into_iter().map(Some).collect(),
+                    })),
+                })
+            })
+            .collect();
+
+        items.push(ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr: Box::new(Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                ctxt: SyntaxContext::empty(),
+                callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
+                    "registerStepFunctions".into(),
+                    DUMMY_SP,
+                    SyntaxContext::empty(),
+                )))),
+                args: vec![ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(Expr::Array(ArrayLit {
+                        span: DUMMY_SP,
+                        elems: entries,
+                    })),
+                }],
+                type_args: None,
+            })),
+        })));
+    }
+
+    // Create a registration call for step mode
+    fn create_registration_call(&mut self, name: &str, span: swc_core::common::Span) {
+        // Create the step ID
+        let step_id = self.create_id(Some(name), span, false);
+
+        // Only register each function once
+        if self.registered_functions.insert(step_id.clone()) {
+            // The registration carries the step function's span so it maps back to its source
+            self.registration_calls.push(Stmt::Expr(ExprStmt {
+                span,
+                expr: Box::new(Expr::Call(CallExpr {
+                    span,
+                    ctxt: SyntaxContext::empty(),
+                    callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
+                        "registerStepFunction".into(),
+                        DUMMY_SP,
+                        SyntaxContext::empty(),
+                    )))),
                     args: vec![
                         // First argument: step ID
                         ExprOrSpread {
@@ -2983,16 +6010,101 @@ impl StepTransform {
         }
     }
 
+    // Register a step hoisted to module level under `step_id`, with a stepId assignment in
+    // client mode and a registerStepFunction call otherwise. Like create_registration_call, each
+    // ID is only registered once. Distinct functions that end up with the same ID (e.g. two
+    // nested steps of the same name in different blocks) are an error, since only one of them
+    // could be called by its ID.
+    fn push_hoisted_step_registration(
+        &mut self,
+        step_id: String,
+        hoisted_name: &str,
+        span: swc_core::common::Span,
+    ) {
+        match self.hoisted_step_spans.get(&step_id) {
+            Some(registered) if *registered != span => {
+                emit_error(WorkflowErrorKind::DuplicateStepId { span, id: &step_id });
+                return;
+            }
+            Some(_) => {}
+            None => {
+                self.hoisted_step_spans.insert(step_id.clone(), span);
+            }
+        }
+        if !self.registered_functions.insert(step_id.clone()) {
+            return;
+        }
+
+        if self.mode == TransformMode::Client {
+            // In client mode, use stepId property assignment instead of registerStepFunction
+            let step_id_assignment = self.create_step_id_assignment_with_id(hoisted_name, &step_id);
+            self.registration_calls.push(step_id_assignment);
+        } else {
+            // In step mode, use registerStepFunction
+            let registration_call = Stmt::Expr(ExprStmt {
+                span: DUMMY_SP,
+                expr: Box::new(Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    ctxt: SyntaxContext::empty(),
+                    callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
+                        "registerStepFunction".into(),
+                        DUMMY_SP,
+                        SyntaxContext::empty(),
+                    )))),
+                    args: vec![
+                        ExprOrSpread {
+                            spread: None,
+                            expr: Box::new(Expr::Lit(Lit::Str(Str {
+                                span: DUMMY_SP,
+                                value: step_id.into(),
+                                raw: None,
+                            }))),
+                        },
+                        ExprOrSpread {
+                            spread: None,
+                            expr: Box::new(Expr::Ident(Ident::new(
+                                hoisted_name.into(),
+                                DUMMY_SP,
+                                SyntaxContext::empty(),
+                            ))),
+                        },
+                    ],
+                    type_args: None,
+                })),
+            });
+
+            self.registration_calls.push(registration_call);
+        }
+    }
+
+    // Where `async ` goes to fix a non-async function, if `suggestAsyncFix` is on: `span` starts at
+    // the `function` keyword of a function or at the params of an arrow function
+    fn async_fix_at(&self, span: Span) -> Option<BytePos> {
+        self.options.suggest_async_fix.then_some(span.lo)
+    }
+
+    // Same as `async_fix_at` for a method, where `async` goes right before the name so that
+    // modifiers such as `static` stay in front. The `*` of a generator method has to follow
+    // `async`, but its position isn't known, so generator methods get no suggestion.
+    fn method_async_fix_at(&self, key: &PropName, function: &Function) -> Option<BytePos> {
+        if function.is_generator {
+            return None;
+        }
+        self.async_fix_at(key.span())
+    }
+
     // Validate that the function is async
-    fn validate_async_function(&self, function: &Function, span: swc_core::common::Span) -> bool {
-        if !function.is_async {
-            HANDLER.with(|handler| {
-                handler
-                    .struct_span_err(
-                        span,
-                        "Functions marked with \"use step\" must be async functions",
-                    )
-                    .emit()
+    fn validate_async_function(
+        &self,
+        function: &Function,
+        span: swc_core::common::Span,
+        directive: &str,
+    ) -> bool {
+        if !is_async_function(function) {
+            emit_error(WorkflowErrorKind::NonAsyncFunction {
+                span,
+                directive,
+                async_at: self.async_fix_at(span),
             });
             false
         } else {
@@ -3005,7 +6117,8 @@ impl StepTransform {
         let has_directive = self.has_use_step_directive(&function.body);
 
         // Function has explicit directive OR file has directive and function is exported
-        (has_directive || (self.has_file_step_directive && is_exported)) && function.is_async
+        (has_directive || (self.has_file_step_directive && is_exported))
+            && is_async_function(function)
     }
 
     // Check if a function should be treated as a workflow function
@@ -3043,215 +6156,117 @@ impl StepTransform {
         used_identifiers
     }
 
-    // Remove dead code (unused functions, variables, statements, and imports) recursively
+    // Remove dead code (unused functions, variables, statements, and imports). Module-level
+    // declarations form a graph through the identifiers they reference: everything reachable
+    // from the items that are always kept (exports, statements with side effects) and from the
+    // root names is kept, and the rest is removed in a single pass.
     fn remove_dead_code(&self, items: &mut Vec<ModuleItem>) {
         // Only runs in workflow and client mode
         if !matches!(self.mode, TransformMode::Workflow | TransformMode::Client) {
             return;
         }
 
-        // Keep removing dead code until no more changes are made
-        loop {
-            // Analyze which identifiers are used
-            let module = Module {
-                span: DUMMY_SP,
-                body: items.clone(),
-                shebang: None,
-            };
-            let used_identifiers = self.analyze_usage_comprehensive(&module);
-
-            // Note: used_identifiers now contains only actually referenced identifiers
-
-            let mut items_changed = false;
-            let mut items_to_remove = Vec::new();
-
-            // Check each item for whether it should be removed
-            for (i, item) in items.iter().enumerate() {
-                let should_remove = match item {
-                    // Remove unused function declarations
-                    ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl))) => {
-                        let fn_name = fn_decl.ident.sym.to_string();
-                        // Don't remove if it's used or if it's a step/workflow function
-                        !used_identifiers.contains(&fn_name)
-                            && !self.step_function_names.contains(&fn_name)
-                            && !self.workflow_function_names.contains(&fn_name)
-                    }
-                    // Remove unused variable declarations
-                    ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) => {
-                        // Check if all variables in this declaration are unused
-                        var_decl.decls.iter().all(|declarator| {
-                            match &declarator.name {
-                                Pat::Ident(binding) => {
-                                    let name = binding.id.sym.to_string();
-                                    !used_identifiers.contains(&name)
-                                        && !self.step_function_names.contains(&name)
-                                        && !self.workflow_function_names.contains(&name)
-                                }
-                                // For destructuring patterns, be conservative and keep them
-                                // unless we can determine all bindings are unused
-                                Pat::Array(array_pat) => {
-                                    self.all_bindings_unused(array_pat, &used_identifiers)
-                                }
-                                Pat::Object(obj_pat) => {
-                                    self.all_object_bindings_unused(obj_pat, &used_identifiers)
-                                }
-                                _ => false, // Keep other patterns
-                            }
-                        })
-                    }
-                    // Remove unused expression statements (but keep side effects and directives)
-                    ModuleItem::Stmt(Stmt::Expr(expr_stmt)) => {
-                        // Don't remove expression statements that might have side effects
-                        // Only remove pure identifier expressions and non-string literals
-                        match &*expr_stmt.expr {
-                            Expr::Ident(_) => true,
-                            // Keep all string literals (might be directives or misspelled directives)
-                            Expr::Lit(Lit::Str(_)) => false,
-                            Expr::Lit(_) => true,
-                            _ => false,
-                        }
-                    }
-                    // Remove empty statements
-                    ModuleItem::Stmt(Stmt::Empty(_)) => true,
-                    // Don't remove exports, imports (handled separately), or other items
-                    _ => false,
-                };
-
-                if should_remove {
-                    items_to_remove.push(i);
-                }
-            }
-
-            // Remove unused items (in reverse order to maintain indices)
-            if !items_to_remove.is_empty() {
-                items_changed = true;
-                for i in items_to_remove.into_iter().rev() {
-                    items.remove(i);
-                }
+        // Identifiers referenced by each item, and the names declared by each removable item
+        // (`None` for items that are always kept)
+        let references: Vec<HashSet<String>> = items
+            .iter()
+            .map(|item| {
+                let mut used_identifiers = HashSet::new();
+                item.visit_with(&mut ComprehensiveUsageCollector {
+                    used_identifiers: &mut used_identifiers,
+                    step_function_names: &self.step_function_names,
+                    current_function: None,
+                });
+                used_identifiers
+            })
+            .collect();
+        let keep_calls = self.mode == TransformMode::Client;
+        let declared: Vec<Option<Vec<String>>> = items
+            .iter()
+            .map(|item| removable_bindings(item, keep_calls))
+            .collect();
+
+        let mut declared_by: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (i, names) in declared.iter().enumerate() {
+            for name in names.iter().flatten() {
+                declared_by.entry(name.as_str()).or_default().push(i);
             }
+        }
 
-            // Remove unused imports
-            let mut imports_to_remove = Vec::new();
-            let mut imports_modified = false;
-
-            for (i, item) in items.iter_mut().enumerate() {
-                if let ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) = item {
-                    let mut new_specifiers = Vec::new();
-
-                    for spec in &import_decl.specifiers {
-                        let local_name = match spec {
-                            ImportSpecifier::Named(named) => named.local.sym.to_string(),
-                            ImportSpecifier::Default(default) => default.local.sym.to_string(),
-                            ImportSpecifier::Namespace(ns) => ns.local.sym.to_string(),
-                        };
-
-                        // Keep the import if it's used
-                        if used_identifiers.contains(&local_name) {
-                            new_specifiers.push(spec.clone());
-                        }
-                    }
+        let mut used_identifiers = self.dead_code_roots(items);
+        let mut pending: Vec<usize> = (0..items.len())
+            .filter(|&i| declared[i].is_none())
+            .collect();
+        for name in &used_identifiers {
+            pending.extend(declared_by.get(name.as_str()).into_iter().flatten());
+        }
 
-                    // Update or mark for removal
-                    if new_specifiers.is_empty() {
-                        imports_to_remove.push(i);
-                    } else if new_specifiers.len() != import_decl.specifiers.len() {
-                        imports_modified = true;
-                        import_decl.specifiers = new_specifiers;
-                    }
+        // Mark every item reachable from the roots, along with the identifiers it uses
+        let mut reachable = vec![false; items.len()];
+        while let Some(i) = pending.pop() {
+            if reachable[i] {
+                continue;
+            }
+            reachable[i] = true;
+            for name in &references[i] {
+                if used_identifiers.insert(name.clone()) {
+                    pending.extend(declared_by.get(name.as_str()).into_iter().flatten());
                 }
             }
+        }
 
-            // Remove imports marked for removal (in reverse order to maintain indices)
-            let imports_removed = !imports_to_remove.is_empty();
-            for i in imports_to_remove.into_iter().rev() {
-                items.remove(i);
-            }
+        let mut index = 0;
+        items.retain(|_| {
+            index += 1;
+            reachable[index - 1]
+        });
 
-            // If nothing changed, we're done
-            if !items_changed && !imports_removed && !imports_modified {
-                break;
-            }
-        }
+        // Remove unused imports
+        items.retain_mut(|item| {
+            let ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) = item else {
+                return true;
+            };
+            import_decl.specifiers.retain(|spec| {
+                let local = match spec {
+                    ImportSpecifier::Named(named) => &named.local,
+                    ImportSpecifier::Default(default) => &default.local,
+                    ImportSpecifier::Namespace(ns) => &ns.local,
+                };
+                used_identifiers.contains(local.sym.as_str())
+            });
+            !import_decl.specifiers.is_empty()
+        });
     }
 
-    // Helper to check if all bindings in an array pattern are unused
-    fn all_bindings_unused(
-        &self,
-        array_pat: &ArrayPat,
-        used_identifiers: &HashSet<String>,
-    ) -> bool {
-        array_pat.elems.iter().all(|elem| {
-            match elem {
-                Some(pat) => {
-                    match pat {
-                        Pat::Ident(binding) => {
-                            let name = binding.id.sym.to_string();
-                            !used_identifiers.contains(&name)
-                                && !self.step_function_names.contains(&name)
-                                && !self.workflow_function_names.contains(&name)
-                        }
-                        Pat::Array(nested) => self.all_bindings_unused(nested, used_identifiers),
-                        Pat::Object(nested) => {
-                            self.all_object_bindings_unused(nested, used_identifiers)
-                        }
-                        _ => false, // Keep other patterns
-                    }
-                }
-                None => true, // Holes in array patterns are fine
-            }
-        })
-    }
+    // Identifiers that are used before looking at any references: exported bindings (including
+    // ones exported through specifiers), step and workflow functions, and serializable classes
+    fn dead_code_roots(&self, items: &[ModuleItem]) -> HashSet<String> {
+        let mut used_identifiers = HashSet::new();
 
-    // Helper to check if all bindings in an object pattern are unused
-    fn all_object_bindings_unused(
-        &self,
-        obj_pat: &ObjectPat,
-        used_identifiers: &HashSet<String>,
-    ) -> bool {
-        obj_pat.props.iter().all(|prop| {
-            match prop {
-                ObjectPatProp::KeyValue(kv) => {
-                    match &*kv.value {
-                        Pat::Ident(binding) => {
-                            let name = binding.id.sym.to_string();
-                            !used_identifiers.contains(&name)
-                                && !self.step_function_names.contains(&name)
-                                && !self.workflow_function_names.contains(&name)
-                        }
-                        Pat::Array(nested) => self.all_bindings_unused(nested, used_identifiers),
-                        Pat::Object(nested) => {
-                            self.all_object_bindings_unused(nested, used_identifiers)
+        for item in items {
+            // Local bindings exported through `export { foo, bar as baz }` or `export default foo`
+            match item {
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(named_export))
+                    if named_export.src.is_none() =>
+                {
+                    for specifier in &named_export.specifiers {
+                        if let ExportSpecifier::Named(ExportNamedSpecifier {
+                            orig: ModuleExportName::Ident(orig),
+                            ..
+                        }) = specifier
+                        {
+                            used_identifiers.insert(orig.sym.to_string());
                         }
-                        _ => false, // Keep other patterns
                     }
                 }
-                ObjectPatProp::Assign(assign) => {
-                    let name = assign.key.sym.to_string();
-                    !used_identifiers.contains(&name)
-                        && !self.step_function_names.contains(&name)
-                        && !self.workflow_function_names.contains(&name)
-                }
-                ObjectPatProp::Rest(rest) => {
-                    match &*rest.arg {
-                        Pat::Ident(binding) => {
-                            let name = binding.id.sym.to_string();
-                            !used_identifiers.contains(&name)
-                                && !self.step_function_names.contains(&name)
-                                && !self.workflow_function_names.contains(&name)
-                        }
-                        _ => false, // Keep other patterns
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(default_export)) => {
+                    if let Expr::Ident(ident) = &*default_export.expr {
+                        used_identifiers.insert(ident.sym.to_string());
                     }
                 }
+                _ => {}
             }
-        })
-    }
-
-    // Comprehensive usage analysis that considers all remaining code
-    fn analyze_usage_comprehensive(&self, module: &Module) -> HashSet<String> {
-        let mut used_identifiers = HashSet::new();
 
-        // First, mark exported identifiers as used
-        for item in &module.body {
             if let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) = item {
                 match &export_decl.decl {
                     Decl::Fn(fn_decl) => {
@@ -3273,16 +6288,12 @@ impl StepTransform {
             }
         }
 
-        // Then, visit all items to find used identifiers
-        let mut visitor = ComprehensiveUsageCollector {
-            used_identifiers: &mut used_identifiers,
-            step_function_names: &self.step_function_names,
-            current_function: None,
-        };
+        // Step and workflow functions are never removed
+        used_identifiers.extend(self.step_function_names.iter().cloned());
+        used_identifiers.extend(self.workflow_function_names.iter().cloned());
 
-        // Visit the module directly (not clones) to analyze the already-transformed code
-        let mut module_clone = module.clone();
-        module_clone.visit_mut_with(&mut visitor);
+        // Serializable classes are referenced by the registrations added after this pass
+        used_identifiers.extend(self.classes_needing_serialization.iter().cloned());
 
         used_identifiers
     }
@@ -3316,113 +6327,1347 @@ impl StepTransform {
             || self.has_use_workflow_directive_arrow(&arrow_fn.body)
     }
 
+    // Drop the parentheses of `export default (async () => { ... })` so it's handled like the
+    // unparenthesized form. Only steps and workflows are unwrapped; other default exports are
+    // left as written.
+    fn unwrap_parenthesized_default_export(&self, export_default: &mut ExportDefaultExpr) {
+        let mut inner = &*export_default.expr;
+        while let Expr::Paren(paren) = inner {
+            inner = &paren.expr;
+        }
+        if std::ptr::eq(inner, &*export_default.expr) {
+            return;
+        }
+
+        let body = match inner {
+            Expr::Arrow(arrow_expr) => match &*arrow_expr.body {
+                BlockStmtOrExpr::BlockStmt(block) => Some(block),
+                BlockStmtOrExpr::Expr(_) => None,
+            },
+            Expr::Fn(fn_expr) => fn_expr.function.body.as_ref(),
+            _ => return,
+        };
+        let has_directive = body.is_some_and(|body| {
+            find_prologue_directive(&body.stmts, self.step_directive.as_str()).is_some()
+                || find_prologue_directive(&body.stmts, self.workflow_directive.as_str()).is_some()
+        });
+        if has_directive || self.has_file_step_directive || self.has_file_workflow_directive {
+            *export_default.expr = inner.clone();
+        }
+    }
+
     // Generate metadata comment for the transformed file
+    // Collect the (name, ID) pairs of everything the manifest lists, each sorted by name
+    fn manifest(&self) -> WorkflowManifest {
+        // Steps, including object property step functions
+        let mut steps: Vec<(String, String)> = self
+            .step_function_names
+            .iter()
+            .map(|fn_name| {
+                let step_id = self.create_id(Some(fn_name), DUMMY_SP, false);
+                (fn_name.clone(), step_id)
+            })
+            .collect();
+        for (parent_var, prop_name, step_id, _) in &self.object_property_workflow_conversions {
+            steps.push((
+                self.object_property_id_path(parent_var, prop_name),
+                step_id.clone(),
+            ));
+        }
+        steps.sort();
+
+        let mut workflows: Vec<(String, String)> = self
+            .workflow_function_names
+            .iter()
+            .map(|fn_name| {
+                let fn_name_str: &str = fn_name;
+                // Look up the actual const/function name for this export
+                let actual_name = self
+                    .workflow_export_to_const_name
+                    .get(fn_name_str)
+                    .map(|s| s.as_str())
+                    .unwrap_or(fn_name_str);
+                // For auto-generated __default names (anonymous default exports),
+                // normalize to "default" for the workflow ID
+                let id_name = if (actual_name == "__default"
+                    || actual_name.starts_with("__default$"))
+                    && fn_name_str == "default"
+                {
+                    "default"
+                } else {
+                    actual_name
+                };
+                let workflow_id = self.create_id(Some(id_name), DUMMY_SP, true);
+                (fn_name.clone(), workflow_id)
+            })
+            .collect();
+        workflows.sort();
+
+        let module_path = self.get_module_path();
+        let mut classes: Vec<(String, String)> = self
+            .classes_for_manifest
+            .iter()
+            .map(|class_name| {
+                let class_id = naming::format_name("class", &module_path, class_name);
+                (class_name.clone(), class_id)
+            })
+            .collect();
+        classes.sort();
+
+        let mut caches: Vec<(String, String)> = self
+            .cache_function_names
+            .iter()
+            .map(|fn_name| (fn_name.clone(), self.create_cache_id(fn_name)))
+            .collect();
+        caches.sort();
+
+        WorkflowManifest {
+            workflows,
+            steps,
+            classes,
+            caches,
+        }
+    }
+
     fn generate_metadata_comment(&self) -> String {
-        let mut metadata = std::collections::HashMap::new();
+        self.generate_metadata(self.options.manifest_format)
+    }
 
-        // Build steps metadata (including object properties)
-        if !self.step_function_names.is_empty()
-            || !self.object_property_workflow_conversions.is_empty()
-        {
-            let mut steps_entries: Vec<String> = self
-                .step_function_names
-                .iter()
-                .map(|fn_name| {
-                    let step_id = self.create_id(Some(fn_name), DUMMY_SP, false);
-                    format!("\"{}\":{{\"stepId\":\"{}\"}}", fn_name, step_id)
-                })
-                .collect();
+    // Build `export const __workflow_manifest = { ... }` with the contents of the manifest comment
+    fn generate_metadata_export(&self) -> Option<ModuleItem> {
+        let value = self.manifest_value(ManifestFormat::Comment)?;
+        Some(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            span: DUMMY_SP,
+            decl: Decl::Var(Box::new(VarDecl {
+                span: DUMMY_SP,
+                ctxt: SyntaxContext::empty(),
+                kind: VarDeclKind::Const,
+                declare: false,
+                decls: vec![VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(BindingIdent {
+                        id: Ident::new(
+                            "__workflow_manifest".into(),
+                            DUMMY_SP,
+                            SyntaxContext::empty(),
+                        ),
+                        type_ann: None,
+                    }),
+                    init: Some(Box::new(value.to_expr())),
+                    definite: false,
+                }],
+            })),
+        })))
+    }
 
-            // Add object property step functions to metadata
-            for (parent_var, prop_name, step_id) in &self.object_property_workflow_conversions {
-                let key = format!("{}/{}", parent_var, prop_name);
-                steps_entries.push(format!("\"{}\":{{\"stepId\":\"{}\"}}", key, step_id));
-            }
+    // The 1-based line and column a step or workflow of the manifest starts at, with
+    // `include_positions` and a source map
+    fn manifest_position(&self, kind: &str, name: &str) -> Option<(usize, usize)> {
+        if !self.options.include_positions {
+            return None;
+        }
+        let source_map = self.source_map.as_ref()?;
+        let span = match kind {
+            "steps" => self.step_function_spans.get(name).copied().or_else(|| {
+                self.object_property_workflow_conversions
+                    .iter()
+                    .find(|(parent_var, prop_name, _, _)| {
+                        self.object_property_id_path(parent_var, prop_name) == name
+                    })
+                    .map(|(_, _, _, span)| *span)
+            }),
+            "workflows" => self.workflow_function_spans.get(name).copied(),
+            _ => None,
+        }?;
+        if span.is_dummy() {
+            return None;
+        }
+        let loc = source_map.0.lookup_char_pos(span.lo);
+        Some((loc.line, loc.col.0 + 1))
+    }
 
-            if !steps_entries.is_empty() {
-                steps_entries.sort();
-                metadata.insert("steps", format!("{{{}}}", steps_entries.join(",")));
+    fn generate_metadata(&self, format: ManifestFormat) -> String {
+        let Some(value) = self.manifest_value(format) else {
+            return String::new();
+        };
+        match format {
+            ManifestFormat::Comment => format!("/**__internal_workflows{}*/", value.to_json()),
+            // A single JSON record on its own line
+            ManifestFormat::Ndjson => {
+                format!("/**__internal_workflows_ndjson\n{}\n*/", value.to_json())
             }
         }
+    }
 
-        // Build workflows metadata
-        if !self.workflow_function_names.is_empty() {
-            // Sort function names for deterministic ordering
-            let mut sorted_workflow_names: Vec<_> = self.workflow_function_names.iter().collect();
-            sorted_workflow_names.sort();
+    // The JSON the manifest is emitted as, or `None` if the file defines nothing
+    fn manifest_value(&self, format: ManifestFormat) -> Option<ManifestValue> {
+        let manifest = self.manifest();
 
-            let workflow_entries: Vec<String> = sorted_workflow_names
-                .into_iter()
-                .map(|fn_name| {
-                    let fn_name_str: &str = fn_name;
-                    // Look up the actual const/function name for this export
-                    let actual_name = self
-                        .workflow_export_to_const_name
-                        .get(fn_name_str)
-                        .map(|s| s.as_str())
-                        .unwrap_or(fn_name_str);
-                    // For auto-generated __default names (anonymous default exports),
-                    // normalize to "default" for the workflow ID
-                    let id_name = if (actual_name == "__default"
-                        || actual_name.starts_with("__default$"))
-                        && fn_name_str == "default"
-                    {
-                        "default"
-                    } else {
-                        actual_name
-                    };
-                    let workflow_id = self.create_id(Some(id_name), DUMMY_SP, true);
-                    format!("\"{}\":{{\"workflowId\":\"{}\"}}", fn_name_str, workflow_id)
+        let relative_filename = self.filename.replace('\\', "/"); // Normalize path separators
+        let sections: Vec<(&str, ManifestValue)> = [
+            ("workflows", "workflowId", &manifest.workflows),
+            ("steps", "stepId", &manifest.steps),
+            ("classes", "classId", &manifest.classes),
+            ("caches", "cacheId", &manifest.caches),
+        ]
+        .into_iter()
+        .filter(|(_, _, entries)| !entries.is_empty())
+        .map(|(kind, id_key, entries)| {
+            let entries = entries
+                .iter()
+                .map(|(name, id)| {
+                    let mut fields =
+                        vec![ManifestValue::entry(id_key, ManifestValue::Str(id.clone()))];
+                    if let Some((line, col)) = self.manifest_position(kind, name) {
+                        fields.push(ManifestValue::entry("line", ManifestValue::Num(line)));
+                        fields.push(ManifestValue::entry("col", ManifestValue::Num(col)));
+                    }
+                    ManifestValue::entry(name, ManifestValue::Object(fields))
                 })
                 .collect();
+            (kind, ManifestValue::Object(entries))
+        })
+        .collect();
+
+        if sections.is_empty() {
+            return None;
+        }
 
-            metadata.insert("workflows", format!("{{{}}}", workflow_entries.join(",")));
+        // Optionally tag the manifest with the mode it was produced in and the version of the
+        // transform that produced it
+        let mut tags = Vec::new();
+        if self.options.manifest_mode {
+            let mode = match self.mode {
+                TransformMode::Step => "step",
+                TransformMode::Workflow => "workflow",
+                TransformMode::Client => "client",
+            };
+            tags.push(ManifestValue::entry(
+                "mode",
+                ManifestValue::Str(mode.to_string()),
+            ));
+        }
+        if self.options.manifest_transform_version {
+            tags.push(ManifestValue::entry(
+                "transformVersion",
+                ManifestValue::Str(env!("CARGO_PKG_VERSION").to_string()),
+            ));
         }
 
-        // Build classes metadata
-        if !self.classes_for_manifest.is_empty() {
-            let mut sorted_classes: Vec<_> = self.classes_for_manifest.iter().collect();
-            sorted_classes.sort();
+        let value = match format {
+            ManifestFormat::Comment => {
+                let sections = sections.into_iter().map(|(kind, entries)| {
+                    let files = vec![ManifestValue::entry(&relative_filename, entries)];
+                    ManifestValue::entry(kind, ManifestValue::Object(files))
+                });
+                ManifestValue::Object(tags.into_iter().chain(sections).collect())
+            }
+            // Keyed by filename, so that records from different files can be merged with a
+            // shallow object merge
+            ManifestFormat::Ndjson => {
+                let sections = sections
+                    .into_iter()
+                    .map(|(kind, entries)| ManifestValue::entry(kind, entries));
+                let record = ManifestValue::Object(tags.into_iter().chain(sections).collect());
+                ManifestValue::Object(vec![ManifestValue::entry(&relative_filename, record)])
+            }
+        };
+        Some(value)
+    }
+}
+
+// Reports async generator functions marked as steps. The step proxy returns a single promise,
+// so rewriting a generator's body to call it would yield nothing.
+struct GeneratorStepChecker<'a> {
+    step_directive: &'a str,
+    has_file_step_directive: bool,
+    // Set right before visiting an exported function, which is a step in a "use step" file
+    in_export: bool,
+}
+
+impl<'a> Visit for GeneratorStepChecker<'a> {
+    fn visit_export_decl(&mut self, export_decl: &ExportDecl) {
+        match &export_decl.decl {
+            Decl::Fn(fn_decl) => {
+                self.in_export = true;
+                fn_decl.function.visit_with(self);
+            }
+            Decl::Var(var_decl) => {
+                for declarator in &var_decl.decls {
+                    if let Some(Expr::Fn(fn_expr)) = declarator.init.as_deref() {
+                        self.in_export = true;
+                        fn_expr.function.visit_with(self);
+                    } else {
+                        declarator.visit_with(self);
+                    }
+                }
+            }
+            decl => decl.visit_with(self),
+        }
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        let is_exported = std::mem::take(&mut self.in_export);
+        let has_directive = function
+            .body
+            .as_ref()
+            .is_some_and(|body| body_has_directive(&body.stmts, self.step_directive));
+
+        if function.is_async
+            && function.is_generator
+            && (has_directive || (self.has_file_step_directive && is_exported))
+        {
+            emit_error(WorkflowErrorKind::GeneratorStep {
+                span: function.span,
+                directive: self.step_directive,
+            });
+        }
+
+        function.visit_children_with(self);
+    }
+}
+
+// Reports function bodies that start with both "use step" and "use workflow". They would be
+// treated as steps, so the workflow directive would silently do nothing. This runs before the
+// transform, which removes the step directive before visiting a function's body.
+struct ConflictingDirectiveChecker<'a> {
+    step_directive: &'a str,
+    workflow_directive: &'a str,
+}
+
+impl ConflictingDirectiveChecker<'_> {
+    fn check_body(&self, body: &BlockStmt) {
+        let (Some(step_index), Some(workflow_index)) = (
+            find_prologue_directive(&body.stmts, self.step_directive),
+            find_prologue_directive(&body.stmts, self.workflow_directive),
+        ) else {
+            return;
+        };
+
+        // Point at whichever directive comes second
+        let (index, directive, other) = if step_index < workflow_index {
+            (workflow_index, self.workflow_directive, self.step_directive)
+        } else {
+            (step_index, self.step_directive, self.workflow_directive)
+        };
+        emit_error(WorkflowErrorKind::ConflictingDirectives {
+            span: body.stmts[index].span(),
+            directive,
+            other,
+        });
+    }
+}
+
+impl Visit for ConflictingDirectiveChecker<'_> {
+    fn visit_function(&mut self, function: &Function) {
+        if let Some(body) = &function.body {
+            self.check_body(body);
+        }
+        function.visit_children_with(self);
+    }
+
+    fn visit_arrow_expr(&mut self, arrow: &ArrowExpr) {
+        if let BlockStmtOrExpr::BlockStmt(body) = &*arrow.body {
+            self.check_body(body);
+        }
+        arrow.visit_children_with(self);
+    }
+}
+
+// Collects the functions and arrows with a step or workflow directive at any depth, with their
+// directive. Run before the transform, which removes the directives before visiting a function's
+// body, it also tells which functions were steps or workflows.
+struct DirectiveFunctionFinder<'a> {
+    directives: [&'a str; 2],
+    functions: Vec<(swc_core::common::Span, &'a str)>,
+}
+
+impl DirectiveFunctionFinder<'_> {
+    fn check_body(&mut self, span: swc_core::common::Span, stmts: &[Stmt]) {
+        for directive in self.directives {
+            if body_has_directive(stmts, directive) {
+                self.functions.push((span, directive));
+            }
+        }
+    }
+}
+
+impl Visit for DirectiveFunctionFinder<'_> {
+    fn visit_function(&mut self, function: &Function) {
+        if let Some(body) = &function.body {
+            self.check_body(function.span, &body.stmts);
+        }
+        function.visit_children_with(self);
+    }
+
+    fn visit_arrow_expr(&mut self, arrow: &ArrowExpr) {
+        if let BlockStmtOrExpr::BlockStmt(body) = &*arrow.body {
+            self.check_body(arrow.span, &body.stmts);
+        }
+        arrow.visit_children_with(self);
+    }
+}
+
+// Removes the directive of steps nested inside another step that capture variables, so they're
+// left in place as plain functions. The enclosing step calls them directly, within its own
+// context, so hoisted they'd get the enclosing step's closure variables instead of their own.
+struct NestedStepInliner<'a> {
+    step_directive: &'a str,
+    workflow_directive: &'a str,
+    module_imports: &'a HashSet<String>,
+    diagnostics: DiagnosticLevel,
+    in_step: bool,
+}
+
+impl NestedStepInliner<'_> {
+    // Strip the step directive of a nested step that captures variables, and return whether
+    // the body is a step or, if not, a workflow. The step no longer gets an ID of its own, which
+    // is reported so it doesn't go unnoticed.
+    fn inline_nested_step(
+        &self,
+        span: swc_core::common::Span,
+        body: &mut BlockStmt,
+        captures: bool,
+    ) -> (bool, bool) {
+        let is_step = body_has_directive(&body.stmts, self.step_directive);
+        let is_workflow = body_has_directive(&body.stmts, self.workflow_directive);
+        if is_step && self.in_step && captures {
+            match find_prologue_directive(&body.stmts, self.step_directive) {
+                Some(index) => {
+                    body.stmts.remove(index);
+                }
+                None => remove_directive_from_using_pattern(&mut body.stmts, self.step_directive),
+            }
+            emit_warning(WorkflowWarningKind::InlinedNestedStep {
+                span,
+                directive: self.step_directive,
+            });
+        }
+        (is_step, is_workflow)
+    }
+
+    fn enter(&mut self, (is_step, is_workflow): (bool, bool)) -> bool {
+        let old_in_step = self.in_step;
+        if is_step {
+            self.in_step = true;
+        } else if is_workflow {
+            self.in_step = false;
+        }
+        old_in_step
+    }
+}
+
+impl VisitMut for NestedStepInliner<'_> {
+    fn visit_mut_function(&mut self, function: &mut Function) {
+        // Only nested steps are collected: other functions inside the step (e.g. the methods of
+        // a class declared in it) aren't hoisted, and have a `this` of their own
+        let captures = self.in_step
+            && function
+                .body
+                .as_ref()
+                .is_some_and(|body| body_has_directive(&body.stmts, self.step_directive))
+            && !ClosureVariableCollector::collect_from_function(
+                function,
+                self.module_imports,
+                self.step_directive,
+                self.diagnostics,
+            )
+            .is_empty();
+        let kind = match &mut function.body {
+            Some(body) => self.inline_nested_step(function.span, body, captures),
+            None => (false, false),
+        };
+
+        let old_in_step = self.enter(kind);
+        function.visit_mut_children_with(self);
+        self.in_step = old_in_step;
+    }
+
+    fn visit_mut_arrow_expr(&mut self, arrow: &mut ArrowExpr) {
+        let is_step = match &*arrow.body {
+            BlockStmtOrExpr::BlockStmt(body) => {
+                body_has_directive(&body.stmts, self.step_directive)
+            }
+            BlockStmtOrExpr::Expr(_) => false,
+        };
+        let captures = self.in_step
+            && is_step
+            && !ClosureVariableCollector::collect_from_arrow_expr(
+                arrow,
+                self.module_imports,
+                self.step_directive,
+                self.diagnostics,
+            )
+            .is_empty();
+        let kind = match &mut *arrow.body {
+            BlockStmtOrExpr::BlockStmt(body) => self.inline_nested_step(arrow.span, body, captures),
+            BlockStmtOrExpr::Expr(_) => (false, false),
+        };
+
+        let old_in_step = self.enter(kind);
+        arrow.visit_mut_children_with(self);
+        self.in_step = old_in_step;
+    }
+}
+
+// Turns the decorators named by the `step_decorators`/`workflow_decorators` options into
+// directives. A decorated method gets the directive at the start of its body, and a decorated
+// class hands it to each async method it can apply to (all of them for steps, the static ones
+// for workflows). The decorators are only markers, so they are removed. A directive already in
+// the body takes precedence over both.
+struct DecoratorDirectives<'a> {
+    step_decorators: &'a [String],
+    workflow_decorators: &'a [String],
+    step_directive: &'a str,
+    workflow_directive: &'a str,
+}
+
+impl<'a> DecoratorDirectives<'a> {
+    // Removes the marker decorators (`@step` or `@step()`) and returns the directive of the
+    // first one
+    fn take_directive(&self, decorators: &mut Vec<Decorator>) -> Option<&'a str> {
+        let mut directive = None;
+        decorators.retain(|decorator| {
+            let name = match &*decorator.expr {
+                Expr::Ident(ident) => ident.sym.as_str(),
+                Expr::Call(CallExpr {
+                    callee: Callee::Expr(callee),
+                    args,
+                    ..
+                }) if args.is_empty() => match &**callee {
+                    Expr::Ident(ident) => ident.sym.as_str(),
+                    _ => return true,
+                },
+                _ => return true,
+            };
+            let marked = if self.step_decorators.iter().any(|marker| marker == name) {
+                self.step_directive
+            } else if self.workflow_decorators.iter().any(|marker| marker == name) {
+                self.workflow_directive
+            } else {
+                return true;
+            };
+            directive.get_or_insert(marked);
+            false
+        });
+        directive
+    }
+
+    fn add_directive(&self, function: &mut Function, directive: &str) {
+        let Some(body) = &mut function.body else {
+            return;
+        };
+        if body_has_directive(&body.stmts, self.step_directive)
+            || body_has_directive(&body.stmts, self.workflow_directive)
+        {
+            return;
+        }
+        body.stmts.insert(
+            0,
+            Stmt::Expr(ExprStmt {
+                span: DUMMY_SP,
+                expr: Box::new(Expr::Lit(Lit::Str(Str {
+                    span: DUMMY_SP,
+                    value: directive.into(),
+                    raw: None,
+                }))),
+            }),
+        );
+    }
+}
+
+impl VisitMut for DecoratorDirectives<'_> {
+    noop_visit_mut_type!();
+
+    fn visit_mut_class(&mut self, class: &mut Class) {
+        let class_directive = self.take_directive(&mut class.decorators);
+        for member in &mut class.body {
+            match member {
+                ClassMember::Method(method) => {
+                    if let Some(directive) = self.take_directive(&mut method.function.decorators) {
+                        self.add_directive(&mut method.function, directive);
+                    } else if let Some(directive) = class_directive {
+                        let applies = method.kind == MethodKind::Method
+                            && method.function.is_async
+                            && !method.function.is_generator
+                            && (method.is_static || directive == self.step_directive);
+                        if applies {
+                            self.add_directive(&mut method.function, directive);
+                        }
+                    }
+                }
+                // Reported as private methods that can't be steps or workflows, like with a
+                // directive
+                ClassMember::PrivateMethod(method) => {
+                    if let Some(directive) = self.take_directive(&mut method.function.decorators) {
+                        self.add_directive(&mut method.function, directive);
+                    }
+                }
+                _ => {}
+            }
+        }
+        class.visit_mut_children_with(self);
+    }
+}
+
+// What the step call warnings need to know about a workflow function (see `check_step_calls`)
+#[derive(Debug)]
+struct WorkflowStepCalls {
+    span: swc_core::common::Span,
+    // The identifiers and member names its body references
+    references: HashSet<String>,
+    awaited_calls: Vec<AwaitedCall>,
+}
+
+// A call awaited by a workflow body (see `call_target`)
+#[derive(Debug)]
+struct AwaitedCall {
+    span: swc_core::common::Span,
+    name: String,
+    root: Option<String>,
+    // Whether a try/catch around it handles its rejection
+    in_try: bool,
+}
+
+// Collects the workflow functions of the module for the step call warnings, before the transform
+// removes their directives. The warnings themselves wait until the steps are known, and since
+// steps imported from other files are unknown even then, they are all opt-in.
+struct StepCallCollector<'a> {
+    step_directive: &'a str,
+    workflow_directive: &'a str,
+    has_file_workflow_directive: bool,
+    workflows: Vec<WorkflowStepCalls>,
+    // Set right before visiting an exported function, which is a workflow in a "use workflow" file
+    in_export: bool,
+}
+
+impl StepCallCollector<'_> {
+    fn collect_body(&mut self, span: swc_core::common::Span, body: &BlockStmt) {
+        let is_exported = std::mem::take(&mut self.in_export);
+        let is_workflow = body_has_directive(&body.stmts, self.workflow_directive)
+            || (self.has_file_workflow_directive && is_exported);
+        if !is_workflow {
+            return;
+        }
+
+        let mut references = ReferenceCollector::default();
+        body.visit_with(&mut references);
+        let mut awaited_calls = AwaitedCallCollector {
+            step_directive: self.step_directive,
+            workflow_directive: self.workflow_directive,
+            calls: Vec::new(),
+            in_try: false,
+        };
+        body.visit_with(&mut awaited_calls);
+        self.workflows.push(WorkflowStepCalls {
+            span,
+            references: references.names,
+            awaited_calls: awaited_calls.calls,
+        });
+    }
+}
+
+impl Visit for StepCallCollector<'_> {
+    fn visit_export_decl(&mut self, export_decl: &ExportDecl) {
+        match &export_decl.decl {
+            Decl::Fn(fn_decl) => {
+                self.in_export = true;
+                fn_decl.function.visit_with(self);
+            }
+            Decl::Var(var_decl) => {
+                for declarator in &var_decl.decls {
+                    if matches!(
+                        declarator.init.as_deref(),
+                        Some(Expr::Fn(_) | Expr::Arrow(_))
+                    ) {
+                        self.in_export = true;
+                    }
+                    declarator.init.visit_with(self);
+                }
+            }
+            decl => decl.visit_with(self),
+        }
+    }
+
+    fn visit_export_default_decl(&mut self, export_default: &ExportDefaultDecl) {
+        if let DefaultDecl::Fn(fn_expr) = &export_default.decl {
+            self.in_export = true;
+            fn_expr.function.visit_with(self);
+        } else {
+            export_default.visit_children_with(self);
+        }
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        if let Some(body) = &function.body {
+            self.collect_body(function.span, body);
+        }
+        self.in_export = false;
+        function.visit_children_with(self);
+    }
+
+    fn visit_arrow_expr(&mut self, arrow: &ArrowExpr) {
+        if let BlockStmtOrExpr::BlockStmt(body) = &*arrow.body {
+            self.collect_body(arrow.span, body);
+        }
+        self.in_export = false;
+        arrow.visit_children_with(self);
+    }
+}
+
+// Collects the identifiers and member names referenced, which are the ways a body can reference
+// a step
+#[derive(Default)]
+struct ReferenceCollector {
+    names: HashSet<String>,
+}
+
+impl Visit for ReferenceCollector {
+    fn visit_ident(&mut self, ident: &Ident) {
+        self.names.insert(ident.sym.to_string());
+    }
+
+    fn visit_member_prop(&mut self, prop: &MemberProp) {
+        if let MemberProp::Ident(ident) = prop {
+            self.names.insert(ident.sym.to_string());
+        }
+        prop.visit_children_with(self);
+    }
+}
+
+// Warns about assignments (including compound ones such as `??=`) to one of the given variables.
+// Assignments inside a scope that redeclares the name target that local binding instead, so
+// `shadowed` tracks the names declared by the scopes being visited.
+struct StepObjectReassignmentFinder<'a> {
+    names: &'a HashSet<&'a str>,
+    shadowed: Vec<&'a str>,
+}
+
+impl<'a> StepObjectReassignmentFinder<'a> {
+    fn shadow_ident(&mut self, ident: &Ident) {
+        if let Some(name) = self.names.get(ident.sym.as_str()) {
+            self.shadowed.push(name);
+        }
+    }
+
+    fn shadow_pat(&mut self, pat: &Pat) {
+        let mut idents = Vec::new();
+        collect_binding_idents(pat, &mut idents);
+        for ident in idents {
+            self.shadow_ident(ident);
+        }
+    }
+
+    fn shadow_var_decl(&mut self, var_decl: &VarDecl) {
+        for declarator in &var_decl.decls {
+            self.shadow_pat(&declarator.name);
+        }
+    }
+
+    // `var` declarations anywhere in a function body belong to the function's scope
+    fn shadow_hoisted_vars(&mut self, stmts: &[Stmt]) {
+        let mut finder = HoistedVarFinder::default();
+        stmts.visit_with(&mut finder);
+        for ident in &finder.idents {
+            self.shadow_ident(ident);
+        }
+    }
+
+    fn shadow_for_head(&mut self, head: &ForHead) {
+        if let ForHead::VarDecl(var_decl) = head {
+            if var_decl.kind != VarDeclKind::Var {
+                self.shadow_var_decl(var_decl);
+            }
+        }
+    }
+
+    fn visit_in_scope(&mut self, shadowed: usize, visit: impl FnOnce(&mut Self)) {
+        visit(self);
+        self.shadowed.truncate(shadowed);
+    }
+}
+
+impl Visit for StepObjectReassignmentFinder<'_> {
+    fn visit_assign_expr(&mut self, assign: &AssignExpr) {
+        if let AssignTarget::Simple(SimpleAssignTarget::Ident(binding)) = &assign.left {
+            let name = binding.id.sym.as_str();
+            if self.names.contains(name) && !self.shadowed.contains(&name) {
+                emit_warning(WorkflowWarningKind::ReassignedStepObject {
+                    span: assign.span,
+                    name: &binding.id.sym,
+                });
+            }
+        }
+        assign.visit_children_with(self);
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        let shadowed = self.shadowed.len();
+        for param in &function.params {
+            self.shadow_pat(&param.pat);
+        }
+        if let Some(body) = &function.body {
+            self.shadow_hoisted_vars(&body.stmts);
+        }
+        self.visit_in_scope(shadowed, |this| function.visit_children_with(this));
+    }
+
+    fn visit_arrow_expr(&mut self, arrow: &ArrowExpr) {
+        let shadowed = self.shadowed.len();
+        for param in &arrow.params {
+            self.shadow_pat(param);
+        }
+        if let BlockStmtOrExpr::BlockStmt(body) = &*arrow.body {
+            self.shadow_hoisted_vars(&body.stmts);
+        }
+        self.visit_in_scope(shadowed, |this| arrow.visit_children_with(this));
+    }
+
+    // `let`, `const`, functions and classes declared directly in a block
+    fn visit_block_stmt(&mut self, block: &BlockStmt) {
+        let shadowed = self.shadowed.len();
+        for stmt in &block.stmts {
+            match stmt {
+                Stmt::Decl(Decl::Var(var_decl)) if var_decl.kind != VarDeclKind::Var => {
+                    self.shadow_var_decl(var_decl);
+                }
+                Stmt::Decl(Decl::Fn(fn_decl)) => self.shadow_ident(&fn_decl.ident),
+                Stmt::Decl(Decl::Class(class_decl)) => self.shadow_ident(&class_decl.ident),
+                _ => {}
+            }
+        }
+        self.visit_in_scope(shadowed, |this| block.visit_children_with(this));
+    }
+
+    fn visit_for_stmt(&mut self, for_stmt: &ForStmt) {
+        let shadowed = self.shadowed.len();
+        if let Some(VarDeclOrExpr::VarDecl(var_decl)) = &for_stmt.init {
+            if var_decl.kind != VarDeclKind::Var {
+                self.shadow_var_decl(var_decl);
+            }
+        }
+        self.visit_in_scope(shadowed, |this| for_stmt.visit_children_with(this));
+    }
+
+    fn visit_for_in_stmt(&mut self, for_in: &ForInStmt) {
+        let shadowed = self.shadowed.len();
+        self.shadow_for_head(&for_in.left);
+        self.visit_in_scope(shadowed, |this| for_in.visit_children_with(this));
+    }
+
+    fn visit_for_of_stmt(&mut self, for_of: &ForOfStmt) {
+        let shadowed = self.shadowed.len();
+        self.shadow_for_head(&for_of.left);
+        self.visit_in_scope(shadowed, |this| for_of.visit_children_with(this));
+    }
+
+    fn visit_catch_clause(&mut self, catch: &CatchClause) {
+        let shadowed = self.shadowed.len();
+        if let Some(param) = &catch.param {
+            self.shadow_pat(param);
+        }
+        self.visit_in_scope(shadowed, |this| catch.visit_children_with(this));
+    }
+}
+
+// Collects the names declared with `var` in a function body, without descending into nested
+// functions
+#[derive(Default)]
+struct HoistedVarFinder {
+    idents: Vec<Ident>,
+}
+
+impl Visit for HoistedVarFinder {
+    fn visit_var_decl(&mut self, var_decl: &VarDecl) {
+        if var_decl.kind == VarDeclKind::Var {
+            for declarator in &var_decl.decls {
+                let mut idents = Vec::new();
+                collect_binding_idents(&declarator.name, &mut idents);
+                self.idents.extend(idents.into_iter().cloned());
+            }
+        }
+    }
+
+    fn visit_function(&mut self, _: &Function) {}
+    fn visit_arrow_expr(&mut self, _: &ArrowExpr) {}
+}
+
+// The source of `expr` if it's a call whose result differs between replays of a workflow
+fn non_deterministic_call(expr: &Expr) -> Option<&'static str> {
+    match expr {
+        Expr::Call(CallExpr {
+            callee: Callee::Expr(callee),
+            ..
+        }) => match &**callee {
+            Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(prop),
+                ..
+            }) => match (&**obj, prop.sym.as_str()) {
+                (Expr::Ident(obj), "random") if obj.sym == "Math" => Some("Math.random()"),
+                (Expr::Ident(obj), "now") if obj.sym == "Date" => Some("Date.now()"),
+                _ => None,
+            },
+            _ => None,
+        },
+        // `new Date(timestamp)` is fine, only the current time differs
+        Expr::New(NewExpr { callee, args, .. })
+            if args.as_ref().is_none_or(|args| args.is_empty()) =>
+        {
+            match &**callee {
+                Expr::Ident(ident) if ident.sym == "Date" => Some("new Date()"),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// What an awaited call ultimately calls: the last name of its callee's member chain (`send` for
+// `this.mailer.send(to)`, or for `mailer.send.call(null, to)`), and the identifier the chain
+// starts at (`mailer`, or none for `this`)
+struct CallTarget<'a> {
+    name: &'a str,
+    root: Option<&'a str>,
+}
+
+fn call_target(mut expr: &Expr) -> Option<CallTarget<'_>> {
+    while let Expr::Paren(paren) = expr {
+        expr = &paren.expr;
+    }
+    let mut callee = match expr {
+        Expr::Call(CallExpr {
+            callee: Callee::Expr(callee),
+            ..
+        }) => &**callee,
+        Expr::OptChain(OptChainExpr { base, .. }) => match &**base {
+            OptChainBase::Call(call) => &*call.callee,
+            OptChainBase::Member(_) => return None,
+        },
+        _ => return None,
+    };
+    while let Expr::Paren(paren) = callee {
+        callee = &paren.expr;
+    }
+    if let Expr::Member(MemberExpr {
+        obj,
+        prop: MemberProp::Ident(prop),
+        ..
+    }) = callee
+    {
+        if prop.sym == "call" || prop.sym == "apply" {
+            callee = obj;
+        }
+    }
+
+    let name = match callee {
+        Expr::Ident(ident) => ident.sym.as_str(),
+        Expr::Member(MemberExpr {
+            prop: MemberProp::Ident(prop),
+            ..
+        }) => prop.sym.as_str(),
+        Expr::OptChain(OptChainExpr { base, .. }) => match &**base {
+            OptChainBase::Member(MemberExpr {
+                prop: MemberProp::Ident(prop),
+                ..
+            }) => prop.sym.as_str(),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let mut root = callee;
+    loop {
+        root = match root {
+            Expr::Member(member) => &member.obj,
+            Expr::OptChain(OptChainExpr { base, .. }) => match &**base {
+                OptChainBase::Member(member) => &member.obj,
+                OptChainBase::Call(call) => &call.callee,
+            },
+            Expr::Paren(paren) => &paren.expr,
+            _ => break,
+        };
+    }
+    let root = match root {
+        Expr::Ident(ident) => Some(ident.sym.as_str()),
+        _ => None,
+    };
+    Some(CallTarget { name, root })
+}
+
+// Collects the calls a workflow body awaits and whether they are in the block of a try/catch,
+// for the unhandled step call and awaited non-step warnings. Calls with a `.catch()` handler
+// aren't step calls here, and nested steps and workflows are collected (or not) on their own.
+struct AwaitedCallCollector<'a> {
+    step_directive: &'a str,
+    workflow_directive: &'a str,
+    calls: Vec<AwaitedCall>,
+    in_try: bool,
+}
+
+impl AwaitedCallCollector<'_> {
+    fn is_directive_body(&self, stmts: &[Stmt]) -> bool {
+        body_has_directive(stmts, self.step_directive)
+            || body_has_directive(stmts, self.workflow_directive)
+    }
+
+    // A try/catch around a nested function doesn't see the rejections of calls it awaits
+    fn visit_nested(&mut self, visit: impl FnOnce(&mut Self)) {
+        let old_in_try = std::mem::replace(&mut self.in_try, false);
+        visit(self);
+        self.in_try = old_in_try;
+    }
+}
+
+impl Visit for AwaitedCallCollector<'_> {
+    fn visit_await_expr(&mut self, await_expr: &AwaitExpr) {
+        if let Some(target) = call_target(&await_expr.arg) {
+            self.calls.push(AwaitedCall {
+                span: await_expr.span,
+                name: target.name.to_string(),
+                root: target.root.map(str::to_string),
+                in_try: self.in_try,
+            });
+        }
+        await_expr.visit_children_with(self);
+    }
+
+    fn visit_try_stmt(&mut self, try_stmt: &TryStmt) {
+        let old_in_try = self.in_try;
+        self.in_try |= try_stmt.handler.is_some();
+        try_stmt.block.visit_with(self);
+        self.in_try = old_in_try;
+        try_stmt.handler.visit_with(self);
+        try_stmt.finalizer.visit_with(self);
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        let is_directive_function = function
+            .body
+            .as_ref()
+            .is_some_and(|body| self.is_directive_body(&body.stmts));
+        if !is_directive_function {
+            self.visit_nested(|this| function.visit_children_with(this));
+        }
+    }
+
+    fn visit_arrow_expr(&mut self, arrow: &ArrowExpr) {
+        let is_directive_function = match &*arrow.body {
+            BlockStmtOrExpr::BlockStmt(body) => self.is_directive_body(&body.stmts),
+            BlockStmtOrExpr::Expr(_) => false,
+        };
+        if !is_directive_function {
+            self.visit_nested(|this| arrow.visit_children_with(this));
+        }
+    }
+}
+
+// Pre-pass visitor that handles "use cache" functions before the main transform runs.
+// Cache functions keep their bodies in every mode (the directive is removed); in workflow
+// mode they are additionally wrapped with the runtime's cache initializer.
+struct CacheFunctionTransform<'a> {
+    transform: &'a mut StepTransform,
+    // Names of the enclosing named functions, used to scope nested cache function IDs
+    parent_names: Vec<String>,
+    // Imports, module-level declarations and extra globals, which nested cache functions can
+    // reach wherever they run and so don't capture
+    module_bindings: HashSet<String>,
+}
+
+impl<'a> CacheFunctionTransform<'a> {
+    fn scoped_name(&self, name: &str) -> String {
+        match self.parent_names.last() {
+            Some(parent) => format!("{}/{}", parent, name),
+            None => name.to_string(),
+        }
+    }
+
+    // Record a cache function and return its ID
+    fn register(&mut self, name: &str) -> String {
+        let name = self.scoped_name(name);
+        let cache_id = self.transform.create_cache_id(&name);
+        self.transform.cache_function_names.insert(name);
+        cache_id
+    }
+
+    // The variables a cache function captures from the functions it's nested in. Module-level
+    // cache functions capture nothing.
+    fn closure_vars(&self, cache_fn: &Expr) -> Vec<String> {
+        if self.parent_names.is_empty() {
+            return Vec::new();
+        }
+        let step_directive = &self.transform.step_directive;
+        let diagnostics = self.transform.options.diagnostics;
+        match cache_fn {
+            Expr::Fn(fn_expr) => ClosureVariableCollector::collect_from_function(
+                &fn_expr.function,
+                &self.module_bindings,
+                step_directive,
+                diagnostics,
+            ),
+            Expr::Arrow(arrow_expr) => ClosureVariableCollector::collect_from_arrow_expr(
+                arrow_expr,
+                &self.module_bindings,
+                step_directive,
+                diagnostics,
+            ),
+            _ => Vec::new(),
+        }
+    }
+
+    fn initializer(&self, cache_id: &str, cache_fn: Expr) -> Expr {
+        let closure_vars = self.closure_vars(&cache_fn);
+        self.transform
+            .create_cache_initializer(cache_id, cache_fn, &closure_vars)
+    }
+
+    // `export default async function () { "use cache"; }` and the arrow form are registered
+    // as `default`, like default-exported workflows
+    fn transform_default_export(&mut self, item: &mut ModuleItem) {
+        let cache_fn = match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                decl: DefaultDecl::Fn(fn_expr),
+                ..
+            })) => Expr::Fn(fn_expr.clone()),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) => match &*export.expr {
+                Expr::Fn(_) | Expr::Arrow(_) => (*export.expr).clone(),
+                _ => return,
+            },
+            _ => return,
+        };
+        let is_cache_function = match &cache_fn {
+            Expr::Fn(fn_expr) => {
+                self.transform
+                    .has_use_cache_directive(&fn_expr.function.body)
+                    && self.transform.validate_async_function(
+                        &fn_expr.function,
+                        fn_expr.function.span,
+                        "use cache",
+                    )
+            }
+            Expr::Arrow(arrow_expr) => {
+                self.transform
+                    .has_use_cache_directive_arrow(&arrow_expr.body)
+                    && {
+                        if !arrow_expr.is_async {
+                            emit_error(WorkflowErrorKind::NonAsyncFunction {
+                                span: arrow_expr.span,
+                                directive: "use cache",
+                                async_at: self.transform.async_fix_at(arrow_expr.span),
+                            });
+                        }
+                        arrow_expr.is_async
+                    }
+            }
+            _ => false,
+        };
+        if !is_cache_function {
+            return;
+        }
+
+        let cache_id = self.register("default");
+        let mut cache_fn = cache_fn;
+        match &mut cache_fn {
+            Expr::Fn(fn_expr) => self
+                .transform
+                .remove_use_cache_directive(&mut fn_expr.function.body),
+            Expr::Arrow(arrow_expr) => self
+                .transform
+                .remove_use_cache_directive_arrow(&mut arrow_expr.body),
+            _ => {}
+        }
+        let expr = if self.transform.mode == TransformMode::Workflow {
+            self.initializer(&cache_id, cache_fn)
+        } else {
+            cache_fn
+        };
+        *item = match expr {
+            // Outside workflow mode a function keeps its declaration form (and its hoisting)
+            Expr::Fn(fn_expr) => {
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                    span: DUMMY_SP,
+                    decl: DefaultDecl::Fn(fn_expr),
+                }))
+            }
+            expr => ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                span: DUMMY_SP,
+                expr: Box::new(expr),
+            })),
+        };
+    }
+}
+
+impl<'a> VisitMut for CacheFunctionTransform<'a> {
+    fn visit_mut_module(&mut self, module: &mut Module) {
+        self.module_bindings = self.transform.module_imports.clone();
+        for item in &module.body {
+            match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                    for specifier in &import.specifiers {
+                        let local = match specifier {
+                            ImportSpecifier::Named(named) => &named.local,
+                            ImportSpecifier::Default(default) => &default.local,
+                            ImportSpecifier::Namespace(namespace) => &namespace.local,
+                        };
+                        self.module_bindings.insert(local.sym.to_string());
+                    }
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. }))
+                | ModuleItem::Stmt(Stmt::Decl(decl)) => match decl {
+                    Decl::Fn(fn_decl) => {
+                        self.module_bindings.insert(fn_decl.ident.sym.to_string());
+                    }
+                    Decl::Class(class_decl) => {
+                        self.module_bindings
+                            .insert(class_decl.ident.sym.to_string());
+                    }
+                    Decl::Var(var_decl) => {
+                        let mut idents = Vec::new();
+                        for declarator in &var_decl.decls {
+                            collect_binding_idents(&declarator.name, &mut idents);
+                        }
+                        self.module_bindings
+                            .extend(idents.into_iter().map(|ident| ident.sym.to_string()));
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+
+        for item in module.body.iter_mut() {
+            self.transform_default_export(item);
+        }
+        module.visit_mut_children_with(self);
+    }
+
+    // Cache functions nested in a default export are scoped by `default`
+    fn visit_mut_export_default_decl(&mut self, export: &mut ExportDefaultDecl) {
+        self.parent_names.push("default".to_string());
+        export.visit_mut_children_with(self);
+        self.parent_names.pop();
+    }
+
+    fn visit_mut_export_default_expr(&mut self, export: &mut ExportDefaultExpr) {
+        self.parent_names.push("default".to_string());
+        export.visit_mut_children_with(self);
+        self.parent_names.pop();
+    }
+
+    fn visit_mut_decl(&mut self, decl: &mut Decl) {
+        let Decl::Fn(fn_decl) = decl else {
+            decl.visit_mut_children_with(self);
+            return;
+        };
+
+        let fn_name = fn_decl.ident.sym.to_string();
+        let is_cache_function = self
+            .transform
+            .has_use_cache_directive(&fn_decl.function.body)
+            && self.transform.validate_async_function(
+                &fn_decl.function,
+                fn_decl.function.span,
+                "use cache",
+            );
+
+        self.parent_names.push(fn_name.clone());
+        fn_decl.function.visit_mut_with(self);
+        self.parent_names.pop();
+
+        if !is_cache_function {
+            return;
+        }
+
+        let cache_id = self.register(&fn_name);
+        self.transform
+            .remove_use_cache_directive(&mut fn_decl.function.body);
+
+        if self.transform.mode == TransformMode::Workflow {
+            // async function name() {} -> var name = globalThis[...]("cache_id", async function name() {})
+            let cache_fn = Expr::Fn(FnExpr {
+                ident: Some(fn_decl.ident.clone()),
+                function: fn_decl.function.clone(),
+            });
+            *decl = Decl::Var(Box::new(VarDecl {
+                span: DUMMY_SP,
+                ctxt: SyntaxContext::empty(),
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls: vec![VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(BindingIdent {
+                        id: fn_decl.ident.clone(),
+                        type_ann: None,
+                    }),
+                    init: Some(Box::new(self.initializer(&cache_id, cache_fn))),
+                    definite: false,
+                }],
+            }));
+        }
+    }
+
+    fn visit_mut_var_declarator(&mut self, declarator: &mut VarDeclarator) {
+        let var_name = match &declarator.name {
+            Pat::Ident(binding) => binding.id.sym.to_string(),
+            _ => {
+                declarator.visit_mut_children_with(self);
+                return;
+            }
+        };
+
+        let is_cache_function = match declarator.init.as_deref() {
+            Some(Expr::Arrow(arrow_expr)) => {
+                self.transform
+                    .has_use_cache_directive_arrow(&arrow_expr.body)
+                    && {
+                        if !arrow_expr.is_async {
+                            emit_error(WorkflowErrorKind::NonAsyncFunction {
+                                span: arrow_expr.span,
+                                directive: "use cache",
+                                async_at: self.transform.async_fix_at(arrow_expr.span),
+                            });
+                        }
+                        arrow_expr.is_async
+                    }
+            }
+            Some(Expr::Fn(fn_expr)) => {
+                self.transform
+                    .has_use_cache_directive(&fn_expr.function.body)
+                    && self.transform.validate_async_function(
+                        &fn_expr.function,
+                        fn_expr.function.span,
+                        "use cache",
+                    )
+            }
+            _ => {
+                declarator.visit_mut_children_with(self);
+                return;
+            }
+        };
 
-            let module_path = self.get_module_path();
-            let class_entries: Vec<String> = sorted_classes
-                .into_iter()
-                .map(|class_name| {
-                    let class_id = naming::format_name("class", &module_path, class_name);
-                    format!("\"{}\":{{\"classId\":\"{}\"}}", class_name, class_id)
-                })
-                .collect();
+        self.parent_names.push(var_name.clone());
+        declarator.init.visit_mut_with(self);
+        self.parent_names.pop();
 
-            metadata.insert("classes", format!("{{{}}}", class_entries.join(",")));
+        if !is_cache_function {
+            return;
         }
 
-        // Build the final comment structure
-        let relative_filename = self.filename.replace('\\', "/"); // Normalize path separators
-        let mut parts = Vec::new();
-
-        if metadata.contains_key("workflows") {
-            parts.push(format!(
-                "\"workflows\":{{\"{}\":{}}}",
-                relative_filename, metadata["workflows"]
-            ));
-        }
-        if metadata.contains_key("steps") {
-            parts.push(format!(
-                "\"steps\":{{\"{}\":{}}}",
-                relative_filename, metadata["steps"]
-            ));
-        }
-        if metadata.contains_key("classes") {
-            parts.push(format!(
-                "\"classes\":{{\"{}\":{}}}",
-                relative_filename, metadata["classes"]
-            ));
-        }
+        let cache_id = self.register(&var_name);
+        if let Some(init) = &mut declarator.init {
+            match &mut **init {
+                Expr::Arrow(arrow_expr) => self
+                    .transform
+                    .remove_use_cache_directive_arrow(&mut arrow_expr.body),
+                Expr::Fn(fn_expr) => self
+                    .transform
+                    .remove_use_cache_directive(&mut fn_expr.function.body),
+                _ => {}
+            }
 
-        if parts.is_empty() {
-            String::new()
-        } else {
-            format!("/**__internal_workflows{{{}}}*/", parts.join(","))
+            if self.transform.mode == TransformMode::Workflow {
+                let cache_fn = (**init).clone();
+                **init = self.initializer(&cache_id, cache_fn);
+            }
         }
     }
 }
@@ -3454,15 +7699,12 @@ impl<'a> VisitMut for UsageCollector<'a> {
     }
 
     fn visit_mut_export_decl(&mut self, export_decl: &mut ExportDecl) {
-        match &mut export_decl.decl {
-            Decl::Fn(fn_decl) => {
-                let fn_name = fn_decl.ident.sym.to_string();
-                if self.step_function_names.contains(&fn_name) {
-                    // Don't visit step function bodies
-                    return;
-                }
+        if let Decl::Fn(fn_decl) = &mut export_decl.decl {
+            let fn_name = fn_decl.ident.sym.to_string();
+            if self.step_function_names.contains(&fn_name) {
+                // Don't visit step function bodies
+                return;
             }
-            _ => {}
         }
         export_decl.visit_mut_children_with(self);
     }
@@ -3498,29 +7740,24 @@ struct ComprehensiveUsageCollector<'a> {
     current_function: Option<String>,
 }
 
-impl<'a> VisitMut for ComprehensiveUsageCollector<'a> {
-    fn visit_mut_module_item(&mut self, item: &mut ModuleItem) {
+impl<'a> Visit for ComprehensiveUsageCollector<'a> {
+    fn visit_module_item(&mut self, item: &ModuleItem) {
         match item {
             ModuleItem::ModuleDecl(ModuleDecl::Import(_)) => {
                 // Skip import declarations
-                return;
-            }
-            ModuleItem::Stmt(Stmt::Decl(Decl::Fn(_))) => {
-                // Handle function declarations specially to avoid marking them as "used" by declaration
-                item.visit_mut_children_with(self);
             }
-            ModuleItem::Stmt(Stmt::Decl(Decl::Var(_))) => {
-                // Handle variable declarations specially
-                item.visit_mut_children_with(self);
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(_)) => {
+                // Skip export specifiers: local ones are roots, and `export { x } from "..."`
+                // doesn't refer to a local `x`
             }
             _ => {
                 // Visit all other items
-                item.visit_mut_children_with(self);
+                item.visit_children_with(self);
             }
         }
     }
 
-    fn visit_mut_fn_decl(&mut self, fn_decl: &mut FnDecl) {
+    fn visit_fn_decl(&mut self, fn_decl: &FnDecl) {
         let fn_name = fn_decl.ident.sym.to_string();
         let is_step_function = self.step_function_names.contains(&fn_name);
 
@@ -3534,19 +7771,19 @@ impl<'a> VisitMut for ComprehensiveUsageCollector<'a> {
         self.current_function = Some(fn_name.clone());
 
         // Visit function parameters (which can contain default values that use other identifiers)
-        for param in &mut fn_decl.function.params {
-            param.visit_mut_with(self);
+        for param in &fn_decl.function.params {
+            param.visit_with(self);
         }
 
         // Visit the function content to find used identifiers (but don't mark the function name itself as used)
-        if let Some(body) = &mut fn_decl.function.body {
-            body.visit_mut_with(self);
+        if let Some(body) = &fn_decl.function.body {
+            body.visit_with(self);
         }
 
         self.current_function = prev_function;
     }
 
-    fn visit_mut_call_expr(&mut self, call: &mut CallExpr) {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
         // Track function calls specifically
         if let Callee::Expr(expr) = &call.callee {
             if let Expr::Ident(ident) = &**expr {
@@ -3556,10 +7793,10 @@ impl<'a> VisitMut for ComprehensiveUsageCollector<'a> {
         }
 
         // Visit arguments
-        call.visit_mut_children_with(self);
+        call.visit_children_with(self);
     }
 
-    fn visit_mut_ident(&mut self, ident: &mut Ident) {
+    fn visit_ident(&mut self, ident: &Ident) {
         // Track identifier usage, but be careful about function names in declarations
         let name = ident.sym.to_string();
 
@@ -3573,8 +7810,8 @@ impl<'a> VisitMut for ComprehensiveUsageCollector<'a> {
         self.used_identifiers.insert(name);
     }
 
-    fn visit_mut_export_decl(&mut self, export_decl: &mut ExportDecl) {
-        match &mut export_decl.decl {
+    fn visit_export_decl(&mut self, export_decl: &ExportDecl) {
+        match &export_decl.decl {
             Decl::Fn(fn_decl) => {
                 let fn_name = fn_decl.ident.sym.to_string();
                 if self.step_function_names.contains(&fn_name) {
@@ -3583,21 +7820,21 @@ impl<'a> VisitMut for ComprehensiveUsageCollector<'a> {
                 }
 
                 // For exported functions, visit their body
-                self.visit_mut_fn_decl(fn_decl);
+                self.visit_fn_decl(fn_decl);
             }
             Decl::Var(var_decl) => {
                 // For exported variables, visit their initializers
-                for declarator in &mut var_decl.decls {
-                    self.visit_mut_var_declarator(declarator);
+                for declarator in &var_decl.decls {
+                    self.visit_var_declarator(declarator);
                 }
             }
             _ => {
-                export_decl.visit_mut_children_with(self);
+                export_decl.visit_children_with(self);
             }
         }
     }
 
-    fn visit_mut_var_declarator(&mut self, var_decl: &mut VarDeclarator) {
+    fn visit_var_declarator(&mut self, var_decl: &VarDeclarator) {
         // Check if this is a step function assigned to a variable
         if let Some(init) = &var_decl.init {
             if let Pat::Ident(binding) = &var_decl.name {
@@ -3617,19 +7854,121 @@ impl<'a> VisitMut for ComprehensiveUsageCollector<'a> {
 
         // Only visit the initializer, not the variable name pattern
         // This prevents marking the variable name itself as "used"
-        if let Some(init) = &mut var_decl.init {
-            init.visit_mut_with(self);
+        if let Some(init) = &var_decl.init {
+            init.visit_with(self);
         }
     }
 
-    noop_visit_mut_type!();
+    noop_visit_type!();
+}
+
+// Names declared by a module item that dead code elimination may remove, or `None` if the item
+// is always kept. With `keep_calls` (client mode), variables initialized with a call or `new`
+// expression are kept as well, since it may have side effects. Workflow mode drops them like
+// any other unused declaration, since they usually set up clients that only steps use. Unused
+// identifier and literal expression statements and empty statements declare nothing and are
+// always removed; imports are pruned separately.
+fn removable_bindings(item: &ModuleItem, keep_calls: bool) -> Option<Vec<String>> {
+    match item {
+        ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl))) => {
+            Some(vec![fn_decl.ident.sym.to_string()])
+        }
+        ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) => {
+            let mut names = Vec::new();
+            var_decl
+                .decls
+                .iter()
+                .all(|declarator| {
+                    !(keep_calls && declarator.init.as_deref().is_some_and(is_call_or_new))
+                        && removable_pattern_bindings(&declarator.name, &mut names)
+                })
+                .then_some(names)
+        }
+        ModuleItem::Stmt(Stmt::Expr(expr_stmt)) => match &*expr_stmt.expr {
+            Expr::Ident(_) => Some(vec![]),
+            // Keep all string literals (might be directives or misspelled directives)
+            Expr::Lit(Lit::Str(_)) => None,
+            Expr::Lit(_) => Some(vec![]),
+            _ => None,
+        },
+        ModuleItem::Stmt(Stmt::Empty(_)) => Some(vec![]),
+        _ => None,
+    }
+}
+
+fn is_call_or_new(expr: &Expr) -> bool {
+    match expr {
+        Expr::Call(_) | Expr::New(_) => true,
+        Expr::OptChain(opt_chain) => matches!(&*opt_chain.base, OptChainBase::Call(_)),
+        Expr::Paren(paren) => is_call_or_new(&paren.expr),
+        _ => false,
+    }
+}
+
+// Collects the bindings of a declared pattern, returning false for patterns that are kept to be
+// safe (e.g. array elements with default values)
+fn removable_pattern_bindings(pat: &Pat, names: &mut Vec<String>) -> bool {
+    match pat {
+        Pat::Ident(binding) => {
+            names.push(binding.id.sym.to_string());
+            true
+        }
+        Pat::Array(array_pat) => array_pat
+            .elems
+            .iter()
+            .flatten()
+            .all(|elem| removable_pattern_bindings(elem, names)),
+        Pat::Object(obj_pat) => obj_pat.props.iter().all(|prop| match prop {
+            ObjectPatProp::KeyValue(kv) => removable_pattern_bindings(&kv.value, names),
+            ObjectPatProp::Assign(assign) => {
+                names.push(assign.key.sym.to_string());
+                true
+            }
+            ObjectPatProp::Rest(rest) => match &*rest.arg {
+                Pat::Ident(_) => removable_pattern_bindings(&rest.arg, names),
+                _ => false,
+            },
+        }),
+        _ => false,
+    }
 }
 
 impl VisitMut for StepTransform {
     fn visit_mut_program(&mut self, program: &mut Program) {
+        // Handle "use cache" functions up front, so the passes below only see plain
+        // functions (or cache initializers in workflow mode)
+        program.visit_mut_with(&mut CacheFunctionTransform {
+            transform: self,
+            parent_names: Vec::new(),
+            module_bindings: HashSet::new(),
+        });
+
+        // Keep the untouched script around in case it has to be rejected below
+        let original_script = match program {
+            Program::Script(script) if self.options.script_handling == ScriptHandling::Error => {
+                Some(script.clone())
+            }
+            _ => None,
+        };
+
         // First pass: collect step functions
         program.visit_mut_children_with(self);
 
+        // Needs the workflow functions collected above
+        if self.mode == TransformMode::Workflow {
+            if let Program::Module(module) = &*program {
+                self.check_forbidden_workflow_imports(&module.body);
+            }
+        }
+
+        // Needs the object property steps collected above, before step mode drains them
+        if self.options.warn_reassigned_step_objects {
+            self.check_reassigned_step_objects(program);
+        }
+        if !self.workflow_step_calls.is_empty() {
+            self.check_step_calls();
+        }
+
         // Preserve class names for manifest before they get drained during registration
         self.classes_for_manifest = self.classes_needing_serialization.clone();
 
@@ -3653,7 +7992,8 @@ impl VisitMut for StepTransform {
                             || !self.object_property_step_functions.is_empty()
                             || !self.nested_step_functions.is_empty()
                             || !self.static_method_step_registrations.is_empty()
-                            || !self.instance_method_step_registrations.is_empty();
+                            || !self.instance_method_step_registrations.is_empty()
+                            || !self.instance_field_step_registrations.is_empty();
 
                         // Check if any nested steps have closure variables
                         let needs_closure_import = self
@@ -3689,7 +8029,9 @@ impl VisitMut for StepTransform {
                     }
                 }
 
-                // Add imports at the beginning
+                // Add imports at the beginning. With the dual registration format these are plain
+                // statements, so remember how many there are to keep skipping them below.
+                let runtime_imports_len = imports_to_add.len();
                 for import in imports_to_add.into_iter().rev() {
                     module.body.insert(0, import);
                 }
@@ -3700,8 +8042,10 @@ impl VisitMut for StepTransform {
                     let initial_insert_pos = module
                         .body
                         .iter()
-                        .position(|item| {
-                            !matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_)))
+                        .enumerate()
+                        .position(|(i, item)| {
+                            i >= runtime_imports_len
+                                && !matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_)))
                         })
                         .unwrap_or(0);
                     let mut current_insert_pos = initial_insert_pos;
@@ -3722,7 +8066,7 @@ impl VisitMut for StepTransform {
                         let hoisted_name = if parent_workflow_name.is_empty() {
                             fn_name.clone()
                         } else {
-                            format!("{}${}", parent_workflow_name, fn_name)
+                            self.hoisted_step_name(&parent_workflow_name, &fn_name)
                         };
                         // If there are closure variables, add destructuring as first statement
                         if !closure_vars.is_empty() {
@@ -3782,25 +8126,39 @@ impl VisitMut for StepTransform {
                                         declare: false,
                                     })));
 
-                                // Prepend to function body
-                                body.stmts.insert(0, closure_destructure);
+                                // Prepend to function body, after any remaining prologue
+                                // (e.g. "use strict") so it stays a directive
+                                let prologue_len = body
+                                    .stmts
+                                    .iter()
+                                    .take_while(|stmt| is_prologue_stmt(stmt))
+                                    .count();
+                                body.stmts.insert(prologue_len, closure_destructure);
                             }
                         }
 
+                        // The function itself keeps its span; with preserve_hoisted_spans the
+                        // declaration around it does too
+                        let decl_span = if self.options.preserve_hoisted_spans {
+                            span
+                        } else {
+                            DUMMY_SP
+                        };
+
                         // Create the appropriate hoisted declaration based on original function type
                         let hoisted_decl = if was_arrow {
                             // Convert back to arrow function: var name = async () => { ... };
                             let arrow_expr = self.convert_fn_expr_to_arrow(&fn_expr);
                             ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
-                                span: DUMMY_SP,
+                                span: decl_span,
                                 ctxt: SyntaxContext::empty(),
                                 kind: VarDeclKind::Var,
                                 decls: vec![VarDeclarator {
-                                    span: DUMMY_SP,
+                                    span: decl_span,
                                     name: Pat::Ident(BindingIdent {
                                         id: Ident::new(
                                             hoisted_name.clone().into(),
-                                            DUMMY_SP,
+                                            decl_span,
                                             SyntaxContext::empty(),
                                         ),
                                         type_ann: None,
@@ -3812,10 +8170,15 @@ impl VisitMut for StepTransform {
                             }))))
                         } else {
                             // Keep as function declaration: async function name() { ... }
+                            // Its name maps to the original name, if it had one
+                            let ident_span = match &fn_expr.ident {
+                                Some(ident) if self.options.preserve_hoisted_spans => ident.span,
+                                _ => decl_span,
+                            };
                             ModuleItem::Stmt(Stmt::Decl(Decl::Fn(FnDecl {
                                 ident: Ident::new(
                                     hoisted_name.clone().into(),
-                                    DUMMY_SP,
+                                    ident_span,
                                     SyntaxContext::empty(),
                                 ),
                                 function: fn_expr.function,
@@ -3831,51 +8194,11 @@ impl VisitMut for StepTransform {
                         let step_fn_name = if parent_workflow_name.is_empty() {
                             fn_name.clone()
                         } else {
-                            format!("{}/{}", parent_workflow_name, fn_name)
+                            self.nested_step_id_name(&parent_workflow_name, &fn_name)
                         };
                         let step_id = self.create_id(Some(&step_fn_name), span, false);
 
-                        if self.mode == TransformMode::Client {
-                            // In client mode, use stepId property assignment instead of registerStepFunction
-                            let step_id_assignment =
-                                self.create_step_id_assignment_with_id(&hoisted_name, &step_id);
-                            self.registration_calls.push(step_id_assignment);
-                        } else {
-                            // In step mode, use registerStepFunction
-                            let registration_call = Stmt::Expr(ExprStmt {
-                                span: DUMMY_SP,
-                                expr: Box::new(Expr::Call(CallExpr {
-                                    span: DUMMY_SP,
-                                    ctxt: SyntaxContext::empty(),
-                                    callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
-                                        "registerStepFunction".into(),
-                                        DUMMY_SP,
-                                        SyntaxContext::empty(),
-                                    )))),
-                                    args: vec![
-                                        ExprOrSpread {
-                                            spread: None,
-                                            expr: Box::new(Expr::Lit(Lit::Str(Str {
-                                                span: DUMMY_SP,
-                                                value: step_id.into(),
-                                                raw: None,
-                                            }))),
-                                        },
-                                        ExprOrSpread {
-                                            spread: None,
-                                            expr: Box::new(Expr::Ident(Ident::new(
-                                                hoisted_name.into(),
-                                                DUMMY_SP,
-                                                SyntaxContext::empty(),
-                                            ))),
-                                        },
-                                    ],
-                                    type_args: None,
-                                })),
-                            });
-
-                            self.registration_calls.push(registration_call);
-                        }
+                        self.push_hoisted_step_registration(step_id, &hoisted_name, span);
                     }
 
                     // Then process object property step functions (they typically appear later)
@@ -3884,23 +8207,32 @@ impl VisitMut for StepTransform {
                         .object_property_step_functions
                         .iter()
                         .map(
-                            |(parent_var, prop_name, fn_expr, _span, workflow_name, _was_arrow)| {
-                                // Replace slashes with $ in parent_var to create valid JS identifier
-                                let safe_parent_var = parent_var.replace('/', "$");
-                                let hoist_var_name = if !workflow_name.is_empty() {
-                                    format!("{}${}${}", workflow_name, safe_parent_var, prop_name)
-                                } else {
-                                    format!("{}${}", safe_parent_var, prop_name)
-                                };
+                            |(parent_var, prop_name, fn_expr, span, workflow_name, _was_arrow)| {
                                 let wf_name = if workflow_name.is_empty() {
                                     None
                                 } else {
                                     Some(workflow_name.as_str())
                                 };
+                                let hoist_var_name = self
+                                    .object_property_hoisted_names
+                                    .get(&(
+                                        workflow_name.clone(),
+                                        parent_var.clone(),
+                                        prop_name.clone(),
+                                    ))
+                                    .cloned()
+                                    .unwrap_or_else(|| {
+                                        object_property_hoist_name(
+                                            wf_name,
+                                            parent_var,
+                                            prop_name,
+                                            &self.options.hoisted_name_separator,
+                                        )
+                                    });
                                 let step_id = self.create_object_property_id(
-                                    parent_var, prop_name, false, wf_name,
+                                    parent_var, prop_name, false, wf_name, *span,
                                 );
-                                (hoist_var_name, fn_expr.clone(), step_id, parent_var.clone())
+                                (hoist_var_name, fn_expr.clone(), step_id, *span)
                             },
                         )
                         .collect();
@@ -3908,20 +8240,26 @@ impl VisitMut for StepTransform {
                     // Now drain and process
                     self.object_property_step_functions.drain(..);
 
-                    for (hoist_var_name, fn_expr, step_id, _parent_var) in hoisting_info {
+                    for (hoist_var_name, fn_expr, step_id, span) in hoisting_info {
+                        let decl_span = if self.options.preserve_hoisted_spans {
+                            span
+                        } else {
+                            DUMMY_SP
+                        };
+
                         // Create a var declaration for the hoisted function
                         // Using function expression (not arrow) to preserve `this` binding
                         let hoisted_decl =
                             ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
-                                span: DUMMY_SP,
+                                span: decl_span,
                                 ctxt: SyntaxContext::empty(),
                                 kind: VarDeclKind::Var,
                                 decls: vec![VarDeclarator {
-                                    span: DUMMY_SP,
+                                    span: decl_span,
                                     name: Pat::Ident(BindingIdent {
                                         id: Ident::new(
                                             hoist_var_name.clone().into(),
-                                            DUMMY_SP,
+                                            decl_span,
                                             SyntaxContext::empty(),
                                         ),
                                         type_ann: None,
@@ -3936,54 +8274,18 @@ impl VisitMut for StepTransform {
                         module.body.insert(current_insert_pos, hoisted_decl);
                         current_insert_pos += 1;
 
-                        if self.mode == TransformMode::Client {
-                            // In client mode, use stepId property assignment instead of registerStepFunction
-                            let step_id_assignment =
-                                self.create_step_id_assignment_with_id(&hoist_var_name, &step_id);
-                            self.registration_calls.push(step_id_assignment);
-                        } else {
-                            // In step mode, use registerStepFunction
-                            let registration_call = Stmt::Expr(ExprStmt {
-                                span: DUMMY_SP,
-                                expr: Box::new(Expr::Call(CallExpr {
-                                    span: DUMMY_SP,
-                                    ctxt: SyntaxContext::empty(),
-                                    callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
-                                        "registerStepFunction".into(),
-                                        DUMMY_SP,
-                                        SyntaxContext::empty(),
-                                    )))),
-                                    args: vec![
-                                        ExprOrSpread {
-                                            spread: None,
-                                            expr: Box::new(Expr::Lit(Lit::Str(Str {
-                                                span: DUMMY_SP,
-                                                value: step_id.into(),
-                                                raw: None,
-                                            }))),
-                                        },
-                                        ExprOrSpread {
-                                            spread: None,
-                                            expr: Box::new(Expr::Ident(Ident::new(
-                                                hoist_var_name.into(),
-                                                DUMMY_SP,
-                                                SyntaxContext::empty(),
-                                            ))),
-                                        },
-                                    ],
-                                    type_args: None,
-                                })),
-                            });
-
-                            self.registration_calls.push(registration_call);
-                        }
+                        self.push_hoisted_step_registration(step_id, &hoist_var_name, span);
                     }
 
+                    let registrations_start = module.body.len();
                     for call in self.registration_calls.drain(..) {
                         module.body.push(ModuleItem::Stmt(call));
                     }
 
-                    // Add static method step registrations
+                    // Add static method step registrations, sorted by class and method so the
+                    // output doesn't depend on traversal order
+                    self.static_method_step_registrations
+                        .sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
                     for (class_name, method_name, step_id, _span) in
                         self.static_method_step_registrations.drain(..)
                     {
@@ -4032,6 +8334,8 @@ impl VisitMut for StepTransform {
 
                     // Add instance method step registrations
                     // For instance methods, we register ClassName.prototype.methodName
+                    self.instance_method_step_registrations
+                        .sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
                     for (class_name, method_name, step_id, _span) in
                         self.instance_method_step_registrations.drain(..)
                     {
@@ -4089,6 +8393,101 @@ impl VisitMut for StepTransform {
                         module.body.push(ModuleItem::Stmt(registration_call));
                     }
 
+                    // Add instance field step registrations:
+                    // registerStepFunction("step_id", function(...args) { return this.field(...args); })
+                    // The step ID starts with the class name, so sorting by it groups each class
+                    self.instance_field_step_registrations
+                        .sort_by(|a, b| a.1.cmp(&b.1));
+                    for (field_name, step_id) in self.instance_field_step_registrations.drain(..) {
+                        let args = Ident::new("args".into(), DUMMY_SP, SyntaxContext::empty());
+                        let forward_call = Expr::Call(CallExpr {
+                            span: DUMMY_SP,
+                            ctxt: SyntaxContext::empty(),
+                            callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+                                span: DUMMY_SP,
+                                obj: Box::new(Expr::This(ThisExpr { span: DUMMY_SP })),
+                                prop: MemberProp::Computed(ComputedPropName {
+                                    span: DUMMY_SP,
+                                    expr: Box::new(Expr::Lit(Lit::Str(Str {
+                                        span: DUMMY_SP,
+                                        value: field_name.into(),
+                                        raw: None,
+                                    }))),
+                                }),
+                            }))),
+                            args: vec![ExprOrSpread {
+                                spread: Some(DUMMY_SP),
+                                expr: Box::new(Expr::Ident(args.clone())),
+                            }],
+                            type_args: None,
+                        });
+                        let forward_fn = Expr::Fn(FnExpr {
+                            ident: None,
+                            function: Box::new(Function {
+                                params: vec![Param {
+                                    span: DUMMY_SP,
+                                    decorators: vec![],
+                                    pat: Pat::Rest(RestPat {
+                                        span: DUMMY_SP,
+                                        dot3_token: DUMMY_SP,
+                                        arg: Box::new(Pat::Ident(BindingIdent {
+                                            id: args,
+                                            type_ann: None,
+                                        })),
+                                        type_ann: None,
+                                    }),
+                                }],
+                                decorators: vec![],
+                                span: DUMMY_SP,
+                                ctxt: SyntaxContext::empty(),
+                                body: Some(BlockStmt {
+                                    span: DUMMY_SP,
+                                    ctxt: SyntaxContext::empty(),
+                                    stmts: vec![Stmt::Return(ReturnStmt {
+                                        span: DUMMY_SP,
+                                        arg: Some(Box::new(forward_call)),
+                                    })],
+                                }),
+                                is_generator: false,
+                                is_async: false,
+                                type_params: None,
+                                return_type: None,
+                            }),
+                        });
+                        let registration_call = Stmt::Expr(ExprStmt {
+                            span: DUMMY_SP,
+                            expr: Box::new(Expr::Call(CallExpr {
+                                span: DUMMY_SP,
+                                ctxt: SyntaxContext::empty(),
+                                callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
+                                    "registerStepFunction".into(),
+                                    DUMMY_SP,
+                                    SyntaxContext::empty(),
+                                )))),
+                                args: vec![
+                                    ExprOrSpread {
+                                        spread: None,
+                                        expr: Box::new(Expr::Lit(Lit::Str(Str {
+                                            span: DUMMY_SP,
+                                            value: step_id.into(),
+                                            raw: None,
+                                        }))),
+                                    },
+                                    ExprOrSpread {
+                                        spread: None,
+                                        expr: Box::new(forward_fn),
+                                    },
+                                ],
+                                type_args: None,
+                            })),
+                        });
+                        module.body.push(ModuleItem::Stmt(registration_call));
+                    }
+
+                    if self.mode == TransformMode::Step {
+                        self.batch_registration_calls(&mut module.body, registrations_start);
+                    }
+
                     // Add class serialization registrations for step mode
                     // In step mode, we need:
                     // 1. registerSerializationClass(classId, ClassName) - for deserialization
@@ -4338,103 +8737,68 @@ impl VisitMut for StepTransform {
                     sorted_classes.sort();
                     for class_name in sorted_classes {
                         let registration_call =
-                            self.create_class_serialization_registration(&class_name);
-                        module.body.push(ModuleItem::Stmt(registration_call));
-                    }
-                }
-
-                // Add static method workflow registrations (workflowId and __private_workflows.set)
-                if matches!(self.mode, TransformMode::Workflow) {
-                    for (class_name, method_name, workflow_id, _span) in
-                        self.static_method_workflow_registrations.drain(..)
-                    {
-                        // Add ClassName.methodName.workflowId = "workflow_id"
-                        let workflow_id_assignment = Stmt::Expr(ExprStmt {
-                            span: DUMMY_SP,
-                            expr: Box::new(Expr::Assign(AssignExpr {
-                                span: DUMMY_SP,
-                                left: AssignTarget::Simple(SimpleAssignTarget::Member(
-                                    MemberExpr {
-                                        span: DUMMY_SP,
-                                        obj: Box::new(Expr::Member(MemberExpr {
-                                            span: DUMMY_SP,
-                                            obj: Box::new(Expr::Ident(Ident::new(
-                                                class_name.clone().into(),
-                                                DUMMY_SP,
-                                                SyntaxContext::empty(),
-                                            ))),
-                                            prop: MemberProp::Ident(IdentName::new(
-                                                method_name.clone().into(),
-                                                DUMMY_SP,
-                                            )),
-                                        })),
-                                        prop: MemberProp::Ident(IdentName::new(
-                                            "workflowId".into(),
-                                            DUMMY_SP,
-                                        )),
-                                    },
-                                )),
-                                op: AssignOp::Assign,
-                                right: Box::new(Expr::Lit(Lit::Str(Str {
-                                    span: DUMMY_SP,
-                                    value: workflow_id.clone().into(),
-                                    raw: None,
-                                }))),
-                            })),
-                        });
-                        module.body.push(ModuleItem::Stmt(workflow_id_assignment));
-
-                        // Add globalThis.__private_workflows.set("workflow_id", ClassName.methodName)
-                        let workflows_set_call = Stmt::Expr(ExprStmt {
-                            span: DUMMY_SP,
-                            expr: Box::new(Expr::Call(CallExpr {
-                                span: DUMMY_SP,
-                                ctxt: SyntaxContext::empty(),
-                                callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
-                                    span: DUMMY_SP,
-                                    obj: Box::new(Expr::Member(MemberExpr {
-                                        span: DUMMY_SP,
-                                        obj: Box::new(Expr::Ident(Ident::new(
-                                            "globalThis".into(),
-                                            DUMMY_SP,
-                                            SyntaxContext::empty(),
-                                        ))),
-                                        prop: MemberProp::Ident(IdentName::new(
-                                            "__private_workflows".into(),
-                                            DUMMY_SP,
-                                        )),
-                                    })),
-                                    prop: MemberProp::Ident(IdentName::new("set".into(), DUMMY_SP)),
-                                }))),
-                                args: vec![
-                                    ExprOrSpread {
-                                        spread: None,
-                                        expr: Box::new(Expr::Lit(Lit::Str(Str {
-                                            span: DUMMY_SP,
-                                            value: workflow_id.into(),
-                                            raw: None,
-                                        }))),
-                                    },
-                                    ExprOrSpread {
-                                        spread: None,
-                                        expr: Box::new(Expr::Member(MemberExpr {
+                            self.create_class_serialization_registration(&class_name);
+                        module.body.push(ModuleItem::Stmt(registration_call));
+                    }
+                }
+
+                // Add static method workflow registrations (workflowId and __private_workflows.set),
+                // sorted by class and method like the step registrations
+                self.static_method_workflow_registrations
+                    .sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+                if matches!(self.mode, TransformMode::Workflow) {
+                    for (class_name, method_name, workflow_id, _span) in
+                        std::mem::take(&mut self.static_method_workflow_registrations)
+                    {
+                        // Add ClassName.methodName.workflowId = "workflow_id"
+                        let workflow_id_assignment = Stmt::Expr(ExprStmt {
+                            span: DUMMY_SP,
+                            expr: Box::new(Expr::Assign(AssignExpr {
+                                span: DUMMY_SP,
+                                left: AssignTarget::Simple(SimpleAssignTarget::Member(
+                                    MemberExpr {
+                                        span: DUMMY_SP,
+                                        obj: Box::new(Expr::Member(MemberExpr {
                                             span: DUMMY_SP,
                                             obj: Box::new(Expr::Ident(Ident::new(
-                                                class_name.into(),
+                                                class_name.clone().into(),
                                                 DUMMY_SP,
                                                 SyntaxContext::empty(),
                                             ))),
                                             prop: MemberProp::Ident(IdentName::new(
-                                                method_name.into(),
+                                                method_name.clone().into(),
                                                 DUMMY_SP,
                                             )),
                                         })),
+                                        prop: MemberProp::Ident(IdentName::new(
+                                            "workflowId".into(),
+                                            DUMMY_SP,
+                                        )),
                                     },
-                                ],
-                                type_args: None,
+                                )),
+                                op: AssignOp::Assign,
+                                right: Box::new(Expr::Lit(Lit::Str(Str {
+                                    span: DUMMY_SP,
+                                    value: workflow_id.clone().into(),
+                                    raw: None,
+                                }))),
                             })),
                         });
-                        module.body.push(ModuleItem::Stmt(workflows_set_call));
+                        module.body.push(ModuleItem::Stmt(workflow_id_assignment));
+
+                        // Add globalThis.__private_workflows.set("workflow_id", ClassName.methodName)
+                        let method = Expr::Member(MemberExpr {
+                            span: DUMMY_SP,
+                            obj: Box::new(Expr::Ident(Ident::new(
+                                class_name.into(),
+                                DUMMY_SP,
+                                SyntaxContext::empty(),
+                            ))),
+                            prop: MemberProp::Ident(IdentName::new(method_name.into(), DUMMY_SP)),
+                        });
+                        for stmt in self.create_workflows_set(&workflow_id, method) {
+                            module.body.push(ModuleItem::Stmt(stmt));
+                        }
                     }
                 } else if matches!(self.mode, TransformMode::Step | TransformMode::Client) {
                     // For step/client mode, just add the workflowId assignment
@@ -4482,14 +8846,20 @@ impl VisitMut for StepTransform {
 
                 // Add metadata comment at the beginning of the file
                 let metadata_comment = self.generate_metadata_comment();
-                if !metadata_comment.is_empty() {
+                if self.options.manifest_output == ManifestOutput::Comment
+                    && !metadata_comment.is_empty()
+                {
                     // Insert the metadata as a string literal expression statement
-                    // This will appear as a comment-like string in the output
+                    // This will appear as a comment-like string in the output. It goes after
+                    // any directive prologue (e.g. "use strict"), which it would otherwise end.
                     let insert_position = module
                         .body
                         .iter()
-                        .position(|item| {
-                            !matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_)))
+                        .enumerate()
+                        .position(|(i, item)| {
+                            i >= runtime_imports_len
+                                && !matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_)))
+                                && !matches!(item, ModuleItem::Stmt(stmt) if is_prologue_stmt(stmt))
                         })
                         .unwrap_or(0);
 
@@ -4505,11 +8875,28 @@ impl VisitMut for StepTransform {
                         })),
                     );
                 }
+                if self.options.manifest_output == ManifestOutput::ExportConst {
+                    module.body.extend(self.generate_metadata_export());
+                }
+                keep_leading_comments_first(&mut module.body, module.span);
             }
             Program::Script(script) => {
                 // For scripts, we need to convert to module if we have step or workflow functions
                 if !self.step_function_names.is_empty() || !self.workflow_function_names.is_empty()
                 {
+                    if let Some(original_script) = original_script {
+                        emit_error(WorkflowErrorKind::ScriptNotModule {
+                            span: script.span,
+                            directive: if self.workflow_function_names.is_empty() {
+                                self.step_directive.as_str()
+                            } else {
+                                self.workflow_directive.as_str()
+                            },
+                        });
+                        *script = original_script;
+                        return;
+                    }
+
                     let mut module_items = Vec::new();
 
                     match self.mode {
@@ -4538,15 +8925,18 @@ impl VisitMut for StepTransform {
                     }
 
                     // Convert script statements to module items
+                    let runtime_imports_len = module_items.len();
                     for stmt in &script.body {
                         module_items.push(ModuleItem::Stmt(stmt.clone()));
                     }
 
                     // Add registration calls for step mode
                     if matches!(self.mode, TransformMode::Step) {
+                        let registrations_start = module_items.len();
                         for call in self.registration_calls.drain(..) {
                             module_items.push(ModuleItem::Stmt(call));
                         }
+                        self.batch_registration_calls(&mut module_items, registrations_start);
                     }
 
                     // Add class serialization registrations for client mode (Script case)
@@ -4565,12 +8955,23 @@ impl VisitMut for StepTransform {
 
                     // Add metadata comment at the beginning of the module
                     let metadata_comment = self.generate_metadata_comment();
-                    if !metadata_comment.is_empty() {
-                        // Find position after imports
+                    if self.options.manifest_output == ManifestOutput::Comment
+                        && !metadata_comment.is_empty()
+                    {
+                        // Find position after imports and the directive prologue
                         let insert_position = module_items
                             .iter()
-                            .position(|item| {
-                                !matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_)))
+                            .enumerate()
+                            .position(|(i, item)| {
+                                i >= runtime_imports_len
+                                    && !matches!(
+                                        item,
+                                        ModuleItem::ModuleDecl(ModuleDecl::Import(_))
+                                    )
+                                    && !matches!(
+                                        item,
+                                        ModuleItem::Stmt(stmt) if is_prologue_stmt(stmt)
+                                    )
                             })
                             .unwrap_or(0);
 
@@ -4586,6 +8987,10 @@ impl VisitMut for StepTransform {
                             })),
                         );
                     }
+                    if self.options.manifest_output == ManifestOutput::ExportConst {
+                        module_items.extend(self.generate_metadata_export());
+                    }
+                    keep_leading_comments_first(&mut module_items, script.span);
 
                     // Replace program with module
                     *program = Program::Module(Module {
@@ -4596,6 +9001,9 @@ impl VisitMut for StepTransform {
                 }
             }
         }
+
+        self.report_missing_module_specifier();
+        self.report_invalid_ids();
     }
 
     fn visit_mut_function(&mut self, function: &mut Function) {
@@ -4607,6 +9015,8 @@ impl VisitMut for StepTransform {
         let old_in_workflow = self.in_workflow_function;
         let old_workflow_name = self.current_workflow_function_name.clone();
         let old_in_module = self.in_module_level;
+        let old_in_method_step = self.in_method_step;
+        let old_in_plain_function = self.in_plain_function;
 
         if has_step_directive {
             self.in_step_function = true;
@@ -4614,6 +9024,17 @@ impl VisitMut for StepTransform {
         if has_workflow_directive {
             self.in_workflow_function = true;
         }
+        if has_step_directive || has_workflow_directive {
+            self.in_method_step = false;
+        }
+        if has_step_directive
+            || has_workflow_directive
+            || self.directive_function_spans.contains(&function.span)
+        {
+            self.in_plain_function = false;
+        } else if self.in_step_function || self.in_workflow_function {
+            self.in_plain_function = true;
+        }
         self.in_module_level = false;
 
         // Visit children
@@ -4622,6 +9043,8 @@ impl VisitMut for StepTransform {
         // Restore context
         self.in_step_function = old_in_step;
         self.in_workflow_function = old_in_workflow;
+        self.in_method_step = old_in_method_step;
+        self.in_plain_function = old_in_plain_function;
         self.current_workflow_function_name = old_workflow_name;
         self.in_module_level = old_in_module;
     }
@@ -4635,6 +9058,8 @@ impl VisitMut for StepTransform {
         let old_in_workflow = self.in_workflow_function;
         let old_workflow_name = self.current_workflow_function_name.clone();
         let old_in_module = self.in_module_level;
+        let old_in_method_step = self.in_method_step;
+        let old_in_plain_function = self.in_plain_function;
 
         if has_step_directive {
             self.in_step_function = true;
@@ -4642,6 +9067,12 @@ impl VisitMut for StepTransform {
         if has_workflow_directive {
             self.in_workflow_function = true;
         }
+        // Arrows without a directive share the enclosing function's `this`, so they keep its
+        // context
+        if has_step_directive || has_workflow_directive {
+            self.in_method_step = false;
+            self.in_plain_function = false;
+        }
         self.in_module_level = false;
 
         // Visit children
@@ -4650,57 +9081,118 @@ impl VisitMut for StepTransform {
         // Restore context
         self.in_step_function = old_in_step;
         self.in_workflow_function = old_in_workflow;
+        self.in_method_step = old_in_method_step;
+        self.in_plain_function = old_in_plain_function;
         self.current_workflow_function_name = old_workflow_name;
         self.in_module_level = old_in_module;
     }
 
+    fn visit_mut_constructor(&mut self, constructor: &mut Constructor) {
+        // Like a function without a directive, a constructor binds its own `this` and `super`
+        let old_in_plain_function = self.in_plain_function;
+        if self.in_step_function || self.in_workflow_function {
+            self.in_plain_function = true;
+        }
+        constructor.visit_mut_children_with(self);
+        self.in_plain_function = old_in_plain_function;
+    }
+
     // Add forbidden expression checks
     fn visit_mut_this_expr(&mut self, expr: &mut ThisExpr) {
+        if self.in_method_step || self.in_plain_function {
+            return;
+        }
         if self.in_step_function {
-            emit_error(WorkflowErrorKind::ForbiddenExpression {
-                span: expr.span,
-                expr: "this",
-                directive: "use step",
-            });
+            emit_diagnostic(
+                WorkflowErrorKind::ForbiddenExpression {
+                    span: expr.span,
+                    expr: "this",
+                    directive: self.step_directive.as_str(),
+                },
+                self.options.diagnostics,
+            );
         } else if self.in_workflow_function {
-            emit_error(WorkflowErrorKind::ForbiddenExpression {
-                span: expr.span,
-                expr: "this",
-                directive: "use workflow",
-            });
+            emit_diagnostic(
+                WorkflowErrorKind::ForbiddenExpression {
+                    span: expr.span,
+                    expr: "this",
+                    directive: self.workflow_directive.as_str(),
+                },
+                self.options.diagnostics,
+            );
+        }
+    }
+
+    fn visit_mut_meta_prop_expr(&mut self, expr: &mut MetaPropExpr) {
+        if expr.kind != MetaPropKind::NewTarget || self.in_method_step || self.in_plain_function {
+            return;
+        }
+        if self.in_step_function {
+            emit_diagnostic(
+                WorkflowErrorKind::ForbiddenExpression {
+                    span: expr.span,
+                    expr: "new.target",
+                    directive: self.step_directive.as_str(),
+                },
+                self.options.diagnostics,
+            );
+        } else if self.in_workflow_function {
+            emit_diagnostic(
+                WorkflowErrorKind::ForbiddenExpression {
+                    span: expr.span,
+                    expr: "new.target",
+                    directive: self.workflow_directive.as_str(),
+                },
+                self.options.diagnostics,
+            );
         }
     }
 
     fn visit_mut_super(&mut self, sup: &mut Super) {
+        if self.in_plain_function {
+            return;
+        }
         if self.in_step_function {
-            emit_error(WorkflowErrorKind::ForbiddenExpression {
-                span: sup.span,
-                expr: "super",
-                directive: "use step",
-            });
+            emit_diagnostic(
+                WorkflowErrorKind::ForbiddenExpression {
+                    span: sup.span,
+                    expr: "super",
+                    directive: self.step_directive.as_str(),
+                },
+                self.options.diagnostics,
+            );
         } else if self.in_workflow_function {
-            emit_error(WorkflowErrorKind::ForbiddenExpression {
-                span: sup.span,
-                expr: "super",
-                directive: "use workflow",
-            });
+            emit_diagnostic(
+                WorkflowErrorKind::ForbiddenExpression {
+                    span: sup.span,
+                    expr: "super",
+                    directive: self.workflow_directive.as_str(),
+                },
+                self.options.diagnostics,
+            );
         }
     }
 
     fn visit_mut_ident(&mut self, ident: &mut Ident) {
-        if ident.sym == *"arguments" {
+        if ident.sym == *"arguments" && !self.in_plain_function {
             if self.in_step_function {
-                emit_error(WorkflowErrorKind::ForbiddenExpression {
-                    span: ident.span,
-                    expr: "arguments",
-                    directive: "use step",
-                });
+                emit_diagnostic(
+                    WorkflowErrorKind::ForbiddenExpression {
+                        span: ident.span,
+                        expr: "arguments",
+                        directive: self.step_directive.as_str(),
+                    },
+                    self.options.diagnostics,
+                );
             } else if self.in_workflow_function {
-                emit_error(WorkflowErrorKind::ForbiddenExpression {
-                    span: ident.span,
-                    expr: "arguments",
-                    directive: "use workflow",
-                });
+                emit_diagnostic(
+                    WorkflowErrorKind::ForbiddenExpression {
+                        span: ident.span,
+                        expr: "arguments",
+                        directive: self.workflow_directive.as_str(),
+                    },
+                    self.options.diagnostics,
+                );
             }
         }
     }
@@ -4734,45 +9226,138 @@ impl VisitMut for StepTransform {
                     }
                 }
             }
+            if let ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(import_equals)) = item {
+                self.module_imports.insert(import_equals.id.sym.to_string());
+            }
+        }
+
+        if !self.options.step_decorators.is_empty() || !self.options.workflow_decorators.is_empty()
+        {
+            items.visit_mut_with(&mut DecoratorDirectives {
+                step_decorators: &self.options.step_decorators,
+                workflow_decorators: &self.options.workflow_decorators,
+                step_directive: &self.step_directive,
+                workflow_directive: &self.workflow_directive,
+            });
         }
 
         // Check for file-level directives
         self.has_file_step_directive = self.check_module_directive(items);
         self.has_file_workflow_directive = self.check_module_workflow_directive(items);
+        self.mark_specifier_exports(items);
+
+        // Needs the file-level directives to know which exports are steps/workflows
+        self.check_shadowed_directive_functions(items);
+        self.check_immediately_invoked_directives(items);
+        for item in items.iter() {
+            item.visit_with(&mut GeneratorStepChecker {
+                step_directive: &self.step_directive,
+                has_file_step_directive: self.has_file_step_directive,
+                in_export: false,
+            });
+            item.visit_with(&mut ConflictingDirectiveChecker {
+                step_directive: &self.step_directive,
+                workflow_directive: &self.workflow_directive,
+            });
+        }
+        // Exports of a "use step" file are steps without a directive of their own
+        for item in items.iter_mut() {
+            let is_export = matches!(
+                item,
+                ModuleItem::ModuleDecl(
+                    ModuleDecl::ExportDecl(_)
+                        | ModuleDecl::ExportDefaultDecl(_)
+                        | ModuleDecl::ExportDefaultExpr(_)
+                )
+            );
+            item.visit_mut_with(&mut NestedStepInliner {
+                step_directive: &self.step_directive,
+                workflow_directive: &self.workflow_directive,
+                module_imports: &self.module_imports,
+                diagnostics: self.options.diagnostics,
+                in_step: self.has_file_step_directive && is_export,
+            });
+        }
+        let mut directive_functions = DirectiveFunctionFinder {
+            directives: [&self.step_directive, &self.workflow_directive],
+            functions: Vec::new(),
+        };
+        items.visit_with(&mut directive_functions);
+        self.directive_function_spans = directive_functions
+            .functions
+            .into_iter()
+            .map(|(span, _)| span)
+            .collect();
+        if self.options.warn_workflows_without_steps
+            || self.options.warn_unhandled_step_calls
+            || self.options.warn_awaited_non_steps
+        {
+            let mut collector = StepCallCollector {
+                step_directive: &self.step_directive,
+                workflow_directive: &self.workflow_directive,
+                has_file_workflow_directive: self.has_file_workflow_directive,
+                workflows: Vec::new(),
+                in_export: false,
+            };
+            items.visit_with(&mut collector);
+            self.workflow_step_calls = collector.workflows;
+        }
+
+        if self.options.strict_import_order {
+            self.check_import_order(items);
+        }
+        if self.options.require_exported_workflows {
+            self.check_unexported_workflows(items);
+        }
+        if self.options.warn_directive_file_imports {
+            self.check_directive_file_imports(items);
+        }
 
         // Remove file-level directive if present
-        if !items.is_empty() {
-            if let ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. })) = &items[0] {
-                if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
-                    let should_remove = match self.mode {
-                        TransformMode::Step => value == "use step" || value == "use workflow",
-                        TransformMode::Workflow => value == "use workflow",
-                        TransformMode::Client => value == "use step" || value == "use workflow",
-                    };
-                    if should_remove {
-                        items.remove(0);
+        // (other prologue entries such as "use strict" may come before it)
+        let directive_index = items
+            .iter()
+            .take_while(|item| matches!(item, ModuleItem::Stmt(stmt) if is_prologue_stmt(stmt)))
+            .position(|item| {
+                if let ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. })) = item {
+                    if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
+                        let is_step =
+                            value == self.step_directive.as_str() && self.has_file_step_directive;
+                        let is_workflow = value == self.workflow_directive.as_str()
+                            && self.has_file_workflow_directive;
+                        return match self.mode {
+                            TransformMode::Step | TransformMode::Client => is_step || is_workflow,
+                            TransformMode::Workflow => is_workflow,
+                        };
                     }
                 }
-            }
+                false
+            });
+        if let Some(index) = directive_index {
+            items.remove(index);
         }
 
         // Process items and collect functions that need workflowId assignments
         let mut items_to_insert = Vec::new();
 
         for (i, item) in items.iter_mut().enumerate() {
+            if let ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export_default)) = item {
+                self.unwrap_parenthesized_default_export(export_default);
+            }
+
             // Validate exports if we have a file-level directive
             if self.has_file_step_directive || self.has_file_workflow_directive {
                 match item {
                     ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
                         match &export.decl {
                             Decl::Fn(fn_decl) => {
-                                if !fn_decl.function.is_async {
+                                if !is_async_function(&fn_decl.function) {
                                     emit_error(WorkflowErrorKind::InvalidExport {
                                         span: export.span,
                                         directive: if self.has_file_step_directive {
-                                            "use step"
+                                            self.step_directive.as_str()
                                         } else {
-                                            "use workflow"
+                                            self.workflow_directive.as_str()
                                         },
                                     });
                                 }
@@ -4782,38 +9367,36 @@ impl VisitMut for StepTransform {
                                 for decl in &var_decl.decls {
                                     if let Some(init) = &decl.init {
                                         match &**init {
-                                            Expr::Fn(fn_expr) => {
-                                                if !fn_expr.function.is_async {
-                                                    emit_error(WorkflowErrorKind::InvalidExport {
-                                                        span: export.span,
-                                                        directive: if self.has_file_step_directive {
-                                                            "use step"
-                                                        } else {
-                                                            "use workflow"
-                                                        },
-                                                    });
-                                                }
+                                            Expr::Fn(fn_expr) if !fn_expr.function.is_async => {
+                                                emit_error(WorkflowErrorKind::InvalidExport {
+                                                    span: export.span,
+                                                    directive: if self.has_file_step_directive {
+                                                        self.step_directive.as_str()
+                                                    } else {
+                                                        self.workflow_directive.as_str()
+                                                    },
+                                                });
                                             }
-                                            Expr::Arrow(arrow_expr) => {
-                                                if !arrow_expr.is_async {
-                                                    emit_error(WorkflowErrorKind::InvalidExport {
-                                                        span: export.span,
-                                                        directive: if self.has_file_step_directive {
-                                                            "use step"
-                                                        } else {
-                                                            "use workflow"
-                                                        },
-                                                    });
-                                                }
+                                            Expr::Arrow(arrow_expr) if !arrow_expr.is_async => {
+                                                emit_error(WorkflowErrorKind::InvalidExport {
+                                                    span: export.span,
+                                                    directive: if self.has_file_step_directive {
+                                                        self.step_directive.as_str()
+                                                    } else {
+                                                        self.workflow_directive.as_str()
+                                                    },
+                                                });
                                             }
-                                            Expr::Lit(_) => {
+                                            Expr::Lit(_)
+                                                if !self.options.allow_non_function_exports =>
+                                            {
                                                 // Literals are not allowed
                                                 emit_error(WorkflowErrorKind::InvalidExport {
                                                     span: export.span,
                                                     directive: if self.has_file_step_directive {
-                                                        "use step"
+                                                        self.step_directive.as_str()
                                                     } else {
-                                                        "use workflow"
+                                                        self.workflow_directive.as_str()
                                                     },
                                                 });
                                             }
@@ -4825,17 +9408,20 @@ impl VisitMut for StepTransform {
                                     }
                                 }
                             }
-                            Decl::Class(_) => {
+                            Decl::Class(_) if !self.options.allow_non_function_exports => {
                                 // Classes are not allowed
                                 emit_error(WorkflowErrorKind::InvalidExport {
                                     span: export.span,
                                     directive: if self.has_file_step_directive {
-                                        "use step"
+                                        self.step_directive.as_str()
                                     } else {
-                                        "use workflow"
+                                        self.workflow_directive.as_str()
                                     },
                                 });
                             }
+                            Decl::Class(_) => {
+                                // Allowed with `allow_non_function_exports`, and left untouched
+                            }
                             Decl::TsInterface(_)
                             | Decl::TsTypeAlias(_)
                             | Decl::TsEnum(_)
@@ -4846,26 +9432,26 @@ impl VisitMut for StepTransform {
                                 emit_error(WorkflowErrorKind::InvalidExport {
                                     span: export.span,
                                     directive: if self.has_file_step_directive {
-                                        "use step"
+                                        self.step_directive.as_str()
                                     } else {
-                                        "use workflow"
+                                        self.workflow_directive.as_str()
                                     },
                                 });
                             }
                         }
                     }
-                    ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(named)) => {
-                        if named.src.is_some() {
-                            // Re-exports are not allowed
-                            emit_error(WorkflowErrorKind::InvalidExport {
-                                span: named.span,
-                                directive: if self.has_file_step_directive {
-                                    "use step"
-                                } else {
-                                    "use workflow"
-                                },
-                            });
-                        }
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(named))
+                        if named.src.is_some() =>
+                    {
+                        // Re-exports are not allowed
+                        emit_error(WorkflowErrorKind::InvalidExport {
+                            span: named.span,
+                            directive: if self.has_file_step_directive {
+                                self.step_directive.as_str()
+                            } else {
+                                self.workflow_directive.as_str()
+                            },
+                        });
                     }
                     ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(default)) => {
                         match &default.decl {
@@ -4874,20 +9460,23 @@ impl VisitMut for StepTransform {
                                     emit_error(WorkflowErrorKind::InvalidExport {
                                         span: default.span,
                                         directive: if self.has_file_step_directive {
-                                            "use step"
+                                            self.step_directive.as_str()
                                         } else {
-                                            "use workflow"
+                                            self.workflow_directive.as_str()
                                         },
                                     });
                                 }
                             }
+                            DefaultDecl::Class(_) if self.options.allow_non_function_exports => {
+                                // Allowed with `allow_non_function_exports`, and left untouched
+                            }
                             DefaultDecl::Class(_) => {
                                 emit_error(WorkflowErrorKind::InvalidExport {
                                     span: default.span,
                                     directive: if self.has_file_step_directive {
-                                        "use step"
+                                        self.step_directive.as_str()
                                     } else {
-                                        "use workflow"
+                                        self.workflow_directive.as_str()
                                     },
                                 });
                             }
@@ -4903,9 +9492,9 @@ impl VisitMut for StepTransform {
                                     emit_error(WorkflowErrorKind::InvalidExport {
                                         span: expr.span,
                                         directive: if self.has_file_step_directive {
-                                            "use step"
+                                            self.step_directive.as_str()
                                         } else {
-                                            "use workflow"
+                                            self.workflow_directive.as_str()
                                         },
                                     });
                                 }
@@ -4915,21 +9504,24 @@ impl VisitMut for StepTransform {
                                     emit_error(WorkflowErrorKind::InvalidExport {
                                         span: expr.span,
                                         directive: if self.has_file_step_directive {
-                                            "use step"
+                                            self.step_directive.as_str()
                                         } else {
-                                            "use workflow"
+                                            self.workflow_directive.as_str()
                                         },
                                     });
                                 }
                             }
+                            Expr::Lit(_) if self.options.allow_non_function_exports => {
+                                // Allowed with `allow_non_function_exports`
+                            }
                             _ => {
                                 // Other default exports are not allowed
                                 emit_error(WorkflowErrorKind::InvalidExport {
                                     span: expr.span,
                                     directive: if self.has_file_step_directive {
-                                        "use step"
+                                        self.step_directive.as_str()
                                     } else {
-                                        "use workflow"
+                                        self.workflow_directive.as_str()
                                     },
                                 });
                             }
@@ -4940,9 +9532,34 @@ impl VisitMut for StepTransform {
                         emit_error(WorkflowErrorKind::InvalidExport {
                             span: export_all.span,
                             directive: if self.has_file_step_directive {
-                                "use step"
+                                self.step_directive.as_str()
+                            } else {
+                                self.workflow_directive.as_str()
+                            },
+                        });
+                    }
+                    ModuleItem::ModuleDecl(ModuleDecl::TsExportAssignment(export_assignment)) => {
+                        // TypeScript's `export = value` replaces the module's exports, so it
+                        // can't be checked like named exports
+                        emit_error(WorkflowErrorKind::InvalidExport {
+                            span: export_assignment.span,
+                            directive: if self.has_file_step_directive {
+                                self.step_directive.as_str()
+                            } else {
+                                self.workflow_directive.as_str()
+                            },
+                        });
+                    }
+                    ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(import_equals))
+                        if import_equals.is_export =>
+                    {
+                        // `export import x = require("y")` re-exports another module's value
+                        emit_error(WorkflowErrorKind::InvalidExport {
+                            span: import_equals.span,
+                            directive: if self.has_file_step_directive {
+                                self.step_directive.as_str()
                             } else {
-                                "use workflow"
+                                self.workflow_directive.as_str()
                             },
                         });
                     }
@@ -4970,13 +9587,14 @@ impl VisitMut for StepTransform {
                             ));
                             // In workflow mode, also register the workflow function
                             if self.mode == TransformMode::Workflow {
-                                items_to_insert.push((
-                                    i + 1,
-                                    ModuleItem::Stmt(self.create_workflow_registration(
+                                items_to_insert.extend(
+                                    self.create_workflow_registration(
                                         &fn_name,
                                         fn_decl.function.span,
-                                    )),
-                                ));
+                                    )
+                                    .into_iter()
+                                    .map(|stmt| (i + 1, ModuleItem::Stmt(stmt))),
+                                );
                             }
                         }
                     } else if let Decl::Var(var_decl) = &export_decl.decl {
@@ -4998,12 +9616,11 @@ impl VisitMut for StepTransform {
                                         ));
                                         // In workflow mode, also register the workflow function
                                         if self.mode == TransformMode::Workflow {
-                                            items_to_insert.push((
-                                                i + 1,
-                                                ModuleItem::Stmt(
-                                                    self.create_workflow_registration(&name, span),
-                                                ),
-                                            ));
+                                            items_to_insert.extend(
+                                                self.create_workflow_registration(&name, span)
+                                                    .into_iter()
+                                                    .map(|stmt| (i + 1, ModuleItem::Stmt(stmt))),
+                                            );
                                         }
                                     }
                                 }
@@ -5030,13 +9647,14 @@ impl VisitMut for StepTransform {
                                 ));
                                 // In workflow mode, also register the workflow function
                                 if self.mode == TransformMode::Workflow {
-                                    items_to_insert.push((
-                                        i + 1,
-                                        ModuleItem::Stmt(self.create_workflow_registration(
+                                    items_to_insert.extend(
+                                        self.create_workflow_registration(
                                             &fn_name,
                                             fn_expr.function.span,
-                                        )),
-                                    ));
+                                        )
+                                        .into_iter()
+                                        .map(|stmt| (i + 1, ModuleItem::Stmt(stmt))),
+                                    );
                                 }
                             }
                             // Anonymous default exports will have workflowId added by default_workflow_exports processing
@@ -5055,15 +9673,11 @@ impl VisitMut for StepTransform {
                         ));
                         // In workflow mode, also register the workflow function
                         if self.mode == TransformMode::Workflow {
-                            items_to_insert.push((
-                                i + 1,
-                                ModuleItem::Stmt(
-                                    self.create_workflow_registration(
-                                        &fn_name,
-                                        fn_decl.function.span,
-                                    ),
-                                ),
-                            ));
+                            items_to_insert.extend(
+                                self.create_workflow_registration(&fn_name, fn_decl.function.span)
+                                    .into_iter()
+                                    .map(|stmt| (i + 1, ModuleItem::Stmt(stmt))),
+                            );
                         }
                     }
                 }
@@ -5087,12 +9701,11 @@ impl VisitMut for StepTransform {
                                     ));
                                     // In workflow mode, also register the workflow function
                                     if self.mode == TransformMode::Workflow {
-                                        items_to_insert.push((
-                                            i + 1,
-                                            ModuleItem::Stmt(
-                                                self.create_workflow_registration(&name, span),
-                                            ),
-                                        ));
+                                        items_to_insert.extend(
+                                            self.create_workflow_registration(&name, span)
+                                                .into_iter()
+                                                .map(|stmt| (i + 1, ModuleItem::Stmt(stmt))),
+                                        );
                                     }
                                 }
                             }
@@ -5103,10 +9716,10 @@ impl VisitMut for StepTransform {
             }
         }
 
-        // Insert workflowId assignments in reverse order to maintain indices
-        for (index, item) in items_to_insert.into_iter().rev() {
-            items.insert(index, item);
-        }
+        // Insert workflowId assignments (and registrations) in the order they were
+        // pushed, so that `fn.workflowId = ...` always runs before
+        // `__private_workflows.set(..., fn)` hands the function to the registry
+        splice_items_in_order(items, items_to_insert);
 
         // Clear workflow_exports_to_expand since workflowId is now added inline
         self.workflow_exports_to_expand.clear();
@@ -5169,11 +9782,13 @@ impl VisitMut for StepTransform {
 
                     // In workflow mode, also insert registration after workflowId
                     let export_pos = if self.mode == TransformMode::Workflow {
-                        items.insert(
-                            pos + 2,
-                            ModuleItem::Stmt(self.create_workflow_registration(&const_name, span)),
+                        let registration = self.create_workflow_registration(&const_name, span);
+                        let registration_len = registration.len();
+                        items.splice(
+                            pos + 2..pos + 2,
+                            registration.into_iter().map(ModuleItem::Stmt),
                         );
-                        pos + 3
+                        pos + 2 + registration_len
                     } else {
                         pos + 2
                     };
@@ -5198,27 +9813,27 @@ impl VisitMut for StepTransform {
             for (export_name, replacement_expr) in default_exports {
                 for item in items.iter_mut() {
                     match item {
-                        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export_default)) => {
-                            if export_name == "default" {
-                                *item = ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(
-                                    ExportDefaultExpr {
-                                        span: export_default.span,
-                                        expr: Box::new(replacement_expr.clone()),
-                                    },
-                                ));
-                                break;
-                            }
+                        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export_default))
+                            if export_name == "default" =>
+                        {
+                            *item = ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(
+                                ExportDefaultExpr {
+                                    span: export_default.span,
+                                    expr: Box::new(replacement_expr.clone()),
+                                },
+                            ));
+                            break;
                         }
-                        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export_default)) => {
-                            if export_name == "default" {
-                                *item = ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(
-                                    ExportDefaultExpr {
-                                        span: export_default.span,
-                                        expr: Box::new(replacement_expr.clone()),
-                                    },
-                                ));
-                                break;
-                            }
+                        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export_default))
+                            if export_name == "default" =>
+                        {
+                            *item = ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(
+                                ExportDefaultExpr {
+                                    span: export_default.span,
+                                    expr: Box::new(replacement_expr.clone()),
+                                },
+                            ));
+                            break;
                         }
                         _ => {}
                     }
@@ -5333,7 +9948,7 @@ impl VisitMut for StepTransform {
             }
 
             // Insert items in reverse order to maintain correct indices
-            items_to_insert.sort_by(|a, b| b.0.cmp(&a.0));
+            items_to_insert.sort_by_key(|b| std::cmp::Reverse(b.0));
             for (pos, item) in items_to_insert {
                 items.insert(pos, item);
             }
@@ -5354,7 +9969,14 @@ impl VisitMut for StepTransform {
                                 // This is a step function - convert to var declaration (for named functions)
                                 let step_id =
                                     self.create_id(Some(&fn_name), fn_decl.function.span, false);
-                                let initializer = self.create_step_initializer(&step_id);
+                                let initializer = self.wrap_step_proxy(
+                                    &fn_name,
+                                    self.create_step_initializer(&step_id),
+                                    &[],
+                                    fn_length_of_function(&fn_decl.function),
+                                );
+                                let type_ann =
+                                    self.create_proxy_type_ann_for_function(&fn_decl.function);
                                 // Preserve the original identifier's syntax context to avoid SWC renaming
                                 let orig_ctxt = fn_decl.ident.ctxt;
                                 export_decl.decl = Decl::Var(Box::new(VarDecl {
@@ -5369,7 +9991,7 @@ impl VisitMut for StepTransform {
                                                 fn_decl.ident.span,
                                                 orig_ctxt,
                                             ),
-                                            type_ann: None,
+                                            type_ann,
                                         }),
                                         init: Some(Box::new(initializer)),
                                         definite: false,
@@ -5401,8 +10023,20 @@ impl VisitMut for StepTransform {
                                             // This is an exported step function variable - convert to assignment
                                             let step_id =
                                                 self.create_id(Some(&name), declarator.span, false);
-                                            let initializer =
-                                                self.create_step_initializer(&step_id);
+                                            let arity = declarator
+                                                .init
+                                                .as_deref()
+                                                .map_or(0, fn_length_of_expr);
+                                            let initializer = self.wrap_step_proxy(
+                                                &name,
+                                                self.create_step_initializer(&step_id),
+                                                &[],
+                                                arity,
+                                            );
+                                            let type_ann =
+                                                declarator.init.as_deref().and_then(|init| {
+                                                    self.create_proxy_type_ann_for_expr(init)
+                                                });
                                             // Preserve the original identifier's syntax context to avoid SWC renaming
                                             let orig_ctxt = binding.id.ctxt;
                                             declarator.init = Some(Box::new(initializer));
@@ -5415,6 +10049,10 @@ impl VisitMut for StepTransform {
                                             if let Pat::Ident(ref mut new_binding) = declarator.name
                                             {
                                                 new_binding.id = new_ident;
+                                                // Keep an explicit annotation written by the user
+                                                if new_binding.type_ann.is_none() {
+                                                    new_binding.type_ann = type_ann;
+                                                }
                                             }
                                         }
                                     }
@@ -5431,7 +10069,14 @@ impl VisitMut for StepTransform {
                             // This is a non-exported step function - convert to var declaration (for named functions)
                             let step_id =
                                 self.create_id(Some(&fn_name), fn_decl.function.span, false);
-                            let initializer = self.create_step_initializer(&step_id);
+                            let initializer = self.wrap_step_proxy(
+                                &fn_name,
+                                self.create_step_initializer(&step_id),
+                                &[],
+                                fn_length_of_function(&fn_decl.function),
+                            );
+                            let type_ann =
+                                self.create_proxy_type_ann_for_function(&fn_decl.function);
                             // Preserve the original identifier's syntax context to avoid SWC renaming
                             let orig_ctxt = fn_decl.ident.ctxt;
                             let var_decl = Decl::Var(Box::new(VarDecl {
@@ -5446,7 +10091,7 @@ impl VisitMut for StepTransform {
                                             fn_decl.ident.span,
                                             orig_ctxt,
                                         ),
-                                        type_ann: None,
+                                        type_ann,
                                     }),
                                     init: Some(Box::new(initializer)),
                                     definite: false,
@@ -5481,8 +10126,25 @@ impl VisitMut for StepTransform {
                                         // This is a non-exported step function variable - convert to assignment
                                         let step_id =
                                             self.create_id(Some(&name), declarator.span, false);
-                                        let initializer = self.create_step_initializer(&step_id);
+                                        let arity =
+                                            declarator.init.as_deref().map_or(0, fn_length_of_expr);
+                                        let initializer = self.wrap_step_proxy(
+                                            &name,
+                                            self.create_step_initializer(&step_id),
+                                            &[],
+                                            arity,
+                                        );
+                                        let type_ann =
+                                            declarator.init.as_deref().and_then(|init| {
+                                                self.create_proxy_type_ann_for_expr(init)
+                                            });
                                         declarator.init = Some(Box::new(initializer));
+                                        // Keep an explicit annotation written by the user
+                                        if let Pat::Ident(binding) = &mut declarator.name {
+                                            if binding.type_ann.is_none() {
+                                                binding.type_ann = type_ann;
+                                            }
+                                        }
                                     }
                                 }
                             }
@@ -5513,14 +10175,15 @@ impl VisitMut for StepTransform {
         // Check for step directive first
         if self.has_step_directive(&fn_decl.function, false) {
             // Validate that it's async - emit error if not
-            if !fn_decl.function.is_async {
+            if !is_async_function(&fn_decl.function) {
                 emit_error(WorkflowErrorKind::NonAsyncFunction {
                     span: fn_decl.function.span,
-                    directive: "use step",
+                    directive: self.step_directive.as_str(),
+                    async_at: self.async_fix_at(fn_decl.function.span),
                 });
             } else {
                 // It's valid - proceed with transformation
-                self.step_function_names.insert(fn_name.clone());
+                self.record_step_function(fn_name.clone(), fn_decl.function.span);
 
                 match self.mode {
                     TransformMode::Step => {
@@ -5548,11 +10211,12 @@ impl VisitMut for StepTransform {
             if !fn_decl.function.is_async {
                 emit_error(WorkflowErrorKind::NonAsyncFunction {
                     span: fn_decl.function.span,
-                    directive: "use workflow",
+                    directive: self.workflow_directive.as_str(),
+                    async_at: self.async_fix_at(fn_decl.function.span),
                 });
             } else {
                 // It's valid - proceed with transformation
-                self.workflow_function_names.insert(fn_name.clone());
+                self.record_workflow_function(fn_name.clone(), fn_decl.function.span);
 
                 match self.mode {
                     TransformMode::Step => {
@@ -5615,6 +10279,7 @@ impl VisitMut for StepTransform {
             // Get the function name for context tracking
             if let Decl::Fn(fn_decl) = &export_decl.decl {
                 self.current_workflow_function_name = Some(fn_decl.ident.sym.to_string());
+                self.directive_function_spans.insert(fn_decl.function.span);
             }
         }
 
@@ -5625,14 +10290,15 @@ impl VisitMut for StepTransform {
                 // Check for step directive first
                 if self.has_step_directive(&fn_decl.function, true) {
                     // Validate that it's async - emit error if not
-                    if !fn_decl.function.is_async {
+                    if !is_async_function(&fn_decl.function) {
                         emit_error(WorkflowErrorKind::NonAsyncFunction {
                             span: fn_decl.function.span,
-                            directive: "use step",
+                            directive: self.step_directive.as_str(),
+                            async_at: self.async_fix_at(fn_decl.function.span),
                         });
                     } else {
                         // It's valid - proceed with transformation
-                        self.step_function_names.insert(fn_name.clone());
+                        self.record_step_function(fn_name.clone(), fn_decl.function.span);
 
                         match self.mode {
                             TransformMode::Step => {
@@ -5664,11 +10330,12 @@ impl VisitMut for StepTransform {
                     if !fn_decl.function.is_async {
                         emit_error(WorkflowErrorKind::NonAsyncFunction {
                             span: fn_decl.function.span,
-                            directive: "use workflow",
+                            directive: self.workflow_directive.as_str(),
+                            async_at: self.async_fix_at(fn_decl.function.span),
                         });
                     } else {
                         // It's valid - proceed with transformation
-                        self.workflow_function_names.insert(fn_name.clone());
+                        self.record_workflow_function(fn_name.clone(), fn_decl.function.span);
 
                         match self.mode {
                             TransformMode::Step => {
@@ -5799,8 +10466,12 @@ impl VisitMut for StepTransform {
                                         if self.validate_async_function(
                                             &fn_expr.function,
                                             fn_expr.function.span,
+                                            self.step_directive.as_str(),
                                         ) {
-                                            self.step_function_names.insert(name.clone());
+                                            self.record_step_function(
+                                                name.clone(),
+                                                fn_expr.function.span,
+                                            );
 
                                             match self.mode {
                                                 TransformMode::Step => {
@@ -5832,28 +10503,82 @@ impl VisitMut for StepTransform {
                                                         false,
                                                     );
                                                     // Replace the entire function expression with the initializer
-                                                    *init = Box::new(
-                                                        self.create_step_initializer(&step_id),
+                                                    **init = self.create_step_initializer(&step_id);
+                                                }
+                                            }
+                                        }
+                                    } else if self
+                                        .should_transform_workflow_function(&fn_expr.function, true)
+                                        && self.validate_async_function(
+                                            &fn_expr.function,
+                                            fn_expr.function.span,
+                                            self.workflow_directive.as_str(),
+                                        )
+                                    {
+                                        self.record_workflow_function(
+                                            name.clone(),
+                                            fn_expr.function.span,
+                                        );
+
+                                        match self.mode {
+                                            TransformMode::Step => {
+                                                // In step mode, transform workflow function expression with throw error
+                                                self.remove_use_workflow_directive(
+                                                    &mut fn_expr.function.body,
+                                                );
+
+                                                if let Some(body) = &mut fn_expr.function.body {
+                                                    let error_msg = format!(
+                                                        "You attempted to execute workflow {} function directly. To start a workflow, use start({}) from workflow/api",
+                                                        name, name
                                                     );
+                                                    let error_expr = Expr::New(NewExpr {
+                                                        span: DUMMY_SP,
+                                                        ctxt: SyntaxContext::empty(),
+                                                        callee: Box::new(Expr::Ident(Ident::new(
+                                                            "Error".into(),
+                                                            DUMMY_SP,
+                                                            SyntaxContext::empty(),
+                                                        ))),
+                                                        args: Some(vec![ExprOrSpread {
+                                                            spread: None,
+                                                            expr: Box::new(Expr::Lit(Lit::Str(
+                                                                Str {
+                                                                    span: DUMMY_SP,
+                                                                    value: error_msg.into(),
+                                                                    raw: None,
+                                                                },
+                                                            ))),
+                                                        }]),
+                                                        type_args: None,
+                                                    });
+                                                    body.stmts = vec![Stmt::Throw(ThrowStmt {
+                                                        span: DUMMY_SP,
+                                                        arg: Box::new(error_expr),
+                                                    })];
                                                 }
-                                            }
-                                        }
-                                    } else if self
-                                        .should_transform_workflow_function(&fn_expr.function, true)
-                                    {
-                                        if self.validate_async_function(
-                                            &fn_expr.function,
-                                            fn_expr.function.span,
-                                        ) {
-                                            self.workflow_function_names.insert(name.clone());
 
-                                            match self.mode {
-                                                TransformMode::Step => {
-                                                    // In step mode, transform workflow function expression with throw error
-                                                    self.remove_use_workflow_directive(
-                                                        &mut fn_expr.function.body,
+                                                self.workflow_functions_needing_id
+                                                    .push((name.clone(), fn_expr.function.span));
+                                            }
+                                            TransformMode::Workflow => {
+                                                // Just remove the directive - workflowId is added inline in visit_mut_module_items
+                                                self.remove_use_workflow_directive(
+                                                    &mut fn_expr.function.body,
+                                                );
+                                            }
+                                            TransformMode::Client => {
+                                                // Only replace with throw if function has inline directive
+                                                let has_inline_directive = self
+                                                    .has_use_workflow_directive(
+                                                        &fn_expr.function.body,
                                                     );
 
+                                                self.remove_use_workflow_directive(
+                                                    &mut fn_expr.function.body,
+                                                );
+
+                                                if has_inline_directive {
                                                     if let Some(body) = &mut fn_expr.function.body {
                                                         let error_msg = format!(
                                                             "You attempted to execute workflow {} function directly. To start a workflow, use start({}) from workflow/api",
@@ -5886,72 +10611,10 @@ impl VisitMut for StepTransform {
                                                             arg: Box::new(error_expr),
                                                         })];
                                                     }
-
-                                                    self.workflow_functions_needing_id.push((
-                                                        name.clone(),
-                                                        fn_expr.function.span,
-                                                    ));
-                                                }
-                                                TransformMode::Workflow => {
-                                                    // Just remove the directive - workflowId is added inline in visit_mut_module_items
-                                                    self.remove_use_workflow_directive(
-                                                        &mut fn_expr.function.body,
-                                                    );
                                                 }
-                                                TransformMode::Client => {
-                                                    // Only replace with throw if function has inline directive
-                                                    let has_inline_directive = self
-                                                        .has_use_workflow_directive(
-                                                            &fn_expr.function.body,
-                                                        );
-
-                                                    self.remove_use_workflow_directive(
-                                                        &mut fn_expr.function.body,
-                                                    );
-
-                                                    if has_inline_directive {
-                                                        if let Some(body) =
-                                                            &mut fn_expr.function.body
-                                                        {
-                                                            let error_msg = format!(
-                                                                "You attempted to execute workflow {} function directly. To start a workflow, use start({}) from workflow/api",
-                                                                name, name
-                                                            );
-                                                            let error_expr = Expr::New(NewExpr {
-                                                                span: DUMMY_SP,
-                                                                ctxt: SyntaxContext::empty(),
-                                                                callee: Box::new(Expr::Ident(
-                                                                    Ident::new(
-                                                                        "Error".into(),
-                                                                        DUMMY_SP,
-                                                                        SyntaxContext::empty(),
-                                                                    ),
-                                                                )),
-                                                                args: Some(vec![ExprOrSpread {
-                                                                    spread: None,
-                                                                    expr: Box::new(Expr::Lit(
-                                                                        Lit::Str(Str {
-                                                                            span: DUMMY_SP,
-                                                                            value: error_msg.into(),
-                                                                            raw: None,
-                                                                        }),
-                                                                    )),
-                                                                }]),
-                                                                type_args: None,
-                                                            });
-                                                            body.stmts =
-                                                                vec![Stmt::Throw(ThrowStmt {
-                                                                    span: DUMMY_SP,
-                                                                    arg: Box::new(error_expr),
-                                                                })];
-                                                        }
-                                                    }
 
-                                                    self.workflow_functions_needing_id.push((
-                                                        name.clone(),
-                                                        fn_expr.function.span,
-                                                    ));
-                                                }
+                                                self.workflow_functions_needing_id
+                                                    .push((name.clone(), fn_expr.function.span));
                                             }
                                         }
                                     }
@@ -5963,11 +10626,15 @@ impl VisitMut for StepTransform {
                                         if !arrow_expr.is_async {
                                             emit_error(WorkflowErrorKind::NonAsyncFunction {
                                                 span: arrow_expr.span,
-                                                directive: "use step",
+                                                directive: self.step_directive.as_str(),
+                                                async_at: self.async_fix_at(arrow_expr.span),
                                             });
                                         } else {
                                             // It's valid - proceed with transformation
-                                            self.step_function_names.insert(name.clone());
+                                            self.record_step_function(
+                                                name.clone(),
+                                                arrow_expr.span,
+                                            );
 
                                             match self.mode {
                                                 TransformMode::Step => {
@@ -5997,9 +10664,7 @@ impl VisitMut for StepTransform {
                                                         false,
                                                     );
                                                     // Replace the entire arrow function with the initializer
-                                                    *init = Box::new(
-                                                        self.create_step_initializer(&step_id),
-                                                    );
+                                                    **init = self.create_step_initializer(&step_id);
                                                 }
                                             }
                                         }
@@ -6008,11 +10673,15 @@ impl VisitMut for StepTransform {
                                         if !arrow_expr.is_async {
                                             emit_error(WorkflowErrorKind::NonAsyncFunction {
                                                 span: arrow_expr.span,
-                                                directive: "use workflow",
+                                                directive: self.workflow_directive.as_str(),
+                                                async_at: self.async_fix_at(arrow_expr.span),
                                             });
                                         } else {
                                             // It's valid - proceed with transformation
-                                            self.workflow_function_names.insert(name.clone());
+                                            self.record_workflow_function(
+                                                name.clone(),
+                                                arrow_expr.span,
+                                            );
 
                                             match self.mode {
                                                 TransformMode::Step => {
@@ -6045,7 +10714,7 @@ impl VisitMut for StepTransform {
                                                         }]),
                                                         type_args: None,
                                                     });
-                                                    arrow_expr.body = Box::new(
+                                                    *arrow_expr.body =
                                                         BlockStmtOrExpr::BlockStmt(BlockStmt {
                                                             span: DUMMY_SP,
                                                             ctxt: SyntaxContext::empty(),
@@ -6053,8 +10722,7 @@ impl VisitMut for StepTransform {
                                                                 span: DUMMY_SP,
                                                                 arg: Box::new(error_expr),
                                                             })],
-                                                        }),
-                                                    );
+                                                        });
 
                                                     self.workflow_functions_needing_id
                                                         .push((name.clone(), arrow_expr.span));
@@ -6103,7 +10771,7 @@ impl VisitMut for StepTransform {
                                                             }]),
                                                             type_args: None,
                                                         });
-                                                        arrow_expr.body = Box::new(
+                                                        *arrow_expr.body =
                                                             BlockStmtOrExpr::BlockStmt(BlockStmt {
                                                                 span: DUMMY_SP,
                                                                 ctxt: SyntaxContext::empty(),
@@ -6113,8 +10781,7 @@ impl VisitMut for StepTransform {
                                                                         arg: Box::new(error_expr),
                                                                     },
                                                                 )],
-                                                            }),
-                                                        );
+                                                            });
                                                     }
 
                                                     self.workflow_functions_needing_id
@@ -6155,26 +10822,23 @@ impl VisitMut for StepTransform {
         // Remove workflow directive after processing children (for Decl::Var cases)
         // Decl::Fn is handled case-by-case above based on mode and nested steps
         if is_workflow_function {
-            match &mut export_decl.decl {
-                Decl::Var(var_decl) => {
-                    // Handle arrow functions and function expressions in var declarations
-                    for declarator in var_decl.decls.iter_mut() {
-                        if let Some(init) = &mut declarator.init {
-                            match &mut **init {
-                                Expr::Arrow(arrow_expr) => {
-                                    // For arrow functions, always remove directive (they can't have nested steps in the same way)
-                                    self.remove_use_workflow_directive_arrow(&mut arrow_expr.body);
-                                }
-                                Expr::Fn(fn_expr) => {
-                                    // For function expressions, always remove directive
-                                    self.remove_use_workflow_directive(&mut fn_expr.function.body);
-                                }
-                                _ => {}
+            if let Decl::Var(var_decl) = &mut export_decl.decl {
+                // Handle arrow functions and function expressions in var declarations
+                for declarator in var_decl.decls.iter_mut() {
+                    if let Some(init) = &mut declarator.init {
+                        match &mut **init {
+                            Expr::Arrow(arrow_expr) => {
+                                // For arrow functions, always remove directive (they can't have nested steps in the same way)
+                                self.remove_use_workflow_directive_arrow(&mut arrow_expr.body);
+                            }
+                            Expr::Fn(fn_expr) => {
+                                // For function expressions, always remove directive
+                                self.remove_use_workflow_directive(&mut fn_expr.function.body);
                             }
+                            _ => {}
                         }
                     }
                 }
-                _ => {}
             }
         }
 
@@ -6187,6 +10851,12 @@ impl VisitMut for StepTransform {
         // Handle variable declarations with function expressions
         for decl in var_decl.decls.iter_mut() {
             if let Some(init) = &mut decl.init {
+                // `const { run } = { run: async () => { "use step"; } }` - the object literal
+                // has no name of its own, so its steps are named by their property path alone
+                if let (Pat::Object(_), Expr::Object(obj_lit)) = (&decl.name, &mut **init) {
+                    self.process_object_properties_for_step_functions(obj_lit, "");
+                    continue;
+                }
                 if let Pat::Ident(binding) = &decl.name {
                     let name = binding.id.sym.to_string();
 
@@ -6202,11 +10872,12 @@ impl VisitMut for StepTransform {
                                 if !fn_expr.function.is_async {
                                     emit_error(WorkflowErrorKind::NonAsyncFunction {
                                         span: fn_expr.function.span,
-                                        directive: "use step",
+                                        directive: self.step_directive.as_str(),
+                                        async_at: self.async_fix_at(fn_expr.function.span),
                                     });
                                 } else {
                                     // It's valid - proceed with transformation
-                                    self.step_function_names.insert(name.clone());
+                                    self.record_step_function(name.clone(), fn_expr.function.span);
 
                                     match self.mode {
                                         TransformMode::Step => {
@@ -6239,8 +10910,10 @@ impl VisitMut for StepTransform {
                                                     fn_expr.function.span,
                                                     false,
                                                 );
-                                                let mut proxy_call =
-                                                    self.create_step_proxy(&step_id);
+                                                let mut proxy_call = self.create_step_proxy(
+                                                    &step_id,
+                                                    fn_expr.function.span,
+                                                );
                                                 // Add function arguments to the proxy call
                                                 if let Expr::Call(call) = &mut proxy_call {
                                                     call.args = fn_expr
@@ -6277,11 +10950,15 @@ impl VisitMut for StepTransform {
                                 if !fn_expr.function.is_async {
                                     emit_error(WorkflowErrorKind::NonAsyncFunction {
                                         span: fn_expr.function.span,
-                                        directive: "use workflow",
+                                        directive: self.workflow_directive.as_str(),
+                                        async_at: self.async_fix_at(fn_expr.function.span),
                                     });
                                 } else {
                                     // It's valid - proceed with transformation
-                                    self.workflow_function_names.insert(name.clone());
+                                    self.record_workflow_function(
+                                        name.clone(),
+                                        fn_expr.function.span,
+                                    );
 
                                     match self.mode {
                                         TransformMode::Step => {
@@ -6387,11 +11064,12 @@ impl VisitMut for StepTransform {
                                 if !arrow_expr.is_async {
                                     emit_error(WorkflowErrorKind::NonAsyncFunction {
                                         span: arrow_expr.span,
-                                        directive: "use step",
+                                        directive: self.step_directive.as_str(),
+                                        async_at: self.async_fix_at(arrow_expr.span),
                                     });
                                 } else {
                                     // It's valid - proceed with transformation
-                                    self.step_function_names.insert(name.clone());
+                                    self.record_step_function(name.clone(), arrow_expr.span);
 
                                     // Check if we're inside any function (nested), not just workflow functions
                                     if !self.in_module_level {
@@ -6404,7 +11082,7 @@ impl VisitMut for StepTransform {
                                                 );
 
                                                 // Collect closure variables before conversion
-                                                let closure_vars = ClosureVariableCollector::collect_from_arrow_expr(&cloned_arrow, &self.module_imports);
+                                                let closure_vars = ClosureVariableCollector::collect_from_arrow_expr(&cloned_arrow, &self.module_imports, &self.step_directive, self.options.diagnostics);
 
                                                 // Create a function expression from the arrow function
                                                 // (We need to convert it to a regular function for hoisting)
@@ -6471,18 +11149,18 @@ impl VisitMut for StepTransform {
                                                     &self.current_parent_function_name
                                                 {
                                                     if !parent.is_empty() {
-                                                        format!("{}${}", parent, name)
+                                                        self.hoisted_step_name(parent, &name)
                                                     } else {
                                                         name
                                                     }
                                                 } else {
                                                     name
                                                 };
-                                                *init = Box::new(Expr::Ident(Ident::new(
+                                                **init = Expr::Ident(Ident::new(
                                                     hoisted_name.into(),
                                                     DUMMY_SP,
                                                     SyntaxContext::empty(),
-                                                )));
+                                                ));
                                             }
                                             TransformMode::Workflow => {
                                                 // Replace with proxy reference (not a function call)
@@ -6490,7 +11168,7 @@ impl VisitMut for StepTransform {
                                                 let step_fn_name = if let Some(parent) =
                                                     &self.current_workflow_function_name
                                                 {
-                                                    format!("{}/{}", parent, name)
+                                                    self.nested_step_id_name(parent, &name)
                                                 } else {
                                                     name.clone()
                                                 };
@@ -6501,11 +11179,27 @@ impl VisitMut for StepTransform {
                                                 );
 
                                                 // Collect closure variables
-                                                let closure_vars = ClosureVariableCollector::collect_from_arrow_expr(&arrow_expr, &self.module_imports);
-                                                *init = Box::new(self.create_step_proxy_reference(
-                                                    &step_id,
+                                                let closure_vars = ClosureVariableCollector::collect_from_arrow_expr(arrow_expr, &self.module_imports, &self.step_directive, self.options.diagnostics);
+                                                let arrow_span = arrow_expr.span;
+                                                let arity = fn_length(&arrow_expr.params);
+                                                let type_ann =
+                                                    self.create_proxy_type_ann_for_expr(init);
+                                                **init = self.wrap_step_proxy(
+                                                    &name,
+                                                    self.create_step_proxy_reference(
+                                                        &step_id,
+                                                        &closure_vars,
+                                                        arrow_span,
+                                                    ),
                                                     &closure_vars,
-                                                ));
+                                                    arity,
+                                                );
+                                                // Keep an explicit annotation written by the user
+                                                if let Pat::Ident(binding) = &mut decl.name {
+                                                    if binding.type_ann.is_none() {
+                                                        binding.type_ann = type_ann;
+                                                    }
+                                                }
                                             }
                                             TransformMode::Client => {
                                                 // In client mode for nested step functions, just remove directive
@@ -6545,8 +11239,8 @@ impl VisitMut for StepTransform {
                                                     arrow_expr.span,
                                                     false,
                                                 );
-                                                let mut proxy_call =
-                                                    self.create_step_proxy(&step_id);
+                                                let mut proxy_call = self
+                                                    .create_step_proxy(&step_id, arrow_expr.span);
                                                 // Add function arguments to the proxy call
                                                 if let Expr::Call(call) = &mut proxy_call {
                                                     call.args = arrow_expr
@@ -6569,9 +11263,8 @@ impl VisitMut for StepTransform {
                                                         })
                                                         .collect();
                                                 }
-                                                arrow_expr.body = Box::new(BlockStmtOrExpr::Expr(
-                                                    Box::new(proxy_call),
-                                                ));
+                                                *arrow_expr.body =
+                                                    BlockStmtOrExpr::Expr(Box::new(proxy_call));
                                             }
                                         }
                                     }
@@ -6581,11 +11274,12 @@ impl VisitMut for StepTransform {
                                 if !arrow_expr.is_async {
                                     emit_error(WorkflowErrorKind::NonAsyncFunction {
                                         span: arrow_expr.span,
-                                        directive: "use workflow",
+                                        directive: self.workflow_directive.as_str(),
+                                        async_at: self.async_fix_at(arrow_expr.span),
                                     });
                                 } else {
                                     // It's valid - proceed with transformation
-                                    self.workflow_function_names.insert(name.clone());
+                                    self.record_workflow_function(name.clone(), arrow_expr.span);
 
                                     match self.mode {
                                         TransformMode::Step => {
@@ -6615,15 +11309,15 @@ impl VisitMut for StepTransform {
                                                 }]),
                                                 type_args: None,
                                             });
-                                            arrow_expr.body =
-                                                Box::new(BlockStmtOrExpr::BlockStmt(BlockStmt {
+                                            *arrow_expr.body =
+                                                BlockStmtOrExpr::BlockStmt(BlockStmt {
                                                     span: DUMMY_SP,
                                                     ctxt: SyntaxContext::empty(),
                                                     stmts: vec![Stmt::Throw(ThrowStmt {
                                                         span: DUMMY_SP,
                                                         arg: Box::new(error_expr),
                                                     })],
-                                                }));
+                                                });
                                             self.workflow_functions_needing_id
                                                 .push((name.clone(), arrow_expr.span));
                                         }
@@ -6661,15 +11355,15 @@ impl VisitMut for StepTransform {
                                                 }]),
                                                 type_args: None,
                                             });
-                                            arrow_expr.body =
-                                                Box::new(BlockStmtOrExpr::BlockStmt(BlockStmt {
+                                            *arrow_expr.body =
+                                                BlockStmtOrExpr::BlockStmt(BlockStmt {
                                                     span: DUMMY_SP,
                                                     ctxt: SyntaxContext::empty(),
                                                     stmts: vec![Stmt::Throw(ThrowStmt {
                                                         span: DUMMY_SP,
                                                         arg: Box::new(error_expr),
                                                     })],
-                                                }));
+                                                });
                                             self.workflow_functions_needing_id
                                                 .push((name.clone(), arrow_expr.span));
                                         }
@@ -6734,31 +11428,40 @@ impl VisitMut for StepTransform {
 
     // Handle object properties with function values
     fn visit_mut_prop_or_spread(&mut self, prop: &mut PropOrSpread) {
-        match prop {
-            PropOrSpread::Prop(boxed_prop) => {
-                match &mut **boxed_prop {
-                    Prop::Method(method_prop) => {
-                        // Handle object methods
-                        let has_step = self.has_use_step_directive(&method_prop.function.body);
-                        let has_workflow =
-                            self.has_use_workflow_directive(&method_prop.function.body);
+        if let PropOrSpread::Prop(boxed_prop) = prop {
+            // Accessors are left in place, so they can't carry a directive. Functions marked
+            // inside them are reported and left untouched as well.
+            let accessor = match &**boxed_prop {
+                Prop::Getter(getter) => Some((getter.span, &getter.body)),
+                Prop::Setter(setter) => Some((setter.span, &setter.body)),
+                _ => None,
+            };
+            if let Some((span, body)) = accessor {
+                if let Some(body) = body {
+                    self.check_accessor_directives(span, &body.stmts);
+                }
+                return;
+            }
 
-                        if has_step && !method_prop.function.is_async {
-                            emit_error(WorkflowErrorKind::NonAsyncFunction {
-                                span: method_prop.function.span,
-                                directive: "use step",
-                            });
-                        } else if has_workflow && !method_prop.function.is_async {
-                            emit_error(WorkflowErrorKind::NonAsyncFunction {
-                                span: method_prop.function.span,
-                                directive: "use workflow",
-                            });
-                        }
-                    }
-                    _ => {}
+            if let Prop::Method(method_prop) = &mut **boxed_prop {
+                // Handle object methods
+                let has_step = self.has_use_step_directive(&method_prop.function.body);
+                let has_workflow = self.has_use_workflow_directive(&method_prop.function.body);
+
+                if has_step && !method_prop.function.is_async {
+                    emit_error(WorkflowErrorKind::NonAsyncFunction {
+                        span: method_prop.function.span,
+                        directive: self.step_directive.as_str(),
+                        async_at: self.method_async_fix_at(&method_prop.key, &method_prop.function),
+                    });
+                } else if has_workflow && !method_prop.function.is_async {
+                    emit_error(WorkflowErrorKind::NonAsyncFunction {
+                        span: method_prop.function.span,
+                        directive: self.workflow_directive.as_str(),
+                        async_at: self.method_async_fix_at(&method_prop.key, &method_prop.function),
+                    });
                 }
             }
-            _ => {}
         }
 
         prop.visit_mut_children_with(self);
@@ -6827,28 +11530,25 @@ impl VisitMut for StepTransform {
         // Get the binding name set by visit_mut_var_decl (e.g., "Foo" from `var Foo = class { ... }`)
         let binding_name = self.current_class_binding_name.take();
 
-        // Get the internal class name (used for current_class_name tracking)
-        let mut internal_class_name = class_expr
-            .ident
-            .as_ref()
-            .map(|i| i.sym.to_string())
-            .unwrap_or_else(|| "AnonymousClass".to_string());
-
-        // For serialization registration, use the binding name if available
-        // e.g., for `var Bash = class _Bash {}`, use "Bash" not "_Bash"
-        // because "_Bash" is not accessible at module scope
-        let registration_name = binding_name
-            .clone()
-            .unwrap_or_else(|| internal_class_name.clone());
+        // Registrations, IDs and the method-stripping filters below use the binding name if
+        // available: for `var Bash = class _Bash {}` they are emitted at module scope, where
+        // "_Bash" is not accessible, and an anonymous class has no name of its own
+        let class_name = binding_name.clone().unwrap_or_else(|| {
+            class_expr
+                .ident
+                .as_ref()
+                .map(|i| i.sym.to_string())
+                .unwrap_or_else(|| "AnonymousClass".to_string())
+        });
 
         let old_class_name = self.current_class_name.take();
-        self.current_class_name = Some(internal_class_name.clone());
+        self.current_class_name = Some(class_name.clone());
 
         // Check if class has custom serialization methods (WORKFLOW_SERIALIZE/WORKFLOW_DESERIALIZE)
         let has_serde = self.has_custom_serialization_methods(&class_expr.class);
         if has_serde {
             self.classes_needing_serialization
-                .insert(registration_name.clone());
+                .insert(class_name.clone());
         }
 
         // esbuild emits anonymous class expressions for classes that don't
@@ -6866,12 +11566,6 @@ impl VisitMut for StepTransform {
                     DUMMY_SP,
                     SyntaxContext::empty(),
                 ));
-                // Recompute internal_class_name and update current_class_name so
-                // that subsequent logic (e.g. step/workflow method naming and
-                // method-stripping filters) uses the actual class name rather
-                // than "AnonymousClass".
-                internal_class_name = name.clone();
-                self.current_class_name = Some(name.clone());
             }
         }
 
@@ -6883,14 +11577,14 @@ impl VisitMut for StepTransform {
             let static_methods_to_strip: Vec<_> = self
                 .static_step_methods_to_strip
                 .iter()
-                .filter(|(cn, _, _)| cn == &internal_class_name)
+                .filter(|(cn, _, _)| cn == &class_name)
                 .map(|(_, mn, _)| mn.clone())
                 .collect();
 
             let instance_methods_to_strip: Vec<_> = self
                 .instance_step_methods_to_strip
                 .iter()
-                .filter(|(cn, _, _)| cn == &internal_class_name)
+                .filter(|(cn, _, _)| cn == &class_name)
                 .map(|(_, mn, _)| mn.clone())
                 .collect();
 
@@ -6915,8 +11609,137 @@ impl VisitMut for StepTransform {
         self.current_class_name = old_class_name;
     }
 
+    // Class fields holding a step, e.g. `run = async () => { "use step"; }`. Instance fields are
+    // named like instance method steps (`Class#run`) and may use `this` the same way, static
+    // ones like static method steps (`Class.run`). Their initializer becomes the step proxy in
+    // workflow mode, so each instance gets its own.
+    fn visit_mut_class_prop(&mut self, prop: &mut ClassProp) {
+        let (has_step, is_async, span) = match prop.value.as_deref() {
+            Some(Expr::Arrow(arrow)) => (
+                self.has_use_step_directive_arrow(&arrow.body),
+                arrow.is_async,
+                arrow.span,
+            ),
+            Some(Expr::Fn(fn_expr)) => (
+                self.has_use_step_directive(&fn_expr.function.body),
+                fn_expr.function.is_async,
+                fn_expr.function.span,
+            ),
+            _ => (false, false, DUMMY_SP),
+        };
+        if !has_step {
+            prop.visit_mut_children_with(self);
+            return;
+        }
+        if !is_async {
+            emit_error(WorkflowErrorKind::NonAsyncFunction {
+                span,
+                directive: self.step_directive.as_str(),
+                async_at: self.async_fix_at(span),
+            });
+            return;
+        }
+
+        let field_name = match &prop.key {
+            PropName::Ident(ident) => ident.sym.to_string(),
+            PropName::Str(s) => s.value.to_string_lossy().to_string(),
+            _ => {
+                // Complex key - skip
+                prop.visit_mut_children_with(self);
+                return;
+            }
+        };
+        let Some(class_name) = self.current_class_name.clone() else {
+            prop.visit_mut_children_with(self);
+            return;
+        };
+
+        let full_name = if prop.is_static {
+            format!("{}.{}", class_name, field_name)
+        } else {
+            format!("{}#{}", class_name, field_name)
+        };
+        self.record_step_function(full_name.clone(), span);
+        // Track class for serialization (needed for `this` serialization)
+        self.classes_needing_serialization
+            .insert(class_name.clone());
+        let step_id = self.create_id(Some(&full_name), span, false);
+
+        let Some(value) = prop.value.as_deref_mut() else {
+            return;
+        };
+        match value {
+            Expr::Arrow(arrow) => self.remove_use_step_directive_arrow(&mut arrow.body),
+            Expr::Fn(fn_expr) => self.remove_use_step_directive(&mut fn_expr.function.body),
+            _ => {}
+        }
+
+        match self.mode {
+            TransformMode::Workflow => {
+                *value = self.create_step_initializer(&step_id);
+            }
+            TransformMode::Step | TransformMode::Client => {
+                if self.mode == TransformMode::Step {
+                    if prop.is_static {
+                        self.static_method_step_registrations.push((
+                            class_name.clone(),
+                            field_name.clone(),
+                            step_id,
+                            span,
+                        ));
+                    } else {
+                        self.instance_field_step_registrations
+                            .push((field_name.clone(), step_id));
+                    }
+                }
+
+                // Use $ instead of # to produce valid JS identifiers for nested steps
+                let old_parent = self.current_parent_function_name.clone();
+                self.current_parent_function_name = Some(format!("{}${}", class_name, field_name));
+                let old_in_method_step = self.in_method_step;
+                self.in_method_step = true;
+
+                prop.visit_mut_children_with(self);
+
+                self.current_parent_function_name = old_parent;
+                self.in_method_step = old_in_method_step;
+            }
+        }
+    }
+
+    // Like private methods, `#private` fields can't be registered from outside the class body
+    fn visit_mut_private_prop(&mut self, prop: &mut PrivateProp) {
+        let stmts = match prop.value.as_deref() {
+            Some(Expr::Arrow(arrow)) => match &*arrow.body {
+                BlockStmtOrExpr::BlockStmt(body) => Some(&body.stmts),
+                BlockStmtOrExpr::Expr(_) => None,
+            },
+            Some(Expr::Fn(fn_expr)) => fn_expr.function.body.as_ref().map(|body| &body.stmts),
+            _ => None,
+        };
+        if let Some(stmts) = stmts {
+            for directive in [&self.step_directive, &self.workflow_directive] {
+                if body_has_directive(stmts, directive) {
+                    emit_error(WorkflowErrorKind::PrivateMethod {
+                        span: prop.span,
+                        directive,
+                    });
+                }
+            }
+        }
+        prop.visit_mut_children_with(self);
+    }
+
     // Handle class methods
     fn visit_mut_class_method(&mut self, method: &mut ClassMethod) {
+        // Like object accessors, getters and setters are reported and left untouched
+        if method.kind != MethodKind::Method {
+            if let Some(body) = &method.function.body {
+                self.check_accessor_directives(method.span, &body.stmts);
+            }
+            return;
+        }
+
         if !method.is_static {
             // Instance methods can have "use step" (but not "use workflow")
             let has_step = self.has_use_step_directive(&method.function.body);
@@ -6938,7 +11761,8 @@ impl VisitMut for StepTransform {
                 if !method.function.is_async {
                     emit_error(WorkflowErrorKind::NonAsyncFunction {
                         span: method.function.span,
-                        directive: "use step",
+                        directive: self.step_directive.as_str(),
+                        async_at: self.method_async_fix_at(&method.key, &method.function),
                     });
                     return;
                 }
@@ -6970,7 +11794,7 @@ impl VisitMut for StepTransform {
                 // For nested step hoisting, use $ instead of # to produce valid JS identifiers
                 let hoisted_parent_name = format!("{}${}", class_name, method_name);
 
-                self.step_function_names.insert(full_name.clone());
+                self.record_step_function(full_name.clone(), method.function.span);
 
                 // Track class for serialization (needed for `this` serialization)
                 self.classes_needing_serialization
@@ -6997,12 +11821,15 @@ impl VisitMut for StepTransform {
                         // Use $ instead of # to produce valid JS identifiers
                         let old_parent = self.current_parent_function_name.clone();
                         self.current_parent_function_name = Some(hoisted_parent_name.clone());
+                        let old_in_method_step = self.in_method_step;
+                        self.in_method_step = true;
 
                         // Visit children to process nested step functions
                         method.visit_mut_children_with(self);
 
                         // Restore parent function name
                         self.current_parent_function_name = old_parent;
+                        self.in_method_step = old_in_method_step;
                     }
                     TransformMode::Workflow => {
                         // Remove directive for consistency with other modes
@@ -7025,12 +11852,15 @@ impl VisitMut for StepTransform {
                         // Use $ instead of # to produce valid JS identifiers
                         let old_parent = self.current_parent_function_name.clone();
                         self.current_parent_function_name = Some(hoisted_parent_name.clone());
+                        let old_in_method_step = self.in_method_step;
+                        self.in_method_step = true;
 
                         // Visit children to process nested step functions
                         method.visit_mut_children_with(self);
 
                         // Restore parent function name
                         self.current_parent_function_name = old_parent;
+                        self.in_method_step = old_in_method_step;
                     }
                 }
             } else {
@@ -7044,10 +11874,15 @@ impl VisitMut for StepTransform {
             if has_step || has_workflow {
                 // Validate async
                 if !method.function.is_async {
-                    let directive = if has_step { "use step" } else { "use workflow" };
+                    let directive = if has_step {
+                        self.step_directive.as_str()
+                    } else {
+                        self.workflow_directive.as_str()
+                    };
                     emit_error(WorkflowErrorKind::NonAsyncFunction {
                         span: method.function.span,
                         directive,
+                        async_at: self.method_async_fix_at(&method.key, &method.function),
                     });
                     return;
                 }
@@ -7077,7 +11912,7 @@ impl VisitMut for StepTransform {
                 let full_name = format!("{}.{}", class_name, method_name);
 
                 if has_step {
-                    self.step_function_names.insert(full_name.clone());
+                    self.record_step_function(full_name.clone(), method.function.span);
 
                     // Track class for serialization (needed for `this` serialization in static method calls)
                     self.classes_needing_serialization
@@ -7129,7 +11964,7 @@ impl VisitMut for StepTransform {
                         }
                     }
                 } else if has_workflow {
-                    self.workflow_function_names.insert(full_name.clone());
+                    self.record_workflow_function(full_name.clone(), method.function.span);
 
                     match self.mode {
                         TransformMode::Workflow => {
@@ -7206,6 +12041,28 @@ impl VisitMut for StepTransform {
         }
     }
 
+    // Handle `#private` methods, which can't be steps or workflows
+    fn visit_mut_private_method(&mut self, method: &mut PrivateMethod) {
+        // Registrations and workflow IDs are attached through the class (e.g.
+        // `Class.prototype.method`), which can't name a `#private` method. Private getters and
+        // setters get the accessor error, like public ones.
+        if let Some(body) = &method.function.body {
+            if method.kind != MethodKind::Method {
+                self.check_accessor_directives(method.span, &body.stmts);
+                return;
+            }
+            for directive in [&self.step_directive, &self.workflow_directive] {
+                if body_has_directive(&body.stmts, directive) {
+                    emit_error(WorkflowErrorKind::PrivateMethod {
+                        span: method.span,
+                        directive,
+                    });
+                }
+            }
+        }
+        method.visit_mut_children_with(self);
+    }
+
     // Handle assignment expressions
     fn visit_mut_assign_expr(&mut self, assign: &mut AssignExpr) {
         // Track function names from assignments like `foo = async () => {}`
@@ -7214,6 +12071,21 @@ impl VisitMut for StepTransform {
 
     // Override visit_mut_expr to track closure variables and handle step functions
     fn visit_mut_expr(&mut self, expr: &mut Expr) {
+        if self.in_workflow_function && !self.in_step_function {
+            if let Some(level) = self.options.non_deterministic_calls {
+                if let Some(call) = non_deterministic_call(expr) {
+                    emit_diagnostic(
+                        WorkflowErrorKind::NonDeterministicCall {
+                            span: expr.span(),
+                            call,
+                            directive: self.workflow_directive.as_str(),
+                        },
+                        level,
+                    );
+                }
+            }
+        }
+
         // Track closure variables first
         if !self.in_module_level && self.should_track_names {
             if let Ok(name) = Name::try_from(&*expr) {
@@ -7228,236 +12100,245 @@ impl VisitMut for StepTransform {
         // Handle step functions that appear in expressions (e.g., return statements)
         // but are not in var declarators (those are handled in visit_mut_var_decl)
         match expr {
-            Expr::Fn(fn_expr) => {
-                if self.has_step_directive(&fn_expr.function, false) {
-                    if !fn_expr.function.is_async {
-                        emit_error(WorkflowErrorKind::NonAsyncFunction {
-                            span: fn_expr.function.span,
-                            directive: "use step",
+            Expr::Fn(fn_expr) if self.has_step_directive(&fn_expr.function, false) => {
+                if !fn_expr.function.is_async {
+                    emit_error(WorkflowErrorKind::NonAsyncFunction {
+                        span: fn_expr.function.span,
+                        directive: self.step_directive.as_str(),
+                        async_at: self.async_fix_at(fn_expr.function.span),
+                    });
+                } else if !self.in_module_level {
+                    // Nested step function in an expression (e.g., return statement)
+                    let name = fn_expr
+                        .ident
+                        .as_ref()
+                        .map(|i| i.sym.to_string())
+                        .unwrap_or_else(|| {
+                            // Generate a name for anonymous functions
+                            let name = format!("_anonymousStep{}", self.anonymous_fn_counter);
+                            self.anonymous_fn_counter += 1;
+                            name
                         });
-                    } else if !self.in_module_level {
-                        // Nested step function in an expression (e.g., return statement)
-                        let name = fn_expr
-                            .ident
-                            .as_ref()
-                            .map(|i| i.sym.to_string())
-                            .unwrap_or_else(|| {
-                                // Generate a name for anonymous functions
-                                let name = format!("_anonymousStep{}", self.anonymous_fn_counter);
-                                self.anonymous_fn_counter += 1;
-                                name
-                            });
 
-                        if fn_expr.ident.is_some() {
-                            // Only increment if we didn't use it above
-                            // (the closure above already incremented)
-                        }
-
-                        self.step_function_names.insert(name.clone());
-
-                        match self.mode {
-                            TransformMode::Step => {
-                                // Hoist to module scope
-                                let mut cloned_function = fn_expr.function.clone();
-                                self.remove_use_step_directive(&mut cloned_function.body);
-
-                                let closure_vars = ClosureVariableCollector::collect_from_function(
-                                    &cloned_function,
-                                    &self.module_imports,
-                                );
-
-                                let hoisted_fn_expr = FnExpr {
-                                    ident: Some(Ident::new(
-                                        name.clone().into(),
-                                        DUMMY_SP,
-                                        SyntaxContext::empty(),
-                                    )),
-                                    function: cloned_function,
-                                };
+                    if fn_expr.ident.is_some() {
+                        // Only increment if we didn't use it above
+                        // (the closure above already incremented)
+                    }
 
-                                self.nested_step_functions.push((
-                                    name.clone(),
-                                    hoisted_fn_expr,
-                                    fn_expr.function.span,
-                                    closure_vars,
-                                    false, // Not an arrow function
-                                    self.current_parent_function_name
-                                        .clone()
-                                        .unwrap_or_default(),
-                                ));
+                    self.record_step_function(name.clone(), fn_expr.function.span);
 
-                                // Replace with identifier reference
-                                let hoisted_name =
-                                    if let Some(parent) = &self.current_parent_function_name {
-                                        if !parent.is_empty() {
-                                            format!("{}${}", parent, name)
-                                        } else {
-                                            name
-                                        }
-                                    } else {
-                                        name
-                                    };
-                                *expr = Expr::Ident(Ident::new(
-                                    hoisted_name.into(),
+                    match self.mode {
+                        TransformMode::Step => {
+                            // Hoist to module scope
+                            let mut cloned_function = fn_expr.function.clone();
+                            self.remove_use_step_directive(&mut cloned_function.body);
+
+                            let closure_vars = ClosureVariableCollector::collect_from_function(
+                                &cloned_function,
+                                &self.module_imports,
+                                &self.step_directive,
+                                self.options.diagnostics,
+                            );
+
+                            let hoisted_fn_expr = FnExpr {
+                                ident: Some(Ident::new(
+                                    name.clone().into(),
                                     DUMMY_SP,
                                     SyntaxContext::empty(),
-                                ));
-                                return; // Don't visit children since we replaced the expr
-                            }
-                            TransformMode::Workflow => {
-                                // Replace with proxy reference
-                                // Use current_parent_function_name to match step mode's ID generation
-                                let step_fn_name =
-                                    if let Some(parent) = &self.current_parent_function_name {
-                                        if !parent.is_empty() {
-                                            format!("{}/{}", parent, name)
-                                        } else {
-                                            name.clone()
-                                        }
-                                    } else {
-                                        name.clone()
-                                    };
-                                let step_id = self.create_id(
-                                    Some(&step_fn_name),
-                                    fn_expr.function.span,
-                                    false,
-                                );
+                                )),
+                                function: cloned_function,
+                            };
+
+                            self.nested_step_functions.push((
+                                name.clone(),
+                                hoisted_fn_expr,
+                                fn_expr.function.span,
+                                closure_vars,
+                                false, // Not an arrow function
+                                self.current_parent_function_name
+                                    .clone()
+                                    .unwrap_or_default(),
+                            ));
 
-                                let closure_vars = ClosureVariableCollector::collect_from_function(
-                                    &fn_expr.function,
-                                    &self.module_imports,
-                                );
-                                *expr = self.create_step_proxy_reference(&step_id, &closure_vars);
-                                return; // Don't visit children since we replaced the expr
-                            }
-                            TransformMode::Client => {
-                                // In client mode, just remove the directive and keep the function
-                                self.remove_use_step_directive(&mut fn_expr.function.body);
-                            }
+                            // Replace with identifier reference
+                            let hoisted_name =
+                                if let Some(parent) = &self.current_parent_function_name {
+                                    if !parent.is_empty() {
+                                        self.hoisted_step_name(parent, &name)
+                                    } else {
+                                        name
+                                    }
+                                } else {
+                                    name
+                                };
+                            *expr = Expr::Ident(Ident::new(
+                                hoisted_name.into(),
+                                DUMMY_SP,
+                                SyntaxContext::empty(),
+                            ));
+                            return; // Don't visit children since we replaced the expr
+                        }
+                        TransformMode::Workflow => {
+                            // Replace with proxy reference
+                            // Use current_parent_function_name to match step mode's ID generation
+                            let step_fn_name =
+                                if let Some(parent) = &self.current_parent_function_name {
+                                    if !parent.is_empty() {
+                                        self.nested_step_id_name(parent, &name)
+                                    } else {
+                                        name.clone()
+                                    }
+                                } else {
+                                    name.clone()
+                                };
+                            let step_id =
+                                self.create_id(Some(&step_fn_name), fn_expr.function.span, false);
+
+                            let closure_vars = ClosureVariableCollector::collect_from_function(
+                                &fn_expr.function,
+                                &self.module_imports,
+                                &self.step_directive,
+                                self.options.diagnostics,
+                            );
+                            *expr = self.create_step_proxy_reference(
+                                &step_id,
+                                &closure_vars,
+                                fn_expr.function.span,
+                            );
+                            return; // Don't visit children since we replaced the expr
+                        }
+                        TransformMode::Client => {
+                            // In client mode, just remove the directive and keep the function
+                            self.remove_use_step_directive(&mut fn_expr.function.body);
                         }
                     }
                 }
             }
-            Expr::Arrow(arrow_expr) => {
-                if self.has_step_directive_arrow(arrow_expr, false) {
-                    if !arrow_expr.is_async {
-                        emit_error(WorkflowErrorKind::NonAsyncFunction {
-                            span: arrow_expr.span,
-                            directive: "use step",
-                        });
-                    } else if !self.in_module_level {
-                        // Nested step arrow function in an expression (e.g., return statement)
-                        let name = format!("_anonymousStep{}", self.anonymous_fn_counter);
-                        self.anonymous_fn_counter += 1;
-                        self.step_function_names.insert(name.clone());
-
-                        match self.mode {
-                            TransformMode::Step => {
-                                // Hoist to module scope
-                                let mut cloned_arrow = arrow_expr.clone();
-                                self.remove_use_step_directive_arrow(&mut cloned_arrow.body);
-
-                                let closure_vars =
-                                    ClosureVariableCollector::collect_from_arrow_expr(
-                                        &cloned_arrow,
-                                        &self.module_imports,
-                                    );
+            Expr::Arrow(arrow_expr) if self.has_step_directive_arrow(arrow_expr, false) => {
+                if !arrow_expr.is_async {
+                    emit_error(WorkflowErrorKind::NonAsyncFunction {
+                        span: arrow_expr.span,
+                        directive: self.step_directive.as_str(),
+                        async_at: self.async_fix_at(arrow_expr.span),
+                    });
+                } else if !self.in_module_level {
+                    // Nested step arrow function in an expression (e.g., return statement)
+                    let name = format!("_anonymousStep{}", self.anonymous_fn_counter);
+                    self.anonymous_fn_counter += 1;
+                    self.record_step_function(name.clone(), arrow_expr.span);
 
-                                // Convert to function expression for hoisting
-                                let fn_expr = FnExpr {
-                                    ident: Some(Ident::new(
-                                        name.clone().into(),
-                                        DUMMY_SP,
-                                        SyntaxContext::empty(),
-                                    )),
-                                    function: Box::new(Function {
-                                        params: cloned_arrow
-                                            .params
-                                            .iter()
-                                            .map(|pat| Param {
-                                                span: DUMMY_SP,
-                                                decorators: vec![],
-                                                pat: pat.clone(),
-                                            })
-                                            .collect(),
-                                        decorators: vec![],
-                                        span: cloned_arrow.span,
-                                        ctxt: SyntaxContext::empty(),
-                                        body: match *cloned_arrow.body {
-                                            BlockStmtOrExpr::BlockStmt(block) => Some(block),
-                                            BlockStmtOrExpr::Expr(expr) => Some(BlockStmt {
+                    match self.mode {
+                        TransformMode::Step => {
+                            // Hoist to module scope
+                            let mut cloned_arrow = arrow_expr.clone();
+                            self.remove_use_step_directive_arrow(&mut cloned_arrow.body);
+
+                            let closure_vars = ClosureVariableCollector::collect_from_arrow_expr(
+                                &cloned_arrow,
+                                &self.module_imports,
+                                &self.step_directive,
+                                self.options.diagnostics,
+                            );
+
+                            // Convert to function expression for hoisting
+                            let fn_expr = FnExpr {
+                                ident: Some(Ident::new(
+                                    name.clone().into(),
+                                    DUMMY_SP,
+                                    SyntaxContext::empty(),
+                                )),
+                                function: Box::new(Function {
+                                    params: cloned_arrow
+                                        .params
+                                        .iter()
+                                        .map(|pat| Param {
+                                            span: DUMMY_SP,
+                                            decorators: vec![],
+                                            pat: pat.clone(),
+                                        })
+                                        .collect(),
+                                    decorators: vec![],
+                                    span: cloned_arrow.span,
+                                    ctxt: SyntaxContext::empty(),
+                                    body: match *cloned_arrow.body {
+                                        BlockStmtOrExpr::BlockStmt(block) => Some(block),
+                                        BlockStmtOrExpr::Expr(expr) => Some(BlockStmt {
+                                            span: DUMMY_SP,
+                                            ctxt: SyntaxContext::empty(),
+                                            stmts: vec![Stmt::Return(ReturnStmt {
                                                 span: DUMMY_SP,
-                                                ctxt: SyntaxContext::empty(),
-                                                stmts: vec![Stmt::Return(ReturnStmt {
-                                                    span: DUMMY_SP,
-                                                    arg: Some(expr),
-                                                })],
-                                            }),
-                                        },
-                                        is_generator: false,
-                                        is_async: cloned_arrow.is_async,
-                                        type_params: cloned_arrow.type_params.clone(),
-                                        return_type: cloned_arrow.return_type.clone(),
-                                    }),
-                                };
-
-                                self.nested_step_functions.push((
-                                    name.clone(),
-                                    fn_expr,
-                                    arrow_expr.span,
-                                    closure_vars,
-                                    true, // Was an arrow function
-                                    self.current_parent_function_name
-                                        .clone()
-                                        .unwrap_or_default(),
-                                ));
+                                                arg: Some(expr),
+                                            })],
+                                        }),
+                                    },
+                                    is_generator: false,
+                                    is_async: cloned_arrow.is_async,
+                                    type_params: cloned_arrow.type_params.clone(),
+                                    return_type: cloned_arrow.return_type.clone(),
+                                }),
+                            };
+
+                            self.nested_step_functions.push((
+                                name.clone(),
+                                fn_expr,
+                                arrow_expr.span,
+                                closure_vars,
+                                true, // Was an arrow function
+                                self.current_parent_function_name
+                                    .clone()
+                                    .unwrap_or_default(),
+                            ));
 
-                                // Replace with identifier reference
-                                let hoisted_name =
-                                    if let Some(parent) = &self.current_parent_function_name {
-                                        if !parent.is_empty() {
-                                            format!("{}${}", parent, name)
-                                        } else {
-                                            name
-                                        }
+                            // Replace with identifier reference
+                            let hoisted_name =
+                                if let Some(parent) = &self.current_parent_function_name {
+                                    if !parent.is_empty() {
+                                        self.hoisted_step_name(parent, &name)
                                     } else {
                                         name
-                                    };
-                                *expr = Expr::Ident(Ident::new(
-                                    hoisted_name.into(),
-                                    DUMMY_SP,
-                                    SyntaxContext::empty(),
-                                ));
-                                return; // Don't visit children since we replaced the expr
-                            }
-                            TransformMode::Workflow => {
-                                // Replace with proxy reference
-                                // Use current_parent_function_name to match step mode's ID generation
-                                let step_fn_name =
-                                    if let Some(parent) = &self.current_parent_function_name {
-                                        if !parent.is_empty() {
-                                            format!("{}/{}", parent, name)
-                                        } else {
-                                            name.clone()
-                                        }
+                                    }
+                                } else {
+                                    name
+                                };
+                            *expr = Expr::Ident(Ident::new(
+                                hoisted_name.into(),
+                                DUMMY_SP,
+                                SyntaxContext::empty(),
+                            ));
+                            return; // Don't visit children since we replaced the expr
+                        }
+                        TransformMode::Workflow => {
+                            // Replace with proxy reference
+                            // Use current_parent_function_name to match step mode's ID generation
+                            let step_fn_name =
+                                if let Some(parent) = &self.current_parent_function_name {
+                                    if !parent.is_empty() {
+                                        self.nested_step_id_name(parent, &name)
                                     } else {
                                         name.clone()
-                                    };
-                                let step_id =
-                                    self.create_id(Some(&step_fn_name), arrow_expr.span, false);
-
-                                let closure_vars =
-                                    ClosureVariableCollector::collect_from_arrow_expr(
-                                        arrow_expr,
-                                        &self.module_imports,
-                                    );
-                                *expr = self.create_step_proxy_reference(&step_id, &closure_vars);
-                                return; // Don't visit children since we replaced the expr
-                            }
-                            TransformMode::Client => {
-                                // In client mode, just remove the directive and keep the function
-                                self.remove_use_step_directive_arrow(&mut arrow_expr.body);
-                            }
+                                    }
+                                } else {
+                                    name.clone()
+                                };
+                            let step_id =
+                                self.create_id(Some(&step_fn_name), arrow_expr.span, false);
+
+                            let closure_vars = ClosureVariableCollector::collect_from_arrow_expr(
+                                arrow_expr,
+                                &self.module_imports,
+                                &self.step_directive,
+                                self.options.diagnostics,
+                            );
+                            *expr = self.create_step_proxy_reference(
+                                &step_id,
+                                &closure_vars,
+                                arrow_expr.span,
+                            );
+                            return; // Don't visit children since we replaced the expr
+                        }
+                        TransformMode::Client => {
+                            // In client mode, just remove the directive and keep the function
+                            self.remove_use_step_directive_arrow(&mut arrow_expr.body);
                         }
                     }
                 }
@@ -7479,7 +12360,11 @@ impl VisitMut for StepTransform {
                     .unwrap_or_else(|| "default".to_string());
 
                 if self.should_transform_workflow_function(&fn_expr.function, true) {
-                    if self.validate_async_function(&fn_expr.function, fn_expr.function.span) {
+                    if self.validate_async_function(
+                        &fn_expr.function,
+                        fn_expr.function.span,
+                        self.workflow_directive.as_str(),
+                    ) {
                         // For ALL default exports, track mapping from "default" to actual const name
                         let const_name = if fn_name == "default" {
                             // Anonymous: generate unique name
@@ -7495,7 +12380,7 @@ impl VisitMut for StepTransform {
                         };
 
                         // Always use "default" as the metadata key for default exports
-                        self.workflow_function_names.insert("default".to_string());
+                        self.record_workflow_function("default".to_string(), fn_expr.function.span);
 
                         match self.mode {
                             TransformMode::Step | TransformMode::Client => {
@@ -7583,54 +12468,52 @@ impl VisitMut for StepTransform {
                             }
                         }
                     }
-                } else if self.should_transform_function(&fn_expr.function, true) {
-                    if self.validate_async_function(&fn_expr.function, fn_expr.function.span) {
-                        self.step_function_names.insert(fn_name.clone());
+                } else if self.should_transform_function(&fn_expr.function, true)
+                    && self.validate_async_function(
+                        &fn_expr.function,
+                        fn_expr.function.span,
+                        self.step_directive.as_str(),
+                    )
+                {
+                    self.record_step_function(fn_name.clone(), fn_expr.function.span);
 
-                        match self.mode {
-                            TransformMode::Step => {
-                                self.remove_use_step_directive(&mut fn_expr.function.body);
-                                self.create_registration_call(&fn_name, fn_expr.function.span);
-                            }
-                            TransformMode::Client => {
-                                self.remove_use_step_directive(&mut fn_expr.function.body);
-                                self.step_functions_needing_id
-                                    .push((fn_name.clone(), fn_expr.function.span));
-                            }
-                            TransformMode::Workflow => {
-                                // Replace function body with step proxy
-                                self.remove_use_step_directive(&mut fn_expr.function.body);
-                                if let Some(body) = &mut fn_expr.function.body {
-                                    let step_id = self.create_id(
-                                        Some(&fn_name),
-                                        fn_expr.function.span,
-                                        false,
-                                    );
-                                    let mut proxy_call = self.create_step_proxy(&step_id);
-                                    // Add function arguments to the proxy call
-                                    if let Expr::Call(call) = &mut proxy_call {
-                                        call.args = fn_expr
-                                            .function
-                                            .params
-                                            .iter()
-                                            .map(|param| {
-                                                let is_rest = matches!(param.pat, Pat::Rest(_));
-                                                ExprOrSpread {
-                                                    spread: if is_rest {
-                                                        Some(DUMMY_SP)
-                                                    } else {
-                                                        None
-                                                    },
-                                                    expr: Box::new(self.pat_to_expr(&param.pat)),
-                                                }
-                                            })
-                                            .collect();
-                                    }
-                                    body.stmts = vec![Stmt::Return(ReturnStmt {
-                                        span: DUMMY_SP,
-                                        arg: Some(Box::new(proxy_call)),
-                                    })];
+                    match self.mode {
+                        TransformMode::Step => {
+                            self.remove_use_step_directive(&mut fn_expr.function.body);
+                            self.create_registration_call(&fn_name, fn_expr.function.span);
+                        }
+                        TransformMode::Client => {
+                            self.remove_use_step_directive(&mut fn_expr.function.body);
+                            self.step_functions_needing_id
+                                .push((fn_name.clone(), fn_expr.function.span));
+                        }
+                        TransformMode::Workflow => {
+                            // Replace function body with step proxy
+                            self.remove_use_step_directive(&mut fn_expr.function.body);
+                            if let Some(body) = &mut fn_expr.function.body {
+                                let step_id =
+                                    self.create_id(Some(&fn_name), fn_expr.function.span, false);
+                                let mut proxy_call =
+                                    self.create_step_proxy(&step_id, fn_expr.function.span);
+                                // Add function arguments to the proxy call
+                                if let Expr::Call(call) = &mut proxy_call {
+                                    call.args = fn_expr
+                                        .function
+                                        .params
+                                        .iter()
+                                        .map(|param| {
+                                            let is_rest = matches!(param.pat, Pat::Rest(_));
+                                            ExprOrSpread {
+                                                spread: if is_rest { Some(DUMMY_SP) } else { None },
+                                                expr: Box::new(self.pat_to_expr(&param.pat)),
+                                            }
+                                        })
+                                        .collect();
                                 }
+                                body.stmts = vec![Stmt::Return(ReturnStmt {
+                                    span: DUMMY_SP,
+                                    arg: Some(Box::new(proxy_call)),
+                                })];
                             }
                         }
                     }
@@ -7650,7 +12533,11 @@ impl VisitMut for StepTransform {
             Expr::Fn(fn_expr) => {
                 // Anonymous function: export default async function() { ... }
                 if self.should_transform_workflow_function(&fn_expr.function, true) {
-                    if self.validate_async_function(&fn_expr.function, fn_expr.function.span) {
+                    if self.validate_async_function(
+                        &fn_expr.function,
+                        fn_expr.function.span,
+                        self.workflow_directive.as_str(),
+                    ) {
                         // Generate unique name first so we can use it in workflow_function_names
                         let unique_name = self.generate_unique_name("__default");
                         // For function expression default exports, track mapping from "default" to actual const name
@@ -7658,7 +12545,7 @@ impl VisitMut for StepTransform {
                             .insert("default".to_string(), unique_name.clone());
 
                         // Always use "default" as the metadata key for default exports
-                        self.workflow_function_names.insert("default".to_string());
+                        self.record_workflow_function("default".to_string(), fn_expr.function.span);
 
                         match self.mode {
                             TransformMode::Step | TransformMode::Client => {
@@ -7735,8 +12622,12 @@ impl VisitMut for StepTransform {
                     }
                 } else if self.should_transform_function(&fn_expr.function, true) {
                     // Handle step functions
-                    if self.validate_async_function(&fn_expr.function, fn_expr.function.span) {
-                        self.step_function_names.insert("default".to_string());
+                    if self.validate_async_function(
+                        &fn_expr.function,
+                        fn_expr.function.span,
+                        self.step_directive.as_str(),
+                    ) {
+                        self.record_step_function("default".to_string(), fn_expr.function.span);
                         // Similar logic for steps...
                     }
                 }
@@ -7747,7 +12638,8 @@ impl VisitMut for StepTransform {
                     if !arrow_expr.is_async {
                         emit_error(WorkflowErrorKind::NonAsyncFunction {
                             span: arrow_expr.span,
-                            directive: "use workflow",
+                            directive: self.workflow_directive.as_str(),
+                            async_at: self.async_fix_at(arrow_expr.span),
                         });
                     } else {
                         // For arrow function default exports, generate unique name and track mapping
@@ -7756,7 +12648,7 @@ impl VisitMut for StepTransform {
                             .insert("default".to_string(), unique_name.clone());
 
                         // Always use "default" as the metadata key for default exports
-                        self.workflow_function_names.insert("default".to_string());
+                        self.record_workflow_function("default".to_string(), arrow_expr.span);
 
                         match self.mode {
                             TransformMode::Step | TransformMode::Client => {
@@ -7785,14 +12677,14 @@ impl VisitMut for StepTransform {
                                     type_args: None,
                                 });
                                 // Replace arrow body with block containing throw statement
-                                arrow_expr.body = Box::new(BlockStmtOrExpr::BlockStmt(BlockStmt {
+                                *arrow_expr.body = BlockStmtOrExpr::BlockStmt(BlockStmt {
                                     span: DUMMY_SP,
                                     ctxt: SyntaxContext::empty(),
                                     stmts: vec![Stmt::Throw(ThrowStmt {
                                         span: DUMMY_SP,
                                         arg: Box::new(error_expr),
                                     })],
-                                }));
+                                });
 
                                 // Track for const declaration and workflowId assignment
                                 self.default_workflow_exports.push((
@@ -7839,10 +12731,11 @@ impl VisitMut for StepTransform {
                     if !arrow_expr.is_async {
                         emit_error(WorkflowErrorKind::NonAsyncFunction {
                             span: arrow_expr.span,
-                            directive: "use step",
+                            directive: self.step_directive.as_str(),
+                            async_at: self.async_fix_at(arrow_expr.span),
                         });
                     } else {
-                        self.step_function_names.insert("default".to_string());
+                        self.record_step_function("default".to_string(), arrow_expr.span);
                         // Similar logic for steps...
                     }
                 }
@@ -7890,232 +12783,234 @@ impl VisitMut for StepTransform {
                         Prop::KeyValue(kv_prop) => {
                             if let Some(_prop_name) = &prop_key {
                                 match &mut *kv_prop.value {
-                                    Expr::Arrow(arrow_expr) => {
-                                        if self.has_step_directive_arrow(arrow_expr, false) {
-                                            if !arrow_expr.is_async {
-                                                emit_error(WorkflowErrorKind::NonAsyncFunction {
-                                                    span: arrow_expr.span,
-                                                    directive: "use step",
-                                                });
-                                            } else {
-                                                // Generate a unique name
-                                                let generated_name = format!(
-                                                    "_anonymousStep{}",
-                                                    self.anonymous_fn_counter
-                                                );
-                                                self.anonymous_fn_counter += 1;
-                                                self.step_function_names
-                                                    .insert(generated_name.clone());
-
-                                                match self.mode {
-                                                    TransformMode::Step => {
-                                                        // Hoist to module scope
-                                                        let mut cloned_arrow = arrow_expr.clone();
-                                                        self.remove_use_step_directive_arrow(
-                                                            &mut cloned_arrow.body,
-                                                        );
+                                    Expr::Arrow(arrow_expr)
+                                        if self.has_step_directive_arrow(arrow_expr, false) =>
+                                    {
+                                        if !arrow_expr.is_async {
+                                            emit_error(WorkflowErrorKind::NonAsyncFunction {
+                                                span: arrow_expr.span,
+                                                directive: self.step_directive.as_str(),
+                                                async_at: self.async_fix_at(arrow_expr.span),
+                                            });
+                                        } else {
+                                            // Generate a unique name
+                                            let generated_name = format!(
+                                                "_anonymousStep{}",
+                                                self.anonymous_fn_counter
+                                            );
+                                            self.anonymous_fn_counter += 1;
+                                            self.step_function_names.insert(generated_name.clone());
 
-                                                        // Collect closure variables
-                                                        let closure_vars = ClosureVariableCollector::collect_from_arrow_expr(&cloned_arrow, &self.module_imports);
+                                            match self.mode {
+                                                TransformMode::Step => {
+                                                    // Hoist to module scope
+                                                    let mut cloned_arrow = arrow_expr.clone();
+                                                    self.remove_use_step_directive_arrow(
+                                                        &mut cloned_arrow.body,
+                                                    );
 
-                                                        // Convert to function expression
-                                                        let fn_expr = FnExpr {
-                                                            ident: Some(Ident::new(
-                                                                generated_name.clone().into(),
-                                                                DUMMY_SP,
-                                                                SyntaxContext::empty(),
-                                                            )),
-                                                            function: Box::new(Function {
-                                                                params: cloned_arrow
-                                                                    .params
-                                                                    .iter()
-                                                                    .map(|pat| Param {
-                                                                        span: DUMMY_SP,
-                                                                        decorators: vec![],
-                                                                        pat: pat.clone(),
-                                                                    })
-                                                                    .collect(),
-                                                                decorators: vec![],
-                                                                span: cloned_arrow.span,
-                                                                ctxt: SyntaxContext::empty(),
-                                                                body: match *cloned_arrow.body {
-                                                                    BlockStmtOrExpr::BlockStmt(
-                                                                        block,
-                                                                    ) => Some(block),
-                                                                    BlockStmtOrExpr::Expr(expr) => {
-                                                                        Some(BlockStmt {
-                                                                            span: DUMMY_SP,
-                                                                            ctxt:
-                                                                                SyntaxContext::empty(
-                                                                                ),
-                                                                            stmts: vec![
-                                                                                Stmt::Return(
-                                                                                    ReturnStmt {
-                                                                                        span:
-                                                                                            DUMMY_SP,
-                                                                                        arg: Some(
-                                                                                            expr,
-                                                                                        ),
-                                                                                    },
-                                                                                ),
-                                                                            ],
-                                                                        })
-                                                                    }
-                                                                },
-                                                                is_generator: false,
-                                                                is_async: cloned_arrow.is_async,
-                                                                type_params: cloned_arrow
-                                                                    .type_params
-                                                                    .clone(),
-                                                                return_type: cloned_arrow
-                                                                    .return_type
-                                                                    .clone(),
-                                                            }),
-                                                        };
-
-                                                        self.nested_step_functions.push((
-                                                            generated_name.clone(),
-                                                            fn_expr,
-                                                            arrow_expr.span,
-                                                            closure_vars,
-                                                            true, // Was an arrow function
-                                                            self.current_workflow_function_name
-                                                                .clone()
-                                                                .unwrap_or_default(),
-                                                        ));
-
-                                                        // Replace with identifier reference
-                                                        *kv_prop.value = Expr::Ident(Ident::new(
-                                                            generated_name.into(),
+                                                    // Collect closure variables
+                                                    let closure_vars = ClosureVariableCollector::collect_from_arrow_expr(&cloned_arrow, &self.module_imports, &self.step_directive, self.options.diagnostics);
+
+                                                    // Convert to function expression
+                                                    let fn_expr = FnExpr {
+                                                        ident: Some(Ident::new(
+                                                            generated_name.clone().into(),
                                                             DUMMY_SP,
                                                             SyntaxContext::empty(),
-                                                        ));
-                                                    }
-                                                    TransformMode::Workflow => {
-                                                        // Replace with step proxy reference
-                                                        self.remove_use_step_directive_arrow(
-                                                            &mut arrow_expr.body,
-                                                        );
-                                                        // Include parent workflow name in step ID
-                                                        let step_fn_name = if let Some(parent) =
-                                                            &self.current_workflow_function_name
-                                                        {
-                                                            format!("{}/{}", parent, generated_name)
-                                                        } else {
-                                                            generated_name.clone()
-                                                        };
-                                                        let step_id = self.create_id(
-                                                            Some(&step_fn_name),
-                                                            arrow_expr.span,
-                                                            false,
-                                                        );
+                                                        )),
+                                                        function: Box::new(Function {
+                                                            params: cloned_arrow
+                                                                .params
+                                                                .iter()
+                                                                .map(|pat| Param {
+                                                                    span: DUMMY_SP,
+                                                                    decorators: vec![],
+                                                                    pat: pat.clone(),
+                                                                })
+                                                                .collect(),
+                                                            decorators: vec![],
+                                                            span: cloned_arrow.span,
+                                                            ctxt: SyntaxContext::empty(),
+                                                            body: match *cloned_arrow.body {
+                                                                BlockStmtOrExpr::BlockStmt(
+                                                                    block,
+                                                                ) => Some(block),
+                                                                BlockStmtOrExpr::Expr(expr) => {
+                                                                    Some(BlockStmt {
+                                                                        span: DUMMY_SP,
+                                                                        ctxt: SyntaxContext::empty(
+                                                                        ),
+                                                                        stmts: vec![Stmt::Return(
+                                                                            ReturnStmt {
+                                                                                span: DUMMY_SP,
+                                                                                arg: Some(expr),
+                                                                            },
+                                                                        )],
+                                                                    })
+                                                                }
+                                                            },
+                                                            is_generator: false,
+                                                            is_async: cloned_arrow.is_async,
+                                                            type_params: cloned_arrow
+                                                                .type_params
+                                                                .clone(),
+                                                            return_type: cloned_arrow
+                                                                .return_type
+                                                                .clone(),
+                                                        }),
+                                                    };
 
-                                                        // Collect closure variables
-                                                        let closure_vars = ClosureVariableCollector::collect_from_arrow_expr(&arrow_expr, &self.module_imports);
-                                                        *kv_prop.value = self
-                                                            .create_step_proxy_reference(
-                                                                &step_id,
-                                                                &closure_vars,
-                                                            );
-                                                    }
-                                                    TransformMode::Client => {
-                                                        // Just remove directive
-                                                        self.remove_use_step_directive_arrow(
-                                                            &mut arrow_expr.body,
+                                                    self.nested_step_functions.push((
+                                                        generated_name.clone(),
+                                                        fn_expr,
+                                                        arrow_expr.span,
+                                                        closure_vars,
+                                                        true, // Was an arrow function
+                                                        self.current_workflow_function_name
+                                                            .clone()
+                                                            .unwrap_or_default(),
+                                                    ));
+
+                                                    // Replace with identifier reference
+                                                    *kv_prop.value = Expr::Ident(Ident::new(
+                                                        generated_name.into(),
+                                                        DUMMY_SP,
+                                                        SyntaxContext::empty(),
+                                                    ));
+                                                }
+                                                TransformMode::Workflow => {
+                                                    // Replace with step proxy reference
+                                                    self.remove_use_step_directive_arrow(
+                                                        &mut arrow_expr.body,
+                                                    );
+                                                    // Include parent workflow name in step ID
+                                                    let step_fn_name = if let Some(parent) =
+                                                        &self.current_workflow_function_name
+                                                    {
+                                                        self.nested_step_id_name(
+                                                            parent,
+                                                            &generated_name,
+                                                        )
+                                                    } else {
+                                                        generated_name.clone()
+                                                    };
+                                                    let step_id = self.create_id(
+                                                        Some(&step_fn_name),
+                                                        arrow_expr.span,
+                                                        false,
+                                                    );
+
+                                                    // Collect closure variables
+                                                    let closure_vars = ClosureVariableCollector::collect_from_arrow_expr(arrow_expr, &self.module_imports, &self.step_directive, self.options.diagnostics);
+                                                    *kv_prop.value = self
+                                                        .create_step_proxy_reference(
+                                                            &step_id,
+                                                            &closure_vars,
+                                                            arrow_expr.span,
                                                         );
-                                                    }
+                                                }
+                                                TransformMode::Client => {
+                                                    // Just remove directive
+                                                    self.remove_use_step_directive_arrow(
+                                                        &mut arrow_expr.body,
+                                                    );
                                                 }
                                             }
                                         }
                                     }
-                                    Expr::Fn(fn_expr) => {
-                                        if self.has_step_directive(&fn_expr.function, false) {
-                                            if !fn_expr.function.is_async {
-                                                emit_error(WorkflowErrorKind::NonAsyncFunction {
-                                                    span: fn_expr.function.span,
-                                                    directive: "use step",
-                                                });
-                                            } else {
-                                                // Generate a unique name
-                                                let generated_name = format!(
-                                                    "_anonymousStep{}",
-                                                    self.anonymous_fn_counter
-                                                );
-                                                self.anonymous_fn_counter += 1;
-                                                self.step_function_names
-                                                    .insert(generated_name.clone());
-
-                                                match self.mode {
-                                                    TransformMode::Step => {
-                                                        // Hoist to module scope
-                                                        let mut cloned_fn = fn_expr.clone();
-                                                        self.remove_use_step_directive(
-                                                            &mut cloned_fn.function.body,
-                                                        );
+                                    Expr::Fn(fn_expr)
+                                        if self.has_step_directive(&fn_expr.function, false) =>
+                                    {
+                                        if !fn_expr.function.is_async {
+                                            emit_error(WorkflowErrorKind::NonAsyncFunction {
+                                                span: fn_expr.function.span,
+                                                directive: self.step_directive.as_str(),
+                                                async_at: self.async_fix_at(fn_expr.function.span),
+                                            });
+                                        } else {
+                                            // Generate a unique name
+                                            let generated_name = format!(
+                                                "_anonymousStep{}",
+                                                self.anonymous_fn_counter
+                                            );
+                                            self.anonymous_fn_counter += 1;
+                                            self.step_function_names.insert(generated_name.clone());
 
-                                                        // Collect closure variables
-                                                        let closure_vars = ClosureVariableCollector::collect_from_function(&*cloned_fn.function, &self.module_imports);
+                                            match self.mode {
+                                                TransformMode::Step => {
+                                                    // Hoist to module scope
+                                                    let mut cloned_fn = fn_expr.clone();
+                                                    self.remove_use_step_directive(
+                                                        &mut cloned_fn.function.body,
+                                                    );
 
-                                                        let hoisted_fn_expr = FnExpr {
-                                                            ident: Some(Ident::new(
-                                                                generated_name.clone().into(),
-                                                                DUMMY_SP,
-                                                                SyntaxContext::empty(),
-                                                            )),
-                                                            function: cloned_fn.function,
-                                                        };
+                                                    // Collect closure variables
+                                                    let closure_vars = ClosureVariableCollector::collect_from_function(&cloned_fn.function, &self.module_imports, &self.step_directive, self.options.diagnostics);
 
-                                                        self.nested_step_functions.push((
-                                                            generated_name.clone(),
-                                                            hoisted_fn_expr,
-                                                            fn_expr.function.span,
-                                                            closure_vars,
-                                                            false, // Was a function expression
-                                                            self.current_workflow_function_name
-                                                                .clone()
-                                                                .unwrap_or_default(),
-                                                        ));
-
-                                                        // Replace with identifier reference
-                                                        *kv_prop.value = Expr::Ident(Ident::new(
-                                                            generated_name.into(),
+                                                    let hoisted_fn_expr = FnExpr {
+                                                        ident: Some(Ident::new(
+                                                            generated_name.clone().into(),
                                                             DUMMY_SP,
                                                             SyntaxContext::empty(),
-                                                        ));
-                                                    }
-                                                    TransformMode::Workflow => {
-                                                        // Replace with step proxy reference
-                                                        self.remove_use_step_directive(
-                                                            &mut fn_expr.function.body,
-                                                        );
-                                                        // Include parent workflow name in step ID
-                                                        let step_fn_name = if let Some(parent) =
-                                                            &self.current_workflow_function_name
-                                                        {
-                                                            format!("{}/{}", parent, generated_name)
-                                                        } else {
-                                                            generated_name.clone()
-                                                        };
-                                                        let step_id = self.create_id(
-                                                            Some(&step_fn_name),
-                                                            fn_expr.function.span,
-                                                            false,
-                                                        );
+                                                        )),
+                                                        function: cloned_fn.function,
+                                                    };
 
-                                                        // Collect closure variables
-                                                        let closure_vars = ClosureVariableCollector::collect_from_function(&fn_expr.function, &self.module_imports);
-                                                        *kv_prop.value = self
-                                                            .create_step_proxy_reference(
-                                                                &step_id,
-                                                                &closure_vars,
-                                                            );
-                                                    }
-                                                    TransformMode::Client => {
-                                                        // Just remove directive
-                                                        self.remove_use_step_directive(
-                                                            &mut fn_expr.function.body,
+                                                    self.nested_step_functions.push((
+                                                        generated_name.clone(),
+                                                        hoisted_fn_expr,
+                                                        fn_expr.function.span,
+                                                        closure_vars,
+                                                        false, // Was a function expression
+                                                        self.current_workflow_function_name
+                                                            .clone()
+                                                            .unwrap_or_default(),
+                                                    ));
+
+                                                    // Replace with identifier reference
+                                                    *kv_prop.value = Expr::Ident(Ident::new(
+                                                        generated_name.into(),
+                                                        DUMMY_SP,
+                                                        SyntaxContext::empty(),
+                                                    ));
+                                                }
+                                                TransformMode::Workflow => {
+                                                    // Replace with step proxy reference
+                                                    self.remove_use_step_directive(
+                                                        &mut fn_expr.function.body,
+                                                    );
+                                                    // Include parent workflow name in step ID
+                                                    let step_fn_name = if let Some(parent) =
+                                                        &self.current_workflow_function_name
+                                                    {
+                                                        self.nested_step_id_name(
+                                                            parent,
+                                                            &generated_name,
+                                                        )
+                                                    } else {
+                                                        generated_name.clone()
+                                                    };
+                                                    let step_id = self.create_id(
+                                                        Some(&step_fn_name),
+                                                        fn_expr.function.span,
+                                                        false,
+                                                    );
+
+                                                    // Collect closure variables
+                                                    let closure_vars = ClosureVariableCollector::collect_from_function(&fn_expr.function, &self.module_imports, &self.step_directive, self.options.diagnostics);
+                                                    *kv_prop.value = self
+                                                        .create_step_proxy_reference(
+                                                            &step_id,
+                                                            &closure_vars,
+                                                            fn_expr.function.span,
                                                         );
-                                                    }
+                                                }
+                                                TransformMode::Client => {
+                                                    // Just remove directive
+                                                    self.remove_use_step_directive(
+                                                        &mut fn_expr.function.body,
+                                                    );
                                                 }
                                             }
                                         }
@@ -8130,14 +13025,21 @@ impl VisitMut for StepTransform {
                                     if !method_prop.function.is_async {
                                         emit_error(WorkflowErrorKind::NonAsyncFunction {
                                             span: method_prop.function.span,
-                                            directive: "use step",
+                                            directive: self.step_directive.as_str(),
+                                            async_at: self.method_async_fix_at(
+                                                &method_prop.key,
+                                                &method_prop.function,
+                                            ),
                                         });
                                     } else {
                                         // Generate a unique name
                                         let generated_name =
                                             format!("_anonymousStep{}", self.anonymous_fn_counter);
                                         self.anonymous_fn_counter += 1;
-                                        self.step_function_names.insert(generated_name.clone());
+                                        self.record_step_function(
+                                            generated_name.clone(),
+                                            method_prop.function.span,
+                                        );
 
                                         match self.mode {
                                             TransformMode::Step => {
@@ -8153,6 +13055,8 @@ impl VisitMut for StepTransform {
                                                     ClosureVariableCollector::collect_from_function(
                                                         &cloned_function,
                                                         &self.module_imports,
+                                                        &self.step_directive,
+                                                        self.options.diagnostics,
                                                     );
 
                                                 let fn_expr = FnExpr {
@@ -8176,15 +13080,14 @@ impl VisitMut for StepTransform {
                                                 ));
 
                                                 // Replace method with property pointing to identifier
-                                                *boxed_prop =
-                                                    Box::new(Prop::KeyValue(KeyValueProp {
-                                                        key: method_prop.key.clone(),
-                                                        value: Box::new(Expr::Ident(Ident::new(
-                                                            generated_name.into(),
-                                                            DUMMY_SP,
-                                                            SyntaxContext::empty(),
-                                                        ))),
-                                                    }));
+                                                **boxed_prop = Prop::KeyValue(KeyValueProp {
+                                                    key: method_prop.key.clone(),
+                                                    value: Box::new(Expr::Ident(Ident::new(
+                                                        generated_name.into(),
+                                                        DUMMY_SP,
+                                                        SyntaxContext::empty(),
+                                                    ))),
+                                                });
                                             }
                                             TransformMode::Workflow => {
                                                 // Replace with step proxy reference
@@ -8195,7 +13098,10 @@ impl VisitMut for StepTransform {
                                                 let step_fn_name = if let Some(parent) =
                                                     &self.current_workflow_function_name
                                                 {
-                                                    format!("{}/{}", parent, generated_name)
+                                                    self.nested_step_id_name(
+                                                        parent,
+                                                        &generated_name,
+                                                    )
                                                 } else {
                                                     generated_name.clone()
                                                 };
@@ -8210,19 +13116,21 @@ impl VisitMut for StepTransform {
                                                     ClosureVariableCollector::collect_from_function(
                                                         &method_prop.function,
                                                         &self.module_imports,
+                                                        &self.step_directive,
+                                                        self.options.diagnostics,
                                                     );
 
                                                 // Replace method with property pointing to proxy
-                                                *boxed_prop =
-                                                    Box::new(Prop::KeyValue(KeyValueProp {
-                                                        key: method_prop.key.clone(),
-                                                        value: Box::new(
-                                                            self.create_step_proxy_reference(
-                                                                &step_id,
-                                                                &closure_vars,
-                                                            ),
+                                                **boxed_prop = Prop::KeyValue(KeyValueProp {
+                                                    key: method_prop.key.clone(),
+                                                    value: Box::new(
+                                                        self.create_step_proxy_reference(
+                                                            &step_id,
+                                                            &closure_vars,
+                                                            method_prop.function.span,
                                                         ),
-                                                    }));
+                                                    ),
+                                                });
                                             }
                                             TransformMode::Client => {
                                                 // Just remove directive
@@ -8247,3 +13155,113 @@ impl VisitMut for StepTransform {
 
     noop_visit_mut_type!();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_similar_strings() {
+        let cases = [
+            // One substitution
+            ("use workflov", true),
+            ("use worcflow", true),
+            ("use Workflow", true),
+            // One insertion
+            ("use workflows", true),
+            ("use wworkflow", true),
+            ("use workfllow", true),
+            // One deletion
+            ("use workflo", true),
+            ("use orkflow", true),
+            ("use wrkflow", true),
+            // Missing the leading "use " word
+            ("use_workflow", false),
+            ("Use workflow", false),
+            ("uuse workflow", false),
+            ("useworkflow", false),
+            ("se workflow", false),
+            // Identical or further away
+            ("use workflow", false),
+            ("use wrkflw", false),
+            ("use flowwork", false),
+            ("use workflow!!", false),
+            ("use step", false),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                detect_similar_strings(input, "use workflow"),
+                expected,
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_detect_similar_strings_ignores_short_and_foreign_directives() {
+        for directive in ["use step", "use workflow", "use cache"] {
+            for input in ["use client", "use server", "use strict", "used", "us"] {
+                assert!(
+                    !detect_similar_strings(input, directive),
+                    "{input:?} vs {directive:?}"
+                );
+            }
+        }
+        assert!(detect_similar_strings("use cach", "use cache"));
+        assert!(detect_similar_strings("use stepp", "use step"));
+        assert!(detect_similar_strings("use stp", "use step"));
+        assert!(detect_similar_strings("use ste", "use step"));
+    }
+
+    #[test]
+    fn test_remove_dead_code_removes_whole_chains() {
+        // Each helper is only referenced by the one before it, so it only becomes unused once
+        // that one is removed
+        let mut src = String::from(
+            "export function checkout(order) { return keep(order); }\n\
+             function keep(order) { return order; }\n",
+        );
+        for index in 0..500 {
+            src.push_str(&format!(
+                "function helper{}() {{ return helper{}(); }}\n",
+                index,
+                index + 1
+            ));
+        }
+        src.push_str("const helper500 = () => null;\n");
+
+        testing::run_test(false, |cm, _handler| {
+            let fm = cm.new_source_file(swc_core::common::FileName::Anon.into(), src.clone());
+            let module = swc_core::ecma::parser::Parser::new(
+                Default::default(),
+                swc_core::ecma::parser::StringInput::from(&*fm),
+                None,
+            )
+            .parse_module()
+            .expect("failed to parse input");
+
+            let transform = StepTransform::new(TransformMode::Workflow, "input.js".into(), None);
+            let mut items = module.body;
+            transform.remove_dead_code(&mut items);
+
+            let names: Vec<String> = items
+                .iter()
+                .filter_map(|item| match item {
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                        decl: Decl::Fn(fn_decl),
+                        ..
+                    }))
+                    | ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl))) => {
+                        Some(fn_decl.ident.sym.to_string())
+                    }
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(names, vec!["checkout".to_string(), "keep".to_string()]);
+            assert_eq!(items.len(), 2);
+            Ok(())
+        })
+        .unwrap();
+    }
+}
@@ -13,19 +13,56 @@ pub fn format_name(prefix: &str, module_path: &str, identifier: impl Display) ->
 
 /// Get the module path to use for ID generation.
 ///
-/// If a module_specifier is provided, use it directly.
+/// If a module_specifier is provided, use it directly (with Windows backslashes normalized).
+/// Relative specifiers ("./" or "../") are canonicalized like file paths, while package
+/// specifiers like "point@0.0.1" are kept as-is.
 /// Otherwise, convert the filepath to a relative path format (prefixed with "./").
 pub fn get_module_path(module_specifier: Option<&str>, filepath: &str) -> String {
     match module_specifier {
-        Some(specifier) => specifier.to_string(),
+        Some(specifier) => {
+            // Specifiers built from a Windows path would otherwise give different IDs than on CI
+            let normalized = specifier.replace('\\', "/");
+            if normalized.starts_with("./") || normalized.starts_with("../") {
+                canonicalize_relative_path(&normalized)
+            } else {
+                normalized
+            }
+        }
         None => {
             // Normalize Windows backslashes to forward slashes for consistent IDs across platforms
             let normalized = filepath.replace('\\', "/");
             // Strip file extension for cleaner IDs
             let path_without_ext = strip_extension(&normalized);
-            format!("./{}", path_without_ext)
+            canonicalize_relative_path(path_without_ext)
+        }
+    }
+}
+
+/// Canonicalize a relative path so every spelling of it gives the same IDs: "." segments are
+/// dropped and "dir/.." pairs collapsed, so "src/a", "./src/a" and "./src/lib/../a" all become
+/// "./src/a". Paths that leave the root keep their leading ".." segments instead of "./".
+fn canonicalize_relative_path(path: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "." => {}
+            // An empty segment is the root of an absolute path, which can't be left
+            ".." => match segments.last() {
+                Some(&last) if last != ".." && !last.is_empty() => {
+                    segments.pop();
+                }
+                _ => segments.push(segment),
+            },
+            _ => segments.push(segment),
         }
     }
+
+    let path = segments.join("/");
+    if segments.first() == Some(&"..") {
+        path
+    } else {
+        format!("./{}", path)
+    }
 }
 
 /// Strip common JS/TS file extensions from a path.
@@ -124,6 +161,40 @@ mod tests {
         assert_eq!(result, "./lib/esm/index");
     }
 
+    #[test]
+    fn test_get_module_path_with_leading_dot_slash() {
+        assert_eq!(
+            get_module_path(None, "./src/a.ts"),
+            get_module_path(None, "src/a.ts")
+        );
+        assert_eq!(get_module_path(None, "./src/a.ts"), "./src/a");
+    }
+
+    #[test]
+    fn test_get_module_path_collapses_redundant_segments() {
+        assert_eq!(get_module_path(None, "src/./lib/../a.ts"), "./src/a");
+        assert_eq!(
+            get_module_path(Some("./src/lib/../a"), "src/a.ts"),
+            "./src/a"
+        );
+    }
+
+    #[test]
+    fn test_get_module_path_parent_directory() {
+        assert_eq!(get_module_path(None, "../x.ts"), "../x");
+        assert_eq!(get_module_path(None, "./../x.ts"), "../x");
+        assert_eq!(get_module_path(None, "src/../../x.ts"), "../x");
+        assert_eq!(get_module_path(Some("../x"), "../x.ts"), "../x");
+    }
+
+    #[test]
+    fn test_format_name_with_leading_dot_slash() {
+        assert_eq!(
+            format_name("step", &get_module_path(None, "./src/a.ts"), "run"),
+            format_name("step", &get_module_path(None, "src/a.ts"), "run")
+        );
+    }
+
     // Tests for strip_extension
     #[test]
     fn test_strip_extension_ts() {
@@ -184,4 +255,17 @@ mod tests {
         let result = format_name("workflow", &module_path, "handleOrder");
         assert_eq!(result, "workflow//./src/workflows/order//handleOrder");
     }
+
+    #[test]
+    fn test_get_module_path_windows_specifier() {
+        let result = get_module_path(Some(".\\src\\models\\Point"), "src\\models\\Point.ts");
+        assert_eq!(result, "./src/models/Point");
+    }
+
+    #[test]
+    fn test_format_name_windows_specifier() {
+        let module_path = get_module_path(Some(".\\src\\models\\Point"), "src\\models\\Point.ts");
+        let result = format_name("step", &module_path, "distance");
+        assert_eq!(result, "step//./src/models/Point//distance");
+    }
 }
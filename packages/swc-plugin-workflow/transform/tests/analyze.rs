@@ -0,0 +1,121 @@
+use common::{load_options, load_syntax};
+use serde_json::{Map, Value, json};
+use std::path::{Path, PathBuf};
+use swc_core::ecma::{ast::Program, parser::Syntax};
+use swc_workflow::{StepTransform, TransformMode, WorkflowManifest, analyze};
+
+mod common;
+
+// Returns the sections of the JSON manifest comment of a fixture output with only the IDs of
+// their entries, or `None` if it has no such comment
+fn parse_manifest_comment(output: &Path) -> Option<Value> {
+    let output = std::fs::read_to_string(output).unwrap();
+    let start = output.find("/**__internal_workflows{")?;
+    let json_start = start + "/**__internal_workflows".len();
+    let json_end = json_start + output[json_start..].find("}*/").unwrap() + 1;
+    let mut manifest: Map<String, Value> =
+        serde_json::from_str(&output[json_start..json_end]).unwrap();
+    // Leave out what `WorkflowManifest` doesn't hold, like `mode` or `line` and `col`
+    manifest.retain(|kind, _| ["workflows", "steps", "classes", "caches"].contains(&kind.as_str()));
+    for files in manifest.values_mut() {
+        for entries in files.as_object_mut().unwrap().values_mut() {
+            for entry in entries.as_object_mut().unwrap().values_mut() {
+                entry
+                    .as_object_mut()
+                    .unwrap()
+                    .retain(|key, _| key.ends_with("Id"));
+            }
+        }
+    }
+    Some(Value::Object(manifest))
+}
+
+// Builds the JSON the manifest comment would hold for the given manifest
+fn manifest_to_json(manifest: &WorkflowManifest) -> Value {
+    let mut sections = Map::new();
+    for (kind, id_key, entries) in [
+        ("workflows", "workflowId", &manifest.workflows),
+        ("steps", "stepId", &manifest.steps),
+        ("classes", "classId", &manifest.classes),
+        ("caches", "cacheId", &manifest.caches),
+    ] {
+        if entries.is_empty() {
+            continue;
+        }
+        let entries: Map<String, Value> = entries
+            .iter()
+            .map(|(name, id)| (name.clone(), json!({ id_key: id })))
+            .collect();
+        sections.insert(kind.to_string(), json!({ "input.js": entries }));
+    }
+    Value::Object(sections)
+}
+
+fn analyze_fixture(input: &Path, mode: TransformMode) -> WorkflowManifest {
+    let src = std::fs::read_to_string(input)
+        .unwrap_or_else(|_| panic!("failed to load {}", input.display()));
+    let options = load_options(input);
+    common::with_module(&src, load_syntax(input), |module, cm, _handler| {
+        StepTransform::new(mode, "input.js".into(), None)
+            .with_source_map(cm.clone())
+            .with_options(options)
+            .analyze(&Program::Module(module))
+    })
+}
+
+#[testing::fixture("tests/fixture/**/input.js")]
+fn analyze_matches_manifest_comment(input: PathBuf) {
+    let dir = input.parent().unwrap();
+
+    for (mode, output) in [
+        (TransformMode::Step, "output-step.js"),
+        (TransformMode::Workflow, "output-workflow.js"),
+        (TransformMode::Client, "output-client.js"),
+    ] {
+        let output = dir.join(output);
+        if !output.exists() {
+            continue;
+        }
+        let expected = match parse_manifest_comment(&output) {
+            Some(manifest) => manifest,
+            // Options can leave the comment out or change its format
+            None if dir.join("options.json").exists() => continue,
+            None => json!({}),
+        };
+        assert_eq!(
+            manifest_to_json(&analyze_fixture(&input, mode)),
+            expected,
+            "{}",
+            output.display()
+        );
+    }
+}
+
+const DIAGNOSTICS: &str = r#"
+export function add(a, b) {
+  "use step";
+  return a + b;
+}
+
+export async function sum(a, b) {
+  "use workflow";
+  return Math.random() + a + b;
+}
+"#;
+
+#[test]
+fn analyze_does_not_report_diagnostics() {
+    common::with_module(DIAGNOSTICS, Syntax::default(), |module, _cm, handler| {
+        let manifest = analyze(
+            &Program::Module(module),
+            TransformMode::Workflow,
+            "input.js".into(),
+            None,
+        );
+        assert_eq!(
+            manifest.workflows,
+            vec![("sum".to_string(), "workflow//./input//sum".to_string())]
+        );
+        assert!(!handler.has_errors());
+    });
+}
@@ -0,0 +1,83 @@
+// Harness shared by the tests: the option and syntax loaders of the fixtures, and helpers for
+// running the transform on inline sources. Each test binary only uses some of them.
+#![allow(dead_code)]
+
+use std::path::Path;
+use swc_core::{
+    common::{FileName, SourceMap, errors::Handler, sync::Lrc},
+    ecma::{
+        ast::*,
+        parser::{EsSyntax, Parser, StringInput, Syntax, TsSyntax, lexer::Lexer},
+        visit::VisitMutWith,
+    },
+};
+use swc_workflow::{ProxyTypeAnnotation, StepTransform, TransformOptions};
+
+// Fixtures may provide an `options.json` next to `input.js` to enable optional transform settings
+pub fn load_options(input: &Path) -> TransformOptions {
+    let options_path = input.parent().unwrap().join("options.json");
+    match std::fs::read_to_string(&options_path) {
+        Ok(json) => serde_json::from_str(&json).expect("failed to parse options.json"),
+        Err(_) => TransformOptions::default(),
+    }
+}
+
+// Fixtures whose output carries type annotations are parsed (input and expected output) as
+// TypeScript, and ones that mark steps or workflows with decorators with decorators enabled
+pub fn load_syntax(input: &Path) -> Syntax {
+    let options = load_options(input);
+    if options.proxy_type_annotation != ProxyTypeAnnotation::None {
+        Syntax::Typescript(TsSyntax::default())
+    } else if !options.step_decorators.is_empty() || !options.workflow_decorators.is_empty() {
+        Syntax::Es(EsSyntax {
+            decorators: true,
+            ..Default::default()
+        })
+    } else {
+        Syntax::default()
+    }
+}
+
+// Calls `f` with a parser for `src` within a test environment, which provides the source map,
+// the error handler and the SWC globals
+pub fn with_parser<T>(
+    src: &str,
+    syntax: Syntax,
+    f: impl FnOnce(Parser<Lexer<'_>>, &Lrc<SourceMap>, &Handler) -> T,
+) -> T {
+    testing::run_test(false, |cm, handler| {
+        let fm = cm.new_source_file(FileName::Anon.into(), src.to_string());
+        Ok(f(
+            Parser::new(syntax, StringInput::from(&*fm), None),
+            &cm,
+            handler,
+        ))
+    })
+    .unwrap()
+}
+
+// Like `with_parser`, with `src` parsed as a module
+pub fn with_module<T>(
+    src: &str,
+    syntax: Syntax,
+    f: impl FnOnce(Module, &Lrc<SourceMap>, &Handler) -> T,
+) -> T {
+    with_parser(src, syntax, |mut parser, cm, handler| {
+        let module = parser.parse_module().expect("failed to parse input");
+        f(module, cm, handler)
+    })
+}
+
+// Runs `transform` over `src` parsed as a module, returning the resulting module and whether
+// any error was emitted
+pub fn transform(src: &str, syntax: Syntax, mut transform: StepTransform) -> (Module, bool) {
+    with_module(src, syntax, |module, _cm, handler| {
+        let mut program = Program::Module(module);
+        program.visit_mut_with(&mut transform);
+
+        let Program::Module(module) = program else {
+            unreachable!()
+        };
+        (module, handler.has_errors())
+    })
+}
@@ -0,0 +1,171 @@
+use swc_core::ecma::{
+    ast::*,
+    parser::{EsSyntax, Syntax},
+};
+use swc_workflow::{StepTransform, TransformMode};
+
+mod common;
+
+// Transforms `src` (which may contain JSX) and returns the names of the module-level function
+// and variable declarations that are left
+fn remaining_declarations(mode: TransformMode, src: &str) -> Vec<String> {
+    let syntax = Syntax::Es(EsSyntax {
+        jsx: true,
+        ..Default::default()
+    });
+    let (module, _) = common::transform(
+        src,
+        syntax,
+        StepTransform::new(mode, "input.js".into(), None),
+    );
+
+    module
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl))) => Some(fn_decl.ident.sym.to_string()),
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) => {
+                match &var_decl.decls.first()?.name {
+                    Pat::Ident(ident) => Some(ident.id.sym.to_string()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn unreachable_cycles_are_removed() {
+    let remaining = remaining_declarations(
+        TransformMode::Workflow,
+        r#"
+async function chargeCard(order) {
+  "use step";
+  return fetch("/api/charge", { method: "POST", body: order });
+}
+
+export async function checkout(order) {
+  "use workflow";
+  return chargeCard(order);
+}
+
+function isEven(n) {
+  return n === 0 || isOdd(n - 1);
+}
+
+const isOdd = (n) => n !== 0 && isEven(n - 1);
+"#,
+    );
+
+    assert_eq!(remaining, vec!["chargeCard".to_string()]);
+}
+
+#[test]
+fn declarations_used_in_jsx_are_kept() {
+    let remaining = remaining_declarations(
+        TransformMode::Client,
+        r#"
+async function renderCard(user) {
+  "use step";
+  return user.name;
+}
+
+function Foo({ helper }) {
+  return helper();
+}
+
+const bar = () => "bar";
+
+const ui = { Badge: () => null };
+
+const title = "Profile";
+
+function unused() {
+  return null;
+}
+
+export const Profile = () => (
+  <>
+    <Foo helper={bar} />
+    <ui.Badge label={`${title}`}>{renderCard}</ui.Badge>
+  </>
+);
+"#,
+    );
+
+    assert_eq!(
+        remaining,
+        vec![
+            "renderCard".to_string(),
+            "Foo".to_string(),
+            "bar".to_string(),
+            "ui".to_string(),
+            "title".to_string(),
+        ]
+    );
+}
+
+const SIDE_EFFECTS: &str = r#"
+import { init } from "./setup";
+import { Client } from "./client";
+
+const _ = init();
+const client = new Client();
+const config = { retries: 3 };
+
+export async function sync(id) {
+  "use step";
+  return client.fetch(id);
+}
+"#;
+
+#[test]
+fn call_initializers_are_kept_in_client_mode() {
+    assert_eq!(
+        remaining_declarations(TransformMode::Client, SIDE_EFFECTS),
+        vec!["_".to_string(), "client".to_string()]
+    );
+}
+
+#[test]
+fn call_initializers_are_removed_in_workflow_mode() {
+    assert!(remaining_declarations(TransformMode::Workflow, SIDE_EFFECTS).is_empty());
+}
+
+const SPECIFIER_EXPORTS: &str = r#"
+import { format } from "./format";
+import { other } from "./other";
+
+function util(value) {
+  return format(value);
+}
+
+function unused() {
+  return other();
+}
+
+const render = (value) => `<${value}>`;
+
+export async function sync(id) {
+  "use step";
+  return id;
+}
+
+export { util };
+export { unused } from "./elsewhere";
+export default render;
+"#;
+
+#[test]
+fn bindings_exported_through_specifiers_are_kept() {
+    for mode in [TransformMode::Workflow, TransformMode::Client] {
+        let label = format!("{:?}", mode);
+        assert_eq!(
+            remaining_declarations(mode, SPECIFIER_EXPORTS),
+            vec!["util".to_string(), "render".to_string()],
+            "{}",
+            label
+        );
+    }
+}
@@ -1,10 +1,13 @@
+use common::load_options;
 use std::path::PathBuf;
 use swc_core::ecma::{
-    transforms::testing::{test_fixture, FixtureTestConfig},
+    transforms::testing::{FixtureTestConfig, test_fixture},
     visit::visit_mut_pass,
 };
 use swc_workflow::{StepTransform, TransformMode};
 
+mod common;
+
 #[testing::fixture("tests/errors/**/input.js")]
 fn step_mode(input: PathBuf) {
     let output = input.parent().unwrap().join("output-step.js");
@@ -15,11 +18,14 @@ fn step_mode(input: PathBuf) {
         Default::default(),
         // The errors occur in any mode, so it doesn't matter
         &|_| {
-            visit_mut_pass(StepTransform::new(
-                TransformMode::Step,
-                input.file_name().unwrap().to_string_lossy().to_string(),
-                None,
-            ))
+            visit_mut_pass(
+                StepTransform::new(
+                    TransformMode::Step,
+                    input.file_name().unwrap().to_string_lossy().to_string(),
+                    None,
+                )
+                .with_options(load_options(&input)),
+            )
         },
         &input,
         &output,
@@ -41,11 +47,14 @@ fn workflow_mode(input: PathBuf) {
         Default::default(),
         // The errors occur in any mode, so it doesn't matter
         &|_| {
-            visit_mut_pass(StepTransform::new(
-                TransformMode::Workflow,
-                input.file_name().unwrap().to_string_lossy().to_string(),
-                None,
-            ))
+            visit_mut_pass(
+                StepTransform::new(
+                    TransformMode::Workflow,
+                    input.file_name().unwrap().to_string_lossy().to_string(),
+                    None,
+                )
+                .with_options(load_options(&input)),
+            )
         },
         &input,
         &output,
@@ -67,11 +76,14 @@ fn client_mode(input: PathBuf) {
         Default::default(),
         // The errors occur in any mode, so it doesn't matter
         &|_| {
-            visit_mut_pass(StepTransform::new(
-                TransformMode::Client,
-                input.file_name().unwrap().to_string_lossy().to_string(),
-                None,
-            ))
+            visit_mut_pass(
+                StepTransform::new(
+                    TransformMode::Client,
+                    input.file_name().unwrap().to_string_lossy().to_string(),
+                    None,
+                )
+                .with_options(load_options(&input)),
+            )
         },
         &input,
         &output,
@@ -0,0 +1,20 @@
+export const settings = {
+    get theme () {
+        'use step';
+        return 'dark';
+    },
+    set theme (value){
+        'use step';
+        console.log(value);
+    }
+};
+export class Profile {
+    static get current() {
+        'use step';
+        return null;
+    }
+    set name(value) {
+        'use step';
+        console.log(value);
+    }
+}
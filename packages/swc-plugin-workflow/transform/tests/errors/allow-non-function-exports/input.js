@@ -0,0 +1,15 @@
+'use workflow';
+
+// Allowed with allowNonFunctionExports
+export const MAX_RETRIES = 3;
+export class RetryPolicy {}
+export default 'orders';
+
+// Still errors - exported functions must be async
+export function syncHelper() {
+  return MAX_RETRIES;
+}
+
+export async function processOrder(orderId) {
+  return orderId;
+}
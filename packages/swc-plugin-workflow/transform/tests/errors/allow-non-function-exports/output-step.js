@@ -0,0 +1,14 @@
+/**__internal_workflows{"workflows":{"input.js":{"processOrder":{"workflowId":"workflow//./input//processOrder"}}}}*/;
+// Allowed with allowNonFunctionExports
+export const MAX_RETRIES = 3;
+export class RetryPolicy {
+}
+export default 'orders';
+// Still errors - exported functions must be async
+export function syncHelper() {
+    throw new Error("You attempted to execute workflow syncHelper function directly. To start a workflow, use start(syncHelper) from workflow/api");
+}
+export async function processOrder(orderId) {
+    throw new Error("You attempted to execute workflow processOrder function directly. To start a workflow, use start(processOrder) from workflow/api");
+}
+processOrder.workflowId = "workflow//./input//processOrder";
@@ -0,0 +1,15 @@
+/**__internal_workflows{"workflows":{"input.js":{"processOrder":{"workflowId":"workflow//./input//processOrder"}}}}*/;
+// Allowed with allowNonFunctionExports
+export const MAX_RETRIES = 3;
+export class RetryPolicy {
+}
+export default 'orders';
+// Still errors - exported functions must be async
+export function syncHelper() {
+    return MAX_RETRIES;
+}
+export async function processOrder(orderId) {
+    return orderId;
+}
+processOrder.workflowId = "workflow//./input//processOrder";
+globalThis.__private_workflows.set("workflow//./input//processOrder", processOrder);
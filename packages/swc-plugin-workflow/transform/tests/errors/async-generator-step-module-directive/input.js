@@ -0,0 +1,9 @@
+'use step';
+
+export async function* streamRows(query) {
+  yield await query.next();
+}
+
+export async function countRows(query) {
+  return query.count();
+}
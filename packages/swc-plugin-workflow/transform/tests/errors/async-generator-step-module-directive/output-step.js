@@ -0,0 +1,10 @@
+import { registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"steps":{"input.js":{"countRows":{"stepId":"step//./input//countRows"},"streamRows":{"stepId":"step//./input//streamRows"}}}}*/;
+export async function* streamRows(query) {
+    yield await query.next();
+}
+export async function countRows(query) {
+    return query.count();
+}
+registerStepFunction("step//./input//streamRows", streamRows);
+registerStepFunction("step//./input//countRows", countRows);
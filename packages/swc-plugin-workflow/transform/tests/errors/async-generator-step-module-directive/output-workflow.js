@@ -0,0 +1,4 @@
+'use step';
+/**__internal_workflows{"steps":{"input.js":{"countRows":{"stepId":"step//./input//countRows"},"streamRows":{"stepId":"step//./input//streamRows"}}}}*/;
+export var streamRows = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//streamRows");
+export var countRows = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//countRows");
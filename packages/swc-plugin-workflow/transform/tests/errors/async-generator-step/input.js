@@ -0,0 +1,19 @@
+export async function* streamPages(url) {
+  'use step';
+  yield await fetch(url);
+}
+
+export async function crawl(url) {
+  'use workflow';
+  async function* readChunks(source) {
+    'use step';
+    for (const chunk of source) {
+      yield chunk;
+    }
+  }
+  const pages = [];
+  for await (const page of streamPages(url)) {
+    pages.push(page);
+  }
+  return readChunks(pages);
+}
@@ -0,0 +1,16 @@
+import { registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"crawl":{"workflowId":"workflow//./input//crawl"}}},"steps":{"input.js":{"readChunks":{"stepId":"step//./input//readChunks"},"streamPages":{"stepId":"step//./input//streamPages"}}}}*/;
+async function* crawl$readChunks(source) {
+    for (const chunk of source){
+        yield chunk;
+    }
+}
+export async function* streamPages(url) {
+    yield await fetch(url);
+}
+export async function crawl(url) {
+    throw new Error("You attempted to execute workflow crawl function directly. To start a workflow, use start(crawl) from workflow/api");
+}
+crawl.workflowId = "workflow//./input//crawl";
+registerStepFunction("step//./input//streamPages", streamPages);
+registerStepFunction("step//./input//crawl/readChunks", crawl$readChunks);
@@ -0,0 +1,12 @@
+/**__internal_workflows{"workflows":{"input.js":{"crawl":{"workflowId":"workflow//./input//crawl"}}},"steps":{"input.js":{"readChunks":{"stepId":"step//./input//readChunks"},"streamPages":{"stepId":"step//./input//streamPages"}}}}*/;
+export var streamPages = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//streamPages");
+export async function crawl(url) {
+    var readChunks = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//crawl/readChunks");
+    const pages = [];
+    for await (const page of streamPages(url)){
+        pages.push(page);
+    }
+    return readChunks(pages);
+}
+crawl.workflowId = "workflow//./input//crawl";
+globalThis.__private_workflows.set("workflow//./input//crawl", crawl);
@@ -1,7 +1,7 @@
 // Error: Can't have both directives in the same file
 import { registerStepFunction } from "workflow/internal/private";
-/**__internal_workflows{"steps":{"input.js":{"test":{"stepId":"step//./input//test"}}}}*/;
 'use workflow';
+/**__internal_workflows{"steps":{"input.js":{"test":{"stepId":"step//./input//test"}}}}*/;
 export async function test() {
     return 42;
 }
@@ -1,5 +1,5 @@
-/**__internal_workflows{"steps":{"input.js":{"test":{"stepId":"step//./input//test"}}}}*/;
 // Error: Can't have both directives in the same file
 'use step';
 'use workflow';
+/**__internal_workflows{"steps":{"input.js":{"test":{"stepId":"step//./input//test"}}}}*/;
 export var test = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//test");
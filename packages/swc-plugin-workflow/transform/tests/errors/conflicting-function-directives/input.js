@@ -0,0 +1,20 @@
+export async function fetchData(url) {
+  'use step';
+  'use workflow';
+  return fetch(url);
+}
+
+export const run = async () => {
+  'use workflow';
+  'use step';
+  return fetchData('/api');
+};
+
+export const handlers = {
+  async process(input) {
+    'use strict';
+    'use step';
+    'use workflow';
+    return input;
+  },
+};
@@ -0,0 +1,22 @@
+import { registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"fetchData":{"workflowId":"workflow//./input//fetchData"},"run":{"workflowId":"workflow//./input//run"}}},"steps":{"input.js":{"fetchData":{"stepId":"step//./input//fetchData"},"handlers/process":{"stepId":"step//./input//handlers/process"},"run":{"stepId":"step//./input//run"}}}}*/;
+var handlers$process = async function(input) {
+    'use strict';
+    'use workflow';
+    return input;
+};
+export async function fetchData(url) {
+    'use workflow';
+    return fetch(url);
+}
+fetchData.workflowId = "workflow//./input//fetchData";
+export const run = async ()=>{
+    throw new Error("You attempted to execute workflow run function directly. To start a workflow, use start(run) from workflow/api");
+};
+run.workflowId = "workflow//./input//run";
+export const handlers = {
+    process: handlers$process
+};
+registerStepFunction("step//./input//fetchData", fetchData);
+registerStepFunction("step//./input//run", run);
+registerStepFunction("step//./input//handlers/process", handlers$process);
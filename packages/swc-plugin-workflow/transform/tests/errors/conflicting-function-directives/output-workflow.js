@@ -0,0 +1,6 @@
+/**__internal_workflows{"steps":{"input.js":{"fetchData":{"stepId":"step//./input//fetchData"},"handlers/process":{"stepId":"step//./input//handlers/process"},"run":{"stepId":"step//./input//run"}}}}*/;
+export var fetchData = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//fetchData");
+export const run = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//run");
+export const handlers = {
+    process: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//handlers/process")
+};
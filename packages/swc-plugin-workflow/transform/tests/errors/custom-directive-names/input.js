@@ -0,0 +1,9 @@
+export function add(a, b) {
+  'server step';
+  return a + b;
+}
+
+export async function subtract(a, b) {
+  'server stepp';
+  return a - b;
+}
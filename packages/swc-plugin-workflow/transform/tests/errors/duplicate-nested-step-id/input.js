@@ -0,0 +1,15 @@
+export async function total(items) {
+  'use workflow';
+  if (items.length > 1) {
+    const add = async (x, y) => {
+      'use step';
+      return x + y;
+    };
+    return add(items[0], items[1]);
+  }
+  const add = async (x, y) => {
+    'use step';
+    return x + y;
+  };
+  return add(items[0], 0);
+}
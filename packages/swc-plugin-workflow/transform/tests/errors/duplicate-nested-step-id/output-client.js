@@ -0,0 +1,5 @@
+/**__internal_workflows{"workflows":{"input.js":{"total":{"workflowId":"workflow//./input//total"}}}}*/;
+export async function total(items) {
+    throw new Error("You attempted to execute workflow total function directly. To start a workflow, use start(total) from workflow/api");
+}
+total.workflowId = "workflow//./input//total";
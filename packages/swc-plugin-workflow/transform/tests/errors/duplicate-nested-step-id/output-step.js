@@ -0,0 +1,9 @@
+import { registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"total":{"workflowId":"workflow//./input//total"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"}}}}*/;
+var total$add = async (x, y)=>x + y;
+var total$add = async (x, y)=>x + y;
+export async function total(items) {
+    throw new Error("You attempted to execute workflow total function directly. To start a workflow, use start(total) from workflow/api");
+}
+total.workflowId = "workflow//./input//total";
+registerStepFunction("step//./input//total/add", total$add);
@@ -0,0 +1,11 @@
+/**__internal_workflows{"workflows":{"input.js":{"total":{"workflowId":"workflow//./input//total"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"}}}}*/;
+export async function total(items) {
+    if (items.length > 1) {
+        const add = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//total/add");
+        return add(items[0], items[1]);
+    }
+    const add = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//total/add");
+    return add(items[0], 0);
+}
+total.workflowId = "workflow//./input//total";
+globalThis.__private_workflows.set("workflow//./input//total", total);
@@ -0,0 +1,34 @@
+export async function sum(values) {
+  'use workflow';
+
+  // OK: a nested function without a directive has its own `this` and `arguments`
+  function count() {
+    return arguments.length + this.offset;
+  }
+
+  // OK: so do constructors and methods, including their `super`
+  class Totals extends Base {
+    constructor(items) {
+      super(items);
+      this.items = items;
+    }
+
+    total() {
+      return super.total() + count(...this.items);
+    }
+  }
+
+  const add = async function (a, b) {
+    'use step';
+    // OK: also inside a step
+    function describe() {
+      return `${arguments.length} values from ${this.name}`;
+    }
+    return describe.call({ name: 'add' }, a, b) && a + b;
+  };
+
+  // Error: arrow functions share the workflow's `arguments`
+  const lexical = () => arguments.length;
+
+  return add(new Totals(values).total(), lexical());
+}
@@ -0,0 +1,25 @@
+/**__internal_workflows{"workflows":{"input.js":{"sum":{"workflowId":"workflow//./input//sum"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"}}}}*/;
+export async function sum(values) {
+    // OK: a nested function without a directive has its own `this` and `arguments`
+    function count() {
+        return arguments.length + this.offset;
+    }
+    // OK: so do constructors and methods, including their `super`
+    class Totals extends Base {
+        constructor(items){
+            super(items);
+            this.items = items;
+        }
+        total() {
+            return super.total() + count(...this.items);
+        }
+    }
+    const add = async function(a, b) {
+        return globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//add")(a, b);
+    };
+    // Error: arrow functions share the workflow's `arguments`
+    const lexical = ()=>arguments.length;
+    return add(new Totals(values).total(), lexical());
+}
+sum.workflowId = "workflow//./input//sum";
+globalThis.__private_workflows.set("workflow//./input//sum", sum);
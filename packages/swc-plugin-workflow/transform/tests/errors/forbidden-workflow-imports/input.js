@@ -0,0 +1,20 @@
+// OK: only the step uses it, so it's removed from the workflow output
+import { readFile } from 'fs/promises';
+// Error: listed module, with or without `node:` and including subpaths
+import { execSync } from 'child_process';
+import fs from 'node:fs';
+import { stat } from 'fs/promises';
+// OK: not listed
+import { createHash } from 'node:crypto';
+import { fsHelpers } from 'fsx';
+
+async function loadConfig(path) {
+  'use step';
+  return JSON.parse(await readFile(path, 'utf8'));
+}
+
+export async function deploy(path) {
+  'use workflow';
+  const config = await loadConfig(path);
+  return { config, fs, stat, execSync, createHash, fsHelpers };
+}
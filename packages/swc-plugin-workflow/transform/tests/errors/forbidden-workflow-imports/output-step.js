@@ -0,0 +1,19 @@
+// OK: only the step uses it, so it's removed from the workflow output
+import { registerStepFunction } from "workflow/internal/private";
+import { readFile } from 'fs/promises';
+// Error: listed module, with or without `node:` and including subpaths
+import { execSync } from 'child_process';
+import fs from 'node:fs';
+import { stat } from 'fs/promises';
+// OK: not listed
+import { createHash } from 'node:crypto';
+import { fsHelpers } from 'fsx';
+/**__internal_workflows{"workflows":{"input.js":{"deploy":{"workflowId":"workflow//./input//deploy"}}},"steps":{"input.js":{"loadConfig":{"stepId":"step//./input//loadConfig"}}}}*/;
+async function loadConfig(path) {
+    return JSON.parse(await readFile(path, 'utf8'));
+}
+export async function deploy(path) {
+    throw new Error("You attempted to execute workflow deploy function directly. To start a workflow, use start(deploy) from workflow/api");
+}
+deploy.workflowId = "workflow//./input//deploy";
+registerStepFunction("step//./input//loadConfig", loadConfig);
@@ -0,0 +1,23 @@
+// OK: only the step uses it, so it's removed from the workflow output
+// Error: listed module, with or without `node:` and including subpaths
+import { execSync } from 'child_process';
+import fs from 'node:fs';
+import { stat } from 'fs/promises';
+// OK: not listed
+import { createHash } from 'node:crypto';
+import { fsHelpers } from 'fsx';
+/**__internal_workflows{"workflows":{"input.js":{"deploy":{"workflowId":"workflow//./input//deploy"}}},"steps":{"input.js":{"loadConfig":{"stepId":"step//./input//loadConfig"}}}}*/;
+var loadConfig = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//loadConfig");
+export async function deploy(path) {
+    const config = await loadConfig(path);
+    return {
+        config,
+        fs,
+        stat,
+        execSync,
+        createHash,
+        fsHelpers
+    };
+}
+deploy.workflowId = "workflow//./input//deploy";
+globalThis.__private_workflows.set("workflow//./input//deploy", deploy);
@@ -0,0 +1,25 @@
+export const api = {
+    get handler () {
+        return async (input)=>{
+            "use step";
+            return input;
+        };
+    },
+    set handler (value){
+        this.run = async function() {
+            "use workflow";
+            return value;
+        };
+    },
+    get helper () {
+        return async (input)=>input;
+    }
+};
+export class Service {
+    static get fetcher() {
+        return async function fetchItem(id) {
+            "use step";
+            return id;
+        };
+    }
+}
@@ -0,0 +1,26 @@
+export async function fetch_data(url) {
+  'use step';
+  return fetch(url);
+}
+
+export async function fetchUser(id) {
+  'use step';
+  return fetch(`/api/users/${id}`);
+}
+
+export const tools = {
+  async sendEmail(to) {
+    'use step';
+    return to;
+  },
+};
+
+export async function onboard(id) {
+  'use workflow';
+  return fetchUser(id);
+}
+
+export async function processOrder(id) {
+  'use workflow';
+  return fetch_data(`/api/orders/${id}`);
+}
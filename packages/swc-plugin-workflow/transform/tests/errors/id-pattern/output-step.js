@@ -0,0 +1,25 @@
+import { registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"onboard":{"workflowId":"workflow//./input//onboard"},"processOrder":{"workflowId":"workflow//./input//processOrder"}}},"steps":{"input.js":{"fetchUser":{"stepId":"step//./input//fetchUser"},"fetch_data":{"stepId":"step//./input//fetch_data"},"tools/sendEmail":{"stepId":"step//./input//tools/sendEmail"}}}}*/;
+var tools$sendEmail = async function(to) {
+    return to;
+};
+export async function fetch_data(url) {
+    return fetch(url);
+}
+export async function fetchUser(id) {
+    return fetch(`/api/users/${id}`);
+}
+export const tools = {
+    sendEmail: tools$sendEmail
+};
+export async function onboard(id) {
+    throw new Error("You attempted to execute workflow onboard function directly. To start a workflow, use start(onboard) from workflow/api");
+}
+onboard.workflowId = "workflow//./input//onboard";
+export async function processOrder(id) {
+    throw new Error("You attempted to execute workflow processOrder function directly. To start a workflow, use start(processOrder) from workflow/api");
+}
+processOrder.workflowId = "workflow//./input//processOrder";
+registerStepFunction("step//./input//fetch_data", fetch_data);
+registerStepFunction("step//./input//fetchUser", fetchUser);
+registerStepFunction("step//./input//tools/sendEmail", tools$sendEmail);
@@ -0,0 +1,16 @@
+/**__internal_workflows{"workflows":{"input.js":{"onboard":{"workflowId":"workflow//./input//onboard"},"processOrder":{"workflowId":"workflow//./input//processOrder"}}},"steps":{"input.js":{"fetchUser":{"stepId":"step//./input//fetchUser"},"fetch_data":{"stepId":"step//./input//fetch_data"},"tools/sendEmail":{"stepId":"step//./input//tools/sendEmail"}}}}*/;
+export var fetch_data = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//fetch_data");
+export var fetchUser = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//fetchUser");
+export const tools = {
+    sendEmail: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//tools/sendEmail")
+};
+export async function onboard(id) {
+    return fetchUser(id);
+}
+onboard.workflowId = "workflow//./input//onboard";
+globalThis.__private_workflows.set("workflow//./input//onboard", onboard);
+export async function processOrder(id) {
+    return fetch_data(`/api/orders/${id}`);
+}
+processOrder.workflowId = "workflow//./input//processOrder";
+globalThis.__private_workflows.set("workflow//./input//processOrder", processOrder);
@@ -0,0 +1,14 @@
+const data = (async () => {
+  'use step';
+  return load();
+})();
+
+await (async function () {
+  'use workflow';
+  return run();
+})();
+
+// No directive, so nothing to report
+export const ready = (async () => {
+  return init();
+})();
@@ -0,0 +1,8 @@
+await async function() {
+    'use workflow';
+    return run();
+}();
+// No directive, so nothing to report
+export const ready = (async ()=>{
+    return init();
+})();
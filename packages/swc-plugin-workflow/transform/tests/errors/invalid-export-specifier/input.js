@@ -0,0 +1,15 @@
+'use step';
+
+function formatName(user) {
+  return `${user.first} ${user.last}`;
+}
+
+const retries = 3;
+
+class Mailer {}
+
+async function fetchUser(id) {
+  return fetch(`/api/users/${id}`);
+}
+
+export { formatName, retries, Mailer, fetchUser };
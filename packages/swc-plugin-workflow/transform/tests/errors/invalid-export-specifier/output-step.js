@@ -0,0 +1,13 @@
+import { registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"steps":{"input.js":{"fetchUser":{"stepId":"step//./input//fetchUser"}}}}*/;
+function formatName(user) {
+    return `${user.first} ${user.last}`;
+}
+const retries = 3;
+class Mailer {
+}
+async function fetchUser(id) {
+    return fetch(`/api/users/${id}`);
+}
+export { formatName, retries, Mailer, fetchUser };
+registerStepFunction("step//./input//fetchUser", fetchUser);
@@ -0,0 +1,10 @@
+'use step';
+/**__internal_workflows{"steps":{"input.js":{"fetchUser":{"stepId":"step//./input//fetchUser"}}}}*/;
+function formatName(user) {
+    return `${user.first} ${user.last}`;
+}
+const retries = 3;
+class Mailer {
+}
+var fetchUser = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//fetchUser");
+export { formatName, retries, Mailer, fetchUser };
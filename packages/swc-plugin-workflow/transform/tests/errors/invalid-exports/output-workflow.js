@@ -1,5 +1,5 @@
-/**__internal_workflows{"steps":{"input.js":{"validStep":{"stepId":"step//./input//validStep"}}}}*/;
 'use step';
+/**__internal_workflows{"steps":{"input.js":{"validStep":{"stepId":"step//./input//validStep"}}}}*/;
 // These should all error - only async functions allowed
 export const value = 42;
 export function syncFunc() {
@@ -0,0 +1,8 @@
+export async function normalize(input) {
+  function trim(value) {
+    return value.trim();
+  }
+  // Error: the helper declaration ends the directive prologue
+  'use step';
+  return trim(input);
+}
@@ -0,0 +1,11 @@
+import { registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"steps":{"input.js":{"normalize":{"stepId":"step//./input//normalize"}}}}*/;
+export async function normalize(input) {
+    function trim(value) {
+        return value.trim();
+    }
+    // Error: the helper declaration ends the directive prologue
+    'use step';
+    return trim(input);
+}
+registerStepFunction("step//./input//normalize", normalize);
@@ -0,0 +1,2 @@
+/**__internal_workflows{"steps":{"input.js":{"normalize":{"stepId":"step//./input//normalize"}}}}*/;
+export var normalize = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//normalize");
@@ -0,0 +1,11 @@
+const retries = 3;
+
+export async function add(a, b) {
+  'use step';
+  return a + b;
+}
+
+export async function sum(a, b) {
+  'use workflow';
+  return add(a, b + retries);
+}
@@ -0,0 +1,8 @@
+/**__internal_workflows{"workflows":{"input.js":{"sum":{"workflowId":"workflow//./input//sum"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"}}}}*/;
+const retries = 3;
+export var add = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//add");
+export async function sum(a, b) {
+    return add(a, b + retries);
+}
+sum.workflowId = "workflow//./input//sum";
+globalThis.__private_workflows.set("workflow//./input//sum", sum);
@@ -0,0 +1,8 @@
+export async function fetchUser(id) {
+    'use stp';
+    return fetch(`/api/users/${id}`);
+}
+export async function fetchOrder(id) {
+    'use sep';
+    return fetch(`/api/orders/${id}`);
+}
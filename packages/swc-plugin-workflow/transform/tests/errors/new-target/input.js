@@ -0,0 +1,28 @@
+export async function create(kind) {
+  'use workflow';
+  // Error: workflows are never constructed
+  if (new.target) {
+    return kind;
+  }
+  return kind;
+}
+
+export default {
+  async build(kind) {
+    'use step';
+    // Error: steps are never constructed
+    return new.target ? null : kind;
+  },
+};
+
+export async function count(items) {
+  'use workflow';
+  // OK: a nested function without a directive has its own `new.target`
+  function Counter() {
+    if (!new.target) {
+      return new Counter();
+    }
+    this.total = items.length;
+  }
+  return new Counter().total;
+}
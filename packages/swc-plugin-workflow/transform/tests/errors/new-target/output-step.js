@@ -0,0 +1,16 @@
+/**__internal_workflows{"workflows":{"input.js":{"count":{"workflowId":"workflow//./input//count"},"create":{"workflowId":"workflow//./input//create"}}}}*/;
+export async function create(kind) {
+    throw new Error("You attempted to execute workflow create function directly. To start a workflow, use start(create) from workflow/api");
+}
+create.workflowId = "workflow//./input//create";
+export default {
+    async build (kind) {
+        'use step';
+        // Error: steps are never constructed
+        return new.target ? null : kind;
+    }
+};
+export async function count(items) {
+    throw new Error("You attempted to execute workflow count function directly. To start a workflow, use start(count) from workflow/api");
+}
+count.workflowId = "workflow//./input//count";
@@ -0,0 +1,29 @@
+/**__internal_workflows{"workflows":{"input.js":{"count":{"workflowId":"workflow//./input//count"},"create":{"workflowId":"workflow//./input//create"}}}}*/;
+export async function create(kind) {
+    // Error: workflows are never constructed
+    if (new.target) {
+        return kind;
+    }
+    return kind;
+}
+create.workflowId = "workflow//./input//create";
+globalThis.__private_workflows.set("workflow//./input//create", create);
+export default {
+    async build (kind) {
+        'use step';
+        // Error: steps are never constructed
+        return new.target ? null : kind;
+    }
+};
+export async function count(items) {
+    // OK: a nested function without a directive has its own `new.target`
+    function Counter() {
+        if (!new.target) {
+            return new Counter();
+        }
+        this.total = items.length;
+    }
+    return new Counter().total;
+}
+count.workflowId = "workflow//./input//count";
+globalThis.__private_workflows.set("workflow//./input//count", count);
@@ -1,6 +1,6 @@
+// Error: sync function with use step
 import { registerStepFunction } from "workflow/internal/private";
 /**__internal_workflows{"workflows":{"input.js":{"validWorkflow":{"workflowId":"workflow//./input//validWorkflow"}}},"steps":{"input.js":{"validStep":{"stepId":"step//./input//validStep"}}}}*/;
-// Error: sync function with use step
 export function syncStep() {
     'use step';
     return 42;
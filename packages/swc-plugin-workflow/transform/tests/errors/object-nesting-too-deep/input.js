@@ -0,0 +1,21 @@
+export const tools = {
+  search: {
+    execute: async (query) => {
+      'use step';
+      return fetch(`/api/search?q=${query}`);
+    },
+  },
+  limits: {
+    search: {
+      perMinute: 60,
+    },
+  },
+  admin: {
+    users: {
+      remove: async (id) => {
+        'use step';
+        return fetch(`/api/users/${id}`, { method: 'DELETE' });
+      },
+    },
+  },
+};
@@ -0,0 +1,26 @@
+import { registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"steps":{"input.js":{"tools/search/execute":{"stepId":"step//./input//tools/search/execute"}}}}*/;
+var tools$search$execute = async function(query) {
+    return fetch(`/api/search?q=${query}`);
+};
+export const tools = {
+    search: {
+        execute: tools$search$execute
+    },
+    limits: {
+        search: {
+            perMinute: 60
+        }
+    },
+    admin: {
+        users: {
+            remove: async (id)=>{
+                'use step';
+                return fetch(`/api/users/${id}`, {
+                    method: 'DELETE'
+                });
+            }
+        }
+    }
+};
+registerStepFunction("step//./input//tools/search/execute", tools$search$execute);
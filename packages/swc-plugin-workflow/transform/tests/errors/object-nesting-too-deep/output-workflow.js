@@ -0,0 +1,21 @@
+/**__internal_workflows{"steps":{"input.js":{"tools/search/execute":{"stepId":"step//./input//tools/search/execute"}}}}*/;
+export const tools = {
+    search: {
+        execute: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//tools/search/execute")
+    },
+    limits: {
+        search: {
+            perMinute: 60
+        }
+    },
+    admin: {
+        users: {
+            remove: async (id)=>{
+                'use step';
+                return fetch(`/api/users/${id}`, {
+                    method: 'DELETE'
+                });
+            }
+        }
+    }
+};
@@ -0,0 +1,10 @@
+export class Account {
+    get #total() {
+        'use step';
+        return 0;
+    }
+    set #total(value) {
+        'use step';
+        console.log(value);
+    }
+}
@@ -0,0 +1,6 @@
+export class Svc {
+    #run = async (input)=>{
+        'use step';
+        return input;
+    };
+}
@@ -0,0 +1,23 @@
+export class Worker {
+  static async #prepare(input) {
+    'use step';
+    return input.trim();
+  }
+
+  async #doWork(input) {
+    'use step';
+    return input.toUpperCase();
+  }
+
+  static async run(input) {
+    'use workflow';
+    return Worker.#prepare(input);
+  }
+}
+
+export class Scheduler {
+  static async #orchestrate(input) {
+    'use workflow';
+    return input;
+  }
+}
@@ -0,0 +1,21 @@
+/**__internal_workflows{"workflows":{"input.js":{"Worker.run":{"workflowId":"workflow//./input//Worker.run"}}}}*/;
+export class Worker {
+    static async #prepare(input) {
+        'use step';
+        return input.trim();
+    }
+    async #doWork(input) {
+        'use step';
+        return input.toUpperCase();
+    }
+    static async run(input) {
+        throw new Error("You attempted to execute workflow Worker.run function directly. To start a workflow, use start(workflow) from workflow/api");
+    }
+}
+export class Scheduler {
+    static async #orchestrate(input) {
+        'use workflow';
+        return input;
+    }
+}
+Worker.run.workflowId = "workflow//./input//Worker.run";
@@ -0,0 +1,22 @@
+/**__internal_workflows{"workflows":{"input.js":{"Worker.run":{"workflowId":"workflow//./input//Worker.run"}}}}*/;
+export class Worker {
+    static async #prepare(input) {
+        'use step';
+        return input.trim();
+    }
+    async #doWork(input) {
+        'use step';
+        return input.toUpperCase();
+    }
+    static async run(input) {
+        return Worker.#prepare(input);
+    }
+}
+export class Scheduler {
+    static async #orchestrate(input) {
+        'use workflow';
+        return input;
+    }
+}
+Worker.run.workflowId = "workflow//./input//Worker.run";
+globalThis.__private_workflows.set("workflow//./input//Worker.run", Worker.run);
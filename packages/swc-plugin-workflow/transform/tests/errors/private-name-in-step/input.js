@@ -0,0 +1,17 @@
+export class Counter {
+  #count = 0;
+
+  createWorkflow() {
+    return async function counterWorkflow(amount) {
+      'use workflow';
+
+      // This should error - `this.#count` is a `this` access, not a variable to capture
+      const read = async () => {
+        'use step';
+        return this.#count + amount;
+      };
+
+      return await read();
+    };
+  }
+}
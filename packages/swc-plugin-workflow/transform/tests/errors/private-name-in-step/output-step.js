@@ -0,0 +1,18 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"steps":{"input.js":{"read":{"stepId":"step//./input//read"}}}}*/;
+var read = async ()=>{
+    const { amount } = __private_getClosureVars();
+    return this.#count + amount;
+};
+export class Counter {
+    #count = 0;
+    createWorkflow() {
+        return async function counterWorkflow(amount) {
+            'use workflow';
+            // This should error - `this.#count` is a `this` access, not a variable to capture
+            const read = read;
+            return await read();
+        };
+    }
+}
+registerStepFunction("step//./input//read", read);
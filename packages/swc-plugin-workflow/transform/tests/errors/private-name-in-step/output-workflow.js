@@ -0,0 +1,14 @@
+/**__internal_workflows{"steps":{"input.js":{"read":{"stepId":"step//./input//read"}}}}*/;
+export class Counter {
+    #count = 0;
+    createWorkflow() {
+        return async function counterWorkflow(amount) {
+            'use workflow';
+            // This should error - `this.#count` is a `this` access, not a variable to capture
+            const read = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//read", ()=>({
+                    amount
+                }));
+            return await read();
+        };
+    }
+}
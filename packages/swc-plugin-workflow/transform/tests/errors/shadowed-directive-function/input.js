@@ -0,0 +1,21 @@
+import { somethingElse, otherWorkflow } from './other';
+
+async function fetchData() {
+  'use step';
+  return 1;
+}
+
+// Redeclaring a step replaces the binding its registration refers to
+const fetchData = somethingElse;
+
+export const runWorkflow = async () => {
+  'use workflow';
+  return await fetchData();
+};
+
+var { runWorkflow: alias, ...rest } = otherWorkflow;
+class runWorkflow {}
+
+// Redeclaring a plain function is not reported
+function helper() {}
+var helper = 1;
@@ -0,0 +1,19 @@
+import { registerStepFunction } from "workflow/internal/private";
+import { somethingElse, otherWorkflow } from './other';
+/**__internal_workflows{"workflows":{"input.js":{"runWorkflow":{"workflowId":"workflow//./input//runWorkflow"}}},"steps":{"input.js":{"fetchData":{"stepId":"step//./input//fetchData"}}}}*/;
+async function fetchData() {
+    return 1;
+}
+// Redeclaring a step replaces the binding its registration refers to
+const fetchData = somethingElse;
+export const runWorkflow = async ()=>{
+    throw new Error("You attempted to execute workflow runWorkflow function directly. To start a workflow, use start(runWorkflow) from workflow/api");
+};
+runWorkflow.workflowId = "workflow//./input//runWorkflow";
+var { runWorkflow: alias, ...rest } = otherWorkflow;
+class runWorkflow {
+}
+// Redeclaring a plain function is not reported
+function helper() {}
+var helper = 1;
+registerStepFunction("step//./input//fetchData", fetchData);
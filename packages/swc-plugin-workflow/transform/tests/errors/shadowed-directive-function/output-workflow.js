@@ -0,0 +1,11 @@
+/**__internal_workflows{"workflows":{"input.js":{"runWorkflow":{"workflowId":"workflow//./input//runWorkflow"}}},"steps":{"input.js":{"fetchData":{"stepId":"step//./input//fetchData"}}}}*/;
+var fetchData = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//fetchData");
+// Redeclaring a step replaces the binding its registration refers to
+const fetchData = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//fetchData");
+export const runWorkflow = async ()=>{
+    return await fetchData();
+};
+runWorkflow.workflowId = "workflow//./input//runWorkflow";
+globalThis.__private_workflows.set("workflow//./input//runWorkflow", runWorkflow);
+class runWorkflow {
+}
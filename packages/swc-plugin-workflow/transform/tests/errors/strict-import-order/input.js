@@ -0,0 +1,16 @@
+'use step';
+
+import { fetchUser } from './users';
+
+const retries = 3;
+
+// This should error - imports must come before any other statement
+import { log } from './log';
+
+export async function loadUser(id) {
+  return await fetchUser(id, retries);
+}
+
+export async function logUser(id) {
+  log(id);
+}
@@ -0,0 +1,14 @@
+import { registerStepFunction } from "workflow/internal/private";
+import { fetchUser } from './users';
+/**__internal_workflows{"steps":{"input.js":{"loadUser":{"stepId":"step//./input//loadUser"},"logUser":{"stepId":"step//./input//logUser"}}}}*/;
+const retries = 3;
+// This should error - imports must come before any other statement
+import { log } from './log';
+export async function loadUser(id) {
+    return await fetchUser(id, retries);
+}
+export async function logUser(id) {
+    log(id);
+}
+registerStepFunction("step//./input//loadUser", loadUser);
+registerStepFunction("step//./input//logUser", logUser);
@@ -0,0 +1,4 @@
+'use step';
+/**__internal_workflows{"steps":{"input.js":{"loadUser":{"stepId":"step//./input//loadUser"},"logUser":{"stepId":"step//./input//logUser"}}}}*/;
+export var loadUser = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//loadUser");
+export var logUser = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//logUser");
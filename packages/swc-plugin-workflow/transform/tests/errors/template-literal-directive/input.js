@@ -0,0 +1,9 @@
+export async function fetchData(url) {
+  `use step`;
+  return fetch(url);
+}
+
+export const run = async () => {
+  `use workflow`;
+  return fetchData('/api');
+};
@@ -0,0 +1,20 @@
+export class Counter {
+  #count = 1;
+
+  // OK: `this` is the instance the step was called on
+  async increment(by) {
+    'use step';
+    this.#count += by;
+    return this.#count;
+  }
+
+  async reset() {
+    'use step';
+    // Error: the nested step is hoisted out of the method, away from its instance
+    const clear = async () => {
+      'use step';
+      this.#count = 0;
+    };
+    await clear();
+  }
+}
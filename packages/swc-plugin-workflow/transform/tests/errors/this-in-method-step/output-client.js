@@ -0,0 +1,18 @@
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+/**__internal_workflows{"steps":{"input.js":{"Counter#increment":{"stepId":"step//./input//Counter#increment"},"Counter#reset":{"stepId":"step//./input//Counter#reset"},"clear":{"stepId":"step//./input//clear"}}},"classes":{"input.js":{"Counter":{"classId":"class//./input//Counter"}}}}*/;
+export class Counter {
+    #count = 1;
+    // OK: `this` is the instance the step was called on
+    async increment(by) {
+        this.#count += by;
+        return this.#count;
+    }
+    async reset() {
+        // Error: the nested step is hoisted out of the method, away from its instance
+        const clear = async ()=>{
+            this.#count = 0;
+        };
+        await clear();
+    }
+}
+registerSerializationClass("class//./input//Counter", Counter);
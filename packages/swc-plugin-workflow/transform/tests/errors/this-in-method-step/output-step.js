@@ -0,0 +1,23 @@
+import { registerStepFunction } from "workflow/internal/private";
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+/**__internal_workflows{"steps":{"input.js":{"Counter#increment":{"stepId":"step//./input//Counter#increment"},"Counter#reset":{"stepId":"step//./input//Counter#reset"},"clear":{"stepId":"step//./input//clear"}}},"classes":{"input.js":{"Counter":{"classId":"class//./input//Counter"}}}}*/;
+var Counter$reset$clear = async ()=>{
+    this.#count = 0;
+};
+export class Counter {
+    #count = 1;
+    // OK: `this` is the instance the step was called on
+    async increment(by) {
+        this.#count += by;
+        return this.#count;
+    }
+    async reset() {
+        // Error: the nested step is hoisted out of the method, away from its instance
+        const clear = Counter$reset$clear;
+        await clear();
+    }
+}
+registerStepFunction("step//./input//Counter$reset/clear", Counter$reset$clear);
+registerStepFunction("step//./input//Counter#increment", Counter.prototype["increment"]);
+registerStepFunction("step//./input//Counter#reset", Counter.prototype["reset"]);
+registerSerializationClass("class//./input//Counter", Counter);
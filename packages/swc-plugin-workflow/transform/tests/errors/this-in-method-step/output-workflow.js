@@ -0,0 +1,8 @@
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+/**__internal_workflows{"steps":{"input.js":{"Counter#increment":{"stepId":"step//./input//Counter#increment"},"Counter#reset":{"stepId":"step//./input//Counter#reset"}}},"classes":{"input.js":{"Counter":{"classId":"class//./input//Counter"}}}}*/;
+export class Counter {
+    #count = 1;
+}
+Counter.prototype["increment"] = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Counter#increment");
+Counter.prototype["reset"] = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Counter#reset");
+registerSerializationClass("class//./input//Counter", Counter);
@@ -0,0 +1,32 @@
+async function internalCleanup() {
+  'use workflow';
+  return null;
+}
+
+const nightlyReport = async () => {
+  'use workflow';
+  return null;
+};
+
+export async function onboard() {
+  'use workflow';
+  return null;
+}
+
+async function checkout() {
+  'use workflow';
+  return null;
+}
+
+const refund = async function () {
+  'use workflow';
+  return null;
+};
+
+async function archive() {
+  'use workflow';
+  return null;
+}
+
+export { checkout, refund as issueRefund };
+export default archive;
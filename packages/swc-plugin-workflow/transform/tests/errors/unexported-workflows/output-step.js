@@ -0,0 +1,27 @@
+/**__internal_workflows{"workflows":{"input.js":{"archive":{"workflowId":"workflow//./input//archive"},"checkout":{"workflowId":"workflow//./input//checkout"},"internalCleanup":{"workflowId":"workflow//./input//internalCleanup"},"nightlyReport":{"workflowId":"workflow//./input//nightlyReport"},"onboard":{"workflowId":"workflow//./input//onboard"},"refund":{"workflowId":"workflow//./input//refund"}}}}*/;
+async function internalCleanup() {
+    throw new Error("You attempted to execute workflow internalCleanup function directly. To start a workflow, use start(internalCleanup) from workflow/api");
+}
+internalCleanup.workflowId = "workflow//./input//internalCleanup";
+const nightlyReport = async ()=>{
+    throw new Error("You attempted to execute workflow nightlyReport function directly. To start a workflow, use start(nightlyReport) from workflow/api");
+};
+nightlyReport.workflowId = "workflow//./input//nightlyReport";
+export async function onboard() {
+    throw new Error("You attempted to execute workflow onboard function directly. To start a workflow, use start(onboard) from workflow/api");
+}
+onboard.workflowId = "workflow//./input//onboard";
+async function checkout() {
+    throw new Error("You attempted to execute workflow checkout function directly. To start a workflow, use start(checkout) from workflow/api");
+}
+checkout.workflowId = "workflow//./input//checkout";
+const refund = async function() {
+    throw new Error("You attempted to execute workflow refund function directly. To start a workflow, use start(refund) from workflow/api");
+};
+refund.workflowId = "workflow//./input//refund";
+async function archive() {
+    throw new Error("You attempted to execute workflow archive function directly. To start a workflow, use start(archive) from workflow/api");
+}
+archive.workflowId = "workflow//./input//archive";
+export { checkout, refund as issueRefund };
+export default archive;
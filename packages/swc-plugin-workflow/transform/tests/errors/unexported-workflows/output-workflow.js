@@ -0,0 +1,33 @@
+/**__internal_workflows{"workflows":{"input.js":{"archive":{"workflowId":"workflow//./input//archive"},"checkout":{"workflowId":"workflow//./input//checkout"},"internalCleanup":{"workflowId":"workflow//./input//internalCleanup"},"nightlyReport":{"workflowId":"workflow//./input//nightlyReport"},"onboard":{"workflowId":"workflow//./input//onboard"},"refund":{"workflowId":"workflow//./input//refund"}}}}*/;
+async function internalCleanup() {
+    return null;
+}
+internalCleanup.workflowId = "workflow//./input//internalCleanup";
+globalThis.__private_workflows.set("workflow//./input//internalCleanup", internalCleanup);
+const nightlyReport = async ()=>{
+    return null;
+};
+nightlyReport.workflowId = "workflow//./input//nightlyReport";
+globalThis.__private_workflows.set("workflow//./input//nightlyReport", nightlyReport);
+export async function onboard() {
+    return null;
+}
+onboard.workflowId = "workflow//./input//onboard";
+globalThis.__private_workflows.set("workflow//./input//onboard", onboard);
+async function checkout() {
+    return null;
+}
+checkout.workflowId = "workflow//./input//checkout";
+globalThis.__private_workflows.set("workflow//./input//checkout", checkout);
+const refund = async function() {
+    return null;
+};
+refund.workflowId = "workflow//./input//refund";
+globalThis.__private_workflows.set("workflow//./input//refund", refund);
+async function archive() {
+    return null;
+}
+archive.workflowId = "workflow//./input//archive";
+globalThis.__private_workflows.set("workflow//./input//archive", archive);
+export { checkout, refund as issueRefund };
+export default archive;
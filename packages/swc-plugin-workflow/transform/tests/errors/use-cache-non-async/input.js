@@ -0,0 +1,14 @@
+export function getUser(id) {
+  'use cache';
+  return { id };
+}
+
+export const getSettings = () => {
+  'use cache';
+  return {};
+};
+
+export async function getTheme() {
+  'use cach';
+  return 'dark';
+}
@@ -1,21 +1,29 @@
+use common::{load_options, load_syntax};
 use std::path::PathBuf;
 use swc_core::ecma::{
-    transforms::testing::{test_fixture, FixtureTestConfig},
+    transforms::testing::{FixtureTestConfig, test_fixture},
     visit::visit_mut_pass,
 };
 use swc_workflow::{StepTransform, TransformMode};
 
+mod common;
+
 #[testing::fixture("tests/fixture/**/input.js")]
 fn step_mode(input: PathBuf) {
     let step_output = input.parent().unwrap().join("output-step.js");
     test_fixture(
-        Default::default(),
-        &|_| {
-            visit_mut_pass(StepTransform::new(
-                TransformMode::Step,
-                input.file_name().unwrap().to_string_lossy().to_string(),
-                None,
-            ))
+        load_syntax(&input),
+        &|t| {
+            visit_mut_pass(
+                StepTransform::new(
+                    TransformMode::Step,
+                    input.file_name().unwrap().to_string_lossy().to_string(),
+                    None,
+                )
+                .with_options(load_options(&input))
+                .with_source_map(t.cm.clone())
+                .with_comments(t.comments.clone()),
+            )
         },
         &input,
         &step_output,
@@ -30,13 +38,18 @@ fn step_mode(input: PathBuf) {
 fn workflow_mode(input: PathBuf) {
     let workflow_output = input.parent().unwrap().join("output-workflow.js");
     test_fixture(
-        Default::default(),
-        &|_| {
-            visit_mut_pass(StepTransform::new(
-                TransformMode::Workflow,
-                input.file_name().unwrap().to_string_lossy().to_string(),
-                None,
-            ))
+        load_syntax(&input),
+        &|t| {
+            visit_mut_pass(
+                StepTransform::new(
+                    TransformMode::Workflow,
+                    input.file_name().unwrap().to_string_lossy().to_string(),
+                    None,
+                )
+                .with_options(load_options(&input))
+                .with_source_map(t.cm.clone())
+                .with_comments(t.comments.clone()),
+            )
         },
         &input,
         &workflow_output,
@@ -51,13 +64,18 @@ fn workflow_mode(input: PathBuf) {
 fn client_mode(input: PathBuf) {
     let client_output = input.parent().unwrap().join("output-client.js");
     test_fixture(
-        Default::default(),
-        &|_| {
-            visit_mut_pass(StepTransform::new(
-                TransformMode::Client,
-                input.file_name().unwrap().to_string_lossy().to_string(),
-                None,
-            ))
+        load_syntax(&input),
+        &|t| {
+            visit_mut_pass(
+                StepTransform::new(
+                    TransformMode::Client,
+                    input.file_name().unwrap().to_string_lossy().to_string(),
+                    None,
+                )
+                .with_options(load_options(&input))
+                .with_source_map(t.cm.clone())
+                .with_comments(t.comments.clone()),
+            )
         },
         &input,
         &client_output,
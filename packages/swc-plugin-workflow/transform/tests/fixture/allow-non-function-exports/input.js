@@ -0,0 +1,19 @@
+'use workflow';
+
+export const MAX_RETRIES = 3;
+
+export class RetryPolicy {
+  constructor(attempts) {
+    this.attempts = attempts;
+  }
+}
+
+export async function processOrder(orderId) {
+  return { orderId, policy: new RetryPolicy(MAX_RETRIES) };
+}
+
+const TIMEOUT_MS = 5000;
+
+class Backoff {}
+
+export { TIMEOUT_MS, Backoff };
@@ -0,0 +1,15 @@
+/**__internal_workflows{"workflows":{"input.js":{"processOrder":{"workflowId":"workflow//./input//processOrder"}}}}*/;
+export const MAX_RETRIES = 3;
+export class RetryPolicy {
+    constructor(attempts){
+        this.attempts = attempts;
+    }
+}
+export async function processOrder(orderId) {
+    throw new Error("You attempted to execute workflow processOrder function directly. To start a workflow, use start(processOrder) from workflow/api");
+}
+processOrder.workflowId = "workflow//./input//processOrder";
+const TIMEOUT_MS = 5000;
+class Backoff {
+}
+export { TIMEOUT_MS, Backoff };
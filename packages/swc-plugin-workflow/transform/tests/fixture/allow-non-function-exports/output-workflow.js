@@ -0,0 +1,19 @@
+/**__internal_workflows{"workflows":{"input.js":{"processOrder":{"workflowId":"workflow//./input//processOrder"}}}}*/;
+export const MAX_RETRIES = 3;
+export class RetryPolicy {
+    constructor(attempts){
+        this.attempts = attempts;
+    }
+}
+export async function processOrder(orderId) {
+    return {
+        orderId,
+        policy: new RetryPolicy(MAX_RETRIES)
+    };
+}
+processOrder.workflowId = "workflow//./input//processOrder";
+globalThis.__private_workflows.set("workflow//./input//processOrder", processOrder);
+const TIMEOUT_MS = 5000;
+class Backoff {
+}
+export { TIMEOUT_MS, Backoff };
@@ -0,0 +1,22 @@
+import { _ as _async_to_generator } from '@swc/helpers/_/_async_to_generator';
+
+export function add(a, b) {
+  return _async_to_generator(function* () {
+    'use step';
+    const result = yield Promise.resolve(a + b);
+    return result;
+  })();
+}
+
+function multiply(a, b) {
+  return _asyncToGenerator(function* () {
+    'use step';
+    return a * b;
+  }).apply(this, arguments);
+}
+
+export function notAStep(a) {
+  return _async_to_generator(function* () {
+    return a;
+  })();
+}
@@ -0,0 +1,20 @@
+import { _ as _async_to_generator } from '@swc/helpers/_/_async_to_generator';
+/**__internal_workflows{"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"multiply":{"stepId":"step//./input//multiply"}}}}*/;
+export function add(a, b) {
+    return _async_to_generator(function*() {
+        const result = yield Promise.resolve(a + b);
+        return result;
+    })();
+}
+add.stepId = "step//./input//add";
+function multiply(a, b) {
+    return _asyncToGenerator(function*() {
+        return a * b;
+    }).apply(this, arguments);
+}
+multiply.stepId = "step//./input//multiply";
+export function notAStep(a) {
+    return _async_to_generator(function*() {
+        return a;
+    })();
+}
@@ -0,0 +1,21 @@
+import { registerStepFunction } from "workflow/internal/private";
+import { _ as _async_to_generator } from '@swc/helpers/_/_async_to_generator';
+/**__internal_workflows{"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"multiply":{"stepId":"step//./input//multiply"}}}}*/;
+export function add(a, b) {
+    return _async_to_generator(function*() {
+        const result = yield Promise.resolve(a + b);
+        return result;
+    })();
+}
+function multiply(a, b) {
+    return _asyncToGenerator(function*() {
+        return a * b;
+    }).apply(this, arguments);
+}
+export function notAStep(a) {
+    return _async_to_generator(function*() {
+        return a;
+    })();
+}
+registerStepFunction("step//./input//add", add);
+registerStepFunction("step//./input//multiply", multiply);
@@ -0,0 +1,9 @@
+import { _ as _async_to_generator } from '@swc/helpers/_/_async_to_generator';
+/**__internal_workflows{"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"multiply":{"stepId":"step//./input//multiply"}}}}*/;
+export var add = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//add");
+var multiply = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//multiply");
+export function notAStep(a) {
+    return _async_to_generator(function*() {
+        return a;
+    })();
+}
@@ -0,0 +1,24 @@
+// This is the TypeScript-transformed output of:
+// async function testStep() {
+//   'use step';
+//   await using resource = getResource();
+//   await doWork(resource);
+// }
+
+export async function testStep() {
+  const env_1 = { stack: [], error: void 0, hasError: false };
+  try {
+    "use step";
+    const resource = __addDisposableResource(env_1, getResource(), true);
+    await doWork(resource);
+  }
+  catch (e_1) {
+    env_1.error = e_1;
+    env_1.hasError = true;
+  }
+  finally {
+    const result_1 = __disposeResources(env_1);
+    if (result_1)
+      await result_1;
+  }
+}
@@ -0,0 +1,33 @@
+export async function add(a, b) {
+  'use step';
+  return a + b;
+}
+
+export const multiply = async (a, b) => {
+  'use step';
+  return a * b;
+};
+
+export const tools = {
+  execute: async (input) => {
+    'use step';
+    return input;
+  },
+};
+
+export class Calculator {
+  static async divide(a, b) {
+    'use step';
+    return a / b;
+  }
+
+  async subtract(a, b) {
+    'use step';
+    return a - b;
+  }
+}
+
+export async function calculate(a, b) {
+  'use workflow';
+  return multiply(await add(a, b), b);
+}
@@ -0,0 +1,30 @@
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+/**__internal_workflows{"workflows":{"input.js":{"calculate":{"workflowId":"workflow//./input//calculate"}}},"steps":{"input.js":{"Calculator#subtract":{"stepId":"step//./input//Calculator#subtract"},"Calculator.divide":{"stepId":"step//./input//Calculator.divide"},"add":{"stepId":"step//./input//add"},"multiply":{"stepId":"step//./input//multiply"},"tools/execute":{"stepId":"step//./input//tools/execute"}}},"classes":{"input.js":{"Calculator":{"classId":"class//./input//Calculator"}}}}*/;
+var tools$execute = async function(input) {
+    return input;
+};
+export async function add(a, b) {
+    return a + b;
+}
+add.stepId = "step//./input//add";
+export const multiply = async (a, b)=>{
+    return a * b;
+};
+multiply.stepId = "step//./input//multiply";
+export const tools = {
+    execute: tools$execute
+};
+export class Calculator {
+    static async divide(a, b) {
+        return a / b;
+    }
+    async subtract(a, b) {
+        return a - b;
+    }
+}
+export async function calculate(a, b) {
+    throw new Error("You attempted to execute workflow calculate function directly. To start a workflow, use start(calculate) from workflow/api");
+}
+calculate.workflowId = "workflow//./input//calculate";
+tools$execute.stepId = "step//./input//tools/execute";
+registerSerializationClass("class//./input//Calculator", Calculator);
@@ -0,0 +1,50 @@
+import { registerStepFunctions } from "workflow/internal/private";
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+/**__internal_workflows{"workflows":{"input.js":{"calculate":{"workflowId":"workflow//./input//calculate"}}},"steps":{"input.js":{"Calculator#subtract":{"stepId":"step//./input//Calculator#subtract"},"Calculator.divide":{"stepId":"step//./input//Calculator.divide"},"add":{"stepId":"step//./input//add"},"multiply":{"stepId":"step//./input//multiply"},"tools/execute":{"stepId":"step//./input//tools/execute"}}},"classes":{"input.js":{"Calculator":{"classId":"class//./input//Calculator"}}}}*/;
+var tools$execute = async function(input) {
+    return input;
+};
+export async function add(a, b) {
+    return a + b;
+}
+export const multiply = async (a, b)=>{
+    return a * b;
+};
+export const tools = {
+    execute: tools$execute
+};
+export class Calculator {
+    static async divide(a, b) {
+        return a / b;
+    }
+    async subtract(a, b) {
+        return a - b;
+    }
+}
+export async function calculate(a, b) {
+    throw new Error("You attempted to execute workflow calculate function directly. To start a workflow, use start(calculate) from workflow/api");
+}
+calculate.workflowId = "workflow//./input//calculate";
+registerStepFunctions([
+    [
+        "step//./input//add",
+        add
+    ],
+    [
+        "step//./input//multiply",
+        multiply
+    ],
+    [
+        "step//./input//tools/execute",
+        tools$execute
+    ],
+    [
+        "step//./input//Calculator.divide",
+        Calculator.divide
+    ],
+    [
+        "step//./input//Calculator#subtract",
+        Calculator.prototype["subtract"]
+    ]
+]);
+registerSerializationClass("class//./input//Calculator", Calculator);
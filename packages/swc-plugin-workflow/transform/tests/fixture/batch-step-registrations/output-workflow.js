@@ -0,0 +1,17 @@
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+/**__internal_workflows{"workflows":{"input.js":{"calculate":{"workflowId":"workflow//./input//calculate"}}},"steps":{"input.js":{"Calculator#subtract":{"stepId":"step//./input//Calculator#subtract"},"Calculator.divide":{"stepId":"step//./input//Calculator.divide"},"add":{"stepId":"step//./input//add"},"multiply":{"stepId":"step//./input//multiply"},"tools/execute":{"stepId":"step//./input//tools/execute"}}},"classes":{"input.js":{"Calculator":{"classId":"class//./input//Calculator"}}}}*/;
+export var add = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//add");
+export const multiply = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//multiply");
+export const tools = {
+    execute: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//tools/execute")
+};
+export class Calculator {
+}
+export async function calculate(a, b) {
+    return multiply(await add(a, b), b);
+}
+calculate.workflowId = "workflow//./input//calculate";
+globalThis.__private_workflows.set("workflow//./input//calculate", calculate);
+Calculator.divide = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Calculator.divide");
+Calculator.prototype["subtract"] = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Calculator#subtract");
+registerSerializationClass("class//./input//Calculator", Calculator);
@@ -1,6 +1,6 @@
-import { registerSerializationClass } from "workflow/internal/class-serialization";
 // Test class expression where binding name differs from internal class name
 // e.g., `var Bash = class _Bash {}` - the registration should use "Bash", not "_Bash"
+import { registerSerializationClass } from "workflow/internal/class-serialization";
 import { WORKFLOW_SERIALIZE, WORKFLOW_DESERIALIZE } from '@workflow/serde';
 /**__internal_workflows{"classes":{"input.js":{"Bash":{"classId":"class//./input//Bash"},"Shell":{"classId":"class//./input//Shell"}}}}*/;
 // Class expression with different binding name
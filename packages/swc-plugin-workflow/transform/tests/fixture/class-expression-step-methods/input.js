@@ -0,0 +1,33 @@
+export const Tool = class {
+  static async run(input) {
+    'use step';
+    return input * 2;
+  }
+
+  async describe() {
+    'use step';
+    return 'tool';
+  }
+
+  static async orchestrate(input) {
+    'use workflow';
+    return Tool.run(input);
+  }
+};
+
+export const Runner = class _Runner {
+  static async run(input) {
+    'use step';
+    return input + 1;
+  }
+
+  async describe() {
+    'use step';
+    return 'runner';
+  }
+
+  static async orchestrate(input) {
+    'use workflow';
+    return Runner.run(input);
+  }
+};
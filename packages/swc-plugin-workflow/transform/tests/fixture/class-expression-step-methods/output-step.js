@@ -0,0 +1,33 @@
+import { registerStepFunction } from "workflow/internal/private";
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+/**__internal_workflows{"workflows":{"input.js":{"Runner.orchestrate":{"workflowId":"workflow//./input//Runner.orchestrate"},"Tool.orchestrate":{"workflowId":"workflow//./input//Tool.orchestrate"}}},"steps":{"input.js":{"Runner#describe":{"stepId":"step//./input//Runner#describe"},"Runner.run":{"stepId":"step//./input//Runner.run"},"Tool#describe":{"stepId":"step//./input//Tool#describe"},"Tool.run":{"stepId":"step//./input//Tool.run"}}},"classes":{"input.js":{"Runner":{"classId":"class//./input//Runner"},"Tool":{"classId":"class//./input//Tool"}}}}*/;
+export const Tool = class {
+    static async run(input) {
+        return input * 2;
+    }
+    async describe() {
+        return 'tool';
+    }
+    static async orchestrate(input) {
+        throw new Error("You attempted to execute workflow Tool.orchestrate function directly. To start a workflow, use start(workflow) from workflow/api");
+    }
+};
+export const Runner = class _Runner {
+    static async run(input) {
+        return input + 1;
+    }
+    async describe() {
+        return 'runner';
+    }
+    static async orchestrate(input) {
+        throw new Error("You attempted to execute workflow Runner.orchestrate function directly. To start a workflow, use start(workflow) from workflow/api");
+    }
+};
+registerStepFunction("step//./input//Runner.run", Runner.run);
+registerStepFunction("step//./input//Tool.run", Tool.run);
+registerStepFunction("step//./input//Runner#describe", Runner.prototype["describe"]);
+registerStepFunction("step//./input//Tool#describe", Tool.prototype["describe"]);
+registerSerializationClass("class//./input//Runner", Runner);
+registerSerializationClass("class//./input//Tool", Tool);
+Runner.orchestrate.workflowId = "workflow//./input//Runner.orchestrate";
+Tool.orchestrate.workflowId = "workflow//./input//Tool.orchestrate";
@@ -0,0 +1,22 @@
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+/**__internal_workflows{"workflows":{"input.js":{"Runner.orchestrate":{"workflowId":"workflow//./input//Runner.orchestrate"},"Tool.orchestrate":{"workflowId":"workflow//./input//Tool.orchestrate"}}},"steps":{"input.js":{"Runner#describe":{"stepId":"step//./input//Runner#describe"},"Runner.run":{"stepId":"step//./input//Runner.run"},"Tool#describe":{"stepId":"step//./input//Tool#describe"},"Tool.run":{"stepId":"step//./input//Tool.run"}}},"classes":{"input.js":{"Runner":{"classId":"class//./input//Runner"},"Tool":{"classId":"class//./input//Tool"}}}}*/;
+export const Tool = class {
+    static async orchestrate(input) {
+        return Tool.run(input);
+    }
+};
+export const Runner = class _Runner {
+    static async orchestrate(input) {
+        return Runner.run(input);
+    }
+};
+Tool.run = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Tool.run");
+Runner.run = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Runner.run");
+Tool.prototype["describe"] = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Tool#describe");
+Runner.prototype["describe"] = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Runner#describe");
+registerSerializationClass("class//./input//Runner", Runner);
+registerSerializationClass("class//./input//Tool", Tool);
+Runner.orchestrate.workflowId = "workflow//./input//Runner.orchestrate";
+globalThis.__private_workflows.set("workflow//./input//Runner.orchestrate", Runner.orchestrate);
+Tool.orchestrate.workflowId = "workflow//./input//Tool.orchestrate";
+globalThis.__private_workflows.set("workflow//./input//Tool.orchestrate", Tool.orchestrate);
@@ -0,0 +1,13 @@
+export class Svc {
+  prefix = "svc";
+
+  run = async (input) => {
+    'use step';
+    return `${this.prefix}:${input}`;
+  };
+
+  static check = async function (input) {
+    'use step';
+    return input > 0;
+  };
+}
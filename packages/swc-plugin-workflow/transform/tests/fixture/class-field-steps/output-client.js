@@ -0,0 +1,12 @@
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+/**__internal_workflows{"steps":{"input.js":{"Svc#run":{"stepId":"step//./input//Svc#run"},"Svc.check":{"stepId":"step//./input//Svc.check"}}},"classes":{"input.js":{"Svc":{"classId":"class//./input//Svc"}}}}*/;
+export class Svc {
+    prefix = "svc";
+    run = async (input)=>{
+        return `${this.prefix}:${input}`;
+    };
+    static check = async function(input) {
+        return input > 0;
+    };
+}
+registerSerializationClass("class//./input//Svc", Svc);
@@ -0,0 +1,8 @@
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+/**__internal_workflows{"steps":{"input.js":{"Svc#run":{"stepId":"step//./input//Svc#run"},"Svc.check":{"stepId":"step//./input//Svc.check"}}},"classes":{"input.js":{"Svc":{"classId":"class//./input//Svc"}}}}*/;
+export class Svc {
+    prefix = "svc";
+    run = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Svc#run");
+    static check = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Svc.check");
+}
+registerSerializationClass("class//./input//Svc", Svc);
@@ -0,0 +1,43 @@
+export class Shipping {
+  static async track(id) {
+    'use step';
+    return fetch(`/api/shipping/${id}`);
+  }
+
+  async label(order) {
+    'use step';
+    return fetch('/api/labels', { method: 'POST', body: order });
+  }
+
+  static async dispatch(order) {
+    'use workflow';
+    return Shipping.track(order.id);
+  }
+}
+
+export class Billing {
+  async refund(order) {
+    'use step';
+    return fetch('/api/refund', { method: 'POST', body: order });
+  }
+
+  static async charge(order) {
+    'use step';
+    return fetch('/api/charge', { method: 'POST', body: order });
+  }
+
+  async authorize(order) {
+    'use step';
+    return fetch('/api/authorize', { method: 'POST', body: order });
+  }
+
+  static async settle(order) {
+    'use workflow';
+    return Billing.charge(order);
+  }
+
+  static async audit(order) {
+    'use step';
+    return fetch('/api/audit', { method: 'POST', body: order });
+  }
+}
@@ -0,0 +1,49 @@
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+/**__internal_workflows{"workflows":{"input.js":{"Billing.settle":{"workflowId":"workflow//./input//Billing.settle"},"Shipping.dispatch":{"workflowId":"workflow//./input//Shipping.dispatch"}}},"steps":{"input.js":{"Billing#authorize":{"stepId":"step//./input//Billing#authorize"},"Billing#refund":{"stepId":"step//./input//Billing#refund"},"Billing.audit":{"stepId":"step//./input//Billing.audit"},"Billing.charge":{"stepId":"step//./input//Billing.charge"},"Shipping#label":{"stepId":"step//./input//Shipping#label"},"Shipping.track":{"stepId":"step//./input//Shipping.track"}}},"classes":{"input.js":{"Billing":{"classId":"class//./input//Billing"},"Shipping":{"classId":"class//./input//Shipping"}}}}*/;
+export class Shipping {
+    static async track(id) {
+        return fetch(`/api/shipping/${id}`);
+    }
+    async label(order) {
+        return fetch('/api/labels', {
+            method: 'POST',
+            body: order
+        });
+    }
+    static async dispatch(order) {
+        throw new Error("You attempted to execute workflow Shipping.dispatch function directly. To start a workflow, use start(workflow) from workflow/api");
+    }
+}
+export class Billing {
+    async refund(order) {
+        return fetch('/api/refund', {
+            method: 'POST',
+            body: order
+        });
+    }
+    static async charge(order) {
+        return fetch('/api/charge', {
+            method: 'POST',
+            body: order
+        });
+    }
+    async authorize(order) {
+        return fetch('/api/authorize', {
+            method: 'POST',
+            body: order
+        });
+    }
+    static async settle(order) {
+        throw new Error("You attempted to execute workflow Billing.settle function directly. To start a workflow, use start(workflow) from workflow/api");
+    }
+    static async audit(order) {
+        return fetch('/api/audit', {
+            method: 'POST',
+            body: order
+        });
+    }
+}
+registerSerializationClass("class//./input//Billing", Billing);
+registerSerializationClass("class//./input//Shipping", Shipping);
+Billing.settle.workflowId = "workflow//./input//Billing.settle";
+Shipping.dispatch.workflowId = "workflow//./input//Shipping.dispatch";
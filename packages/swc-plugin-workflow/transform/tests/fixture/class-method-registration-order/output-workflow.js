@@ -0,0 +1,24 @@
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+/**__internal_workflows{"workflows":{"input.js":{"Billing.settle":{"workflowId":"workflow//./input//Billing.settle"},"Shipping.dispatch":{"workflowId":"workflow//./input//Shipping.dispatch"}}},"steps":{"input.js":{"Billing#authorize":{"stepId":"step//./input//Billing#authorize"},"Billing#refund":{"stepId":"step//./input//Billing#refund"},"Billing.audit":{"stepId":"step//./input//Billing.audit"},"Billing.charge":{"stepId":"step//./input//Billing.charge"},"Shipping#label":{"stepId":"step//./input//Shipping#label"},"Shipping.track":{"stepId":"step//./input//Shipping.track"}}},"classes":{"input.js":{"Billing":{"classId":"class//./input//Billing"},"Shipping":{"classId":"class//./input//Shipping"}}}}*/;
+export class Shipping {
+    static async dispatch(order) {
+        return Shipping.track(order.id);
+    }
+}
+export class Billing {
+    static async settle(order) {
+        return Billing.charge(order);
+    }
+}
+Shipping.track = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Shipping.track");
+Billing.charge = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Billing.charge");
+Billing.audit = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Billing.audit");
+Shipping.prototype["label"] = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Shipping#label");
+Billing.prototype["refund"] = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Billing#refund");
+Billing.prototype["authorize"] = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Billing#authorize");
+registerSerializationClass("class//./input//Billing", Billing);
+registerSerializationClass("class//./input//Shipping", Shipping);
+Billing.settle.workflowId = "workflow//./input//Billing.settle";
+globalThis.__private_workflows.set("workflow//./input//Billing.settle", Billing.settle);
+Shipping.dispatch.workflowId = "workflow//./input//Shipping.dispatch";
+globalThis.__private_workflows.set("workflow//./input//Shipping.dispatch", Shipping.dispatch);
@@ -0,0 +1,24 @@
+export async function catchDestructuringWorkflow(log, defaultCode) {
+  'use workflow';
+
+  async function reportError() {
+    'use step';
+    try {
+      await fetch('https://example.com');
+    } catch ({ message }) {
+      log(message);
+    }
+  }
+
+  async function reportErrorCode() {
+    'use step';
+    try {
+      await fetch('https://example.com');
+    } catch ({ code = defaultCode, cause: [first] }) {
+      log(code, first);
+    }
+  }
+
+  await reportError();
+  await reportErrorCode();
+}
@@ -0,0 +1,5 @@
+/**__internal_workflows{"workflows":{"input.js":{"catchDestructuringWorkflow":{"workflowId":"workflow//./input//catchDestructuringWorkflow"}}}}*/;
+export async function catchDestructuringWorkflow(log, defaultCode) {
+    throw new Error("You attempted to execute workflow catchDestructuringWorkflow function directly. To start a workflow, use start(catchDestructuringWorkflow) from workflow/api");
+}
+catchDestructuringWorkflow.workflowId = "workflow//./input//catchDestructuringWorkflow";
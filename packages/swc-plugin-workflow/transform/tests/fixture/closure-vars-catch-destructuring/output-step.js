@@ -0,0 +1,24 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"catchDestructuringWorkflow":{"workflowId":"workflow//./input//catchDestructuringWorkflow"}}},"steps":{"input.js":{"reportError":{"stepId":"step//./input//reportError"},"reportErrorCode":{"stepId":"step//./input//reportErrorCode"}}}}*/;
+async function catchDestructuringWorkflow$reportError() {
+    const { log } = __private_getClosureVars();
+    try {
+        await fetch('https://example.com');
+    } catch ({ message }) {
+        log(message);
+    }
+}
+async function catchDestructuringWorkflow$reportErrorCode() {
+    const { defaultCode, log } = __private_getClosureVars();
+    try {
+        await fetch('https://example.com');
+    } catch ({ code = defaultCode, cause: [first] }) {
+        log(code, first);
+    }
+}
+export async function catchDestructuringWorkflow(log, defaultCode) {
+    throw new Error("You attempted to execute workflow catchDestructuringWorkflow function directly. To start a workflow, use start(catchDestructuringWorkflow) from workflow/api");
+}
+catchDestructuringWorkflow.workflowId = "workflow//./input//catchDestructuringWorkflow";
+registerStepFunction("step//./input//catchDestructuringWorkflow/reportError", catchDestructuringWorkflow$reportError);
+registerStepFunction("step//./input//catchDestructuringWorkflow/reportErrorCode", catchDestructuringWorkflow$reportErrorCode);
@@ -0,0 +1,14 @@
+/**__internal_workflows{"workflows":{"input.js":{"catchDestructuringWorkflow":{"workflowId":"workflow//./input//catchDestructuringWorkflow"}}},"steps":{"input.js":{"reportError":{"stepId":"step//./input//reportError"},"reportErrorCode":{"stepId":"step//./input//reportErrorCode"}}}}*/;
+export async function catchDestructuringWorkflow(log, defaultCode) {
+    var reportError = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//catchDestructuringWorkflow/reportError", ()=>({
+            log
+        }));
+    var reportErrorCode = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//catchDestructuringWorkflow/reportErrorCode", ()=>({
+            defaultCode,
+            log
+        }));
+    await reportError();
+    await reportErrorCode();
+}
+catchDestructuringWorkflow.workflowId = "workflow//./input//catchDestructuringWorkflow";
+globalThis.__private_workflows.set("workflow//./input//catchDestructuringWorkflow", catchDestructuringWorkflow);
@@ -0,0 +1,16 @@
+export async function computedMemberWorkflow(config, key, handlers, index) {
+  'use workflow';
+
+  const readConfig = async () => {
+    'use step';
+    return config[key];
+  };
+
+  const callHandler = async () => {
+    'use step';
+    return handlers?.[index]?.();
+  };
+
+  await readConfig();
+  return await callHandler();
+}
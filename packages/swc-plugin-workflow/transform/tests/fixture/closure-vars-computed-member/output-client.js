@@ -0,0 +1,5 @@
+/**__internal_workflows{"workflows":{"input.js":{"computedMemberWorkflow":{"workflowId":"workflow//./input//computedMemberWorkflow"}}}}*/;
+export async function computedMemberWorkflow(config, key, handlers, index) {
+    throw new Error("You attempted to execute workflow computedMemberWorkflow function directly. To start a workflow, use start(computedMemberWorkflow) from workflow/api");
+}
+computedMemberWorkflow.workflowId = "workflow//./input//computedMemberWorkflow";
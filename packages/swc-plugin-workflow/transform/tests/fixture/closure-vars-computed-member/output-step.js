@@ -0,0 +1,16 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"computedMemberWorkflow":{"workflowId":"workflow//./input//computedMemberWorkflow"}}},"steps":{"input.js":{"callHandler":{"stepId":"step//./input//callHandler"},"readConfig":{"stepId":"step//./input//readConfig"}}}}*/;
+var computedMemberWorkflow$readConfig = async ()=>{
+    const { config, key } = __private_getClosureVars();
+    return config[key];
+};
+var computedMemberWorkflow$callHandler = async ()=>{
+    const { handlers, index } = __private_getClosureVars();
+    return handlers?.[index]?.();
+};
+export async function computedMemberWorkflow(config, key, handlers, index) {
+    throw new Error("You attempted to execute workflow computedMemberWorkflow function directly. To start a workflow, use start(computedMemberWorkflow) from workflow/api");
+}
+computedMemberWorkflow.workflowId = "workflow//./input//computedMemberWorkflow";
+registerStepFunction("step//./input//computedMemberWorkflow/readConfig", computedMemberWorkflow$readConfig);
+registerStepFunction("step//./input//computedMemberWorkflow/callHandler", computedMemberWorkflow$callHandler);
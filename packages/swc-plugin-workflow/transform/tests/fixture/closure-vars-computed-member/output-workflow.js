@@ -0,0 +1,15 @@
+/**__internal_workflows{"workflows":{"input.js":{"computedMemberWorkflow":{"workflowId":"workflow//./input//computedMemberWorkflow"}}},"steps":{"input.js":{"callHandler":{"stepId":"step//./input//callHandler"},"readConfig":{"stepId":"step//./input//readConfig"}}}}*/;
+export async function computedMemberWorkflow(config, key, handlers, index) {
+    const readConfig = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//computedMemberWorkflow/readConfig", ()=>({
+            config,
+            key
+        }));
+    const callHandler = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//computedMemberWorkflow/callHandler", ()=>({
+            handlers,
+            index
+        }));
+    await readConfig();
+    return await callHandler();
+}
+computedMemberWorkflow.workflowId = "workflow//./input//computedMemberWorkflow";
+globalThis.__private_workflows.set("workflow//./input//computedMemberWorkflow", computedMemberWorkflow);
@@ -0,0 +1,16 @@
+export async function exportWorkflow(client, cursor, page) {
+  'use workflow';
+
+  async function collectPages() {
+    'use step';
+    const rows = [];
+    // `page` is the loop binding here, while `client` and `cursor` come from the workflow
+    for await (const page of client.pages({ cursor })) {
+      rows.push(...page.rows);
+    }
+    return rows;
+  }
+
+  const rows = await collectPages();
+  return { rows, page };
+}
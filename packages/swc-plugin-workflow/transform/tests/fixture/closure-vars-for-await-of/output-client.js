@@ -0,0 +1,5 @@
+/**__internal_workflows{"workflows":{"input.js":{"exportWorkflow":{"workflowId":"workflow//./input//exportWorkflow"}}}}*/;
+export async function exportWorkflow(client, cursor, page) {
+    throw new Error("You attempted to execute workflow exportWorkflow function directly. To start a workflow, use start(exportWorkflow) from workflow/api");
+}
+exportWorkflow.workflowId = "workflow//./input//exportWorkflow";
@@ -0,0 +1,18 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"exportWorkflow":{"workflowId":"workflow//./input//exportWorkflow"}}},"steps":{"input.js":{"collectPages":{"stepId":"step//./input//collectPages"}}}}*/;
+async function exportWorkflow$collectPages() {
+    const { client, cursor } = __private_getClosureVars();
+    const rows = [];
+    // `page` is the loop binding here, while `client` and `cursor` come from the workflow
+    for await (const page of client.pages({
+        cursor
+    })){
+        rows.push(...page.rows);
+    }
+    return rows;
+}
+export async function exportWorkflow(client, cursor, page) {
+    throw new Error("You attempted to execute workflow exportWorkflow function directly. To start a workflow, use start(exportWorkflow) from workflow/api");
+}
+exportWorkflow.workflowId = "workflow//./input//exportWorkflow";
+registerStepFunction("step//./input//exportWorkflow/collectPages", exportWorkflow$collectPages);
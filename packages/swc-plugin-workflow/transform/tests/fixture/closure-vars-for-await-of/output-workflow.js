@@ -0,0 +1,14 @@
+/**__internal_workflows{"workflows":{"input.js":{"exportWorkflow":{"workflowId":"workflow//./input//exportWorkflow"}}},"steps":{"input.js":{"collectPages":{"stepId":"step//./input//collectPages"}}}}*/;
+export async function exportWorkflow(client, cursor, page) {
+    var collectPages = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//exportWorkflow/collectPages", ()=>({
+            client,
+            cursor
+        }));
+    const rows = await collectPages();
+    return {
+        rows,
+        page
+    };
+}
+exportWorkflow.workflowId = "workflow//./input//exportWorkflow";
+globalThis.__private_workflows.set("workflow//./input//exportWorkflow", exportWorkflow);
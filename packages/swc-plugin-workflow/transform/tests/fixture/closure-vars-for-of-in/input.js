@@ -0,0 +1,35 @@
+export async function loopWorkflow(items, settings, stream) {
+  'use workflow';
+
+  async function sumItems() {
+    'use step';
+    let total = 0;
+    for (const item of items) {
+      total += item;
+    }
+    return total;
+  }
+
+  async function settingKeys() {
+    'use step';
+    const keys = [];
+    for (const key in settings) {
+      keys.push(key);
+    }
+    return keys;
+  }
+
+  async function drainStream() {
+    'use step';
+    const chunks = [];
+    for await (const chunk of stream) {
+      chunks.push(chunk);
+    }
+    return chunks;
+  }
+
+  const total = await sumItems();
+  const keys = await settingKeys();
+  const chunks = await drainStream();
+  return { total, keys, chunks };
+}
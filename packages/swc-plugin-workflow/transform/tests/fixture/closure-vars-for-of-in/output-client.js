@@ -0,0 +1,5 @@
+/**__internal_workflows{"workflows":{"input.js":{"loopWorkflow":{"workflowId":"workflow//./input//loopWorkflow"}}}}*/;
+export async function loopWorkflow(items, settings, stream) {
+    throw new Error("You attempted to execute workflow loopWorkflow function directly. To start a workflow, use start(loopWorkflow) from workflow/api");
+}
+loopWorkflow.workflowId = "workflow//./input//loopWorkflow";
@@ -0,0 +1,33 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"loopWorkflow":{"workflowId":"workflow//./input//loopWorkflow"}}},"steps":{"input.js":{"drainStream":{"stepId":"step//./input//drainStream"},"settingKeys":{"stepId":"step//./input//settingKeys"},"sumItems":{"stepId":"step//./input//sumItems"}}}}*/;
+async function loopWorkflow$sumItems() {
+    const { items } = __private_getClosureVars();
+    let total = 0;
+    for (const item of items){
+        total += item;
+    }
+    return total;
+}
+async function loopWorkflow$settingKeys() {
+    const { settings } = __private_getClosureVars();
+    const keys = [];
+    for(const key in settings){
+        keys.push(key);
+    }
+    return keys;
+}
+async function loopWorkflow$drainStream() {
+    const { stream } = __private_getClosureVars();
+    const chunks = [];
+    for await (const chunk of stream){
+        chunks.push(chunk);
+    }
+    return chunks;
+}
+export async function loopWorkflow(items, settings, stream) {
+    throw new Error("You attempted to execute workflow loopWorkflow function directly. To start a workflow, use start(loopWorkflow) from workflow/api");
+}
+loopWorkflow.workflowId = "workflow//./input//loopWorkflow";
+registerStepFunction("step//./input//loopWorkflow/sumItems", loopWorkflow$sumItems);
+registerStepFunction("step//./input//loopWorkflow/settingKeys", loopWorkflow$settingKeys);
+registerStepFunction("step//./input//loopWorkflow/drainStream", loopWorkflow$drainStream);
@@ -0,0 +1,22 @@
+/**__internal_workflows{"workflows":{"input.js":{"loopWorkflow":{"workflowId":"workflow//./input//loopWorkflow"}}},"steps":{"input.js":{"drainStream":{"stepId":"step//./input//drainStream"},"settingKeys":{"stepId":"step//./input//settingKeys"},"sumItems":{"stepId":"step//./input//sumItems"}}}}*/;
+export async function loopWorkflow(items, settings, stream) {
+    var sumItems = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//loopWorkflow/sumItems", ()=>({
+            items
+        }));
+    var settingKeys = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//loopWorkflow/settingKeys", ()=>({
+            settings
+        }));
+    var drainStream = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//loopWorkflow/drainStream", ()=>({
+            stream
+        }));
+    const total = await sumItems();
+    const keys = await settingKeys();
+    const chunks = await drainStream();
+    return {
+        total,
+        keys,
+        chunks
+    };
+}
+loopWorkflow.workflowId = "workflow//./input//loopWorkflow";
+globalThis.__private_workflows.set("workflow//./input//loopWorkflow", loopWorkflow);
@@ -0,0 +1,38 @@
+export async function nestedFunctionsWorkflow(offset, scale, items, n) {
+  'use workflow';
+
+  const oneLevel = async () => {
+    'use step';
+    const f = (n) => n + offset;
+    return f(2);
+  };
+
+  const twoLevels = async () => {
+    'use step';
+    const outer = (value) => {
+      const inner = function (n) {
+        return n * scale + value;
+      };
+      return inner(value);
+    };
+    return items.map((item) => outer(item));
+  };
+
+  async function hoisted() {
+    'use step';
+    function run() {
+      return helper(arguments.length);
+    }
+    function helper(count) {
+      return count + offset;
+    }
+    const factorial = function fact(k) {
+      return k <= 1 ? 1 : k * fact(k - 1);
+    };
+    return run() + factorial(n);
+  }
+
+  await oneLevel();
+  await twoLevels();
+  return await hoisted();
+}
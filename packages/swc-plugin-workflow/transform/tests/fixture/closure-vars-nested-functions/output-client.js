@@ -0,0 +1,5 @@
+/**__internal_workflows{"workflows":{"input.js":{"nestedFunctionsWorkflow":{"workflowId":"workflow//./input//nestedFunctionsWorkflow"}}}}*/;
+export async function nestedFunctionsWorkflow(offset, scale, items, n) {
+    throw new Error("You attempted to execute workflow nestedFunctionsWorkflow function directly. To start a workflow, use start(nestedFunctionsWorkflow) from workflow/api");
+}
+nestedFunctionsWorkflow.workflowId = "workflow//./input//nestedFunctionsWorkflow";
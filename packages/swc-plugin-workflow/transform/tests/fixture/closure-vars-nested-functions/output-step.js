@@ -0,0 +1,37 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"nestedFunctionsWorkflow":{"workflowId":"workflow//./input//nestedFunctionsWorkflow"}}},"steps":{"input.js":{"hoisted":{"stepId":"step//./input//hoisted"},"oneLevel":{"stepId":"step//./input//oneLevel"},"twoLevels":{"stepId":"step//./input//twoLevels"}}}}*/;
+var nestedFunctionsWorkflow$oneLevel = async ()=>{
+    const { offset } = __private_getClosureVars();
+    const f = (n)=>n + offset;
+    return f(2);
+};
+var nestedFunctionsWorkflow$twoLevels = async ()=>{
+    const { items, scale } = __private_getClosureVars();
+    const outer = (value)=>{
+        const inner = function(n) {
+            return n * scale + value;
+        };
+        return inner(value);
+    };
+    return items.map((item)=>outer(item));
+};
+async function nestedFunctionsWorkflow$hoisted() {
+    const { n, offset } = __private_getClosureVars();
+    function run() {
+        return helper(arguments.length);
+    }
+    function helper(count) {
+        return count + offset;
+    }
+    const factorial = function fact(k) {
+        return k <= 1 ? 1 : k * fact(k - 1);
+    };
+    return run() + factorial(n);
+}
+export async function nestedFunctionsWorkflow(offset, scale, items, n) {
+    throw new Error("You attempted to execute workflow nestedFunctionsWorkflow function directly. To start a workflow, use start(nestedFunctionsWorkflow) from workflow/api");
+}
+nestedFunctionsWorkflow.workflowId = "workflow//./input//nestedFunctionsWorkflow";
+registerStepFunction("step//./input//nestedFunctionsWorkflow/oneLevel", nestedFunctionsWorkflow$oneLevel);
+registerStepFunction("step//./input//nestedFunctionsWorkflow/twoLevels", nestedFunctionsWorkflow$twoLevels);
+registerStepFunction("step//./input//nestedFunctionsWorkflow/hoisted", nestedFunctionsWorkflow$hoisted);
@@ -0,0 +1,19 @@
+/**__internal_workflows{"workflows":{"input.js":{"nestedFunctionsWorkflow":{"workflowId":"workflow//./input//nestedFunctionsWorkflow"}}},"steps":{"input.js":{"hoisted":{"stepId":"step//./input//hoisted"},"oneLevel":{"stepId":"step//./input//oneLevel"},"twoLevels":{"stepId":"step//./input//twoLevels"}}}}*/;
+export async function nestedFunctionsWorkflow(offset, scale, items, n) {
+    const oneLevel = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//nestedFunctionsWorkflow/oneLevel", ()=>({
+            offset
+        }));
+    const twoLevels = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//nestedFunctionsWorkflow/twoLevels", ()=>({
+            items,
+            scale
+        }));
+    var hoisted = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//nestedFunctionsWorkflow/hoisted", ()=>({
+            n,
+            offset
+        }));
+    await oneLevel();
+    await twoLevels();
+    return await hoisted();
+}
+nestedFunctionsWorkflow.workflowId = "workflow//./input//nestedFunctionsWorkflow";
+globalThis.__private_workflows.set("workflow//./input//nestedFunctionsWorkflow", nestedFunctionsWorkflow);
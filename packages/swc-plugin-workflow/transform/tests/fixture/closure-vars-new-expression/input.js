@@ -0,0 +1,18 @@
+import { Parser } from './parser';
+
+export async function newExpressionWorkflow(MyParser, config, options) {
+  'use workflow';
+
+  const parse = async () => {
+    'use step';
+    return new MyParser(config).run();
+  };
+
+  const parseWithImport = async () => {
+    'use step';
+    return new Parser(...options).run();
+  };
+
+  await parse();
+  return await parseWithImport();
+}
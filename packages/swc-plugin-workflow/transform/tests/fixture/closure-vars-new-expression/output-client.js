@@ -0,0 +1,5 @@
+/**__internal_workflows{"workflows":{"input.js":{"newExpressionWorkflow":{"workflowId":"workflow//./input//newExpressionWorkflow"}}}}*/;
+export async function newExpressionWorkflow(MyParser, config, options) {
+    throw new Error("You attempted to execute workflow newExpressionWorkflow function directly. To start a workflow, use start(newExpressionWorkflow) from workflow/api");
+}
+newExpressionWorkflow.workflowId = "workflow//./input//newExpressionWorkflow";
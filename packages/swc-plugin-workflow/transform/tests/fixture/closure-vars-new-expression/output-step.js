@@ -0,0 +1,17 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+import { Parser } from './parser';
+/**__internal_workflows{"workflows":{"input.js":{"newExpressionWorkflow":{"workflowId":"workflow//./input//newExpressionWorkflow"}}},"steps":{"input.js":{"parse":{"stepId":"step//./input//parse"},"parseWithImport":{"stepId":"step//./input//parseWithImport"}}}}*/;
+var newExpressionWorkflow$parse = async ()=>{
+    const { MyParser, config } = __private_getClosureVars();
+    return new MyParser(config).run();
+};
+var newExpressionWorkflow$parseWithImport = async ()=>{
+    const { options } = __private_getClosureVars();
+    return new Parser(...options).run();
+};
+export async function newExpressionWorkflow(MyParser, config, options) {
+    throw new Error("You attempted to execute workflow newExpressionWorkflow function directly. To start a workflow, use start(newExpressionWorkflow) from workflow/api");
+}
+newExpressionWorkflow.workflowId = "workflow//./input//newExpressionWorkflow";
+registerStepFunction("step//./input//newExpressionWorkflow/parse", newExpressionWorkflow$parse);
+registerStepFunction("step//./input//newExpressionWorkflow/parseWithImport", newExpressionWorkflow$parseWithImport);
@@ -0,0 +1,14 @@
+/**__internal_workflows{"workflows":{"input.js":{"newExpressionWorkflow":{"workflowId":"workflow//./input//newExpressionWorkflow"}}},"steps":{"input.js":{"parse":{"stepId":"step//./input//parse"},"parseWithImport":{"stepId":"step//./input//parseWithImport"}}}}*/;
+export async function newExpressionWorkflow(MyParser, config, options) {
+    const parse = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//newExpressionWorkflow/parse", ()=>({
+            MyParser,
+            config
+        }));
+    const parseWithImport = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//newExpressionWorkflow/parseWithImport", ()=>({
+            options
+        }));
+    await parse();
+    return await parseWithImport();
+}
+newExpressionWorkflow.workflowId = "workflow//./input//newExpressionWorkflow";
+globalThis.__private_workflows.set("workflow//./input//newExpressionWorkflow", newExpressionWorkflow);
@@ -0,0 +1,16 @@
+export async function configWorkflow(defaults, overrides, extra) {
+  'use workflow';
+
+  const merge = async () => {
+    'use step';
+    return { ...defaults, ...overrides };
+  };
+
+  const mergeNested = async (id) => {
+    'use step';
+    return { id, settings: { ...defaults, nested: { ...extra } }, ...overrides };
+  };
+
+  await mergeNested(1);
+  return await merge();
+}
@@ -0,0 +1,5 @@
+/**__internal_workflows{"workflows":{"input.js":{"configWorkflow":{"workflowId":"workflow//./input//configWorkflow"}}}}*/;
+export async function configWorkflow(defaults, overrides, extra) {
+    throw new Error("You attempted to execute workflow configWorkflow function directly. To start a workflow, use start(configWorkflow) from workflow/api");
+}
+configWorkflow.workflowId = "workflow//./input//configWorkflow";
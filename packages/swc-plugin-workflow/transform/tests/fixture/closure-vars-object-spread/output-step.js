@@ -0,0 +1,28 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"configWorkflow":{"workflowId":"workflow//./input//configWorkflow"}}},"steps":{"input.js":{"merge":{"stepId":"step//./input//merge"},"mergeNested":{"stepId":"step//./input//mergeNested"}}}}*/;
+var configWorkflow$merge = async ()=>{
+    const { defaults, overrides } = __private_getClosureVars();
+    return {
+        ...defaults,
+        ...overrides
+    };
+};
+var configWorkflow$mergeNested = async (id)=>{
+    const { defaults, extra, overrides } = __private_getClosureVars();
+    return {
+        id,
+        settings: {
+            ...defaults,
+            nested: {
+                ...extra
+            }
+        },
+        ...overrides
+    };
+};
+export async function configWorkflow(defaults, overrides, extra) {
+    throw new Error("You attempted to execute workflow configWorkflow function directly. To start a workflow, use start(configWorkflow) from workflow/api");
+}
+configWorkflow.workflowId = "workflow//./input//configWorkflow";
+registerStepFunction("step//./input//configWorkflow/merge", configWorkflow$merge);
+registerStepFunction("step//./input//configWorkflow/mergeNested", configWorkflow$mergeNested);
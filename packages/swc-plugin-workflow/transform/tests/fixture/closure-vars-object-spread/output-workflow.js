@@ -0,0 +1,16 @@
+/**__internal_workflows{"workflows":{"input.js":{"configWorkflow":{"workflowId":"workflow//./input//configWorkflow"}}},"steps":{"input.js":{"merge":{"stepId":"step//./input//merge"},"mergeNested":{"stepId":"step//./input//mergeNested"}}}}*/;
+export async function configWorkflow(defaults, overrides, extra) {
+    const merge = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//configWorkflow/merge", ()=>({
+            defaults,
+            overrides
+        }));
+    const mergeNested = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//configWorkflow/mergeNested", ()=>({
+            defaults,
+            extra,
+            overrides
+        }));
+    await mergeNested(1);
+    return await merge();
+}
+configWorkflow.workflowId = "workflow//./input//configWorkflow";
+globalThis.__private_workflows.set("workflow//./input//configWorkflow", configWorkflow);
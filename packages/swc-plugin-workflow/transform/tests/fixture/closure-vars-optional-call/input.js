@@ -0,0 +1,16 @@
+export async function optionalCallWorkflow(svc, id, hooks, event) {
+  'use workflow';
+
+  const fetchRecord = async () => {
+    'use step';
+    return svc?.fetch(id);
+  };
+
+  const notify = async () => {
+    'use step';
+    return hooks.onEvent?.(event);
+  };
+
+  await notify();
+  return await fetchRecord();
+}
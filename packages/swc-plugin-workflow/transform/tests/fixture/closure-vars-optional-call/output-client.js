@@ -0,0 +1,5 @@
+/**__internal_workflows{"workflows":{"input.js":{"optionalCallWorkflow":{"workflowId":"workflow//./input//optionalCallWorkflow"}}}}*/;
+export async function optionalCallWorkflow(svc, id, hooks, event) {
+    throw new Error("You attempted to execute workflow optionalCallWorkflow function directly. To start a workflow, use start(optionalCallWorkflow) from workflow/api");
+}
+optionalCallWorkflow.workflowId = "workflow//./input//optionalCallWorkflow";
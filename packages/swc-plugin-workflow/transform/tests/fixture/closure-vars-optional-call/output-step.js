@@ -0,0 +1,16 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"optionalCallWorkflow":{"workflowId":"workflow//./input//optionalCallWorkflow"}}},"steps":{"input.js":{"fetchRecord":{"stepId":"step//./input//fetchRecord"},"notify":{"stepId":"step//./input//notify"}}}}*/;
+var optionalCallWorkflow$fetchRecord = async ()=>{
+    const { id, svc } = __private_getClosureVars();
+    return svc?.fetch(id);
+};
+var optionalCallWorkflow$notify = async ()=>{
+    const { event, hooks } = __private_getClosureVars();
+    return hooks.onEvent?.(event);
+};
+export async function optionalCallWorkflow(svc, id, hooks, event) {
+    throw new Error("You attempted to execute workflow optionalCallWorkflow function directly. To start a workflow, use start(optionalCallWorkflow) from workflow/api");
+}
+optionalCallWorkflow.workflowId = "workflow//./input//optionalCallWorkflow";
+registerStepFunction("step//./input//optionalCallWorkflow/fetchRecord", optionalCallWorkflow$fetchRecord);
+registerStepFunction("step//./input//optionalCallWorkflow/notify", optionalCallWorkflow$notify);
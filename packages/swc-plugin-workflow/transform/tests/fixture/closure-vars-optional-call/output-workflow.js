@@ -0,0 +1,15 @@
+/**__internal_workflows{"workflows":{"input.js":{"optionalCallWorkflow":{"workflowId":"workflow//./input//optionalCallWorkflow"}}},"steps":{"input.js":{"fetchRecord":{"stepId":"step//./input//fetchRecord"},"notify":{"stepId":"step//./input//notify"}}}}*/;
+export async function optionalCallWorkflow(svc, id, hooks, event) {
+    const fetchRecord = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//optionalCallWorkflow/fetchRecord", ()=>({
+            id,
+            svc
+        }));
+    const notify = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//optionalCallWorkflow/notify", ()=>({
+            event,
+            hooks
+        }));
+    await notify();
+    return await fetchRecord();
+}
+optionalCallWorkflow.workflowId = "workflow//./input//optionalCallWorkflow";
+globalThis.__private_workflows.set("workflow//./input//optionalCallWorkflow", optionalCallWorkflow);
@@ -0,0 +1,19 @@
+export async function report(items) {
+  'use workflow';
+  const outer = items.length;
+  const label = 'total';
+  const summarize = async () => {
+    'use step';
+    return {
+      label,
+      compute() {
+        return outer;
+      },
+      describe(count) {
+        const local = count * 2;
+        return local;
+      },
+    };
+  };
+  return summarize();
+}
@@ -0,0 +1,20 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"report":{"workflowId":"workflow//./input//report"}}},"steps":{"input.js":{"summarize":{"stepId":"step//./input//summarize"}}}}*/;
+var report$summarize = async ()=>{
+    const { label, outer } = __private_getClosureVars();
+    return {
+        label,
+        compute () {
+            return outer;
+        },
+        describe (count) {
+            const local = count * 2;
+            return local;
+        }
+    };
+};
+export async function report(items) {
+    throw new Error("You attempted to execute workflow report function directly. To start a workflow, use start(report) from workflow/api");
+}
+report.workflowId = "workflow//./input//report";
+registerStepFunction("step//./input//report/summarize", report$summarize);
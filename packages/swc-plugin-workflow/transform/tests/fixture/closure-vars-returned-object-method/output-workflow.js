@@ -0,0 +1,12 @@
+/**__internal_workflows{"workflows":{"input.js":{"report":{"workflowId":"workflow//./input//report"}}},"steps":{"input.js":{"summarize":{"stepId":"step//./input//summarize"}}}}*/;
+export async function report(items) {
+    const outer = items.length;
+    const label = 'total';
+    const summarize = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//report/summarize", ()=>({
+            label,
+            outer
+        }));
+    return summarize();
+}
+report.workflowId = "workflow//./input//report";
+globalThis.__private_workflows.set("workflow//./input//report", report);
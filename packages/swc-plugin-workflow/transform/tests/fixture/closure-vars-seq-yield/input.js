@@ -0,0 +1,20 @@
+export async function seqYieldWorkflow(value, a, b) {
+  'use workflow';
+
+  const collectValues = async () => {
+    'use step';
+    function* streamValue() {
+      yield value;
+      yield* [a];
+    }
+    return [...streamValue()];
+  };
+
+  const runBoth = async () => {
+    'use step';
+    return (a(), b());
+  };
+
+  console.log(await collectValues());
+  return await runBoth();
+}
@@ -0,0 +1,5 @@
+/**__internal_workflows{"workflows":{"input.js":{"seqYieldWorkflow":{"workflowId":"workflow//./input//seqYieldWorkflow"}}}}*/;
+export async function seqYieldWorkflow(value, a, b) {
+    throw new Error("You attempted to execute workflow seqYieldWorkflow function directly. To start a workflow, use start(seqYieldWorkflow) from workflow/api");
+}
+seqYieldWorkflow.workflowId = "workflow//./input//seqYieldWorkflow";
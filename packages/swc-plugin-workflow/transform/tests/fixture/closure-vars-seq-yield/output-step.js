@@ -0,0 +1,24 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"seqYieldWorkflow":{"workflowId":"workflow//./input//seqYieldWorkflow"}}},"steps":{"input.js":{"collectValues":{"stepId":"step//./input//collectValues"},"runBoth":{"stepId":"step//./input//runBoth"}}}}*/;
+var seqYieldWorkflow$collectValues = async ()=>{
+    const { a, value } = __private_getClosureVars();
+    function* streamValue() {
+        yield value;
+        yield* [
+            a
+        ];
+    }
+    return [
+        ...streamValue()
+    ];
+};
+var seqYieldWorkflow$runBoth = async ()=>{
+    const { a, b } = __private_getClosureVars();
+    return a(), b();
+};
+export async function seqYieldWorkflow(value, a, b) {
+    throw new Error("You attempted to execute workflow seqYieldWorkflow function directly. To start a workflow, use start(seqYieldWorkflow) from workflow/api");
+}
+seqYieldWorkflow.workflowId = "workflow//./input//seqYieldWorkflow";
+registerStepFunction("step//./input//seqYieldWorkflow/collectValues", seqYieldWorkflow$collectValues);
+registerStepFunction("step//./input//seqYieldWorkflow/runBoth", seqYieldWorkflow$runBoth);
@@ -0,0 +1,15 @@
+/**__internal_workflows{"workflows":{"input.js":{"seqYieldWorkflow":{"workflowId":"workflow//./input//seqYieldWorkflow"}}},"steps":{"input.js":{"collectValues":{"stepId":"step//./input//collectValues"},"runBoth":{"stepId":"step//./input//runBoth"}}}}*/;
+export async function seqYieldWorkflow(value, a, b) {
+    const collectValues = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//seqYieldWorkflow/collectValues", ()=>({
+            a,
+            value
+        }));
+    const runBoth = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//seqYieldWorkflow/runBoth", ()=>({
+            a,
+            b
+        }));
+    console.log(await collectValues());
+    return await runBoth();
+}
+seqYieldWorkflow.workflowId = "workflow//./input//seqYieldWorkflow";
+globalThis.__private_workflows.set("workflow//./input//seqYieldWorkflow", seqYieldWorkflow);
@@ -0,0 +1,10 @@
+export async function report(items) {
+  'use workflow';
+  const label = 'total';
+  const count = items.length;
+  const summarize = async () => {
+    'use step';
+    return { label, count, kind: 'summary' };
+  };
+  return summarize();
+}
@@ -0,0 +1,5 @@
+/**__internal_workflows{"workflows":{"input.js":{"report":{"workflowId":"workflow//./input//report"}}}}*/;
+export async function report(items) {
+    throw new Error("You attempted to execute workflow report function directly. To start a workflow, use start(report) from workflow/api");
+}
+report.workflowId = "workflow//./input//report";
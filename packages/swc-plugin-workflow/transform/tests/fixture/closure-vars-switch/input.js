@@ -0,0 +1,22 @@
+export async function switchWorkflow(kind) {
+  'use workflow';
+  const SMALL = 'small';
+  const LARGE = 'large';
+  const DEFAULT_LABEL = 'unknown';
+
+  async function classify() {
+    'use step';
+    switch (kind) {
+      case SMALL: {
+        const label = SMALL;
+        return label;
+      }
+      case 'large':
+        return LARGE;
+      default:
+        return DEFAULT_LABEL;
+    }
+  }
+
+  return await classify();
+}
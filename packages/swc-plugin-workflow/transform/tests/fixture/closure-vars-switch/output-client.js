@@ -0,0 +1,5 @@
+/**__internal_workflows{"workflows":{"input.js":{"switchWorkflow":{"workflowId":"workflow//./input//switchWorkflow"}}}}*/;
+export async function switchWorkflow(kind) {
+    throw new Error("You attempted to execute workflow switchWorkflow function directly. To start a workflow, use start(switchWorkflow) from workflow/api");
+}
+switchWorkflow.workflowId = "workflow//./input//switchWorkflow";
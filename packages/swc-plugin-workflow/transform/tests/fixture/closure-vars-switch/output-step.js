@@ -0,0 +1,21 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"switchWorkflow":{"workflowId":"workflow//./input//switchWorkflow"}}},"steps":{"input.js":{"classify":{"stepId":"step//./input//classify"}}}}*/;
+async function switchWorkflow$classify() {
+    const { DEFAULT_LABEL, LARGE, SMALL, kind } = __private_getClosureVars();
+    switch(kind){
+        case SMALL:
+            {
+                const label = SMALL;
+                return label;
+            }
+        case 'large':
+            return LARGE;
+        default:
+            return DEFAULT_LABEL;
+    }
+}
+export async function switchWorkflow(kind) {
+    throw new Error("You attempted to execute workflow switchWorkflow function directly. To start a workflow, use start(switchWorkflow) from workflow/api");
+}
+switchWorkflow.workflowId = "workflow//./input//switchWorkflow";
+registerStepFunction("step//./input//switchWorkflow/classify", switchWorkflow$classify);
@@ -0,0 +1,15 @@
+/**__internal_workflows{"workflows":{"input.js":{"switchWorkflow":{"workflowId":"workflow//./input//switchWorkflow"}}},"steps":{"input.js":{"classify":{"stepId":"step//./input//classify"}}}}*/;
+export async function switchWorkflow(kind) {
+    const SMALL = 'small';
+    const LARGE = 'large';
+    const DEFAULT_LABEL = 'unknown';
+    var classify = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//switchWorkflow/classify", ()=>({
+            DEFAULT_LABEL,
+            LARGE,
+            SMALL,
+            kind
+        }));
+    return await classify();
+}
+switchWorkflow.workflowId = "workflow//./input//switchWorkflow";
+globalThis.__private_workflows.set("workflow//./input//switchWorkflow", switchWorkflow);
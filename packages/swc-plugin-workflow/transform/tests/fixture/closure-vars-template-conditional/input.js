@@ -0,0 +1,16 @@
+export async function greetWorkflow(formal, title, name, greeting) {
+  'use workflow';
+
+  const greet = async () => {
+    'use step';
+    return `${greeting}, ${formal ? `${title} ${name}` : name}!`;
+  };
+
+  const describe = async (count) => {
+    'use step';
+    return `${count === 1 ? 'one' : count > 1 ? `${count} ${name}s` : 'none'}`;
+  };
+
+  await describe(2);
+  return await greet();
+}
@@ -0,0 +1,5 @@
+/**__internal_workflows{"workflows":{"input.js":{"greetWorkflow":{"workflowId":"workflow//./input//greetWorkflow"}}}}*/;
+export async function greetWorkflow(formal, title, name, greeting) {
+    throw new Error("You attempted to execute workflow greetWorkflow function directly. To start a workflow, use start(greetWorkflow) from workflow/api");
+}
+greetWorkflow.workflowId = "workflow//./input//greetWorkflow";
@@ -0,0 +1,16 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"greetWorkflow":{"workflowId":"workflow//./input//greetWorkflow"}}},"steps":{"input.js":{"describe":{"stepId":"step//./input//describe"},"greet":{"stepId":"step//./input//greet"}}}}*/;
+var greetWorkflow$greet = async ()=>{
+    const { formal, greeting, name, title } = __private_getClosureVars();
+    return `${greeting}, ${formal ? `${title} ${name}` : name}!`;
+};
+var greetWorkflow$describe = async (count)=>{
+    const { name } = __private_getClosureVars();
+    return `${count === 1 ? 'one' : count > 1 ? `${count} ${name}s` : 'none'}`;
+};
+export async function greetWorkflow(formal, title, name, greeting) {
+    throw new Error("You attempted to execute workflow greetWorkflow function directly. To start a workflow, use start(greetWorkflow) from workflow/api");
+}
+greetWorkflow.workflowId = "workflow//./input//greetWorkflow";
+registerStepFunction("step//./input//greetWorkflow/greet", greetWorkflow$greet);
+registerStepFunction("step//./input//greetWorkflow/describe", greetWorkflow$describe);
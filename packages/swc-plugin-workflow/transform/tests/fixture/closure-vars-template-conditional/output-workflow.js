@@ -0,0 +1,16 @@
+/**__internal_workflows{"workflows":{"input.js":{"greetWorkflow":{"workflowId":"workflow//./input//greetWorkflow"}}},"steps":{"input.js":{"describe":{"stepId":"step//./input//describe"},"greet":{"stepId":"step//./input//greet"}}}}*/;
+export async function greetWorkflow(formal, title, name, greeting) {
+    const greet = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//greetWorkflow/greet", ()=>({
+            formal,
+            greeting,
+            name,
+            title
+        }));
+    const describe = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//greetWorkflow/describe", ()=>({
+            name
+        }));
+    await describe(2);
+    return await greet();
+}
+greetWorkflow.workflowId = "workflow//./input//greetWorkflow";
+globalThis.__private_workflows.set("workflow//./input//greetWorkflow", greetWorkflow);
@@ -0,0 +1,36 @@
+export async function tryCatchWorkflow(url, fallback, logger) {
+  'use workflow';
+
+  async function fetchInTry() {
+    'use step';
+    try {
+      return await fetch(url);
+    } catch {
+      return null;
+    }
+  }
+
+  async function fallbackInCatch() {
+    'use step';
+    try {
+      throw new Error('boom');
+    } catch ({ message }) {
+      return fallback + message;
+    }
+  }
+
+  async function logInFinally() {
+    'use step';
+    try {
+      return 1;
+    } catch (error) {
+      return error;
+    } finally {
+      logger.flush();
+    }
+  }
+
+  await fetchInTry();
+  await fallbackInCatch();
+  return await logInFinally();
+}
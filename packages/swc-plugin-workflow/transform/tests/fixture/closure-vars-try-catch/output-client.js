@@ -0,0 +1,5 @@
+/**__internal_workflows{"workflows":{"input.js":{"tryCatchWorkflow":{"workflowId":"workflow//./input//tryCatchWorkflow"}}}}*/;
+export async function tryCatchWorkflow(url, fallback, logger) {
+    throw new Error("You attempted to execute workflow tryCatchWorkflow function directly. To start a workflow, use start(tryCatchWorkflow) from workflow/api");
+}
+tryCatchWorkflow.workflowId = "workflow//./input//tryCatchWorkflow";
@@ -0,0 +1,35 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"tryCatchWorkflow":{"workflowId":"workflow//./input//tryCatchWorkflow"}}},"steps":{"input.js":{"fallbackInCatch":{"stepId":"step//./input//fallbackInCatch"},"fetchInTry":{"stepId":"step//./input//fetchInTry"},"logInFinally":{"stepId":"step//./input//logInFinally"}}}}*/;
+async function tryCatchWorkflow$fetchInTry() {
+    const { url } = __private_getClosureVars();
+    try {
+        return await fetch(url);
+    } catch  {
+        return null;
+    }
+}
+async function tryCatchWorkflow$fallbackInCatch() {
+    const { fallback } = __private_getClosureVars();
+    try {
+        throw new Error('boom');
+    } catch ({ message }) {
+        return fallback + message;
+    }
+}
+async function tryCatchWorkflow$logInFinally() {
+    const { logger } = __private_getClosureVars();
+    try {
+        return 1;
+    } catch (error) {
+        return error;
+    } finally{
+        logger.flush();
+    }
+}
+export async function tryCatchWorkflow(url, fallback, logger) {
+    throw new Error("You attempted to execute workflow tryCatchWorkflow function directly. To start a workflow, use start(tryCatchWorkflow) from workflow/api");
+}
+tryCatchWorkflow.workflowId = "workflow//./input//tryCatchWorkflow";
+registerStepFunction("step//./input//tryCatchWorkflow/fetchInTry", tryCatchWorkflow$fetchInTry);
+registerStepFunction("step//./input//tryCatchWorkflow/fallbackInCatch", tryCatchWorkflow$fallbackInCatch);
+registerStepFunction("step//./input//tryCatchWorkflow/logInFinally", tryCatchWorkflow$logInFinally);
@@ -0,0 +1,17 @@
+/**__internal_workflows{"workflows":{"input.js":{"tryCatchWorkflow":{"workflowId":"workflow//./input//tryCatchWorkflow"}}},"steps":{"input.js":{"fallbackInCatch":{"stepId":"step//./input//fallbackInCatch"},"fetchInTry":{"stepId":"step//./input//fetchInTry"},"logInFinally":{"stepId":"step//./input//logInFinally"}}}}*/;
+export async function tryCatchWorkflow(url, fallback, logger) {
+    var fetchInTry = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//tryCatchWorkflow/fetchInTry", ()=>({
+            url
+        }));
+    var fallbackInCatch = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//tryCatchWorkflow/fallbackInCatch", ()=>({
+            fallback
+        }));
+    var logInFinally = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//tryCatchWorkflow/logInFinally", ()=>({
+            logger
+        }));
+    await fetchInTry();
+    await fallbackInCatch();
+    return await logInFinally();
+}
+tryCatchWorkflow.workflowId = "workflow//./input//tryCatchWorkflow";
+globalThis.__private_workflows.set("workflow//./input//tryCatchWorkflow", tryCatchWorkflow);
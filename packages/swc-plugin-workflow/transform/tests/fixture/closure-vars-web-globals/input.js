@@ -0,0 +1,12 @@
+export async function webGlobalsWorkflow() {
+  'use workflow';
+
+  const createRecord = async () => {
+    'use step';
+    const start = performance.now();
+    const id = crypto.randomUUID();
+    return { id, elapsed: performance.now() - start };
+  };
+
+  return await createRecord();
+}
@@ -0,0 +1,5 @@
+/**__internal_workflows{"workflows":{"input.js":{"webGlobalsWorkflow":{"workflowId":"workflow//./input//webGlobalsWorkflow"}}}}*/;
+export async function webGlobalsWorkflow() {
+    throw new Error("You attempted to execute workflow webGlobalsWorkflow function directly. To start a workflow, use start(webGlobalsWorkflow) from workflow/api");
+}
+webGlobalsWorkflow.workflowId = "workflow//./input//webGlobalsWorkflow";
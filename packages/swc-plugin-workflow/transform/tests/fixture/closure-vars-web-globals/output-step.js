@@ -0,0 +1,15 @@
+import { registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"webGlobalsWorkflow":{"workflowId":"workflow//./input//webGlobalsWorkflow"}}},"steps":{"input.js":{"createRecord":{"stepId":"step//./input//createRecord"}}}}*/;
+var webGlobalsWorkflow$createRecord = async ()=>{
+    const start = performance.now();
+    const id = crypto.randomUUID();
+    return {
+        id,
+        elapsed: performance.now() - start
+    };
+};
+export async function webGlobalsWorkflow() {
+    throw new Error("You attempted to execute workflow webGlobalsWorkflow function directly. To start a workflow, use start(webGlobalsWorkflow) from workflow/api");
+}
+webGlobalsWorkflow.workflowId = "workflow//./input//webGlobalsWorkflow";
+registerStepFunction("step//./input//webGlobalsWorkflow/createRecord", webGlobalsWorkflow$createRecord);
@@ -0,0 +1,7 @@
+/**__internal_workflows{"workflows":{"input.js":{"webGlobalsWorkflow":{"workflowId":"workflow//./input//webGlobalsWorkflow"}}},"steps":{"input.js":{"createRecord":{"stepId":"step//./input//createRecord"}}}}*/;
+export async function webGlobalsWorkflow() {
+    const createRecord = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//webGlobalsWorkflow/createRecord");
+    return await createRecord();
+}
+webGlobalsWorkflow.workflowId = "workflow//./input//webGlobalsWorkflow";
+globalThis.__private_workflows.set("workflow//./input//webGlobalsWorkflow", webGlobalsWorkflow);
@@ -0,0 +1,19 @@
+export async function add(a, b) {
+  'server step';
+  return a + b;
+}
+
+export async function notAStep(a, b) {
+  'use step';
+  return a - b;
+}
+
+export async function calculate(a, b) {
+  'server workflow';
+  const scale = 2;
+  const double = async (value) => {
+    'server step';
+    return value * scale;
+  };
+  return double(await add(a, b));
+}
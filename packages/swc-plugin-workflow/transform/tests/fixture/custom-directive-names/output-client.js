@@ -0,0 +1,13 @@
+/**__internal_workflows{"workflows":{"input.js":{"calculate":{"workflowId":"workflow//./input//calculate"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"}}}}*/;
+export async function add(a, b) {
+    return a + b;
+}
+add.stepId = "step//./input//add";
+export async function notAStep(a, b) {
+    'use step';
+    return a - b;
+}
+export async function calculate(a, b) {
+    throw new Error("You attempted to execute workflow calculate function directly. To start a workflow, use start(calculate) from workflow/api");
+}
+calculate.workflowId = "workflow//./input//calculate";
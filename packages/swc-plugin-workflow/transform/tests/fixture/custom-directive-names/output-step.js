@@ -0,0 +1,19 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"calculate":{"workflowId":"workflow//./input//calculate"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"double":{"stepId":"step//./input//double"}}}}*/;
+var calculate$double = async (value)=>{
+    const { scale } = __private_getClosureVars();
+    return value * scale;
+};
+export async function add(a, b) {
+    return a + b;
+}
+export async function notAStep(a, b) {
+    'use step';
+    return a - b;
+}
+export async function calculate(a, b) {
+    throw new Error("You attempted to execute workflow calculate function directly. To start a workflow, use start(calculate) from workflow/api");
+}
+calculate.workflowId = "workflow//./input//calculate";
+registerStepFunction("step//./input//add", add);
+registerStepFunction("step//./input//calculate/double", calculate$double);
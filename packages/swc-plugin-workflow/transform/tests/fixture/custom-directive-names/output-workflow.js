@@ -0,0 +1,15 @@
+/**__internal_workflows{"workflows":{"input.js":{"calculate":{"workflowId":"workflow//./input//calculate"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"double":{"stepId":"step//./input//double"}}}}*/;
+export var add = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//add");
+export async function notAStep(a, b) {
+    'use step';
+    return a - b;
+}
+export async function calculate(a, b) {
+    const scale = 2;
+    const double = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//calculate/double", ()=>({
+            scale
+        }));
+    return double(await add(a, b));
+}
+calculate.workflowId = "workflow//./input//calculate";
+globalThis.__private_workflows.set("workflow//./input//calculate", calculate);
@@ -0,0 +1,30 @@
+import { WORKFLOW_SERIALIZE, WORKFLOW_DESERIALIZE } from '@workflow/serde';
+
+export class Point {
+  constructor(x, y) {
+    this.x = x;
+    this.y = y;
+  }
+
+  static [WORKFLOW_SERIALIZE](instance) {
+    return { x: instance.x, y: instance.y };
+  }
+
+  static [WORKFLOW_DESERIALIZE](data) {
+    return new Point(data.x, data.y);
+  }
+}
+
+export async function move(point, dx) {
+  'use step';
+  return new Point(point.x + dx, point.y);
+}
+
+export async function shift(point, dx) {
+  'use workflow';
+  const scale = async (value) => {
+    'use step';
+    return value * dx;
+  };
+  return move(point, await scale(1));
+}
@@ -0,0 +1,27 @@
+import { registerSerializationClass } from "@acme/runtime/class-serialization";
+import { WORKFLOW_SERIALIZE, WORKFLOW_DESERIALIZE } from '@workflow/serde';
+/**__internal_workflows{"workflows":{"input.js":{"shift":{"workflowId":"workflow//./input//shift"}}},"steps":{"input.js":{"move":{"stepId":"step//./input//move"}}},"classes":{"input.js":{"Point":{"classId":"class//./input//Point"}}}}*/;
+export class Point {
+    constructor(x, y){
+        this.x = x;
+        this.y = y;
+    }
+    static [WORKFLOW_SERIALIZE](instance) {
+        return {
+            x: instance.x,
+            y: instance.y
+        };
+    }
+    static [WORKFLOW_DESERIALIZE](data) {
+        return new Point(data.x, data.y);
+    }
+}
+export async function move(point, dx) {
+    return new Point(point.x + dx, point.y);
+}
+move.stepId = "step//./input//move";
+export async function shift(point, dx) {
+    throw new Error("You attempted to execute workflow shift function directly. To start a workflow, use start(shift) from workflow/api");
+}
+shift.workflowId = "workflow//./input//shift";
+registerSerializationClass("class//./input//Point", Point);
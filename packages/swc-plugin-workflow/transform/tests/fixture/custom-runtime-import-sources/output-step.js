@@ -0,0 +1,33 @@
+import { __private_getClosureVars, registerStepFunction } from "@acme/runtime/private";
+import { registerSerializationClass } from "@acme/runtime/class-serialization";
+import { WORKFLOW_SERIALIZE, WORKFLOW_DESERIALIZE } from '@workflow/serde';
+/**__internal_workflows{"workflows":{"input.js":{"shift":{"workflowId":"workflow//./input//shift"}}},"steps":{"input.js":{"move":{"stepId":"step//./input//move"},"scale":{"stepId":"step//./input//scale"}}},"classes":{"input.js":{"Point":{"classId":"class//./input//Point"}}}}*/;
+var shift$scale = async (value)=>{
+    const { dx } = __private_getClosureVars();
+    return value * dx;
+};
+export class Point {
+    constructor(x, y){
+        this.x = x;
+        this.y = y;
+    }
+    static [WORKFLOW_SERIALIZE](instance) {
+        return {
+            x: instance.x,
+            y: instance.y
+        };
+    }
+    static [WORKFLOW_DESERIALIZE](data) {
+        return new Point(data.x, data.y);
+    }
+}
+export async function move(point, dx) {
+    return new Point(point.x + dx, point.y);
+}
+export async function shift(point, dx) {
+    throw new Error("You attempted to execute workflow shift function directly. To start a workflow, use start(shift) from workflow/api");
+}
+shift.workflowId = "workflow//./input//shift";
+registerStepFunction("step//./input//move", move);
+registerStepFunction("step//./input//shift/scale", shift$scale);
+registerSerializationClass("class//./input//Point", Point);
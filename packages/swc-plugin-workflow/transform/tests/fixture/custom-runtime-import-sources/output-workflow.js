@@ -0,0 +1,28 @@
+import { registerSerializationClass } from "@acme/runtime/class-serialization";
+import { WORKFLOW_SERIALIZE, WORKFLOW_DESERIALIZE } from '@workflow/serde';
+/**__internal_workflows{"workflows":{"input.js":{"shift":{"workflowId":"workflow//./input//shift"}}},"steps":{"input.js":{"move":{"stepId":"step//./input//move"},"scale":{"stepId":"step//./input//scale"}}},"classes":{"input.js":{"Point":{"classId":"class//./input//Point"}}}}*/;
+export class Point {
+    constructor(x, y){
+        this.x = x;
+        this.y = y;
+    }
+    static [WORKFLOW_SERIALIZE](instance) {
+        return {
+            x: instance.x,
+            y: instance.y
+        };
+    }
+    static [WORKFLOW_DESERIALIZE](data) {
+        return new Point(data.x, data.y);
+    }
+}
+export var move = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//move");
+export async function shift(point, dx) {
+    const scale = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//shift/scale", ()=>({
+            dx
+        }));
+    return move(point, await scale(1));
+}
+shift.workflowId = "workflow//./input//shift";
+globalThis.__private_workflows.set("workflow//./input//shift", shift);
+registerSerializationClass("class//./input//Point", Point);
@@ -1,5 +1,5 @@
-import { registerSerializationClass } from "workflow/internal/class-serialization";
 // Test custom serialization with imported symbols from '@workflow/serde'
+import { registerSerializationClass } from "workflow/internal/class-serialization";
 import { WORKFLOW_SERIALIZE, WORKFLOW_DESERIALIZE } from '@workflow/serde';
 /**__internal_workflows{"classes":{"input.js":{"Color":{"classId":"class//./input//Color"},"Vector":{"classId":"class//./input//Vector"}}}}*/;
 // Class using imported symbols
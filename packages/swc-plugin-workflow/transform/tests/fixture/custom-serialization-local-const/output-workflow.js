@@ -1,6 +1,6 @@
+// Test custom serialization with locally defined symbols using Symbol.for()
 import { registerSerializationClass } from "workflow/internal/class-serialization";
 /**__internal_workflows{"classes":{"input.js":{"Circle":{"classId":"class//./input//Circle"},"Rectangle":{"classId":"class//./input//Rectangle"},"Triangle":{"classId":"class//./input//Triangle"}}}}*/;
-// Test custom serialization with locally defined symbols using Symbol.for()
 const WORKFLOW_SERIALIZE = Symbol.for('workflow-serialize');
 const WORKFLOW_DESERIALIZE = Symbol.for('workflow-deserialize');
 // Class using locally defined symbols
@@ -0,0 +1,33 @@
+import { WORKFLOW_SERIALIZE, WORKFLOW_DESERIALIZE } from '@workflow/serde';
+
+// Only referenced through its serialization registration
+class Point {
+  constructor(x, y) {
+    this.x = x;
+    this.y = y;
+  }
+
+  static [WORKFLOW_SERIALIZE](instance) {
+    return { x: instance.x, y: instance.y };
+  }
+
+  static [WORKFLOW_DESERIALIZE](data) {
+    return new Point(data.x, data.y);
+  }
+}
+
+// Bound to a variable that nothing else reads
+const Vector = class Inner {
+  static [WORKFLOW_SERIALIZE](instance) {
+    return { dx: instance.dx, dy: instance.dy };
+  }
+
+  static [WORKFLOW_DESERIALIZE](data) {
+    return { dx: data.dx, dy: data.dy };
+  }
+};
+
+export async function origin() {
+  'use workflow';
+  return 0;
+}
@@ -0,0 +1,40 @@
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+import { WORKFLOW_SERIALIZE, WORKFLOW_DESERIALIZE } from '@workflow/serde';
+/**__internal_workflows{"workflows":{"input.js":{"origin":{"workflowId":"workflow//./input//origin"}}},"classes":{"input.js":{"Point":{"classId":"class//./input//Point"},"Vector":{"classId":"class//./input//Vector"}}}}*/;
+// Only referenced through its serialization registration
+class Point {
+    constructor(x, y){
+        this.x = x;
+        this.y = y;
+    }
+    static [WORKFLOW_SERIALIZE](instance) {
+        return {
+            x: instance.x,
+            y: instance.y
+        };
+    }
+    static [WORKFLOW_DESERIALIZE](data) {
+        return new Point(data.x, data.y);
+    }
+}
+// Bound to a variable that nothing else reads
+const Vector = class Inner {
+    static [WORKFLOW_SERIALIZE](instance) {
+        return {
+            dx: instance.dx,
+            dy: instance.dy
+        };
+    }
+    static [WORKFLOW_DESERIALIZE](data) {
+        return {
+            dx: data.dx,
+            dy: data.dy
+        };
+    }
+};
+export async function origin() {
+    throw new Error("You attempted to execute workflow origin function directly. To start a workflow, use start(origin) from workflow/api");
+}
+origin.workflowId = "workflow//./input//origin";
+registerSerializationClass("class//./input//Point", Point);
+registerSerializationClass("class//./input//Vector", Vector);
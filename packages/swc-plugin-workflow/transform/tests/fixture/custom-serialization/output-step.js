@@ -1,6 +1,6 @@
+// Class with custom serialization methods using symbols
 import { registerSerializationClass } from "workflow/internal/class-serialization";
 /**__internal_workflows{"classes":{"input.js":{"Point":{"classId":"class//./input//Point"}}}}*/;
-// Class with custom serialization methods using symbols
 export class Point {
     constructor(x, y){
         this.x = x;
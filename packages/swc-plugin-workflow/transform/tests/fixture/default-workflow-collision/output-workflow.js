@@ -1,5 +1,5 @@
-/**__internal_workflows{"workflows":{"input.js":{"default":{"workflowId":"workflow//./input//default"}}}}*/;
 // Existing variable named __default
+/**__internal_workflows{"workflows":{"input.js":{"default":{"workflowId":"workflow//./input//default"}}}}*/;
 const __default = "existing variable";
 // Use it to avoid unused variable
 console.log(__default);
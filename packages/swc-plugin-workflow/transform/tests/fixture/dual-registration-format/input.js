@@ -0,0 +1,28 @@
+export class Point {
+  constructor(x, y) {
+    this.x = x;
+    this.y = y;
+  }
+
+  static [Symbol.for('workflow-serialize')](instance) {
+    return { x: instance.x, y: instance.y };
+  }
+
+  static [Symbol.for('workflow-deserialize')](data) {
+    return new Point(data.x, data.y);
+  }
+}
+
+export async function add(a, b) {
+  'use step';
+  return a + b;
+}
+
+export async function scaled(value, factor) {
+  'use workflow';
+  async function scale() {
+    'use step';
+    return value * factor;
+  }
+  return await scale();
+}
@@ -0,0 +1,32 @@
+const { __private_getClosureVars, registerStepFunction } = globalThis[Symbol.for("workflow/internal/private")] ?? (typeof require === "function" ? require("workflow/internal/private") : undefined);
+const { registerSerializationClass } = globalThis[Symbol.for("workflow/internal/class-serialization")] ?? (typeof require === "function" ? require("workflow/internal/class-serialization") : undefined);
+/**__internal_workflows{"workflows":{"input.js":{"scaled":{"workflowId":"workflow//./input//scaled"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"scale":{"stepId":"step//./input//scale"}}},"classes":{"input.js":{"Point":{"classId":"class//./input//Point"}}}}*/;
+async function scaled$scale() {
+    const { factor, value } = __private_getClosureVars();
+    return value * factor;
+}
+export class Point {
+    constructor(x, y){
+        this.x = x;
+        this.y = y;
+    }
+    static [Symbol.for('workflow-serialize')](instance) {
+        return {
+            x: instance.x,
+            y: instance.y
+        };
+    }
+    static [Symbol.for('workflow-deserialize')](data) {
+        return new Point(data.x, data.y);
+    }
+}
+export async function add(a, b) {
+    return a + b;
+}
+export async function scaled(value, factor) {
+    throw new Error("You attempted to execute workflow scaled function directly. To start a workflow, use start(scaled) from workflow/api");
+}
+scaled.workflowId = "workflow//./input//scaled";
+registerStepFunction("step//./input//add", add);
+registerStepFunction("step//./input//scaled/scale", scaled$scale);
+registerSerializationClass("class//./input//Point", Point);
@@ -0,0 +1,28 @@
+const { registerSerializationClass } = globalThis[Symbol.for("workflow/internal/class-serialization")] ?? (typeof require === "function" ? require("workflow/internal/class-serialization") : undefined);
+/**__internal_workflows{"workflows":{"input.js":{"scaled":{"workflowId":"workflow//./input//scaled"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"scale":{"stepId":"step//./input//scale"}}},"classes":{"input.js":{"Point":{"classId":"class//./input//Point"}}}}*/;
+export class Point {
+    constructor(x, y){
+        this.x = x;
+        this.y = y;
+    }
+    static [Symbol.for('workflow-serialize')](instance) {
+        return {
+            x: instance.x,
+            y: instance.y
+        };
+    }
+    static [Symbol.for('workflow-deserialize')](data) {
+        return new Point(data.x, data.y);
+    }
+}
+export var add = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//add");
+export async function scaled(value, factor) {
+    var scale = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//scaled/scale", ()=>({
+            factor,
+            value
+        }));
+    return await scale();
+}
+scaled.workflowId = "workflow//./input//scaled";
+globalThis.__private_workflows.set("workflow//./input//scaled", scaled);
+registerSerializationClass("class//./input//Point", Point);
@@ -0,0 +1,21 @@
+export async function checkout(cart) {
+  'use workflow';
+  return cart;
+}
+
+export const refund = async (order) => {
+  'use workflow';
+  return order;
+};
+
+export class Orders {
+  static async archive(order) {
+    'use workflow';
+    return order;
+  }
+}
+
+export default async (order) => {
+  'use workflow';
+  return order;
+};
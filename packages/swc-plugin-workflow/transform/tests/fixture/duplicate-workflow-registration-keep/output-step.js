@@ -0,0 +1,20 @@
+/**__internal_workflows{"workflows":{"input.js":{"Orders.archive":{"workflowId":"workflow//./input//Orders.archive"},"checkout":{"workflowId":"workflow//./input//checkout"},"default":{"workflowId":"workflow//./input//default"},"refund":{"workflowId":"workflow//./input//refund"}}}}*/;
+export async function checkout(cart) {
+    throw new Error("You attempted to execute workflow checkout function directly. To start a workflow, use start(checkout) from workflow/api");
+}
+checkout.workflowId = "workflow//./input//checkout";
+export const refund = async (order)=>{
+    throw new Error("You attempted to execute workflow refund function directly. To start a workflow, use start(refund) from workflow/api");
+};
+refund.workflowId = "workflow//./input//refund";
+export class Orders {
+    static async archive(order) {
+        throw new Error("You attempted to execute workflow Orders.archive function directly. To start a workflow, use start(workflow) from workflow/api");
+    }
+}
+const __default = async (order)=>{
+    throw new Error("You attempted to execute workflow __default function directly. To start a workflow, use start(__default) from workflow/api");
+};
+__default.workflowId = "workflow//./input//default";
+export default __default;
+Orders.archive.workflowId = "workflow//./input//Orders.archive";
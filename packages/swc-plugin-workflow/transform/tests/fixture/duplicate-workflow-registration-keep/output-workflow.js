@@ -0,0 +1,24 @@
+/**__internal_workflows{"workflows":{"input.js":{"Orders.archive":{"workflowId":"workflow//./input//Orders.archive"},"checkout":{"workflowId":"workflow//./input//checkout"},"default":{"workflowId":"workflow//./input//default"},"refund":{"workflowId":"workflow//./input//refund"}}}}*/;
+export async function checkout(cart) {
+    return cart;
+}
+checkout.workflowId = "workflow//./input//checkout";
+if (!globalThis.__private_workflows.has("workflow//./input//checkout")) globalThis.__private_workflows.set("workflow//./input//checkout", checkout);
+export const refund = async (order)=>{
+    return order;
+};
+refund.workflowId = "workflow//./input//refund";
+if (!globalThis.__private_workflows.has("workflow//./input//refund")) globalThis.__private_workflows.set("workflow//./input//refund", refund);
+export class Orders {
+    static async archive(order) {
+        return order;
+    }
+}
+const __default = async (order)=>{
+    return order;
+};
+__default.workflowId = "workflow//./input//default";
+if (!globalThis.__private_workflows.has("workflow//./input//default")) globalThis.__private_workflows.set("workflow//./input//default", __default);
+export default __default;
+Orders.archive.workflowId = "workflow//./input//Orders.archive";
+if (!globalThis.__private_workflows.has("workflow//./input//Orders.archive")) globalThis.__private_workflows.set("workflow//./input//Orders.archive", Orders.archive);
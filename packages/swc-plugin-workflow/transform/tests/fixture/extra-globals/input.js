@@ -0,0 +1,11 @@
+export async function cloneWorkflow(input) {
+  'use workflow';
+
+  const cloneInput = async () => {
+    'use step';
+    const copy = structuredClone(input);
+    return { copy, runtime: Bun.version };
+  };
+
+  return await cloneInput();
+}
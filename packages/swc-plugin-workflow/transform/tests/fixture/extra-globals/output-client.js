@@ -0,0 +1,5 @@
+/**__internal_workflows{"workflows":{"input.js":{"cloneWorkflow":{"workflowId":"workflow//./input//cloneWorkflow"}}}}*/;
+export async function cloneWorkflow(input) {
+    throw new Error("You attempted to execute workflow cloneWorkflow function directly. To start a workflow, use start(cloneWorkflow) from workflow/api");
+}
+cloneWorkflow.workflowId = "workflow//./input//cloneWorkflow";
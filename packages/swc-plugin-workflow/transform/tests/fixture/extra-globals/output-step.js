@@ -0,0 +1,15 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"cloneWorkflow":{"workflowId":"workflow//./input//cloneWorkflow"}}},"steps":{"input.js":{"cloneInput":{"stepId":"step//./input//cloneInput"}}}}*/;
+var cloneWorkflow$cloneInput = async ()=>{
+    const { input } = __private_getClosureVars();
+    const copy = structuredClone(input);
+    return {
+        copy,
+        runtime: Bun.version
+    };
+};
+export async function cloneWorkflow(input) {
+    throw new Error("You attempted to execute workflow cloneWorkflow function directly. To start a workflow, use start(cloneWorkflow) from workflow/api");
+}
+cloneWorkflow.workflowId = "workflow//./input//cloneWorkflow";
+registerStepFunction("step//./input//cloneWorkflow/cloneInput", cloneWorkflow$cloneInput);
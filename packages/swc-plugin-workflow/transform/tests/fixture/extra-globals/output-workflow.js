@@ -0,0 +1,9 @@
+/**__internal_workflows{"workflows":{"input.js":{"cloneWorkflow":{"workflowId":"workflow//./input//cloneWorkflow"}}},"steps":{"input.js":{"cloneInput":{"stepId":"step//./input//cloneInput"}}}}*/;
+export async function cloneWorkflow(input) {
+    const cloneInput = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//cloneWorkflow/cloneInput", ()=>({
+            input
+        }));
+    return await cloneInput();
+}
+cloneWorkflow.workflowId = "workflow//./input//cloneWorkflow";
+globalThis.__private_workflows.set("workflow//./input//cloneWorkflow", cloneWorkflow);
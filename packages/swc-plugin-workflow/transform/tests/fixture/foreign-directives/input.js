@@ -0,0 +1,11 @@
+'use client';
+
+export async function save(data) {
+  'use server';
+  return fetch('/api/save', { method: 'POST', body: data });
+}
+
+export async function submit(data) {
+  'use step';
+  return save(data);
+}
@@ -0,0 +1,13 @@
+'use client';
+/**__internal_workflows{"steps":{"input.js":{"submit":{"stepId":"step//./input//submit"}}}}*/;
+export async function save(data) {
+    'use server';
+    return fetch('/api/save', {
+        method: 'POST',
+        body: data
+    });
+}
+export async function submit(data) {
+    return save(data);
+}
+submit.stepId = "step//./input//submit";
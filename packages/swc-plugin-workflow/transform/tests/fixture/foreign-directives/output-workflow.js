@@ -0,0 +1,10 @@
+'use client';
+/**__internal_workflows{"steps":{"input.js":{"submit":{"stepId":"step//./input//submit"}}}}*/;
+export async function save(data) {
+    'use server';
+    return fetch('/api/save', {
+        method: 'POST',
+        body: data
+    });
+}
+export var submit = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//submit");
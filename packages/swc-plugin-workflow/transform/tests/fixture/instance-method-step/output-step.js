@@ -21,6 +21,6 @@ export class Calculator {
         return a + b + this.multiplier;
     }
 }
-registerStepFunction("step//./input//Calculator#multiply", Calculator.prototype["multiply"]);
 registerStepFunction("step//./input//Calculator#add", Calculator.prototype["add"]);
+registerStepFunction("step//./input//Calculator#multiply", Calculator.prototype["multiply"]);
 registerSerializationClass("class//./input//Calculator", Calculator);
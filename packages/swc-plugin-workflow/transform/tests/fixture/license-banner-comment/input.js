@@ -0,0 +1,8 @@
+/* @license MIT
+ * Copyright (c) Example */
+import { db } from './db';
+
+export async function fetchUser(id) {
+  'use step';
+  return db.users.find(id);
+}
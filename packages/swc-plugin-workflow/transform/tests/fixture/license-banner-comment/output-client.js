@@ -0,0 +1,6 @@
+/* @license MIT
+ * Copyright (c) Example */ /**__internal_workflows{"steps":{"input.js":{"fetchUser":{"stepId":"step//./input//fetchUser"}}}}*/;
+export async function fetchUser(id) {
+    return db.users.find(id);
+}
+fetchUser.stepId = "step//./input//fetchUser";
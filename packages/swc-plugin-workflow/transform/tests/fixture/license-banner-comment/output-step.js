@@ -0,0 +1,8 @@
+/* @license MIT
+ * Copyright (c) Example */ import { registerStepFunction } from "workflow/internal/private";
+import { db } from './db';
+/**__internal_workflows{"steps":{"input.js":{"fetchUser":{"stepId":"step//./input//fetchUser"}}}}*/;
+export async function fetchUser(id) {
+    return db.users.find(id);
+}
+registerStepFunction("step//./input//fetchUser", fetchUser);
@@ -0,0 +1,3 @@
+/* @license MIT
+ * Copyright (c) Example */ /**__internal_workflows{"steps":{"input.js":{"fetchUser":{"stepId":"step//./input//fetchUser"}}}}*/;
+export var fetchUser = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//fetchUser");
@@ -0,0 +1,6 @@
+/* @license Apache-2.0 */
+'use step';
+
+export async function fetchUser(id) {
+  return fetch(`/api/users/${id}`);
+}
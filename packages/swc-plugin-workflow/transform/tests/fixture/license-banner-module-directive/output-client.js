@@ -0,0 +1,5 @@
+/* @license Apache-2.0 */ /**__internal_workflows{"steps":{"input.js":{"fetchUser":{"stepId":"step//./input//fetchUser"}}}}*/;
+export async function fetchUser(id) {
+    return fetch(`/api/users/${id}`);
+}
+fetchUser.stepId = "step//./input//fetchUser";
@@ -0,0 +1,6 @@
+/* @license Apache-2.0 */ import { registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"steps":{"input.js":{"fetchUser":{"stepId":"step//./input//fetchUser"}}}}*/;
+export async function fetchUser(id) {
+    return fetch(`/api/users/${id}`);
+}
+registerStepFunction("step//./input//fetchUser", fetchUser);
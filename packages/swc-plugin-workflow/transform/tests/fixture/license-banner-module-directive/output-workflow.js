@@ -0,0 +1,3 @@
+/* @license Apache-2.0 */ 'use step';
+/**__internal_workflows{"steps":{"input.js":{"fetchUser":{"stepId":"step//./input//fetchUser"}}}}*/;
+export var fetchUser = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//fetchUser");
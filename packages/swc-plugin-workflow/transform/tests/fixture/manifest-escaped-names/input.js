@@ -0,0 +1,14 @@
+export const replies = {
+  'say "hi"': async (name) => {
+    'use step';
+    return `hi ${name}`;
+  },
+  'close */ comment': async () => {
+    'use step';
+    return 'closed';
+  },
+  'back\\slash': async () => {
+    'use step';
+    return 'escaped';
+  },
+};
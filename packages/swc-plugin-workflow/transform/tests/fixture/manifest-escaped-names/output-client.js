@@ -0,0 +1,18 @@
+/**__internal_workflows{"steps":{"input.js":{"replies/back\\slash":{"stepId":"step//./input//replies/back\\slash"},"replies/close *\/ comment":{"stepId":"step//./input//replies/close *\/ comment"},"replies/say \"hi\"":{"stepId":"step//./input//replies/say \"hi\""}}}}*/;
+var replies$say__hi_ = async function(name) {
+    return `hi ${name}`;
+};
+var replies$close__$_comment = async function() {
+    return 'closed';
+};
+var replies$back_slash = async function() {
+    return 'escaped';
+};
+export const replies = {
+    'say "hi"': replies$say__hi_,
+    'close */ comment': replies$close__$_comment,
+    'back\\slash': replies$back_slash
+};
+replies$say__hi_.stepId = 'step//./input//replies/say "hi"';
+replies$close__$_comment.stepId = "step//./input//replies/close */ comment";
+replies$back_slash.stepId = "step//./input//replies/back\\slash";
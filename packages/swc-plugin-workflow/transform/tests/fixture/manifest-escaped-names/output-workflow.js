@@ -0,0 +1,6 @@
+/**__internal_workflows{"steps":{"input.js":{"replies/back\\slash":{"stepId":"step//./input//replies/back\\slash"},"replies/close *\/ comment":{"stepId":"step//./input//replies/close *\/ comment"},"replies/say \"hi\"":{"stepId":"step//./input//replies/say \"hi\""}}}}*/;
+export const replies = {
+    'say "hi"': globalThis[Symbol.for("WORKFLOW_USE_STEP")]('step//./input//replies/say "hi"'),
+    'close */ comment': globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//replies/close */ comment"),
+    'back\\slash': globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//replies/back\\slash")
+};
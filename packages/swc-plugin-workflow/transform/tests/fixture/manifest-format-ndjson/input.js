@@ -0,0 +1,13 @@
+export async function add(a, b) {
+  'use step';
+  return a + b;
+}
+
+export async function sum(values) {
+  'use workflow';
+  let total = 0;
+  for (const value of values) {
+    total = await add(total, value);
+  }
+  return total;
+}
@@ -0,0 +1,13 @@
+/**__internal_workflows_ndjson
+{"input.js":{"workflows":{"sum":{"workflowId":"workflow//./input//sum"}},"steps":{"add":{"stepId":"step//./input//add"}}}}
+*/;
+export var add = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//add");
+export async function sum(values) {
+    let total = 0;
+    for (const value of values){
+        total = await add(total, value);
+    }
+    return total;
+}
+sum.workflowId = "workflow//./input//sum";
+globalThis.__private_workflows.set("workflow//./input//sum", sum);
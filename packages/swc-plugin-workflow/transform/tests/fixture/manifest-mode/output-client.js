@@ -0,0 +1,9 @@
+/**__internal_workflows{"mode":"client","workflows":{"input.js":{"sum":{"workflowId":"workflow//./input//sum"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"}}}}*/;
+export async function add(a, b) {
+    return a + b;
+}
+add.stepId = "step//./input//add";
+export async function sum(a, b) {
+    throw new Error("You attempted to execute workflow sum function directly. To start a workflow, use start(sum) from workflow/api");
+}
+sum.workflowId = "workflow//./input//sum";
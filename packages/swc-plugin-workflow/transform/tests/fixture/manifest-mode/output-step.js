@@ -0,0 +1,10 @@
+import { registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"mode":"step","workflows":{"input.js":{"sum":{"workflowId":"workflow//./input//sum"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"}}}}*/;
+export async function add(a, b) {
+    return a + b;
+}
+export async function sum(a, b) {
+    throw new Error("You attempted to execute workflow sum function directly. To start a workflow, use start(sum) from workflow/api");
+}
+sum.workflowId = "workflow//./input//sum";
+registerStepFunction("step//./input//add", add);
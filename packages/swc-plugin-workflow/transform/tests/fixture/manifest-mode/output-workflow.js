@@ -0,0 +1,7 @@
+/**__internal_workflows{"mode":"workflow","workflows":{"input.js":{"sum":{"workflowId":"workflow//./input//sum"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"}}}}*/;
+export var add = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//add");
+export async function sum(a, b) {
+    return add(a, b);
+}
+sum.workflowId = "workflow//./input//sum";
+globalThis.__private_workflows.set("workflow//./input//sum", sum);
@@ -0,0 +1,20 @@
+export async function add(a, b) {
+  'use step';
+  return a + b;
+}
+
+export async function sum(values) {
+  'use workflow';
+  let total = 0;
+  for (const value of values) {
+    total = await add(total, value);
+  }
+  return total;
+}
+
+export const replies = {
+  'say "hi"': async (name) => {
+    'use step';
+    return `hi ${name}`;
+  },
+};
@@ -0,0 +1,36 @@
+import { registerStepFunction } from "workflow/internal/private";
+var replies$say__hi_ = async function(name) {
+    return `hi ${name}`;
+};
+export async function add(a, b) {
+    return a + b;
+}
+export async function sum(values) {
+    throw new Error("You attempted to execute workflow sum function directly. To start a workflow, use start(sum) from workflow/api");
+}
+sum.workflowId = "workflow//./input//sum";
+export const replies = {
+    'say "hi"': replies$say__hi_
+};
+registerStepFunction("step//./input//add", add);
+registerStepFunction('step//./input//replies/say "hi"', replies$say__hi_);
+export const __workflow_manifest = {
+    "mode": "step",
+    "workflows": {
+        "input.js": {
+            "sum": {
+                "workflowId": "workflow//./input//sum"
+            }
+        }
+    },
+    "steps": {
+        "input.js": {
+            "add": {
+                "stepId": "step//./input//add"
+            },
+            'replies/say "hi"': {
+                "stepId": 'step//./input//replies/say "hi"'
+            }
+        }
+    }
+};
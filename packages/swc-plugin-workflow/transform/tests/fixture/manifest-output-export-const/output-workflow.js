@@ -0,0 +1,33 @@
+export var add = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//add");
+export async function sum(values) {
+    let total = 0;
+    for (const value of values){
+        total = await add(total, value);
+    }
+    return total;
+}
+sum.workflowId = "workflow//./input//sum";
+globalThis.__private_workflows.set("workflow//./input//sum", sum);
+export const replies = {
+    'say "hi"': globalThis[Symbol.for("WORKFLOW_USE_STEP")]('step//./input//replies/say "hi"')
+};
+export const __workflow_manifest = {
+    "mode": "workflow",
+    "workflows": {
+        "input.js": {
+            "sum": {
+                "workflowId": "workflow//./input//sum"
+            }
+        }
+    },
+    "steps": {
+        "input.js": {
+            "add": {
+                "stepId": "step//./input//add"
+            },
+            'replies/say "hi"': {
+                "stepId": 'step//./input//replies/say "hi"'
+            }
+        }
+    }
+};
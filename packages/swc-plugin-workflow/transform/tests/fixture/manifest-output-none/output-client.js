@@ -0,0 +1,8 @@
+export async function add(a, b) {
+    return a + b;
+}
+add.stepId = "step//./input//add";
+export async function sum(values) {
+    throw new Error("You attempted to execute workflow sum function directly. To start a workflow, use start(sum) from workflow/api");
+}
+sum.workflowId = "workflow//./input//sum";
@@ -0,0 +1,9 @@
+import { registerStepFunction } from "workflow/internal/private";
+export async function add(a, b) {
+    return a + b;
+}
+export async function sum(values) {
+    throw new Error("You attempted to execute workflow sum function directly. To start a workflow, use start(sum) from workflow/api");
+}
+sum.workflowId = "workflow//./input//sum";
+registerStepFunction("step//./input//add", add);
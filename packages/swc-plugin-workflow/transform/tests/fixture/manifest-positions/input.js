@@ -0,0 +1,23 @@
+export async function fetchUser(id) {
+  'use step';
+  return fetch(`/api/users/${id}`);
+}
+
+export const sendEmail = async (to) => {
+  'use step';
+  return fetch('/api/mail', { method: 'POST', body: to });
+};
+
+export const mailer = {
+  notify: async (to) => {
+    'use step';
+    return sendEmail(to);
+  },
+};
+
+export async function onboard(id) {
+  'use workflow';
+  const user = await fetchUser(id);
+  await mailer.notify(user.email);
+  return user;
+}
@@ -0,0 +1,24 @@
+import { registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"onboard":{"workflowId":"workflow//./input//onboard","line":18,"col":8}}},"steps":{"input.js":{"fetchUser":{"stepId":"step//./input//fetchUser","line":1,"col":8},"mailer/notify":{"stepId":"step//./input//mailer/notify","line":12,"col":11},"sendEmail":{"stepId":"step//./input//sendEmail","line":6,"col":26}}}}*/;
+var mailer$notify = async function(to) {
+    return sendEmail(to);
+};
+export async function fetchUser(id) {
+    return fetch(`/api/users/${id}`);
+}
+export const sendEmail = async (to)=>{
+    return fetch('/api/mail', {
+        method: 'POST',
+        body: to
+    });
+};
+export const mailer = {
+    notify: mailer$notify
+};
+export async function onboard(id) {
+    throw new Error("You attempted to execute workflow onboard function directly. To start a workflow, use start(onboard) from workflow/api");
+}
+onboard.workflowId = "workflow//./input//onboard";
+registerStepFunction("step//./input//fetchUser", fetchUser);
+registerStepFunction("step//./input//sendEmail", sendEmail);
+registerStepFunction("step//./input//mailer/notify", mailer$notify);
@@ -0,0 +1,13 @@
+/**__internal_workflows{"workflows":{"input.js":{"onboard":{"workflowId":"workflow//./input//onboard","line":18,"col":8}}},"steps":{"input.js":{"fetchUser":{"stepId":"step//./input//fetchUser","line":1,"col":8},"mailer/notify":{"stepId":"step//./input//mailer/notify","line":12,"col":11},"sendEmail":{"stepId":"step//./input//sendEmail","line":6,"col":26}}}}*/;
+export var fetchUser = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//fetchUser");
+export const sendEmail = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//sendEmail");
+export const mailer = {
+    notify: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//mailer/notify")
+};
+export async function onboard(id) {
+    const user = await fetchUser(id);
+    await mailer.notify(user.email);
+    return user;
+}
+onboard.workflowId = "workflow//./input//onboard";
+globalThis.__private_workflows.set("workflow//./input//onboard", onboard);
@@ -1,4 +1,4 @@
-/**__internal_workflows{"steps":{"input.js":{"step":{"stepId":"step//./input//step"},"stepArrow":{"stepId":"step//./input//stepArrow"}}}}*/;
 'use step';
+/**__internal_workflows{"steps":{"input.js":{"step":{"stepId":"step//./input//step"},"stepArrow":{"stepId":"step//./input//stepArrow"}}}}*/;
 export var step = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//step");
 export const stepArrow = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//stepArrow");
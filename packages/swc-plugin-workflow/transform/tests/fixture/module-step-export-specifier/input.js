@@ -0,0 +1,13 @@
+'use step';
+
+async function fetchUser(id) {
+  return fetch(`/api/users/${id}`);
+}
+
+const sendEmail = async (to) => fetch('/api/mail', { method: 'POST', body: to });
+
+async function helper() {
+  return 1;
+}
+
+export { fetchUser, sendEmail as notify };
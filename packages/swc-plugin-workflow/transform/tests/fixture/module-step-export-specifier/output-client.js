@@ -0,0 +1,13 @@
+/**__internal_workflows{"steps":{"input.js":{"fetchUser":{"stepId":"step//./input//fetchUser"},"sendEmail":{"stepId":"step//./input//sendEmail"}}}}*/;
+async function fetchUser(id) {
+    return fetch(`/api/users/${id}`);
+}
+fetchUser.stepId = "step//./input//fetchUser";
+const sendEmail = async (to)=>{
+    return fetch('/api/mail', {
+        method: 'POST',
+        body: to
+    });
+};
+sendEmail.stepId = "step//./input//sendEmail";
+export { fetchUser, sendEmail as notify };
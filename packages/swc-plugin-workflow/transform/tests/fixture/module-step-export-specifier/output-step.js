@@ -0,0 +1,17 @@
+import { registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"steps":{"input.js":{"fetchUser":{"stepId":"step//./input//fetchUser"},"sendEmail":{"stepId":"step//./input//sendEmail"}}}}*/;
+async function fetchUser(id) {
+    return fetch(`/api/users/${id}`);
+}
+const sendEmail = async (to)=>{
+    return fetch('/api/mail', {
+        method: 'POST',
+        body: to
+    });
+};
+async function helper() {
+    return 1;
+}
+export { fetchUser, sendEmail as notify };
+registerStepFunction("step//./input//fetchUser", fetchUser);
+registerStepFunction("step//./input//sendEmail", sendEmail);
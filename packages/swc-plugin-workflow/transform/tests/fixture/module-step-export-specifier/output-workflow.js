@@ -0,0 +1,5 @@
+'use step';
+/**__internal_workflows{"steps":{"input.js":{"fetchUser":{"stepId":"step//./input//fetchUser"},"sendEmail":{"stepId":"step//./input//sendEmail"}}}}*/;
+var fetchUser = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//fetchUser");
+const sendEmail = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//sendEmail");
+export { fetchUser, sendEmail as notify };
@@ -0,0 +1,14 @@
+'use workflow';
+
+import { fetchUser } from './steps';
+
+async function onboard(id) {
+  const user = await fetchUser(id);
+  return user;
+}
+
+const reonboard = async function (id) {
+  return onboard(id);
+};
+
+export { onboard, reonboard as default };
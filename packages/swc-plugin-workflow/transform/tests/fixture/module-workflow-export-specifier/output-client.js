@@ -0,0 +1,10 @@
+/**__internal_workflows{"workflows":{"input.js":{"onboard":{"workflowId":"workflow//./input//onboard"},"reonboard":{"workflowId":"workflow//./input//reonboard"}}}}*/;
+async function onboard(id) {
+    throw new Error("You attempted to execute workflow onboard function directly. To start a workflow, use start(onboard) from workflow/api");
+}
+onboard.workflowId = "workflow//./input//onboard";
+const reonboard = async function(id) {
+    throw new Error("You attempted to execute workflow reonboard function directly. To start a workflow, use start(reonboard) from workflow/api");
+};
+reonboard.workflowId = "workflow//./input//reonboard";
+export { onboard, reonboard as default };
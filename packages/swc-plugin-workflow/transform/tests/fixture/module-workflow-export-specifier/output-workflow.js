@@ -0,0 +1,14 @@
+import { fetchUser } from './steps';
+/**__internal_workflows{"workflows":{"input.js":{"onboard":{"workflowId":"workflow//./input//onboard"},"reonboard":{"workflowId":"workflow//./input//reonboard"}}}}*/;
+async function onboard(id) {
+    const user = await fetchUser(id);
+    return user;
+}
+onboard.workflowId = "workflow//./input//onboard";
+globalThis.__private_workflows.set("workflow//./input//onboard", onboard);
+const reonboard = async function(id) {
+    return onboard(id);
+};
+reonboard.workflowId = "workflow//./input//reonboard";
+globalThis.__private_workflows.set("workflow//./input//reonboard", reonboard);
+export { onboard, reonboard as default };
@@ -0,0 +1,24 @@
+'use workflow';
+
+import { WORKFLOW_SERIALIZE, WORKFLOW_DESERIALIZE } from '@workflow/serde';
+import { fetchOrder } from './steps';
+
+class Money {
+  constructor(amount, currency) {
+    this.amount = amount;
+    this.currency = currency;
+  }
+
+  static [WORKFLOW_SERIALIZE](instance) {
+    return { amount: instance.amount, currency: instance.currency };
+  }
+
+  static [WORKFLOW_DESERIALIZE](data) {
+    return new Money(data.amount, data.currency);
+  }
+}
+
+export async function checkout(id) {
+  const order = await fetchOrder(id);
+  return new Money(order.total, order.currency);
+}
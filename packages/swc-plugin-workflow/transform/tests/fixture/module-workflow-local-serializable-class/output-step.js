@@ -0,0 +1,24 @@
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+import { WORKFLOW_SERIALIZE, WORKFLOW_DESERIALIZE } from '@workflow/serde';
+import { fetchOrder } from './steps';
+/**__internal_workflows{"workflows":{"input.js":{"checkout":{"workflowId":"workflow//./input//checkout"}}},"classes":{"input.js":{"Money":{"classId":"class//./input//Money"}}}}*/;
+class Money {
+    constructor(amount, currency){
+        this.amount = amount;
+        this.currency = currency;
+    }
+    static [WORKFLOW_SERIALIZE](instance) {
+        return {
+            amount: instance.amount,
+            currency: instance.currency
+        };
+    }
+    static [WORKFLOW_DESERIALIZE](data) {
+        return new Money(data.amount, data.currency);
+    }
+}
+export async function checkout(id) {
+    throw new Error("You attempted to execute workflow checkout function directly. To start a workflow, use start(checkout) from workflow/api");
+}
+checkout.workflowId = "workflow//./input//checkout";
+registerSerializationClass("class//./input//Money", Money);
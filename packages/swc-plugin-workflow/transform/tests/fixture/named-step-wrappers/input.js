@@ -0,0 +1,25 @@
+export async function add(a, b) {
+  'use step';
+  return a + b;
+}
+
+const multiply = async (a, b) => {
+  'use step';
+  return a * b;
+};
+
+export async function calculate(x, args) {
+  'use workflow';
+
+  async function scale(value) {
+    'use step';
+    return value * x;
+  }
+
+  const spread = async () => {
+    'use step';
+    return Math.max(...args);
+  };
+
+  return await scale(await multiply(await add(x, 1), await spread()));
+}
@@ -0,0 +1,24 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"calculate":{"workflowId":"workflow//./input//calculate"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"multiply":{"stepId":"step//./input//multiply"},"scale":{"stepId":"step//./input//scale"},"spread":{"stepId":"step//./input//spread"}}}}*/;
+async function calculate$scale(value) {
+    const { x } = __private_getClosureVars();
+    return value * x;
+}
+var calculate$spread = async ()=>{
+    const { args } = __private_getClosureVars();
+    return Math.max(...args);
+};
+export async function add(a, b) {
+    return a + b;
+}
+const multiply = async (a, b)=>{
+    return a * b;
+};
+export async function calculate(x, args) {
+    throw new Error("You attempted to execute workflow calculate function directly. To start a workflow, use start(calculate) from workflow/api");
+}
+calculate.workflowId = "workflow//./input//calculate";
+registerStepFunction("step//./input//add", add);
+registerStepFunction("step//./input//multiply", multiply);
+registerStepFunction("step//./input//calculate/scale", calculate$scale);
+registerStepFunction("step//./input//calculate/spread", calculate$spread);
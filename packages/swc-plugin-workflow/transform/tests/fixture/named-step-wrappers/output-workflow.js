@@ -0,0 +1,22 @@
+/**__internal_workflows{"workflows":{"input.js":{"calculate":{"workflowId":"workflow//./input//calculate"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"multiply":{"stepId":"step//./input//multiply"},"scale":{"stepId":"step//./input//scale"},"spread":{"stepId":"step//./input//spread"}}}}*/;
+export var add = function add(...args) {
+    return globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//add")(...args);
+};
+const multiply = function multiply(...args) {
+    return globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//multiply")(...args);
+};
+export async function calculate(x, args) {
+    var scale = function scale(...args) {
+        return globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//calculate/scale", ()=>({
+                x
+            }))(...args);
+    };
+    const spread = function spread(...args$1) {
+        return globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//calculate/spread", ()=>({
+                args
+            }))(...args$1);
+    };
+    return await scale(await multiply(await add(x, 1), await spread()));
+}
+calculate.workflowId = "workflow//./input//calculate";
+globalThis.__private_workflows.set("workflow//./input//calculate", calculate);
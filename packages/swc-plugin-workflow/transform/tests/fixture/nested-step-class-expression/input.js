@@ -0,0 +1,24 @@
+export async function report(prefix, rows) {
+  'use workflow';
+  const separator = ', ';
+
+  const build = async () => {
+    'use step';
+    const Summary = class Report {
+      [prefix + 'Rows'] = rows.length;
+      title = this.constructor.name;
+
+      format(row) {
+        const cells = Object.values(row);
+        return cells.join(separator);
+      }
+
+      clone() {
+        return new Report();
+      }
+    };
+    return new Summary();
+  };
+
+  return build();
+}
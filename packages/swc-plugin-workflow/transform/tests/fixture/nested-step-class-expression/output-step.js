@@ -0,0 +1,22 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"report":{"workflowId":"workflow//./input//report"}}},"steps":{"input.js":{"build":{"stepId":"step//./input//build"}}}}*/;
+var report$build = async ()=>{
+    const { prefix, rows, separator } = __private_getClosureVars();
+    const Summary = class Report {
+        [prefix + 'Rows'] = rows.length;
+        title = this.constructor.name;
+        format(row) {
+            const cells = Object.values(row);
+            return cells.join(separator);
+        }
+        clone() {
+            return new Report();
+        }
+    };
+    return new Summary();
+};
+export async function report(prefix, rows) {
+    throw new Error("You attempted to execute workflow report function directly. To start a workflow, use start(report) from workflow/api");
+}
+report.workflowId = "workflow//./input//report";
+registerStepFunction("step//./input//report/build", report$build);
@@ -0,0 +1,12 @@
+/**__internal_workflows{"workflows":{"input.js":{"report":{"workflowId":"workflow//./input//report"}}},"steps":{"input.js":{"build":{"stepId":"step//./input//build"}}}}*/;
+export async function report(prefix, rows) {
+    const separator = ', ';
+    const build = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//report/build", ()=>({
+            prefix,
+            rows,
+            separator
+        }));
+    return build();
+}
+report.workflowId = "workflow//./input//report";
+globalThis.__private_workflows.set("workflow//./input//report", report);
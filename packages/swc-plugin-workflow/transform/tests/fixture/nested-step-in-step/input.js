@@ -0,0 +1,39 @@
+// A step nested in another step runs when the enclosing step calls it, so when it captures
+// variables it stays in place instead of being hoisted
+export async function checkout(cartId) {
+  'use workflow';
+  const currency = 'usd';
+
+  async function charge(amount) {
+    'use step';
+    const fee = 30;
+
+    async function capture(paymentId) {
+      'use step';
+      return { paymentId, amount: amount + fee, currency };
+    }
+
+    return capture(`${cartId}-${amount}`);
+  }
+
+  return charge(100);
+}
+
+export async function refund(amount) {
+  'use step';
+  const reason = 'requested_by_customer';
+
+  const reverse = async (paymentId) => {
+    'use step';
+    return { paymentId, amount, reason };
+  };
+
+  // Captures nothing, so it's hoisted like other nested steps
+  const notify = async (message) => {
+    'use step';
+    return message.toUpperCase();
+  };
+
+  await notify('refunding');
+  return reverse('pay_123');
+}
@@ -0,0 +1,24 @@
+// A step nested in another step runs when the enclosing step calls it, so when it captures
+// variables it stays in place instead of being hoisted
+/**__internal_workflows{"workflows":{"input.js":{"checkout":{"workflowId":"workflow//./input//checkout"}}},"steps":{"input.js":{"notify":{"stepId":"step//./input//notify"},"refund":{"stepId":"step//./input//refund"}}}}*/;
+export async function checkout(cartId) {
+    throw new Error("You attempted to execute workflow checkout function directly. To start a workflow, use start(checkout) from workflow/api");
+}
+checkout.workflowId = "workflow//./input//checkout";
+export async function refund(amount) {
+    const reason = 'requested_by_customer';
+    const reverse = async (paymentId)=>{
+        return {
+            paymentId,
+            amount,
+            reason
+        };
+    };
+    // Captures nothing, so it's hoisted like other nested steps
+    const notify = async (message)=>{
+        return message.toUpperCase();
+    };
+    await notify('refunding');
+    return reverse('pay_123');
+}
+refund.stepId = "step//./input//refund";
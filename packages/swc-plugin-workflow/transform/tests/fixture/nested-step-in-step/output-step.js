@@ -0,0 +1,38 @@
+// A step nested in another step runs when the enclosing step calls it, so when it captures
+// variables it stays in place instead of being hoisted
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"checkout":{"workflowId":"workflow//./input//checkout"}}},"steps":{"input.js":{"charge":{"stepId":"step//./input//charge"},"notify":{"stepId":"step//./input//notify"},"refund":{"stepId":"step//./input//refund"}}}}*/;
+async function checkout$charge(amount) {
+    const { cartId, currency } = __private_getClosureVars();
+    const fee = 30;
+    async function capture(paymentId) {
+        return {
+            paymentId,
+            amount: amount + fee,
+            currency
+        };
+    }
+    return capture(`${cartId}-${amount}`);
+}
+var refund$notify = async (message)=>message.toUpperCase();
+export async function checkout(cartId) {
+    throw new Error("You attempted to execute workflow checkout function directly. To start a workflow, use start(checkout) from workflow/api");
+}
+checkout.workflowId = "workflow//./input//checkout";
+export async function refund(amount) {
+    const reason = 'requested_by_customer';
+    const reverse = async (paymentId)=>{
+        return {
+            paymentId,
+            amount,
+            reason
+        };
+    };
+    // Captures nothing, so it's hoisted like other nested steps
+    const notify = refund$notify;
+    await notify('refunding');
+    return reverse('pay_123');
+}
+registerStepFunction("step//./input//refund", refund);
+registerStepFunction("step//./input//checkout/charge", checkout$charge);
+registerStepFunction("step//./input//refund/notify", refund$notify);
@@ -0,0 +1,14 @@
+// A step nested in another step runs when the enclosing step calls it, so when it captures
+// variables it stays in place instead of being hoisted
+/**__internal_workflows{"workflows":{"input.js":{"checkout":{"workflowId":"workflow//./input//checkout"}}},"steps":{"input.js":{"charge":{"stepId":"step//./input//charge"},"refund":{"stepId":"step//./input//refund"}}}}*/;
+export async function checkout(cartId) {
+    const currency = 'usd';
+    var charge = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//checkout/charge", ()=>({
+            cartId,
+            currency
+        }));
+    return charge(100);
+}
+checkout.workflowId = "workflow//./input//checkout";
+globalThis.__private_workflows.set("workflow//./input//checkout", checkout);
+export var refund = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//refund");
@@ -1,5 +1,6 @@
 /**__internal_workflows{"workflows":{"input.js":{"example":{"workflowId":"workflow//./input//example"}}},"steps":{"input.js":{"arrowStep":{"stepId":"step//./input//arrowStep"},"helpers/objectStep":{"stepId":"step//./input//example/helpers/objectStep"},"letArrowStep":{"stepId":"step//./input//letArrowStep"},"step":{"stepId":"step//./input//step"},"varArrowStep":{"stepId":"step//./input//varArrowStep"}}}}*/;
 export async function example(a, b) {
+    // Function declaration step
     var step = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//example/step");
     // Arrow function with const
     const arrowStep = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//example/arrowStep");
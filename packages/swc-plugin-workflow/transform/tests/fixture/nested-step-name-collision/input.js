@@ -0,0 +1,13 @@
+export async function add(a, b) {
+  'use step';
+  return a + b;
+}
+
+export async function sum(a, b) {
+  'use workflow';
+  async function add(x, y) {
+    'use step';
+    return x + y;
+  }
+  return add(a, b);
+}
@@ -0,0 +1,28 @@
+export async function calculate(x) {
+  'use workflow';
+
+  async function scale(value) {
+    'use step';
+    return value * 2;
+  }
+
+  const offset = async (value) => {
+    'use step';
+    return value + 1;
+  };
+
+  const tools = {
+    round: async (value) => {
+      'use step';
+      return Math.round(value);
+    },
+    math: {
+      floor: async (value) => {
+        'use step';
+        return Math.floor(value);
+      },
+    },
+  };
+
+  return await tools.math.floor(await tools.round(await offset(await scale(x))));
+}
@@ -0,0 +1,20 @@
+import { registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"calculate":{"workflowId":"workflow//./input//calculate"}}},"steps":{"input.js":{"offset":{"stepId":"step//./input//offset"},"scale":{"stepId":"step//./input//scale"},"tools.math.floor":{"stepId":"step//./input//calculate.tools.math.floor"},"tools.round":{"stepId":"step//./input//calculate.tools.round"}}}}*/;
+async function calculate__scale(value) {
+    return value * 2;
+}
+var calculate__offset = async (value)=>value + 1;
+var calculate__tools__round = async function(value) {
+    return Math.round(value);
+};
+var calculate__tools__math__floor = async function(value) {
+    return Math.floor(value);
+};
+export async function calculate(x) {
+    throw new Error("You attempted to execute workflow calculate function directly. To start a workflow, use start(calculate) from workflow/api");
+}
+calculate.workflowId = "workflow//./input//calculate";
+registerStepFunction("step//./input//calculate.scale", calculate__scale);
+registerStepFunction("step//./input//calculate.offset", calculate__offset);
+registerStepFunction("step//./input//calculate.tools.round", calculate__tools__round);
+registerStepFunction("step//./input//calculate.tools.math.floor", calculate__tools__math__floor);
@@ -0,0 +1,14 @@
+/**__internal_workflows{"workflows":{"input.js":{"calculate":{"workflowId":"workflow//./input//calculate"}}},"steps":{"input.js":{"offset":{"stepId":"step//./input//offset"},"scale":{"stepId":"step//./input//scale"},"tools.math.floor":{"stepId":"step//./input//calculate.tools.math.floor"},"tools.round":{"stepId":"step//./input//calculate.tools.round"}}}}*/;
+export async function calculate(x) {
+    var scale = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//calculate.scale");
+    const offset = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//calculate.offset");
+    const tools = {
+        round: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//calculate.tools.round"),
+        math: {
+            floor: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//calculate.tools.math.floor")
+        }
+    };
+    return await tools.math.floor(await tools.round(await offset(await scale(x))));
+}
+calculate.workflowId = "workflow//./input//calculate";
+globalThis.__private_workflows.set("workflow//./input//calculate", calculate);
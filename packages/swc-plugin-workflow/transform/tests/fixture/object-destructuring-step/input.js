@@ -0,0 +1,30 @@
+const { run } = {
+  run: async (input) => {
+    'use step';
+    return input;
+  },
+};
+
+export async function main(input) {
+  'use workflow';
+  const { save } = {
+    save: async (value) => {
+      'use step';
+      return value;
+    },
+  };
+  return save(await run(input));
+}
+
+export const { load, helpers: { parse } } = {
+  async load(id) {
+    'use step';
+    return id;
+  },
+  helpers: {
+    parse: async function (text) {
+      'use step';
+      return JSON.parse(text);
+    },
+  },
+};
@@ -0,0 +1,23 @@
+/**__internal_workflows{"workflows":{"input.js":{"main":{"workflowId":"workflow//./input//main"}}},"steps":{"input.js":{"helpers/parse":{"stepId":"step//./input//helpers/parse"},"load":{"stepId":"step//./input//load"},"run":{"stepId":"step//./input//run"}}}}*/;
+var $run = async function(input) {
+    return input;
+};
+var $load = async function(id) {
+    return id;
+};
+var helpers$parse = async function(text) {
+    return JSON.parse(text);
+};
+export async function main(input) {
+    throw new Error("You attempted to execute workflow main function directly. To start a workflow, use start(main) from workflow/api");
+}
+main.workflowId = "workflow//./input//main";
+export const { load, helpers: { parse } } = {
+    load: $load,
+    helpers: {
+        parse: helpers$parse
+    }
+};
+$run.stepId = "step//./input//run";
+$load.stepId = "step//./input//load";
+helpers$parse.stepId = "step//./input//helpers/parse";
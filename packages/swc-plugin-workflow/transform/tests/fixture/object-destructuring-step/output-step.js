@@ -0,0 +1,31 @@
+import { registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"main":{"workflowId":"workflow//./input//main"}}},"steps":{"input.js":{"helpers/parse":{"stepId":"step//./input//helpers/parse"},"load":{"stepId":"step//./input//load"},"run":{"stepId":"step//./input//run"},"save":{"stepId":"step//./input//main/save"}}}}*/;
+var $run = async function(input) {
+    return input;
+};
+var main$$save = async function(value) {
+    return value;
+};
+var $load = async function(id) {
+    return id;
+};
+var helpers$parse = async function(text) {
+    return JSON.parse(text);
+};
+const { run } = {
+    run: $run
+};
+export async function main(input) {
+    throw new Error("You attempted to execute workflow main function directly. To start a workflow, use start(main) from workflow/api");
+}
+main.workflowId = "workflow//./input//main";
+export const { load, helpers: { parse } } = {
+    load: $load,
+    helpers: {
+        parse: helpers$parse
+    }
+};
+registerStepFunction("step//./input//run", $run);
+registerStepFunction("step//./input//main/save", main$$save);
+registerStepFunction("step//./input//load", $load);
+registerStepFunction("step//./input//helpers/parse", helpers$parse);
@@ -0,0 +1,18 @@
+/**__internal_workflows{"workflows":{"input.js":{"main":{"workflowId":"workflow//./input//main"}}},"steps":{"input.js":{"helpers/parse":{"stepId":"step//./input//helpers/parse"},"load":{"stepId":"step//./input//load"},"run":{"stepId":"step//./input//run"},"save":{"stepId":"step//./input//main/save"}}}}*/;
+const { run } = {
+    run: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//run")
+};
+export async function main(input) {
+    const { save } = {
+        save: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//main/save")
+    };
+    return save(await run(input));
+}
+main.workflowId = "workflow//./input//main";
+globalThis.__private_workflows.set("workflow//./input//main", main);
+export const { load, helpers: { parse } } = {
+    load: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//load"),
+    helpers: {
+        parse: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//helpers/parse")
+    }
+};
@@ -0,0 +1,22 @@
+import { agent } from 'experimental-agent';
+
+const toolName = 'search';
+
+export const assistant = agent({
+  tools: {
+    ['lookup']: {
+      execute: async (query) => {
+        'use step';
+        return query.toUpperCase();
+      },
+    },
+    [toolName]: async (query) => {
+      'use step';
+      return fetch(`/api/search?q=${query}`);
+    },
+    async [`${toolName}All`](queries) {
+      'use step';
+      return queries.length;
+    },
+  },
+});
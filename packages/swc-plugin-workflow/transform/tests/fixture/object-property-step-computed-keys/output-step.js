@@ -0,0 +1,25 @@
+import { registerStepFunction } from "workflow/internal/private";
+import { agent } from 'experimental-agent';
+/**__internal_workflows{"steps":{"input.js":{"assistant/tools/[computed1]":{"stepId":"step//./input//assistant/tools/[computed1]"},"assistant/tools/[computed2]":{"stepId":"step//./input//assistant/tools/[computed2]"},"assistant/tools/lookup/execute":{"stepId":"step//./input//assistant/tools/lookup/execute"}}}}*/;
+var assistant$tools$lookup$execute = async function(query) {
+    return query.toUpperCase();
+};
+var assistant$tools$computed1 = async function(query) {
+    return fetch(`/api/search?q=${query}`);
+};
+var assistant$tools$computed2 = async function(queries) {
+    return queries.length;
+};
+const toolName = 'search';
+export const assistant = agent({
+    tools: {
+        ['lookup']: {
+            execute: assistant$tools$lookup$execute
+        },
+        [toolName]: assistant$tools$computed1,
+        [`${toolName}All`]: assistant$tools$computed2
+    }
+});
+registerStepFunction("step//./input//assistant/tools/lookup/execute", assistant$tools$lookup$execute);
+registerStepFunction("step//./input//assistant/tools/[computed1]", assistant$tools$computed1);
+registerStepFunction("step//./input//assistant/tools/[computed2]", assistant$tools$computed2);
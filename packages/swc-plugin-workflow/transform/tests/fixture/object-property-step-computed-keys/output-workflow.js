@@ -0,0 +1,12 @@
+import { agent } from 'experimental-agent';
+/**__internal_workflows{"steps":{"input.js":{"assistant/tools/[computed1]":{"stepId":"step//./input//assistant/tools/[computed1]"},"assistant/tools/[computed2]":{"stepId":"step//./input//assistant/tools/[computed2]"},"assistant/tools/lookup/execute":{"stepId":"step//./input//assistant/tools/lookup/execute"}}}}*/;
+const toolName = 'search';
+export const assistant = agent({
+    tools: {
+        ['lookup']: {
+            execute: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//assistant/tools/lookup/execute")
+        },
+        [toolName]: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//assistant/tools/[computed1]"),
+        [`${toolName}All`]: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//assistant/tools/[computed2]")
+    }
+});
@@ -0,0 +1,25 @@
+const tools$a_b = 'declared';
+const toolName = 'search';
+
+export const tools = {
+  'a-b': async (input) => {
+    'use step';
+    return input + tools$a_b;
+  },
+  a_b: async (input) => {
+    'use step';
+    return input;
+  },
+  computed3: async (input) => {
+    'use step';
+    return input * 3;
+  },
+  [toolName]: async (input) => {
+    'use step';
+    return fetch(`/api/search?q=${input}`);
+  },
+  2: async (input) => {
+    'use step';
+    return input * 2;
+  },
+};
@@ -0,0 +1,31 @@
+import { registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"steps":{"input.js":{"tools/2":{"stepId":"step//./input//tools/2"},"tools/[computed3]":{"stepId":"step//./input//tools/[computed3]"},"tools/a-b":{"stepId":"step//./input//tools/a-b"},"tools/a_b":{"stepId":"step//./input//tools/a_b"},"tools/computed3":{"stepId":"step//./input//tools/computed3"}}}}*/;
+var tools$a_b$1 = async function(input) {
+    return input + tools$a_b;
+};
+var tools$a_b$2 = async function(input) {
+    return input;
+};
+var tools$computed3 = async function(input) {
+    return input * 3;
+};
+var tools$computed3$1 = async function(input) {
+    return fetch(`/api/search?q=${input}`);
+};
+var tools$2 = async function(input) {
+    return input * 2;
+};
+const tools$a_b = 'declared';
+const toolName = 'search';
+export const tools = {
+    'a-b': tools$a_b$1,
+    a_b: tools$a_b$2,
+    computed3: tools$computed3,
+    [toolName]: tools$computed3$1,
+    2: tools$2
+};
+registerStepFunction("step//./input//tools/a-b", tools$a_b$1);
+registerStepFunction("step//./input//tools/a_b", tools$a_b$2);
+registerStepFunction("step//./input//tools/computed3", tools$computed3);
+registerStepFunction("step//./input//tools/[computed3]", tools$computed3$1);
+registerStepFunction("step//./input//tools/2", tools$2);
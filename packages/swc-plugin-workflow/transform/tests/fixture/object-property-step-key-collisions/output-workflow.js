@@ -0,0 +1,9 @@
+/**__internal_workflows{"steps":{"input.js":{"tools/2":{"stepId":"step//./input//tools/2"},"tools/[computed3]":{"stepId":"step//./input//tools/[computed3]"},"tools/a-b":{"stepId":"step//./input//tools/a-b"},"tools/a_b":{"stepId":"step//./input//tools/a_b"},"tools/computed3":{"stepId":"step//./input//tools/computed3"}}}}*/;
+const toolName = 'search';
+export const tools = {
+    'a-b': globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//tools/a-b"),
+    a_b: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//tools/a_b"),
+    computed3: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//tools/computed3"),
+    [toolName]: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//tools/[computed3]"),
+    2: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//tools/2")
+};
@@ -0,0 +1,14 @@
+export const handlers = {
+  'not-found': async (req) => {
+    'use step';
+    return req;
+  },
+  [404]: async (req) => {
+    'use step';
+    return req;
+  },
+  async [1.5](x) {
+    'use step';
+    return x;
+  },
+};
@@ -0,0 +1,19 @@
+import { registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"steps":{"input.js":{"handlers/1.5":{"stepId":"step//./input//handlers/1.5"},"handlers/404":{"stepId":"step//./input//handlers/404"},"handlers/not-found":{"stepId":"step//./input//handlers/not-found"}}}}*/;
+var handlers$not_found = async function(req) {
+    return req;
+};
+var handlers$404 = async function(req) {
+    return req;
+};
+var handlers$1_5 = async function(x) {
+    return x;
+};
+export const handlers = {
+    'not-found': handlers$not_found,
+    [404]: handlers$404,
+    [1.5]: handlers$1_5
+};
+registerStepFunction("step//./input//handlers/not-found", handlers$not_found);
+registerStepFunction("step//./input//handlers/404", handlers$404);
+registerStepFunction("step//./input//handlers/1.5", handlers$1_5);
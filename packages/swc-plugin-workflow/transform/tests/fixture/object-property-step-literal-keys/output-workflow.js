@@ -0,0 +1,6 @@
+/**__internal_workflows{"steps":{"input.js":{"handlers/1.5":{"stepId":"step//./input//handlers/1.5"},"handlers/404":{"stepId":"step//./input//handlers/404"},"handlers/not-found":{"stepId":"step//./input//handlers/not-found"}}}}*/;
+export const handlers = {
+    'not-found': globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//handlers/not-found"),
+    [404]: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//handlers/404"),
+    [1.5]: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//handlers/1.5")
+};
@@ -0,0 +1,9 @@
+async function greet(name) {
+  'use step';
+  return `Hello, ${name}`;
+}
+
+export default (async (name) => {
+  'use workflow';
+  return greet(name);
+});
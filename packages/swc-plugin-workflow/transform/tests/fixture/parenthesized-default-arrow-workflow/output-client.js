@@ -0,0 +1,10 @@
+/**__internal_workflows{"workflows":{"input.js":{"default":{"workflowId":"workflow//./input//default"}}},"steps":{"input.js":{"greet":{"stepId":"step//./input//greet"}}}}*/;
+async function greet(name) {
+    return `Hello, ${name}`;
+}
+greet.stepId = "step//./input//greet";
+const __default = async (name)=>{
+    throw new Error("You attempted to execute workflow __default function directly. To start a workflow, use start(__default) from workflow/api");
+};
+__default.workflowId = "workflow//./input//default";
+export default __default;
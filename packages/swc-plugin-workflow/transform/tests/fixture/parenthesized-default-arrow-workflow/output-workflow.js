@@ -0,0 +1,8 @@
+/**__internal_workflows{"workflows":{"input.js":{"default":{"workflowId":"workflow//./input//default"}}},"steps":{"input.js":{"greet":{"stepId":"step//./input//greet"}}}}*/;
+var greet = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//greet");
+const __default = async (name)=>{
+    return greet(name);
+};
+__default.workflowId = "workflow//./input//default";
+globalThis.__private_workflows.set("workflow//./input//default", __default);
+export default __default;
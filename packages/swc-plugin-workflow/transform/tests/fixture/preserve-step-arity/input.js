@@ -0,0 +1,30 @@
+export async function add(a, b) {
+  'use step';
+  return a + b;
+}
+
+const multiply = async (a, b = 1) => {
+  'use step';
+  return a * b;
+};
+
+const sum = async function ({ values }, ...rest) {
+  'use step';
+  return values.reduce((total, value) => total + value, 0) + rest.length;
+};
+
+export async function calculate(x, arg0) {
+  'use workflow';
+
+  async function scale(value) {
+    'use step';
+    return value * x;
+  }
+
+  const offset = async (value) => {
+    'use step';
+    return value + arg0;
+  };
+
+  return await scale(await offset(await multiply(await add(x, 1), await sum({ values: [x] }))));
+}
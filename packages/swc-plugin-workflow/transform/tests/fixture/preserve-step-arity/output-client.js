@@ -0,0 +1,17 @@
+/**__internal_workflows{"workflows":{"input.js":{"calculate":{"workflowId":"workflow//./input//calculate"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"multiply":{"stepId":"step//./input//multiply"},"sum":{"stepId":"step//./input//sum"}}}}*/;
+export async function add(a, b) {
+    return a + b;
+}
+add.stepId = "step//./input//add";
+const multiply = async (a, b = 1)=>{
+    return a * b;
+};
+multiply.stepId = "step//./input//multiply";
+const sum = async function({ values }, ...rest) {
+    return values.reduce((total, value)=>total + value, 0) + rest.length;
+};
+sum.stepId = "step//./input//sum";
+export async function calculate(x, arg0) {
+    throw new Error("You attempted to execute workflow calculate function directly. To start a workflow, use start(calculate) from workflow/api");
+}
+calculate.workflowId = "workflow//./input//calculate";
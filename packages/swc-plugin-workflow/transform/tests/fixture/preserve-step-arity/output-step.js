@@ -0,0 +1,28 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"calculate":{"workflowId":"workflow//./input//calculate"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"multiply":{"stepId":"step//./input//multiply"},"offset":{"stepId":"step//./input//offset"},"scale":{"stepId":"step//./input//scale"},"sum":{"stepId":"step//./input//sum"}}}}*/;
+async function calculate$scale(value) {
+    const { x } = __private_getClosureVars();
+    return value * x;
+}
+var calculate$offset = async (value)=>{
+    const { arg0 } = __private_getClosureVars();
+    return value + arg0;
+};
+export async function add(a, b) {
+    return a + b;
+}
+const multiply = async (a, b = 1)=>{
+    return a * b;
+};
+const sum = async function({ values }, ...rest) {
+    return values.reduce((total, value)=>total + value, 0) + rest.length;
+};
+export async function calculate(x, arg0) {
+    throw new Error("You attempted to execute workflow calculate function directly. To start a workflow, use start(calculate) from workflow/api");
+}
+calculate.workflowId = "workflow//./input//calculate";
+registerStepFunction("step//./input//add", add);
+registerStepFunction("step//./input//multiply", multiply);
+registerStepFunction("step//./input//sum", sum);
+registerStepFunction("step//./input//calculate/scale", calculate$scale);
+registerStepFunction("step//./input//calculate/offset", calculate$offset);
@@ -0,0 +1,29 @@
+/**__internal_workflows{"workflows":{"input.js":{"calculate":{"workflowId":"workflow//./input//calculate"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"multiply":{"stepId":"step//./input//multiply"},"offset":{"stepId":"step//./input//offset"},"scale":{"stepId":"step//./input//scale"},"sum":{"stepId":"step//./input//sum"}}}}*/;
+export var add = function(arg0, arg1, ...args) {
+    return globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//add")(arg0, arg1, ...args);
+};
+const multiply = function(arg0, ...args) {
+    return globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//multiply")(arg0, ...args);
+};
+const sum = function(arg0, ...args) {
+    return globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//sum")(arg0, ...args);
+};
+export async function calculate(x, arg0) {
+    var scale = function(arg0, ...args) {
+        return globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//calculate/scale", ()=>({
+                x
+            }))(arg0, ...args);
+    };
+    const offset = function(arg0$1, ...args) {
+        return globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//calculate/offset", ()=>({
+                arg0
+            }))(arg0$1, ...args);
+    };
+    return await scale(await offset(await multiply(await add(x, 1), await sum({
+        values: [
+            x
+        ]
+    }))));
+}
+calculate.workflowId = "workflow//./input//calculate";
+globalThis.__private_workflows.set("workflow//./input//calculate", calculate);
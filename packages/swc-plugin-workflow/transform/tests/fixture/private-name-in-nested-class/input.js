@@ -0,0 +1,20 @@
+export async function pack(x) {
+  'use workflow';
+  const make = async () => {
+    'use step';
+    // `this.#v` refers to the instance of the class declared inside the step, not to the step
+    class Box {
+      #v = x;
+      get() {
+        return this.#v;
+      }
+      double() {
+        const read = () => this.#v * 2;
+        return read();
+      }
+    }
+    const box = new Box();
+    return box.get() + box.double();
+  };
+  return make();
+}
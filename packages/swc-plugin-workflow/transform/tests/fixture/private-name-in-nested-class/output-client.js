@@ -0,0 +1,5 @@
+/**__internal_workflows{"workflows":{"input.js":{"pack":{"workflowId":"workflow//./input//pack"}}}}*/;
+export async function pack(x) {
+    throw new Error("You attempted to execute workflow pack function directly. To start a workflow, use start(pack) from workflow/api");
+}
+pack.workflowId = "workflow//./input//pack";
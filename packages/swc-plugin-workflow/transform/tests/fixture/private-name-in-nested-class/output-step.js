@@ -0,0 +1,23 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"pack":{"workflowId":"workflow//./input//pack"}}},"steps":{"input.js":{"make":{"stepId":"step//./input//make"}}}}*/;
+var pack$make = async ()=>{
+    const { x } = __private_getClosureVars();
+    // `this.#v` refers to the instance of the class declared inside the step, not to the step
+    class Box {
+        #v = x;
+        get() {
+            return this.#v;
+        }
+        double() {
+            const read = ()=>this.#v * 2;
+            return read();
+        }
+    }
+    const box = new Box();
+    return box.get() + box.double();
+};
+export async function pack(x) {
+    throw new Error("You attempted to execute workflow pack function directly. To start a workflow, use start(pack) from workflow/api");
+}
+pack.workflowId = "workflow//./input//pack";
+registerStepFunction("step//./input//pack/make", pack$make);
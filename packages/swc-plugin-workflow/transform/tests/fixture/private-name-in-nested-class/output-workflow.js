@@ -0,0 +1,9 @@
+/**__internal_workflows{"workflows":{"input.js":{"pack":{"workflowId":"workflow//./input//pack"}}},"steps":{"input.js":{"make":{"stepId":"step//./input//make"}}}}*/;
+export async function pack(x) {
+    const make = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//pack/make", ()=>({
+            x
+        }));
+    return make();
+}
+pack.workflowId = "workflow//./input//pack";
+globalThis.__private_workflows.set("workflow//./input//pack", pack);
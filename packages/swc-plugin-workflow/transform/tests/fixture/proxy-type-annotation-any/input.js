@@ -0,0 +1,25 @@
+export async function add(a, b = 1, ...rest) {
+  'use step';
+  return a + b + rest.length;
+}
+
+const multiply = async ({ x }, [y]) => {
+  'use step';
+  return x * y;
+};
+
+export async function calculate(factor) {
+  'use workflow';
+
+  async function scale(value) {
+    'use step';
+    return value * factor;
+  }
+
+  const offset = async (value, { by } = {}) => {
+    'use step';
+    return value + by + factor;
+  };
+
+  return await offset(await scale(await multiply({ x: await add(1, 2) }, [3])));
+}
@@ -0,0 +1,13 @@
+/**__internal_workflows{"workflows":{"input.js":{"calculate":{"workflowId":"workflow//./input//calculate"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"multiply":{"stepId":"step//./input//multiply"}}}}*/;
+export async function add(a, b = 1, ...rest) {
+    return a + b + rest.length;
+}
+add.stepId = "step//./input//add";
+const multiply = async ({ x }, [y])=>{
+    return x * y;
+};
+multiply.stepId = "step//./input//multiply";
+export async function calculate(factor) {
+    throw new Error("You attempted to execute workflow calculate function directly. To start a workflow, use start(calculate) from workflow/api");
+}
+calculate.workflowId = "workflow//./input//calculate";
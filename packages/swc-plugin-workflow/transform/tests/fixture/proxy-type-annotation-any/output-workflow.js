@@ -0,0 +1,18 @@
+/**__internal_workflows{"workflows":{"input.js":{"calculate":{"workflowId":"workflow//./input//calculate"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"multiply":{"stepId":"step//./input//multiply"},"offset":{"stepId":"step//./input//offset"},"scale":{"stepId":"step//./input//scale"}}}}*/;
+export var add: any = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//add");
+const multiply: any = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//multiply");
+export async function calculate(factor) {
+    var scale: any = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//calculate/scale", ()=>({
+            factor
+        }));
+    const offset: any = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//calculate/offset", ()=>({
+            factor
+        }));
+    return await offset(await scale(await multiply({
+        x: await add(1, 2)
+    }, [
+        3
+    ])));
+}
+calculate.workflowId = "workflow//./input//calculate";
+globalThis.__private_workflows.set("workflow//./input//calculate", calculate);
@@ -0,0 +1,25 @@
+export async function add(a: number, b = 1, ...rest: number[]): Promise<number> {
+  'use step';
+  return a + b + rest.length;
+}
+
+const multiply = async ({ x }: { x: number }, [y]) => {
+  'use step';
+  return x * y;
+};
+
+export async function calculate(factor: number) {
+  'use workflow';
+
+  async function scale<T extends number>(value: T) {
+    'use step';
+    return value * factor;
+  }
+
+  const offset: (value: number) => Promise<number> = async (value) => {
+    'use step';
+    return value + factor;
+  };
+
+  return await offset(await scale(await multiply({ x: await add(1, 2) }, [3])));
+}
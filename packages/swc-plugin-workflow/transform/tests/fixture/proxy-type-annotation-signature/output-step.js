@@ -0,0 +1,26 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"calculate":{"workflowId":"workflow//./input//calculate"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"multiply":{"stepId":"step//./input//multiply"},"offset":{"stepId":"step//./input//offset"},"scale":{"stepId":"step//./input//scale"}}}}*/;
+async function calculate$scale<T extends number>(value: T) {
+    const { factor } = __private_getClosureVars();
+    return value * factor;
+}
+var calculate$offset = async (value)=>{
+    const { factor } = __private_getClosureVars();
+    return value + factor;
+};
+export async function add(a: number, b = 1, ...rest: number[]): Promise<number> {
+    return a + b + rest.length;
+}
+const multiply = async ({ x }: {
+    x: number;
+}, [y])=>{
+    return x * y;
+};
+export async function calculate(factor: number) {
+    throw new Error("You attempted to execute workflow calculate function directly. To start a workflow, use start(calculate) from workflow/api");
+}
+calculate.workflowId = "workflow//./input//calculate";
+registerStepFunction("step//./input//add", add);
+registerStepFunction("step//./input//multiply", multiply);
+registerStepFunction("step//./input//calculate/scale", calculate$scale);
+registerStepFunction("step//./input//calculate/offset", calculate$offset);
@@ -0,0 +1,21 @@
+export async function add(a, b) {
+  "use step";
+  return a + b;
+}
+
+export const tools = {
+  search: async (query) => {
+    "use step";
+    return fetch(`/api/search?q=${query}`);
+  },
+};
+
+export async function sum(a, b) {
+  "use workflow";
+  // Reads `a`, so it is proxied with a closure
+  async function double() {
+    "use step";
+    return a * 2;
+  }
+  return add(await double(), b);
+}
@@ -0,0 +1,16 @@
+/**__internal_workflows{"workflows":{"input.js":{"sum":{"workflowId":"workflow//./input//sum"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"tools/search":{"stepId":"step//./input//tools/search"}}}}*/;
+var tools$search = async function(query) {
+    return fetch(`/api/search?q=${query}`);
+};
+export async function add(a, b) {
+    return a + b;
+}
+add.stepId = "step//./input//add";
+export const tools = {
+    search: tools$search
+};
+export async function sum(a, b) {
+    throw new Error("You attempted to execute workflow sum function directly. To start a workflow, use start(sum) from workflow/api");
+}
+sum.workflowId = "workflow//./input//sum";
+tools$search.stepId = "step//./input//tools/search";
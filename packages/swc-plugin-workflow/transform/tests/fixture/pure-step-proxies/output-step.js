@@ -0,0 +1,23 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"sum":{"workflowId":"workflow//./input//sum"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"double":{"stepId":"step//./input//double"},"tools/search":{"stepId":"step//./input//tools/search"}}}}*/;
+// Reads `a`, so it is proxied with a closure
+async function sum$double() {
+    const { a } = __private_getClosureVars();
+    return a * 2;
+}
+var tools$search = async function(query) {
+    return fetch(`/api/search?q=${query}`);
+};
+export async function add(a, b) {
+    return a + b;
+}
+export const tools = {
+    search: tools$search
+};
+export async function sum(a, b) {
+    throw new Error("You attempted to execute workflow sum function directly. To start a workflow, use start(sum) from workflow/api");
+}
+sum.workflowId = "workflow//./input//sum";
+registerStepFunction("step//./input//add", add);
+registerStepFunction("step//./input//sum/double", sum$double);
+registerStepFunction("step//./input//tools/search", tools$search);
@@ -0,0 +1,14 @@
+/**__internal_workflows{"workflows":{"input.js":{"sum":{"workflowId":"workflow//./input//sum"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"double":{"stepId":"step//./input//double"},"tools/search":{"stepId":"step//./input//tools/search"}}}}*/;
+export var add = /*#__PURE__*/ globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//add");
+export const tools = {
+    search: /*#__PURE__*/ globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//tools/search")
+};
+export async function sum(a, b) {
+    // Reads `a`, so it is proxied with a closure
+    var double = /*#__PURE__*/ globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//sum/double", ()=>({
+            a
+        }));
+    return add(await double(), b);
+}
+sum.workflowId = "workflow//./input//sum";
+globalThis.__private_workflows.set("workflow//./input//sum", sum);
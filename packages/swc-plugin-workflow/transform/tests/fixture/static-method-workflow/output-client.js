@@ -11,5 +11,5 @@ export class JobRunner {
         return '1.0.0';
     }
 }
-JobRunner.runJob.workflowId = "workflow//./input//JobRunner.runJob";
 JobRunner.execute.workflowId = "workflow//./input//JobRunner.execute";
+JobRunner.runJob.workflowId = "workflow//./input//JobRunner.runJob";
@@ -12,7 +12,7 @@ export class JobRunner {
         return '1.0.0';
     }
 }
-JobRunner.runJob.workflowId = "workflow//./input//JobRunner.runJob";
-globalThis.__private_workflows.set("workflow//./input//JobRunner.runJob", JobRunner.runJob);
 JobRunner.execute.workflowId = "workflow//./input//JobRunner.execute";
 globalThis.__private_workflows.set("workflow//./input//JobRunner.execute", JobRunner.execute);
+JobRunner.runJob.workflowId = "workflow//./input//JobRunner.runJob";
+globalThis.__private_workflows.set("workflow//./input//JobRunner.runJob", JobRunner.runJob);
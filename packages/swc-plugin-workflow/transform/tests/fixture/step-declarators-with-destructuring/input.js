@@ -0,0 +1,22 @@
+const retries = 3, fetchData = async (url) => {
+  'use step';
+  return fetch(url);
+};
+
+export const parse = async function (text) {
+  'use step';
+  return JSON.parse(text);
+}, format = async (value) => {
+  'use step';
+  return String(value);
+};
+
+const { limit } = { limit: 10 }, save = async (value) => {
+  'use step';
+  return value;
+};
+
+export async function run(url) {
+  'use workflow';
+  return format(await parse(await fetchData(url + retries + limit)));
+}
@@ -0,0 +1,22 @@
+/**__internal_workflows{"workflows":{"input.js":{"run":{"workflowId":"workflow//./input//run"}}},"steps":{"input.js":{"fetchData":{"stepId":"step//./input//fetchData"},"format":{"stepId":"step//./input//format"},"parse":{"stepId":"step//./input//parse"},"save":{"stepId":"step//./input//save"}}}}*/;
+const retries = 3, fetchData = async (url)=>{
+    return fetch(url);
+};
+fetchData.stepId = "step//./input//fetchData";
+export const parse = async function(text) {
+    return JSON.parse(text);
+}, format = async (value)=>{
+    return String(value);
+};
+format.stepId = "step//./input//format";
+parse.stepId = "step//./input//parse";
+const { limit } = {
+    limit: 10
+}, save = async (value)=>{
+    return value;
+};
+save.stepId = "step//./input//save";
+export async function run(url) {
+    throw new Error("You attempted to execute workflow run function directly. To start a workflow, use start(run) from workflow/api");
+}
+run.workflowId = "workflow//./input//run";
@@ -0,0 +1,11 @@
+/**__internal_workflows{"workflows":{"input.js":{"run":{"workflowId":"workflow//./input//run"}}},"steps":{"input.js":{"fetchData":{"stepId":"step//./input//fetchData"},"format":{"stepId":"step//./input//format"},"parse":{"stepId":"step//./input//parse"},"save":{"stepId":"step//./input//save"}}}}*/;
+const retries = 3, fetchData = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//fetchData");
+export const parse = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//parse"), format = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//format");
+const { limit } = {
+    limit: 10
+}, save = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//save");
+export async function run(url) {
+    return format(await parse(await fetchData(url + retries + limit)));
+}
+run.workflowId = "workflow//./input//run";
+globalThis.__private_workflows.set("workflow//./input//run", run);
@@ -0,0 +1,41 @@
+import { step, workflow } from "./decorators";
+
+export class Orders {
+  @step
+  static async charge(order) {
+    return fetch("/api/charge", { method: "POST", body: order });
+  }
+
+  @step()
+  async refund(order) {
+    return fetch("/api/refund", { method: "POST", body: order });
+  }
+
+  // The directive takes precedence over the decorator
+  @step
+  static async place(order) {
+    "use workflow";
+    await Orders.charge(order);
+    return order.id;
+  }
+
+  @workflow
+  static async cancel(order) {
+    await new Orders().refund(order);
+  }
+}
+
+// Marks every static async method as a workflow, but leaves the instance method alone
+export @workflow class Reports {
+  static async daily() {
+    return Reports.build("daily");
+  }
+
+  static build(kind) {
+    return { kind };
+  }
+
+  async render() {
+    return "report";
+  }
+}
@@ -0,0 +1,41 @@
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+/**__internal_workflows{"workflows":{"input.js":{"Orders.cancel":{"workflowId":"workflow//./input//Orders.cancel"},"Orders.place":{"workflowId":"workflow//./input//Orders.place"},"Reports.daily":{"workflowId":"workflow//./input//Reports.daily"}}},"steps":{"input.js":{"Orders#refund":{"stepId":"step//./input//Orders#refund"},"Orders.charge":{"stepId":"step//./input//Orders.charge"}}},"classes":{"input.js":{"Orders":{"classId":"class//./input//Orders"}}}}*/;
+export class Orders {
+    static async charge(order) {
+        return fetch("/api/charge", {
+            method: "POST",
+            body: order
+        });
+    }
+    async refund(order) {
+        return fetch("/api/refund", {
+            method: "POST",
+            body: order
+        });
+    }
+    // The directive takes precedence over the decorator
+    static async place(order) {
+        throw new Error("You attempted to execute workflow Orders.place function directly. To start a workflow, use start(workflow) from workflow/api");
+    }
+    static async cancel(order) {
+        throw new Error("You attempted to execute workflow Orders.cancel function directly. To start a workflow, use start(workflow) from workflow/api");
+    }
+}
+// Marks every static async method as a workflow, but leaves the instance method alone
+export class Reports {
+    static async daily() {
+        throw new Error("You attempted to execute workflow Reports.daily function directly. To start a workflow, use start(workflow) from workflow/api");
+    }
+    static build(kind) {
+        return {
+            kind
+        };
+    }
+    async render() {
+        return "report";
+    }
+}
+registerSerializationClass("class//./input//Orders", Orders);
+Orders.cancel.workflowId = "workflow//./input//Orders.cancel";
+Orders.place.workflowId = "workflow//./input//Orders.place";
+Reports.daily.workflowId = "workflow//./input//Reports.daily";
@@ -0,0 +1,35 @@
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+/**__internal_workflows{"workflows":{"input.js":{"Orders.cancel":{"workflowId":"workflow//./input//Orders.cancel"},"Orders.place":{"workflowId":"workflow//./input//Orders.place"},"Reports.daily":{"workflowId":"workflow//./input//Reports.daily"}}},"steps":{"input.js":{"Orders#refund":{"stepId":"step//./input//Orders#refund"},"Orders.charge":{"stepId":"step//./input//Orders.charge"}}},"classes":{"input.js":{"Orders":{"classId":"class//./input//Orders"}}}}*/;
+export class Orders {
+    // The directive takes precedence over the decorator
+    static async place(order) {
+        await Orders.charge(order);
+        return order.id;
+    }
+    static async cancel(order) {
+        await new Orders().refund(order);
+    }
+}
+// Marks every static async method as a workflow, but leaves the instance method alone
+export class Reports {
+    static async daily() {
+        return Reports.build("daily");
+    }
+    static build(kind) {
+        return {
+            kind
+        };
+    }
+    async render() {
+        return "report";
+    }
+}
+Orders.charge = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Orders.charge");
+Orders.prototype["refund"] = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Orders#refund");
+registerSerializationClass("class//./input//Orders", Orders);
+Orders.cancel.workflowId = "workflow//./input//Orders.cancel";
+globalThis.__private_workflows.set("workflow//./input//Orders.cancel", Orders.cancel);
+Orders.place.workflowId = "workflow//./input//Orders.place";
+globalThis.__private_workflows.set("workflow//./input//Orders.place", Orders.place);
+Reports.daily.workflowId = "workflow//./input//Reports.daily";
+globalThis.__private_workflows.set("workflow//./input//Reports.daily", Reports.daily);
@@ -0,0 +1,28 @@
+import { WORKFLOW_SERIALIZE, WORKFLOW_DESERIALIZE } from '@vercel/workflow';
+
+export class Counter {
+  static [WORKFLOW_SERIALIZE](instance) {
+    return { count: instance.count };
+  }
+
+  static [WORKFLOW_DESERIALIZE](data) {
+    const counter = new Counter();
+    counter.count = data.count;
+    return counter;
+  }
+
+  count = 0;
+
+  // `this` is the deserialized instance the step was called on, also in the arrows nested in it
+  async increment(by) {
+    'use step';
+    const add = (value) => this.count + value;
+    this.count = add(by);
+    return this.count;
+  }
+}
+
+export async function track(start) {
+  'use workflow';
+  return new Counter().increment(start);
+}
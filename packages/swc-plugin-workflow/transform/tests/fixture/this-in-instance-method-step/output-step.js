@@ -0,0 +1,29 @@
+import { registerStepFunction } from "workflow/internal/private";
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+import { WORKFLOW_SERIALIZE, WORKFLOW_DESERIALIZE } from '@vercel/workflow';
+/**__internal_workflows{"workflows":{"input.js":{"track":{"workflowId":"workflow//./input//track"}}},"steps":{"input.js":{"Counter#increment":{"stepId":"step//./input//Counter#increment"}}},"classes":{"input.js":{"Counter":{"classId":"class//./input//Counter"}}}}*/;
+export class Counter {
+    static [WORKFLOW_SERIALIZE](instance) {
+        return {
+            count: instance.count
+        };
+    }
+    static [WORKFLOW_DESERIALIZE](data) {
+        const counter = new Counter();
+        counter.count = data.count;
+        return counter;
+    }
+    count = 0;
+    // `this` is the deserialized instance the step was called on, also in the arrows nested in it
+    async increment(by) {
+        const add = (value)=>this.count + value;
+        this.count = add(by);
+        return this.count;
+    }
+}
+export async function track(start) {
+    throw new Error("You attempted to execute workflow track function directly. To start a workflow, use start(track) from workflow/api");
+}
+track.workflowId = "workflow//./input//track";
+registerStepFunction("step//./input//Counter#increment", Counter.prototype["increment"]);
+registerSerializationClass("class//./input//Counter", Counter);
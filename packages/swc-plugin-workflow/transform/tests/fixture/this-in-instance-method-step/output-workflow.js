@@ -0,0 +1,23 @@
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+import { WORKFLOW_SERIALIZE, WORKFLOW_DESERIALIZE } from '@vercel/workflow';
+/**__internal_workflows{"workflows":{"input.js":{"track":{"workflowId":"workflow//./input//track"}}},"steps":{"input.js":{"Counter#increment":{"stepId":"step//./input//Counter#increment"}}},"classes":{"input.js":{"Counter":{"classId":"class//./input//Counter"}}}}*/;
+export class Counter {
+    static [WORKFLOW_SERIALIZE](instance) {
+        return {
+            count: instance.count
+        };
+    }
+    static [WORKFLOW_DESERIALIZE](data) {
+        const counter = new Counter();
+        counter.count = data.count;
+        return counter;
+    }
+    count = 0;
+}
+export async function track(start) {
+    return new Counter().increment(start);
+}
+track.workflowId = "workflow//./input//track";
+globalThis.__private_workflows.set("workflow//./input//track", track);
+Counter.prototype["increment"] = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Counter#increment");
+registerSerializationClass("class//./input//Counter", Counter);
@@ -1,4 +1,6 @@
+import { Resend } from 'resend';
 /**__internal_workflows{"steps":{"input.js":{"sendRecipientEmail":{"stepId":"step//./input//sendRecipientEmail"}}}}*/;
+const resend = new Resend(process.env.RESEND_API_KEY);
 export const sendRecipientEmail = async ({ recipientEmail, cardImage, cardText, rsvpReplies })=>{
     const html = generatePostcardEmailTemplate({
         cardImage,
@@ -0,0 +1,15 @@
+import { fetchPrices } from './api';
+
+const currency = 'usd';
+
+export async function quote(region, items) {
+  'use workflow';
+  const discount = region === 'eu' ? 0.1 : 0;
+  // Captures `region` and `discount`, but not the import or the module-level constant
+  const loadPrices = async (ids) => {
+    'use cache';
+    const prices = await fetchPrices(ids, region, currency);
+    return prices.map((price) => price * (1 - discount));
+  };
+  return loadPrices(items);
+}
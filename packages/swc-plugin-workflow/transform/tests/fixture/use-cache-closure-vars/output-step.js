@@ -0,0 +1,7 @@
+import { fetchPrices } from './api';
+/**__internal_workflows{"workflows":{"input.js":{"quote":{"workflowId":"workflow//./input//quote"}}},"caches":{"input.js":{"quote/loadPrices":{"cacheId":"cache//./input//quote/loadPrices"}}}}*/;
+const currency = 'usd';
+export async function quote(region, items) {
+    throw new Error("You attempted to execute workflow quote function directly. To start a workflow, use start(quote) from workflow/api");
+}
+quote.workflowId = "workflow//./input//quote";
@@ -0,0 +1,17 @@
+import { fetchPrices } from './api';
+/**__internal_workflows{"workflows":{"input.js":{"quote":{"workflowId":"workflow//./input//quote"}}},"caches":{"input.js":{"quote/loadPrices":{"cacheId":"cache//./input//quote/loadPrices"}}}}*/;
+const currency = 'usd';
+export async function quote(region, items) {
+    const discount = region === 'eu' ? 0.1 : 0;
+    // Captures `region` and `discount`, but not the import or the module-level constant
+    const loadPrices = globalThis[Symbol.for("WORKFLOW_USE_CACHE")]("cache//./input//quote/loadPrices", async (ids)=>{
+        const prices = await fetchPrices(ids, region, currency);
+        return prices.map((price)=>price * (1 - discount));
+    }, ()=>({
+            discount,
+            region
+        }));
+    return loadPrices(items);
+}
+quote.workflowId = "workflow//./input//quote";
+globalThis.__private_workflows.set("workflow//./input//quote", quote);
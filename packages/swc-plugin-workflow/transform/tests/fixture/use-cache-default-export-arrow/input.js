@@ -0,0 +1,4 @@
+export default async (env) => {
+  'use cache';
+  return { env };
+};
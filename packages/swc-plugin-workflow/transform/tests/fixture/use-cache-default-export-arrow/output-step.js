@@ -0,0 +1,6 @@
+/**__internal_workflows{"caches":{"input.js":{"default":{"cacheId":"cache//./input//default"}}}}*/;
+export default (async (env)=>{
+    return {
+        env
+    };
+});
@@ -0,0 +1,6 @@
+/**__internal_workflows{"caches":{"input.js":{"default":{"cacheId":"cache//./input//default"}}}}*/;
+export default globalThis[Symbol.for("WORKFLOW_USE_CACHE")]("cache//./input//default", async (env)=>{
+    return {
+        env
+    };
+});
@@ -0,0 +1,9 @@
+export default async function loadConfig(env) {
+  'use cache';
+  const settings = { env };
+  async function loadSecrets() {
+    'use cache';
+    return { env: settings.env, token: 'secret' };
+  }
+  return { ...settings, secrets: await loadSecrets() };
+}
@@ -0,0 +1,16 @@
+/**__internal_workflows{"caches":{"input.js":{"default":{"cacheId":"cache//./input//default"},"default/loadSecrets":{"cacheId":"cache//./input//default/loadSecrets"}}}}*/;
+export default async function loadConfig(env) {
+    const settings = {
+        env
+    };
+    async function loadSecrets() {
+        return {
+            env: settings.env,
+            token: 'secret'
+        };
+    }
+    return {
+        ...settings,
+        secrets: await loadSecrets()
+    };
+}
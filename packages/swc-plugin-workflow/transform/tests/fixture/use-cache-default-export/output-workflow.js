@@ -0,0 +1,18 @@
+/**__internal_workflows{"caches":{"input.js":{"default":{"cacheId":"cache//./input//default"},"default/loadSecrets":{"cacheId":"cache//./input//default/loadSecrets"}}}}*/;
+export default globalThis[Symbol.for("WORKFLOW_USE_CACHE")]("cache//./input//default", async function loadConfig(env) {
+    const settings = {
+        env
+    };
+    var loadSecrets = globalThis[Symbol.for("WORKFLOW_USE_CACHE")]("cache//./input//default/loadSecrets", async function loadSecrets() {
+        return {
+            env: settings.env,
+            token: 'secret'
+        };
+    }, ()=>({
+            settings
+        }));
+    return {
+        ...settings,
+        secrets: await loadSecrets()
+    };
+});
@@ -0,0 +1,28 @@
+import { fetchUser } from './api';
+
+export async function getUser(id) {
+  'use cache';
+  return fetchUser(id);
+}
+
+const getSettings = async (userId) => {
+  'use cache';
+  return { userId, theme: 'dark' };
+};
+
+async function sendEmail(user) {
+  'use step';
+  return { sent: true, to: user.email };
+}
+
+export async function onboard(id) {
+  'use workflow';
+  async function loadProfile(user) {
+    'use cache';
+    return { ...user, settings: await getSettings(user.id) };
+  }
+  const user = await getUser(id);
+  const profile = await loadProfile(user);
+  await sendEmail(profile);
+  return profile;
+}
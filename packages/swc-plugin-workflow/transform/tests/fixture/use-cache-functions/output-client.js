@@ -0,0 +1,16 @@
+import { fetchUser } from './api';
+/**__internal_workflows{"workflows":{"input.js":{"onboard":{"workflowId":"workflow//./input//onboard"}}},"steps":{"input.js":{"sendEmail":{"stepId":"step//./input//sendEmail"}}},"caches":{"input.js":{"getSettings":{"cacheId":"cache//./input//getSettings"},"getUser":{"cacheId":"cache//./input//getUser"},"onboard/loadProfile":{"cacheId":"cache//./input//onboard/loadProfile"}}}}*/;
+export async function getUser(id) {
+    return fetchUser(id);
+}
+async function sendEmail(user) {
+    return {
+        sent: true,
+        to: user.email
+    };
+}
+sendEmail.stepId = "step//./input//sendEmail";
+export async function onboard(id) {
+    throw new Error("You attempted to execute workflow onboard function directly. To start a workflow, use start(onboard) from workflow/api");
+}
+onboard.workflowId = "workflow//./input//onboard";
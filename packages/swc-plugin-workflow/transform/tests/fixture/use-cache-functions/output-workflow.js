@@ -0,0 +1,26 @@
+import { fetchUser } from './api';
+/**__internal_workflows{"workflows":{"input.js":{"onboard":{"workflowId":"workflow//./input//onboard"}}},"steps":{"input.js":{"sendEmail":{"stepId":"step//./input//sendEmail"}}},"caches":{"input.js":{"getSettings":{"cacheId":"cache//./input//getSettings"},"getUser":{"cacheId":"cache//./input//getUser"},"onboard/loadProfile":{"cacheId":"cache//./input//onboard/loadProfile"}}}}*/;
+export var getUser = globalThis[Symbol.for("WORKFLOW_USE_CACHE")]("cache//./input//getUser", async function getUser(id) {
+    return fetchUser(id);
+});
+const getSettings = globalThis[Symbol.for("WORKFLOW_USE_CACHE")]("cache//./input//getSettings", async (userId)=>{
+    return {
+        userId,
+        theme: 'dark'
+    };
+});
+var sendEmail = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//sendEmail");
+export async function onboard(id) {
+    var loadProfile = globalThis[Symbol.for("WORKFLOW_USE_CACHE")]("cache//./input//onboard/loadProfile", async function loadProfile(user) {
+        return {
+            ...user,
+            settings: await getSettings(user.id)
+        };
+    });
+    const user = await getUser(id);
+    const profile = await loadProfile(user);
+    await sendEmail(profile);
+    return profile;
+}
+onboard.workflowId = "workflow//./input//onboard";
+globalThis.__private_workflows.set("workflow//./input//onboard", onboard);
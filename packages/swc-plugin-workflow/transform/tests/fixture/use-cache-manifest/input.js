@@ -0,0 +1,9 @@
+export async function getUser(id) {
+  'use cache';
+  return { id };
+}
+
+export async function getAccount(id) {
+  'use cache';
+  return { id, plan: 'pro' };
+}
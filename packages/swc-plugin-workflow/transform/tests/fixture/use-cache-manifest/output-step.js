@@ -0,0 +1,12 @@
+/**__internal_workflows{"caches":{"input.js":{"getAccount":{"cacheId":"cache//./input//getAccount"},"getUser":{"cacheId":"cache//./input//getUser"}}}}*/;
+export async function getUser(id) {
+    return {
+        id
+    };
+}
+export async function getAccount(id) {
+    return {
+        id,
+        plan: 'pro'
+    };
+}
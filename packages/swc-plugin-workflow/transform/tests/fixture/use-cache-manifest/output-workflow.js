@@ -0,0 +1,12 @@
+/**__internal_workflows{"caches":{"input.js":{"getAccount":{"cacheId":"cache//./input//getAccount"},"getUser":{"cacheId":"cache//./input//getUser"}}}}*/;
+export var getUser = globalThis[Symbol.for("WORKFLOW_USE_CACHE")]("cache//./input//getUser", async function getUser(id) {
+    return {
+        id
+    };
+});
+export var getAccount = globalThis[Symbol.for("WORKFLOW_USE_CACHE")]("cache//./input//getAccount", async function getAccount(id) {
+    return {
+        id,
+        plan: 'pro'
+    };
+});
@@ -0,0 +1,6 @@
+'use strict';
+'use step';
+
+export async function sendEmail(to) {
+  return { sent: true, to };
+}
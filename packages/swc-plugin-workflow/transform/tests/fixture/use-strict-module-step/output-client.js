@@ -0,0 +1,9 @@
+'use strict';
+/**__internal_workflows{"steps":{"input.js":{"sendEmail":{"stepId":"step//./input//sendEmail"}}}}*/;
+export async function sendEmail(to) {
+    return {
+        sent: true,
+        to
+    };
+}
+sendEmail.stepId = "step//./input//sendEmail";
@@ -0,0 +1,10 @@
+import { registerStepFunction } from "workflow/internal/private";
+'use strict';
+/**__internal_workflows{"steps":{"input.js":{"sendEmail":{"stepId":"step//./input//sendEmail"}}}}*/;
+export async function sendEmail(to) {
+    return {
+        sent: true,
+        to
+    };
+}
+registerStepFunction("step//./input//sendEmail", sendEmail);
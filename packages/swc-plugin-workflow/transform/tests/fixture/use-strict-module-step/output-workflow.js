@@ -0,0 +1,4 @@
+'use strict';
+'use step';
+/**__internal_workflows{"steps":{"input.js":{"sendEmail":{"stepId":"step//./input//sendEmail"}}}}*/;
+export var sendEmail = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//sendEmail");
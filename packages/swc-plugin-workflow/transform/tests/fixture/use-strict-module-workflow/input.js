@@ -0,0 +1,6 @@
+'use strict';
+'use workflow';
+
+export async function onboarding(userId) {
+  return { userId };
+}
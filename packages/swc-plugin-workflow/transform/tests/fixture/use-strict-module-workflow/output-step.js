@@ -0,0 +1,6 @@
+'use strict';
+/**__internal_workflows{"workflows":{"input.js":{"onboarding":{"workflowId":"workflow//./input//onboarding"}}}}*/;
+export async function onboarding(userId) {
+    throw new Error("You attempted to execute workflow onboarding function directly. To start a workflow, use start(onboarding) from workflow/api");
+}
+onboarding.workflowId = "workflow//./input//onboarding";
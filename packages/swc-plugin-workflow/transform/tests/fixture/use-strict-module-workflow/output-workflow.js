@@ -0,0 +1,9 @@
+'use strict';
+/**__internal_workflows{"workflows":{"input.js":{"onboarding":{"workflowId":"workflow//./input//onboarding"}}}}*/;
+export async function onboarding(userId) {
+    return {
+        userId
+    };
+}
+onboarding.workflowId = "workflow//./input//onboarding";
+globalThis.__private_workflows.set("workflow//./input//onboarding", onboarding);
@@ -0,0 +1,42 @@
+export const tools = {
+  execute: async (input) => {
+    'use strict';
+    'use step';
+    return input;
+  },
+  async run(input) {
+    'use strict';
+    'use step';
+    return input;
+  },
+};
+
+export class Store {
+  static async get(key) {
+    'use strict';
+    'use step';
+    return key;
+  }
+
+  async set(key) {
+    'use strict';
+    'use step';
+    return key;
+  }
+}
+
+export async function sync(key) {
+  'use strict';
+  'use workflow';
+  const read = async () => {
+    'use strict';
+    'use step';
+    return key;
+  };
+  async function write() {
+    'use strict';
+    'use step';
+    return key;
+  }
+  return [await read(), await write()];
+}
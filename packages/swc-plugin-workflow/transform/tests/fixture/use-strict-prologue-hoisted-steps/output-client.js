@@ -0,0 +1,31 @@
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+/**__internal_workflows{"workflows":{"input.js":{"sync":{"workflowId":"workflow//./input//sync"}}},"steps":{"input.js":{"Store#set":{"stepId":"step//./input//Store#set"},"Store.get":{"stepId":"step//./input//Store.get"},"tools/execute":{"stepId":"step//./input//tools/execute"},"tools/run":{"stepId":"step//./input//tools/run"}}},"classes":{"input.js":{"Store":{"classId":"class//./input//Store"}}}}*/;
+var tools$execute = async function(input) {
+    'use strict';
+    return input;
+};
+var tools$run = async function(input) {
+    'use strict';
+    return input;
+};
+export const tools = {
+    execute: tools$execute,
+    run: tools$run
+};
+export class Store {
+    static async get(key) {
+        'use strict';
+        return key;
+    }
+    async set(key) {
+        'use strict';
+        return key;
+    }
+}
+export async function sync(key) {
+    throw new Error("You attempted to execute workflow sync function directly. To start a workflow, use start(sync) from workflow/api");
+}
+sync.workflowId = "workflow//./input//sync";
+tools$execute.stepId = "step//./input//tools/execute";
+tools$run.stepId = "step//./input//tools/run";
+registerSerializationClass("class//./input//Store", Store);
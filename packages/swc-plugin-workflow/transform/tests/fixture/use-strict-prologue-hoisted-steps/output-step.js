@@ -0,0 +1,46 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+/**__internal_workflows{"workflows":{"input.js":{"sync":{"workflowId":"workflow//./input//sync"}}},"steps":{"input.js":{"Store#set":{"stepId":"step//./input//Store#set"},"Store.get":{"stepId":"step//./input//Store.get"},"read":{"stepId":"step//./input//read"},"tools/execute":{"stepId":"step//./input//tools/execute"},"tools/run":{"stepId":"step//./input//tools/run"},"write":{"stepId":"step//./input//write"}}},"classes":{"input.js":{"Store":{"classId":"class//./input//Store"}}}}*/;
+var sync$read = async ()=>{
+    'use strict';
+    const { key } = __private_getClosureVars();
+    return key;
+};
+async function sync$write() {
+    'use strict';
+    const { key } = __private_getClosureVars();
+    return key;
+}
+var tools$execute = async function(input) {
+    'use strict';
+    return input;
+};
+var tools$run = async function(input) {
+    'use strict';
+    return input;
+};
+export const tools = {
+    execute: tools$execute,
+    run: tools$run
+};
+export class Store {
+    static async get(key) {
+        'use strict';
+        return key;
+    }
+    async set(key) {
+        'use strict';
+        return key;
+    }
+}
+export async function sync(key) {
+    throw new Error("You attempted to execute workflow sync function directly. To start a workflow, use start(sync) from workflow/api");
+}
+sync.workflowId = "workflow//./input//sync";
+registerStepFunction("step//./input//sync/read", sync$read);
+registerStepFunction("step//./input//sync/write", sync$write);
+registerStepFunction("step//./input//tools/execute", tools$execute);
+registerStepFunction("step//./input//tools/run", tools$run);
+registerStepFunction("step//./input//Store.get", Store.get);
+registerStepFunction("step//./input//Store#set", Store.prototype["set"]);
+registerSerializationClass("class//./input//Store", Store);
@@ -0,0 +1,26 @@
+import { registerSerializationClass } from "workflow/internal/class-serialization";
+/**__internal_workflows{"workflows":{"input.js":{"sync":{"workflowId":"workflow//./input//sync"}}},"steps":{"input.js":{"Store#set":{"stepId":"step//./input//Store#set"},"Store.get":{"stepId":"step//./input//Store.get"},"read":{"stepId":"step//./input//read"},"tools/execute":{"stepId":"step//./input//tools/execute"},"tools/run":{"stepId":"step//./input//tools/run"},"write":{"stepId":"step//./input//write"}}},"classes":{"input.js":{"Store":{"classId":"class//./input//Store"}}}}*/;
+export const tools = {
+    execute: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//tools/execute"),
+    run: globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//tools/run")
+};
+export class Store {
+}
+export async function sync(key) {
+    'use strict';
+    const read = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//sync/read", ()=>({
+            key
+        }));
+    var write = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//sync/write", ()=>({
+            key
+        }));
+    return [
+        await read(),
+        await write()
+    ];
+}
+sync.workflowId = "workflow//./input//sync";
+globalThis.__private_workflows.set("workflow//./input//sync", sync);
+Store.get = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Store.get");
+Store.prototype["set"] = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//Store#set");
+registerSerializationClass("class//./input//Store", Store);
@@ -0,0 +1,18 @@
+export async function add(a, b) {
+  'use strict';
+  'use step';
+  return a + b;
+}
+
+export const double = async (value) => {
+  'use strict';
+  'use step';
+  return value * 2;
+};
+
+export async function calculate(a, b) {
+  'use strict';
+  'use workflow';
+  const sum = await add(a, b);
+  return double(sum);
+}
@@ -0,0 +1,15 @@
+/**__internal_workflows{"workflows":{"input.js":{"calculate":{"workflowId":"workflow//./input//calculate"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"double":{"stepId":"step//./input//double"}}}}*/;
+export async function add(a, b) {
+    'use strict';
+    return a + b;
+}
+add.stepId = "step//./input//add";
+export const double = async (value)=>{
+    'use strict';
+    return value * 2;
+};
+double.stepId = "step//./input//double";
+export async function calculate(a, b) {
+    throw new Error("You attempted to execute workflow calculate function directly. To start a workflow, use start(calculate) from workflow/api");
+}
+calculate.workflowId = "workflow//./input//calculate";
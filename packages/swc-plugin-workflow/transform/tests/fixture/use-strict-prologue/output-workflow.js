@@ -0,0 +1,10 @@
+/**__internal_workflows{"workflows":{"input.js":{"calculate":{"workflowId":"workflow//./input//calculate"}}},"steps":{"input.js":{"add":{"stepId":"step//./input//add"},"double":{"stepId":"step//./input//double"}}}}*/;
+export var add = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//add");
+export const double = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//double");
+export async function calculate(a, b) {
+    'use strict';
+    const sum = await add(a, b);
+    return double(sum);
+}
+calculate.workflowId = "workflow//./input//calculate";
+globalThis.__private_workflows.set("workflow//./input//calculate", calculate);
@@ -1,6 +1,6 @@
-/**__internal_workflows{"workflows":{"input.js":{"__default":{"workflowId":"workflow//./input//__default"}}}}*/;
 // User explicitly names their workflow function __default
 // The workflow ID should use "__default", not normalize to "default"
+/**__internal_workflows{"workflows":{"input.js":{"__default":{"workflowId":"workflow//./input//__default"}}}}*/;
 export async function __default() {
     const result = await someStep();
     return result;
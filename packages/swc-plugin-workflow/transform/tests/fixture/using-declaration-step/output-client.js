@@ -1,10 +1,10 @@
-/**__internal_workflows{"steps":{"input.js":{"testStep":{"stepId":"step//./input//testStep"}}}}*/;
 // This is the TypeScript-transformed output of:
 // async function testStep() {
 //   'use step';
 //   using resource = getResource();
 //   await doWork(resource);
 // }
+/**__internal_workflows{"steps":{"input.js":{"testStep":{"stepId":"step//./input//testStep"}}}}*/;
 export async function testStep() {
     const env = {
         stack: [],
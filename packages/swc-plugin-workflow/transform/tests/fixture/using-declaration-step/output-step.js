@@ -1,11 +1,11 @@
-import { registerStepFunction } from "workflow/internal/private";
-/**__internal_workflows{"steps":{"input.js":{"testStep":{"stepId":"step//./input//testStep"}}}}*/;
 // This is the TypeScript-transformed output of:
 // async function testStep() {
 //   'use step';
 //   using resource = getResource();
 //   await doWork(resource);
 // }
+import { registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"steps":{"input.js":{"testStep":{"stepId":"step//./input//testStep"}}}}*/;
 export async function testStep() {
     const env = {
         stack: [],
@@ -1,8 +1,8 @@
-/**__internal_workflows{"steps":{"input.js":{"testStep":{"stepId":"step//./input//testStep"}}}}*/;
 // This is the TypeScript-transformed output of:
 // async function testStep() {
 //   'use step';
 //   using resource = getResource();
 //   await doWork(resource);
 // }
+/**__internal_workflows{"steps":{"input.js":{"testStep":{"stepId":"step//./input//testStep"}}}}*/;
 export var testStep = globalThis[Symbol.for("WORKFLOW_USE_STEP")]("step//./input//testStep");
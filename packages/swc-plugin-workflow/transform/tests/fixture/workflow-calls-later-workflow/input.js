@@ -0,0 +1,15 @@
+export async function parent(id) {
+  'use workflow';
+  const child = await childWorkflow(id);
+  return arrowChild(child);
+}
+
+export async function childWorkflow(id) {
+  'use workflow';
+  return { id };
+}
+
+export const arrowChild = async (value) => {
+  'use workflow';
+  return { value };
+};
@@ -0,0 +1,13 @@
+/**__internal_workflows{"workflows":{"input.js":{"arrowChild":{"workflowId":"workflow//./input//arrowChild"},"childWorkflow":{"workflowId":"workflow//./input//childWorkflow"},"parent":{"workflowId":"workflow//./input//parent"}}}}*/;
+export async function parent(id) {
+    throw new Error("You attempted to execute workflow parent function directly. To start a workflow, use start(parent) from workflow/api");
+}
+parent.workflowId = "workflow//./input//parent";
+export async function childWorkflow(id) {
+    throw new Error("You attempted to execute workflow childWorkflow function directly. To start a workflow, use start(childWorkflow) from workflow/api");
+}
+childWorkflow.workflowId = "workflow//./input//childWorkflow";
+export const arrowChild = async (value)=>{
+    throw new Error("You attempted to execute workflow arrowChild function directly. To start a workflow, use start(arrowChild) from workflow/api");
+};
+arrowChild.workflowId = "workflow//./input//arrowChild";
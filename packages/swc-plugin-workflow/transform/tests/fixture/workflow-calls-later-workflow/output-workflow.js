@@ -0,0 +1,21 @@
+/**__internal_workflows{"workflows":{"input.js":{"arrowChild":{"workflowId":"workflow//./input//arrowChild"},"childWorkflow":{"workflowId":"workflow//./input//childWorkflow"},"parent":{"workflowId":"workflow//./input//parent"}}}}*/;
+export async function parent(id) {
+    const child = await childWorkflow(id);
+    return arrowChild(child);
+}
+parent.workflowId = "workflow//./input//parent";
+globalThis.__private_workflows.set("workflow//./input//parent", parent);
+export async function childWorkflow(id) {
+    return {
+        id
+    };
+}
+childWorkflow.workflowId = "workflow//./input//childWorkflow";
+globalThis.__private_workflows.set("workflow//./input//childWorkflow", childWorkflow);
+export const arrowChild = async (value)=>{
+    return {
+        value
+    };
+};
+arrowChild.workflowId = "workflow//./input//arrowChild";
+globalThis.__private_workflows.set("workflow//./input//arrowChild", arrowChild);
@@ -1,5 +1,5 @@
-/**__internal_workflows{"workflows":{"input.js":{"arrowWorkflow":{"workflowId":"workflow//./input//arrowWorkflow"},"default":{"workflowId":"workflow//./input//defaultWorkflow"},"internalWorkflow":{"workflowId":"workflow//./input//internalWorkflow"},"myWorkflow":{"workflowId":"workflow//./input//myWorkflow"}}}}*/;
 // Test workflow functions in client mode
+/**__internal_workflows{"workflows":{"input.js":{"arrowWorkflow":{"workflowId":"workflow//./input//arrowWorkflow"},"default":{"workflowId":"workflow//./input//defaultWorkflow"},"internalWorkflow":{"workflowId":"workflow//./input//internalWorkflow"},"myWorkflow":{"workflowId":"workflow//./input//myWorkflow"}}}}*/;
 export async function myWorkflow() {
     const result = await someStep();
     return result;
@@ -0,0 +1,23 @@
+export async function first() {
+  'use workflow';
+  return 1;
+}
+
+export const second = async () => {
+    'use workflow';
+    return 2;
+  },
+  third = async function () {
+    'use workflow';
+    return 3;
+  };
+
+async function fourth() {
+  'use workflow';
+  return 4;
+}
+
+export default async function fifth() {
+  'use workflow';
+  return fourth();
+}
@@ -0,0 +1,20 @@
+/**__internal_workflows{"workflows":{"input.js":{"default":{"workflowId":"workflow//./input//fifth"},"first":{"workflowId":"workflow//./input//first"},"fourth":{"workflowId":"workflow//./input//fourth"},"second":{"workflowId":"workflow//./input//second"},"third":{"workflowId":"workflow//./input//third"}}}}*/;
+export async function first() {
+    throw new Error("You attempted to execute workflow first function directly. To start a workflow, use start(first) from workflow/api");
+}
+first.workflowId = "workflow//./input//first";
+export const second = async ()=>{
+    throw new Error("You attempted to execute workflow second function directly. To start a workflow, use start(second) from workflow/api");
+}, third = async function() {
+    throw new Error("You attempted to execute workflow third function directly. To start a workflow, use start(third) from workflow/api");
+};
+second.workflowId = "workflow//./input//second";
+third.workflowId = "workflow//./input//third";
+async function fourth() {
+    throw new Error("You attempted to execute workflow fourth function directly. To start a workflow, use start(fourth) from workflow/api");
+}
+fourth.workflowId = "workflow//./input//fourth";
+export default async function fifth() {
+    throw new Error("You attempted to execute workflow fifth function directly. To start a workflow, use start(fifth) from workflow/api");
+}
+fifth.workflowId = "workflow//./input//fifth";
@@ -0,0 +1,25 @@
+/**__internal_workflows{"workflows":{"input.js":{"default":{"workflowId":"workflow//./input//fifth"},"first":{"workflowId":"workflow//./input//first"},"fourth":{"workflowId":"workflow//./input//fourth"},"second":{"workflowId":"workflow//./input//second"},"third":{"workflowId":"workflow//./input//third"}}}}*/;
+export async function first() {
+    return 1;
+}
+first.workflowId = "workflow//./input//first";
+globalThis.__private_workflows.set("workflow//./input//first", first);
+export const second = async ()=>{
+    return 2;
+}, third = async function() {
+    return 3;
+};
+second.workflowId = "workflow//./input//second";
+globalThis.__private_workflows.set("workflow//./input//second", second);
+third.workflowId = "workflow//./input//third";
+globalThis.__private_workflows.set("workflow//./input//third", third);
+async function fourth() {
+    return 4;
+}
+fourth.workflowId = "workflow//./input//fourth";
+globalThis.__private_workflows.set("workflow//./input//fourth", fourth);
+export default async function fifth() {
+    return fourth();
+}
+fifth.workflowId = "workflow//./input//fifth";
+globalThis.__private_workflows.set("workflow//./input//fifth", fifth);
@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+use swc_core::{
+    common::Mark,
+    ecma::{
+        transforms::{
+            base::resolver,
+            testing::{FixtureTestConfig, test_fixture},
+        },
+        visit::visit_mut_pass,
+    },
+};
+use swc_workflow::{StepTransform, TransformMode};
+
+// Like tests/fixture.rs, but runs the resolver first (as the SWC pipeline does), so bindings
+// that share a name get distinct syntax contexts
+#[testing::fixture("tests/resolved/**/input.js")]
+fn step_mode(input: PathBuf) {
+    let step_output = input.parent().unwrap().join("output-step.js");
+    test_fixture(
+        Default::default(),
+        &|_| {
+            (
+                resolver(Mark::new(), Mark::new(), false),
+                visit_mut_pass(StepTransform::new(
+                    TransformMode::Step,
+                    input.file_name().unwrap().to_string_lossy().to_string(),
+                    None,
+                )),
+            )
+        },
+        &input,
+        &step_output,
+        FixtureTestConfig {
+            module: Some(true),
+            ..Default::default()
+        },
+    );
+}
@@ -0,0 +1,15 @@
+export async function shadowWorkflow(x) {
+  'use workflow';
+
+  const report = async () => {
+    'use step';
+    const before = x;
+    {
+      const x = 'inner';
+      console.log(x, [1].map(() => x));
+    }
+    return before + x;
+  };
+
+  return await report();
+}
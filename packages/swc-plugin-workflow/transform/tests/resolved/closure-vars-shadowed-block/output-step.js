@@ -0,0 +1,18 @@
+import { __private_getClosureVars, registerStepFunction } from "workflow/internal/private";
+/**__internal_workflows{"workflows":{"input.js":{"shadowWorkflow":{"workflowId":"workflow//./input//shadowWorkflow"}}},"steps":{"input.js":{"report":{"stepId":"step//./input//report"}}}}*/;
+var shadowWorkflow$report = async ()=>{
+    const { x } = __private_getClosureVars();
+    const before = x;
+    {
+        const x = 'inner';
+        console.log(x, [
+            1
+        ].map(()=>x));
+    }
+    return before + x;
+};
+export async function shadowWorkflow(x) {
+    throw new Error("You attempted to execute workflow shadowWorkflow function directly. To start a workflow, use start(shadowWorkflow) from workflow/api");
+}
+shadowWorkflow.workflowId = "workflow//./input//shadowWorkflow";
+registerStepFunction("step//./input//shadowWorkflow/report", shadowWorkflow$report);
@@ -0,0 +1,55 @@
+use swc_core::ecma::{ast::*, parser::Syntax, visit::VisitMutWith};
+use swc_workflow::{ScriptHandling, StepTransform, TransformMode, TransformOptions};
+
+mod common;
+
+const INPUT: &str = r#"
+async function add(a, b) {
+  "use step";
+  return a + b;
+}
+"#;
+
+// Transforms `INPUT` parsed as a script, returning the resulting program and whether any
+// error was emitted
+fn transform_script(script_handling: ScriptHandling) -> (Script, Program, bool) {
+    common::with_parser(INPUT, Syntax::default(), |mut parser, _cm, handler| {
+        let script = parser.parse_script().expect("failed to parse input");
+        let mut program = Program::Script(script.clone());
+
+        program.visit_mut_with(
+            &mut StepTransform::new(TransformMode::Step, "input.js".into(), None).with_options(
+                TransformOptions {
+                    script_handling,
+                    ..Default::default()
+                },
+            ),
+        );
+
+        (script, program, handler.has_errors())
+    })
+}
+
+#[test]
+fn scripts_are_converted_by_default() {
+    let (_, program, has_errors) = transform_script(ScriptHandling::default());
+
+    assert!(!has_errors);
+    let Program::Module(module) = program else {
+        panic!("expected the script to be converted to a module");
+    };
+    assert!(
+        module
+            .body
+            .iter()
+            .any(|item| matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_))))
+    );
+}
+
+#[test]
+fn scripts_are_rejected_when_configured() {
+    let (script, program, has_errors) = transform_script(ScriptHandling::Error);
+
+    assert!(has_errors);
+    assert_eq!(program, Program::Script(script));
+}
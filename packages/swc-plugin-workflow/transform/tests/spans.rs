@@ -0,0 +1,290 @@
+use swc_core::{
+    common::{
+        DUMMY_SP, Span, Spanned,
+        comments::{Comments, SingleThreadedComments},
+    },
+    ecma::{
+        ast::*,
+        parser::Syntax,
+        visit::{Visit, VisitMutWith, VisitWith},
+    },
+};
+use swc_workflow::{StepTransform, TransformMode, TransformOptions};
+
+mod common;
+
+// Collects the span of every function declaration, and of every call to the given callee (and
+// of the callee itself)
+#[derive(Default)]
+struct SpanCollector {
+    callee: &'static str,
+    fn_spans: Vec<(String, Span)>,
+    call_spans: Vec<Span>,
+    callee_spans: Vec<Span>,
+}
+
+impl Visit for SpanCollector {
+    fn visit_fn_decl(&mut self, fn_decl: &FnDecl) {
+        self.fn_spans
+            .push((fn_decl.ident.sym.to_string(), fn_decl.function.span));
+        fn_decl.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        let callee = match &call.callee {
+            Callee::Expr(expr) => match &**expr {
+                Expr::Ident(ident) => Some(ident.sym.to_string()),
+                Expr::Member(MemberExpr {
+                    prop: MemberProp::Computed(prop),
+                    ..
+                }) => match &*prop.expr {
+                    Expr::Call(CallExpr { args, .. }) => match args.first().map(|arg| &*arg.expr) {
+                        Some(Expr::Lit(Lit::Str(symbol))) => {
+                            Some(symbol.value.to_string_lossy().to_string())
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        };
+        if callee.as_deref() == Some(self.callee) {
+            self.call_spans.push(call.span);
+            self.callee_spans.push(call.callee.span());
+        }
+        call.visit_children_with(self);
+    }
+}
+
+fn collect_spans(mode: TransformMode, src: &str, callee: &'static str) -> (Span, Vec<Span>) {
+    let (step_span, after) = collect_spans_with(
+        mode,
+        src,
+        callee,
+        TransformOptions::default(),
+        SingleThreadedComments::default(),
+    );
+    (step_span, after.call_spans)
+}
+
+fn collect_spans_with(
+    mode: TransformMode,
+    src: &str,
+    callee: &'static str,
+    options: TransformOptions,
+    comments: SingleThreadedComments,
+) -> (Span, SpanCollector) {
+    common::with_module(src, Syntax::default(), |module, _cm, _handler| {
+        let mut program = Program::Module(module);
+
+        let mut before = SpanCollector {
+            callee,
+            ..Default::default()
+        };
+        program.visit_with(&mut before);
+        let (_, step_span) = before
+            .fn_spans
+            .into_iter()
+            .find(|(name, _)| name == "add")
+            .expect("missing step function");
+
+        program.visit_mut_with(
+            &mut StepTransform::new(mode, "input.js".into(), None)
+                .with_options(options)
+                .with_comments(comments),
+        );
+
+        let mut after = SpanCollector {
+            callee,
+            ..Default::default()
+        };
+        program.visit_with(&mut after);
+        (step_span, after)
+    })
+}
+
+#[test]
+fn nested_step_proxy_keeps_function_span() {
+    let (step_span, call_spans) = collect_spans(
+        TransformMode::Workflow,
+        r#"
+export async function sum(a, b) {
+  "use workflow";
+  async function add(x, y) {
+    "use step";
+    return x + y;
+  }
+  return add(a, b);
+}
+"#,
+        "WORKFLOW_USE_STEP",
+    );
+
+    assert_ne!(step_span, DUMMY_SP);
+    assert_eq!(call_spans, vec![step_span]);
+}
+
+#[test]
+fn pure_nested_step_proxy_keeps_function_span() {
+    let comments = SingleThreadedComments::default();
+    let (step_span, after) = collect_spans_with(
+        TransformMode::Workflow,
+        r#"
+export async function sum(a, b) {
+  "use workflow";
+  async function add(x, y) {
+    "use step";
+    return x + y;
+  }
+  return add(a, b);
+}
+"#,
+        "WORKFLOW_USE_STEP",
+        TransformOptions {
+            pure_step_proxies: true,
+            preserve_hoisted_spans: true,
+            ..Default::default()
+        },
+        comments.clone(),
+    );
+
+    assert_ne!(step_span, DUMMY_SP);
+    assert_eq!(after.call_spans, vec![step_span]);
+    assert!(comments.has_flag(after.callee_spans[0].lo, "PURE"));
+    assert!(!comments.has_flag(step_span.lo, "PURE"));
+}
+
+#[test]
+fn registration_call_keeps_function_span() {
+    let (step_span, call_spans) = collect_spans(
+        TransformMode::Step,
+        r#"
+export async function add(a, b) {
+  "use step";
+  return a + b;
+}
+"#,
+        "registerStepFunction",
+    );
+
+    assert_ne!(step_span, DUMMY_SP);
+    assert_eq!(call_spans, vec![step_span]);
+}
+
+// Collects the spans of function names and arrow functions in the input
+#[derive(Default)]
+struct SourceSpanCollector {
+    idents: Vec<(String, Span)>,
+    arrows: Vec<Span>,
+}
+
+impl Visit for SourceSpanCollector {
+    fn visit_fn_decl(&mut self, fn_decl: &FnDecl) {
+        self.idents
+            .push((fn_decl.ident.sym.to_string(), fn_decl.ident.span));
+        fn_decl.visit_children_with(self);
+    }
+
+    fn visit_arrow_expr(&mut self, arrow: &ArrowExpr) {
+        self.arrows.push(arrow.span);
+        arrow.visit_children_with(self);
+    }
+}
+
+// Transforms `src` in step mode and returns the input spans next to the span of each named
+// module-level declaration of the output (the name of a function, or the whole `var`)
+fn collect_hoisted_spans(
+    src: &str,
+    options: TransformOptions,
+) -> (SourceSpanCollector, Vec<(String, Span)>) {
+    common::with_module(src, Syntax::default(), |module, _cm, _handler| {
+        let mut program = Program::Module(module);
+
+        let mut source = SourceSpanCollector::default();
+        program.visit_with(&mut source);
+
+        program.visit_mut_with(
+            &mut StepTransform::new(TransformMode::Step, "input.js".into(), None)
+                .with_options(options),
+        );
+
+        let Program::Module(module) = program else {
+            unreachable!()
+        };
+        let hoisted = module
+            .body
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl))) => {
+                    Some((fn_decl.ident.sym.to_string(), fn_decl.ident.span))
+                }
+                ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) => {
+                    match &var_decl.decls.first()?.name {
+                        Pat::Ident(ident) => Some((ident.id.sym.to_string(), var_decl.span)),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect();
+        (source, hoisted)
+    })
+}
+
+const HOISTED: &str = r#"
+export async function sum(a, b) {
+  "use workflow";
+  async function add(x, y) {
+    "use step";
+    return x + y;
+  }
+  return add(a, b);
+}
+
+export const tools = {
+  search: async (query) => {
+    "use step";
+    return fetch(`/api/search?q=${query}`);
+  },
+};
+"#;
+
+fn hoisted_span(hoisted: &[(String, Span)], name: &str) -> Span {
+    hoisted
+        .iter()
+        .find(|(hoisted_name, _)| hoisted_name == name)
+        .map(|(_, span)| *span)
+        .unwrap_or_else(|| panic!("missing hoisted {}", name))
+}
+
+#[test]
+fn hoisted_steps_keep_original_spans() {
+    let (source, hoisted) = collect_hoisted_spans(
+        HOISTED,
+        TransformOptions {
+            preserve_hoisted_spans: true,
+            ..Default::default()
+        },
+    );
+
+    let (_, add_span) = source
+        .idents
+        .iter()
+        .find(|(name, _)| name == "add")
+        .expect("missing nested step");
+    assert_ne!(*add_span, DUMMY_SP);
+    assert_eq!(hoisted_span(&hoisted, "sum$add"), *add_span);
+
+    assert_ne!(source.arrows[0], DUMMY_SP);
+    assert_eq!(hoisted_span(&hoisted, "tools$search"), source.arrows[0]);
+}
+
+#[test]
+fn hoisted_steps_have_dummy_spans_by_default() {
+    let (_, hoisted) = collect_hoisted_spans(HOISTED, TransformOptions::default());
+
+    assert_eq!(hoisted_span(&hoisted, "sum$add"), DUMMY_SP);
+    assert_eq!(hoisted_span(&hoisted, "tools$search"), DUMMY_SP);
+}
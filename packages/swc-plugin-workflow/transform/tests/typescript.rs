@@ -0,0 +1,131 @@
+use swc_core::ecma::{
+    ast::*,
+    parser::{Syntax, TsSyntax},
+    visit::{Visit, VisitWith},
+};
+use swc_workflow::{StepTransform, TransformMode};
+
+mod common;
+
+// Transforms `src` parsed as TypeScript, returning the resulting module and whether any error
+// was emitted
+fn transform_ts(mode: TransformMode, src: &str) -> (Module, bool) {
+    common::transform(
+        src,
+        Syntax::Typescript(TsSyntax::default()),
+        StepTransform::new(mode, "input.ts".into(), None),
+    )
+}
+
+// Names of the module-level function and variable declarations
+fn declaration_names(module: &Module) -> Vec<String> {
+    let decls = module.body.iter().filter_map(|item| match item {
+        ModuleItem::Stmt(Stmt::Decl(decl)) => Some(decl),
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => Some(&export_decl.decl),
+        _ => None,
+    });
+    let mut names = Vec::new();
+    for decl in decls {
+        match decl {
+            Decl::Fn(fn_decl) => names.push(fn_decl.ident.sym.to_string()),
+            Decl::Var(var_decl) => names.extend(var_decl.decls.iter().filter_map(|declarator| {
+                declarator
+                    .name
+                    .as_ident()
+                    .map(|ident| ident.id.sym.to_string())
+            })),
+            _ => {}
+        }
+    }
+    names
+}
+
+// Finds references to `__private_getClosureVars`, which steps that capture variables call
+struct ClosureVarsFinder(bool);
+
+impl Visit for ClosureVarsFinder {
+    fn visit_ident(&mut self, ident: &Ident) {
+        self.0 |= ident.sym == "__private_getClosureVars";
+    }
+}
+
+fn reads_closure_vars(module: &Module) -> bool {
+    let mut finder = ClosureVarsFinder(false);
+    module.visit_with(&mut finder);
+    finder.0
+}
+
+#[test]
+fn import_equals_bindings_avoid_generated_name_collisions() {
+    let (module, has_errors) = transform_ts(
+        TransformMode::Workflow,
+        r#"
+import __default = require("./legacy");
+
+export default async function (id: string) {
+  "use workflow";
+  return __default.load(id);
+}
+"#,
+    );
+
+    assert!(!has_errors);
+    assert!(declaration_names(&module).contains(&"__default$1".to_string()));
+}
+
+#[test]
+fn import_equals_bindings_are_not_closure_vars() {
+    let (module, has_errors) = transform_ts(
+        TransformMode::Step,
+        r#"
+import db = require("./db");
+
+export async function save(value: number) {
+  "use workflow";
+  async function store(x: number) {
+    "use step";
+    return db.save(x);
+  }
+  return store(value);
+}
+"#,
+    );
+
+    assert!(!has_errors);
+    assert!(!reads_closure_vars(&module));
+}
+
+#[test]
+fn export_assignments_are_rejected_in_directive_files() {
+    let (_, has_errors) = transform_ts(
+        TransformMode::Step,
+        r#"
+"use step";
+
+async function add(a: number, b: number) {
+  return a + b;
+}
+
+export = add;
+"#,
+    );
+
+    assert!(has_errors);
+}
+
+#[test]
+fn export_assignments_are_allowed_in_other_files() {
+    let (_, has_errors) = transform_ts(
+        TransformMode::Step,
+        r#"
+async function add(a: number, b: number) {
+  "use step";
+  return a + b;
+}
+
+export = add;
+"#,
+    );
+
+    assert!(!has_errors);
+}
@@ -0,0 +1,734 @@
+use std::sync::{Arc, Mutex};
+use swc_core::{
+    common::{
+        SourceMap,
+        errors::{DiagnosticBuilder, Emitter, HANDLER, Handler, HandlerFlags, Level},
+    },
+    ecma::{ast::*, parser::Syntax, visit::VisitMutWith},
+};
+use swc_workflow::{DiagnosticLevel, StepTransform, TransformMode, TransformOptions};
+
+mod common;
+
+// Records each diagnostic as `level, line: message`. The fixture harness drops warnings, so
+// they are checked here instead.
+struct DiagnosticCollector(Arc<Mutex<Vec<(Level, usize, String)>>>, Arc<SourceMap>);
+
+impl Emitter for DiagnosticCollector {
+    fn emit(&mut self, db: &mut DiagnosticBuilder<'_>) {
+        let line = db
+            .span
+            .primary_span()
+            .map(|span| self.1.lookup_char_pos(span.lo).line)
+            .unwrap_or_default();
+        self.0.lock().unwrap().push((db.level, line, db.message()));
+    }
+}
+
+fn collect_warnings(src: &str, options: TransformOptions) -> Vec<(usize, String)> {
+    collect_diagnostics(src, options)
+        .into_iter()
+        .filter(|(level, _, _)| *level == Level::Warning)
+        .map(|(_, line, message)| (line, message))
+        .collect()
+}
+
+fn collect_diagnostics(src: &str, options: TransformOptions) -> Vec<(Level, usize, String)> {
+    let diagnostics = Arc::new(Mutex::new(Vec::new()));
+    transform_with_emitter(src, options, |cm| {
+        DiagnosticCollector(diagnostics.clone(), cm.clone())
+    });
+    std::mem::take(&mut *diagnostics.lock().unwrap())
+}
+
+// Transforms `src` in workflow mode, reporting diagnostics to the emitter created by `emitter`
+fn transform_with_emitter<E: Emitter + 'static>(
+    src: &str,
+    options: TransformOptions,
+    emitter: impl FnOnce(&Arc<SourceMap>) -> E,
+) {
+    common::with_module(src, Syntax::default(), |module, cm, _handler| {
+        let mut program = Program::Module(module);
+
+        let handler = Handler::with_emitter_and_flags(
+            Box::new(emitter(cm)),
+            HandlerFlags {
+                can_emit_warnings: true,
+                ..Default::default()
+            },
+        );
+        HANDLER.set(&handler, || {
+            program.visit_mut_with(
+                &mut StepTransform::new(TransformMode::Workflow, "input.js".into(), None)
+                    .with_options(options),
+            );
+        });
+    })
+}
+
+// Records the replacements suggested by diagnostics as `(offset, snippet)`
+struct SuggestionCollector(Arc<Mutex<Vec<(usize, String)>>>);
+
+impl Emitter for SuggestionCollector {
+    fn emit(&mut self, db: &mut DiagnosticBuilder<'_>) {
+        for suggestion in &db.suggestions {
+            for part in suggestion.substitutions.iter().flat_map(|sub| &sub.parts) {
+                assert_eq!(part.span.lo, part.span.hi, "only insertions are suggested");
+                // Offsets start at 1 for the only file of a test
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((part.span.lo.0 as usize - 1, part.snippet.clone()));
+            }
+        }
+    }
+}
+
+// Applies every suggested insertion to `src`
+fn apply_suggestions(src: &str, options: TransformOptions) -> String {
+    let suggestions = Arc::new(Mutex::new(Vec::new()));
+    transform_with_emitter(src, options, |_cm| SuggestionCollector(suggestions.clone()));
+
+    let mut fixed = src.to_string();
+    let mut suggestions = std::mem::take(&mut *suggestions.lock().unwrap());
+    suggestions.sort();
+    for (offset, snippet) in suggestions.into_iter().rev() {
+        fixed.insert_str(offset, &snippet);
+    }
+    fixed
+}
+
+const WORKFLOWS: &str = r#"
+async function fetchUser(id) {
+  "use step";
+  return fetch(`/api/users/${id}`).then((res) => res.json());
+}
+
+class Mailer {
+  static async send(to) {
+    "use step";
+    return fetch("/api/mail", { method: "POST", body: to });
+  }
+}
+
+export async function onboard(id) {
+  "use workflow";
+  const user = await fetchUser(id);
+  await Mailer.send(user.email);
+  return user;
+}
+
+export const onboardAll = async (ids) => {
+  "use workflow";
+  return Promise.all(ids.map(fetchUser));
+};
+
+export async function inline(id) {
+  "use workflow";
+  const res = await fetch(`/api/users/${id}`);
+  return res.json();
+}
+"#;
+
+#[test]
+fn workflows_without_steps_are_reported() {
+    let warnings = collect_warnings(
+        WORKFLOWS,
+        TransformOptions {
+            warn_workflows_without_steps: true,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        warnings,
+        vec![(
+            26,
+            "This \"use workflow\" function doesn't call any step function; move its work into steps so it runs durably and deterministically".to_string()
+        )]
+    );
+}
+
+#[test]
+fn module_level_directives_are_followed() {
+    let warnings = collect_warnings(
+        r#"
+"use workflow";
+
+async function checkout(cart) {
+  "use step";
+  return fetch("/api/checkout", { method: "POST", body: cart });
+}
+
+export async function purchase(cart) {
+  return checkout(cart);
+}
+
+export default async function browse() {
+  return fetch("/api/products");
+}
+"#,
+        TransformOptions {
+            warn_workflows_without_steps: true,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        warnings.iter().map(|(line, _)| *line).collect::<Vec<_>>(),
+        vec![13]
+    );
+}
+
+#[test]
+fn workflows_without_steps_are_allowed_by_default() {
+    assert!(collect_warnings(WORKFLOWS, TransformOptions::default()).is_empty());
+}
+
+const IMPORTS: &str = r#"
+"use workflow";
+
+import { sendEmail } from "./send-email.step";
+import { chargeCard } from "./billing";
+import { format } from "./format.ts";
+
+export async function checkout(order) {
+  await chargeCard(order);
+  return sendEmail(format(order));
+}
+"#;
+
+#[test]
+fn directive_file_imports_are_reported() {
+    let warnings = collect_warnings(
+        IMPORTS,
+        TransformOptions {
+            warn_directive_file_imports: true,
+            directive_files: vec!["./billing".to_string()],
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        warnings,
+        vec![
+            (
+                4,
+                "This \"use workflow\" file imports from \"./send-email.step\", which is also a directive file; its functions are replaced by the transform, so only call them as steps or workflows".to_string()
+            ),
+            (
+                5,
+                "This \"use workflow\" file imports from \"./billing\", which is also a directive file; its functions are replaced by the transform, so only call them as steps or workflows".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn directive_file_imports_are_allowed_by_default() {
+    assert!(collect_warnings(IMPORTS, TransformOptions::default()).is_empty());
+}
+
+const LENIENT: &str = r#"
+export async function fetchUser(id) {
+  "use stepp";
+  return fetch(`/api/users/${id}`);
+}
+
+export async function onboard(id) {
+  "use workflow";
+  return this.fetchUser(id);
+}
+
+export function sync(id) {
+  "use step";
+  return id;
+}
+
+export class Counter {
+  #count = 0;
+
+  createWorkflow() {
+    return async function counterWorkflow(amount) {
+      "use workflow";
+      const read = async () => {
+        "use step";
+        return this.#count + amount;
+      };
+      return await read();
+    };
+  }
+}
+"#;
+
+#[test]
+fn lenient_diagnostics_are_warnings() {
+    let diagnostics = collect_diagnostics(
+        LENIENT,
+        TransformOptions {
+            diagnostics: DiagnosticLevel::Warn,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            (
+                Level::Warning,
+                3,
+                "Did you mean \"use step\"? \"use stepp\" is not a supported directive".to_string()
+            ),
+            (
+                Level::Warning,
+                9,
+                "Functions marked with \"use workflow\" cannot use `this`".to_string()
+            ),
+            (
+                Level::Error,
+                12,
+                "Functions marked with \"use step\" must be async functions".to_string()
+            ),
+            (
+                Level::Warning,
+                25,
+                "Functions marked with \"use step\" cannot use `this`".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn lenient_diagnostics_are_errors_by_default() {
+    let levels = collect_diagnostics(LENIENT, TransformOptions::default())
+        .into_iter()
+        .map(|(level, line, _)| (level, line))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        levels,
+        vec![
+            (Level::Error, 3),
+            (Level::Error, 9),
+            (Level::Error, 12),
+            (Level::Error, 25)
+        ]
+    );
+}
+
+const COMPUTED_KEYS: &str = r#"
+import { StepNames } from "./names";
+
+const toolName = "search";
+
+export const steps = {
+  async [StepNames.Run](input) {
+    "use step";
+    return input;
+  },
+  [toolName]: async (query) => {
+    "use step";
+    return fetch(`/api/search?q=${query}`);
+  },
+  ["stop"]: async () => {
+    "use step";
+    return null;
+  },
+  [StepNames.Label]: "not a step",
+};
+"#;
+
+#[test]
+fn computed_step_keys_are_reported() {
+    let warnings = collect_warnings(COMPUTED_KEYS, TransformOptions::default());
+
+    assert_eq!(
+        warnings,
+        vec![
+            (
+                7,
+                "Computed keys that read a member (e.g. an enum value) aren't resolved for steps, so this step is named `[computed0]` after its position in the object; use a string literal key to give it a stable ID".to_string()
+            ),
+            (
+                11,
+                "This computed key can't be resolved at compile time, so the step is named `[computed1]` after its position in the object; use a string literal key to give it a stable ID".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn computed_step_keys_can_be_allowed() {
+    let warnings = collect_warnings(
+        COMPUTED_KEYS,
+        TransformOptions {
+            warn_computed_step_keys: false,
+            ..Default::default()
+        },
+    );
+
+    assert!(warnings.is_empty());
+}
+
+const UNHANDLED: &str = r#"
+async function chargeCard(order) {
+  "use step";
+  return fetch("/api/charge", { method: "POST", body: order });
+}
+
+const mailer = {
+  async send(to) {
+    "use step";
+    return fetch("/api/mail", { method: "POST", body: to });
+  },
+};
+
+export async function checkout(order) {
+  "use workflow";
+  try {
+    await chargeCard(order);
+  } catch (error) {
+    await mailer.send(order.email);
+    throw error;
+  }
+  await chargeCard(order).catch(() => null);
+  await Promise.all(order.items.map(async (item) => await chargeCard(item)));
+  return mailer.send(order.email);
+}
+"#;
+
+#[test]
+fn unhandled_step_calls_are_reported() {
+    let warnings = collect_warnings(
+        UNHANDLED,
+        TransformOptions {
+            warn_unhandled_step_calls: true,
+            ..Default::default()
+        },
+    );
+
+    let message = "This step call is awaited outside of a try/catch, so once its retries are used up the error ends the workflow; catch it to handle the failure explicitly";
+    assert_eq!(
+        warnings,
+        vec![(19, message.to_string()), (23, message.to_string())]
+    );
+}
+
+#[test]
+fn unhandled_step_calls_are_allowed_by_default() {
+    assert!(collect_warnings(UNHANDLED, TransformOptions::default()).is_empty());
+}
+
+const AWAITED: &str = r#"
+import { sendEmail } from "./send-email";
+
+async function chargeCard(order) {
+  "use step";
+  return fetch("/api/charge", { method: "POST", body: order });
+}
+
+const billing = {
+  async refund(order) {
+    "use step";
+    return fetch("/api/refund", { method: "POST", body: order });
+  },
+};
+
+export async function checkout(order) {
+  "use workflow";
+  await chargeCard(order);
+  await billing.refund.call(billing, order);
+  await sendEmail(order.email);
+  await Promise.all([chargeCard(order)]);
+  await this.svc.fetch(order.id);
+  return await (order.store?.save)(order);
+}
+"#;
+
+#[test]
+fn awaited_non_steps_are_reported() {
+    let warnings = collect_warnings(
+        AWAITED,
+        TransformOptions {
+            warn_awaited_non_steps: true,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        warnings.iter().map(|(line, _)| *line).collect::<Vec<_>>(),
+        vec![22, 23]
+    );
+    assert_eq!(
+        warnings[0].1,
+        "`fetch` isn't a step function of this file, so awaiting it in a workflow repeats its work on every replay; call it from a step instead"
+    );
+}
+
+#[test]
+fn awaited_non_steps_are_allowed_by_default() {
+    assert!(collect_warnings(AWAITED, TransformOptions::default()).is_empty());
+}
+
+const NON_DETERMINISTIC: &str = r#"
+export async function stamp(order) {
+  "use step";
+  return { ...order, id: Math.random(), at: Date.now(), date: new Date() };
+}
+
+export async function checkout(order) {
+  "use workflow";
+  const id = Math.random();
+  const placedAt = Date.now();
+  const labels = order.items.map(() => new Date());
+  const shipBy = new Date(order.shipBy);
+  async function audit() {
+    "use step";
+    return new Date();
+  }
+  return { id, placedAt, labels, shipBy, audit };
+}
+"#;
+
+#[test]
+fn non_deterministic_calls_are_reported() {
+    let warnings = collect_warnings(
+        NON_DETERMINISTIC,
+        TransformOptions {
+            non_deterministic_calls: Some(DiagnosticLevel::Warn),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        warnings,
+        vec![
+            (
+                9,
+                "`Math.random()` gives a different result on every replay of a \"use workflow\" function; move it into a step so its result is recorded".to_string()
+            ),
+            (
+                10,
+                "`Date.now()` gives a different result on every replay of a \"use workflow\" function; move it into a step so its result is recorded".to_string()
+            ),
+            (
+                11,
+                "`new Date()` gives a different result on every replay of a \"use workflow\" function; move it into a step so its result is recorded".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn non_deterministic_calls_can_be_errors() {
+    let levels = collect_diagnostics(
+        NON_DETERMINISTIC,
+        TransformOptions {
+            non_deterministic_calls: Some(DiagnosticLevel::Error),
+            ..Default::default()
+        },
+    )
+    .into_iter()
+    .map(|(level, line, _)| (level, line))
+    .collect::<Vec<_>>();
+
+    assert_eq!(
+        levels,
+        vec![(Level::Error, 9), (Level::Error, 10), (Level::Error, 11)]
+    );
+}
+
+#[test]
+fn non_deterministic_calls_are_allowed_by_default() {
+    assert!(collect_diagnostics(NON_DETERMINISTIC, TransformOptions::default()).is_empty());
+}
+
+const NON_ASYNC: &str = r#"
+export function add(a, b) {
+  "use step";
+  return a + b;
+}
+
+export const multiply = (a, b) => {
+  "use step";
+  return a * b;
+};
+
+export class Orders {
+  static place(order) {
+    "use workflow";
+    return order;
+  }
+
+  refund(order) {
+    "use step";
+    return order;
+  }
+
+  *audit(order) {
+    "use step";
+    yield order;
+  }
+}
+
+export const tools = {
+  search(query) {
+    "use step";
+    return query;
+  },
+};
+"#;
+
+#[test]
+fn non_async_functions_suggest_async() {
+    let fixed = apply_suggestions(
+        NON_ASYNC,
+        TransformOptions {
+            suggest_async_fix: true,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        fixed,
+        NON_ASYNC
+            .replace("export function add", "export async function add")
+            .replace("multiply = (a, b)", "multiply = async (a, b)")
+            .replace("static place", "static async place")
+            .replace("  refund", "  async refund")
+            .replace("  search", "  async search")
+    );
+}
+
+#[test]
+fn non_async_functions_suggest_nothing_by_default() {
+    assert_eq!(
+        apply_suggestions(NON_ASYNC, TransformOptions::default()),
+        NON_ASYNC
+    );
+}
+
+const REASSIGNED: &str = r#"
+let tools = {
+  async search(query) {
+    "use step";
+    return fetch(`/api/search?q=${query}`);
+  },
+};
+let settings = { retries: 3 };
+
+export function reset() {
+  tools = { search: async () => null };
+  settings = { retries: 0 };
+  tools.search = async () => null;
+}
+"#;
+
+#[test]
+fn reassigned_step_objects_are_reported() {
+    let warnings = collect_warnings(
+        REASSIGNED,
+        TransformOptions {
+            warn_reassigned_step_objects: true,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        warnings,
+        vec![(
+            11,
+            "`tools` holds object property steps, which stay registered for the object it was declared with; reassigning it leaves the new object out of sync with the registered steps".to_string()
+        )]
+    );
+}
+
+#[test]
+fn reassigned_step_objects_are_allowed_by_default() {
+    assert!(collect_warnings(REASSIGNED, TransformOptions::default()).is_empty());
+}
+
+const REASSIGNED_SHADOWED: &str = r#"
+const tools = {
+  async search(query) {
+    "use step";
+    return fetch(`/api/search?q=${query}`);
+  },
+};
+
+export function count() {
+  let tools = 1;
+  tools = 2;
+  return tools;
+}
+
+export function pick(tools) {
+  tools = tools.slice(1);
+  return tools;
+}
+
+export function legacy() {
+  if (Math.random() > 0.5) {
+    var tools = [];
+  }
+  tools = [1];
+  return tools;
+}
+
+export function reset(items) {
+  for (const tools of items) {
+    console.log(tools);
+  }
+  {
+    let tools = [];
+    tools = [2];
+  }
+  tools = null;
+}
+"#;
+
+#[test]
+fn reassigned_step_objects_ignore_shadowing_bindings() {
+    let warnings = collect_warnings(
+        REASSIGNED_SHADOWED,
+        TransformOptions {
+            warn_reassigned_step_objects: true,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(
+        warnings
+            .into_iter()
+            .map(|(line, _)| line)
+            .collect::<Vec<_>>(),
+        vec![36]
+    );
+}
+
+const NESTED_STEPS: &str = r#"
+export async function refund(amount) {
+  "use step";
+  const reverse = async (paymentId) => {
+    "use step";
+    return { paymentId, amount };
+  };
+  const notify = async (message) => {
+    "use step";
+    return message.toUpperCase();
+  };
+  await notify("refunding");
+  return reverse("pay_123");
+}
+"#;
+
+#[test]
+fn inlined_nested_steps_are_reported() {
+    assert_eq!(
+        collect_warnings(NESTED_STEPS, TransformOptions::default()),
+        vec![(
+            4,
+            "This \"use step\" function is nested in another step and captures its variables, so it runs as part of the enclosing step rather than as a step of its own; move it out of the enclosing step to make it a separate step".to_string()
+        )]
+    );
+}